@@ -0,0 +1,71 @@
+//! ページ操作構築のベンチマーク（直列 vs 並列）
+//!
+//! 使用方法:
+//! ```bash
+//! cargo bench --bench page_operations --features parallel
+//! ```
+
+use std::time::{Duration, Instant};
+
+use print_pdf_service::pdf::{FontSet, ReportLabStylePdfClient};
+use print_pdf_service::{Item, Ryohi};
+use printpdf::FontId;
+
+const ITEM_COUNT: usize = 500;
+const ITERATIONS: u32 = 5;
+
+fn main() {
+    let items = create_items(ITEM_COUNT);
+    let fonts = FontSet::single(FontId("bench".to_string()));
+
+    let serial = bench(&ReportLabStylePdfClient::new().with_parallel(false), &fonts, &items);
+    let parallel = bench(&ReportLabStylePdfClient::new().with_parallel(true), &fonts, &items);
+
+    println!("items: {}", ITEM_COUNT);
+    println!("serial:   {:?}/iter", serial);
+    println!("parallel: {:?}/iter", parallel);
+    if !cfg!(feature = "parallel") {
+        println!("(parallel feature が無効のため並列処理は行われません)");
+    }
+}
+
+/// 1回あたりの平均実行時間を計測
+fn bench(client: &ReportLabStylePdfClient, fonts: &FontSet, items: &[Item]) -> Duration {
+    // ウォームアップ
+    client.render_page_operations_with_fonts(fonts, items);
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        let (pages, _) = client.render_page_operations_with_fonts(fonts, items);
+        assert_eq!(pages.len(), items.len());
+    }
+    start.elapsed() / ITERATIONS
+}
+
+/// 合成データを作成
+fn create_items(count: usize) -> Vec<Item> {
+    (0..count)
+        .map(|i| Item {
+            car: format!("{:02}-{:02}", i % 100, (i / 100) % 100),
+            name: format!("社員{}", i),
+            purpose: Some("客先訪問".to_string()),
+            start_date: Some("2024-01-15".to_string()),
+            end_date: Some("2024-01-16".to_string()),
+            price: 25000,
+            ryohi: (0..7)
+                .map(|j| Ryohi {
+                    date: Some(format!("2024-01-{:02}", j + 1)),
+                    dest: Some("東京".to_string()),
+                    detail: vec!["交通費".to_string(), "高速代".to_string(), "駐車場代".to_string()],
+                    kukan: Some("福岡　東京　大阪　名古屋".to_string()),
+                    price: Some(15000 + j * 100),
+                    vol: Some(1.5),
+                    ..Default::default()
+                })
+                .collect(),
+            office: Some("営業部".to_string()),
+            pay_day: Some("2024/01/25".to_string()),
+            ..Default::default()
+        })
+        .collect()
+}
@@ -99,6 +99,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         ],
         office: Some("営業部".to_string()),
         pay_day: Some("2024/12/31".to_string()),
+        external_id: None,
+        layout_preset: None,
+        keep_together: None,
+        receipt_count: None,
+        advance: None,
     }];
 
     // PDF生成サービス
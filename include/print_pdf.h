@@ -0,0 +1,58 @@
+#ifndef PRINT_PDF_H
+#define PRINT_PDF_H
+
+/* cbindgenで生成（直接編集しない）: cbindgen --config cbindgen.toml --crate print-pdf-service --output include/print_pdf.h */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// 成功
+#define PPDF_OK 0
+
+// PDF生成エラー（`PdfError::Generation`）
+#define PPDF_ERR_GENERATION 1
+
+// フォント読み込みエラー（`PdfError::FontLoad`）
+#define PPDF_ERR_FONT_LOAD 2
+
+// 印刷エラー（`PdfError::Print`）
+#define PPDF_ERR_PRINT 3
+
+// ファイルIOエラー（`PdfError::FileIO`）
+#define PPDF_ERR_FILE_IO 4
+
+// 設定エラー（`PdfError::Config`）
+#define PPDF_ERR_CONFIG 5
+
+// 入力データ検証エラー（`PdfError::Validation`、リクエストのJSONが不正な場合を含む）
+#define PPDF_ERR_VALIDATION 6
+
+// 引数が不正（NULLポインタ、UTF-8でない文字列）
+#define PPDF_ERR_INVALID_ARGUMENT -1
+
+// 処理中にパニックが発生
+#define PPDF_ERR_PANIC -2
+
+// リクエストのJSONからPDFを生成（`print` がtrueの場合は印刷）
+//
+// 成功した場合は `out_result_json` に結果のJSONを設定し `PPDF_OK` を返す（`ppdf_free` で解放する）。
+// 失敗した場合は `out_result_json` にNULLを設定し、エラーコードを返す（メッセージは `ppdf_last_error_message`）。
+//
+// # Safety
+// `json_request` はNUL終端の文字列、`out_result_json` は書き込み可能なポインタを指すこと
+int32_t ppdf_generate(const char *json_request, char **out_result_json);
+
+// `ppdf_generate` で返した文字列を解放（NULLの場合は何もしない）
+//
+// # Safety
+// `ptr` は `ppdf_generate` で返した文字列で、解放済みでないこと
+void ppdf_free(char *ptr);
+
+// 同じスレッドの直近のエラーメッセージ（UTF-8、エラーが無い場合はNULL）
+//
+// 返す文字列は同じスレッドで次に `ppdf_generate` を呼び出すまで有効。解放しないこと
+const char *ppdf_last_error_message(void);
+
+#endif  /* PRINT_PDF_H */
@@ -0,0 +1,232 @@
+//! 同期（ブロッキング）API
+//!
+//! 非同期ランタイムを使用しないアプリケーション（GUI・FFI経由の呼び出し等）向けに、
+//! 呼び出し元のスレッドでPDFの生成・印刷を行う。
+//! リクエストの検証・出力パスの決定・警告・結果は `PdfService` と共通の処理を使用する
+
+use std::sync::Arc;
+
+use tokio_util::sync::CancellationToken;
+
+use crate::config::PdfConfig;
+use crate::error::PdfError;
+use crate::fs::Fs;
+use crate::models::Item;
+use crate::pdf::fonts::FontCache;
+use crate::service::{PdfRequest, PdfResult, PdfService};
+use crate::traits::Printer;
+
+/// 同期のPDF生成クライアント
+///
+/// tokioのランタイムは不要。生成の制限時間（`PdfService::with_timeout`）は適用しないため、
+/// 中断する場合は別スレッドから `with_cancellation_token` のトークンをキャンセルする
+#[derive(Debug, Clone, Default)]
+pub struct PdfClient {
+    /// 処理を共有するサービス
+    service: PdfService,
+}
+
+impl PdfClient {
+    /// 設定を指定してクライアントを作成
+    pub fn new(config: PdfConfig) -> Self {
+        Self {
+            service: PdfService::with_config(config),
+        }
+    }
+
+    /// 設定を検証してクライアントを作成
+    ///
+    /// 設定に問題がある場合は全ての問題点をまとめた `PdfError::Config` を返す
+    pub fn try_new(config: PdfConfig) -> Result<Self, PdfError> {
+        Ok(Self {
+            service: PdfService::try_with_config(config)?,
+        })
+    }
+
+    /// ファイル操作を設定（テストではメモリ上のファイルシステムを使用）
+    pub fn with_fs(mut self, fs: Arc<dyn Fs>) -> Self {
+        self.service = self.service.with_fs(fs);
+        self
+    }
+
+    /// 使用するプリンターを設定（設定の印刷バックエンドより優先）
+    pub fn with_printer(mut self, printer: Arc<dyn Printer>) -> Self {
+        self.service = self.service.with_printer(printer);
+        self
+    }
+
+    /// フォントのキャッシュを設定（既定はプロセス全体で共有するキャッシュ）
+    pub fn with_font_cache(mut self, cache: Arc<FontCache>) -> Self {
+        self.service = self.service.with_font_cache(cache);
+        self
+    }
+
+    /// 生成を中断するためのトークンを設定
+    pub fn with_cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.service = self.service.with_cancellation_token(token);
+        self
+    }
+
+    /// リクエストを処理（`PdfService::call` の同期版）
+    pub fn call(&self, req: PdfRequest) -> Result<PdfResult, PdfError> {
+        self.service.call_blocking(req)
+    }
+
+    /// PDFを生成（出力パスはリクエストの既定値）
+    pub fn generate(&self, items: Vec<Item>) -> Result<PdfResult, PdfError> {
+        self.call(PdfRequest::new(items))
+    }
+
+    /// PDFを生成して印刷（プリンター名がNoneの場合は設定のデフォルトプリンター）
+    pub fn generate_and_print(&self, items: Vec<Item>, printer: Option<&str>) -> Result<PdfResult, PdfError> {
+        let mut req = PdfRequest::new(items).with_print(true);
+        if let Some(name) = printer {
+            req = req.with_printer_name(name);
+        }
+        self.call(req)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::{Path, PathBuf};
+
+    use crate::audit::{AuditOutcome, NameRedaction};
+    use crate::fs::MockFs;
+    use crate::models::Ryohi;
+    use crate::pdf::testing::MockFont;
+    use crate::print::MockPrinter;
+    use crate::warning::Warning;
+    use crate::DedupePolicy;
+
+    /// 空のフォントで生成するクライアント（フォントはdrop時に削除する）
+    fn mock_font_client(name: &str, fs: Arc<MockFs>) -> (PdfClient, MockFont) {
+        mock_font_client_with(name, fs, PdfConfig::new())
+    }
+
+    /// 設定を指定して空のフォントで生成するクライアント
+    fn mock_font_client_with(name: &str, fs: Arc<MockFs>, config: PdfConfig) -> (PdfClient, MockFont) {
+        let font = MockFont::new(&format!("blocking_{}", name));
+        let config = config
+            .with_font_path(font.path())
+            .with_output_path("/srv/output")
+            .with_default_printer("Office");
+        let client = PdfClient::new(config).with_fs(fs).with_font_cache(font.cache());
+        (client, font)
+    }
+
+    #[test]
+    fn test_generate_without_runtime() {
+        assert!(tokio::runtime::Handle::try_current().is_err());
+        let fs = Arc::new(MockFs::new());
+        let (client, _font) = mock_font_client("generate", fs.clone());
+
+        let result = client.generate(vec![Item::default()]);
+        // サービスと同じ検証（アイテムが空の場合はエラー）
+        let empty = client.generate(Vec::new());
+        // サービスと同じ入力データ検証の警告
+        let ryohi = Ryohi {
+            date: Some("2024-01-15".to_string()),
+            price: Some(1000),
+            ..Default::default()
+        };
+        let item = Item {
+            ryohi: vec![ryohi.clone(), ryohi],
+            ..Default::default()
+        };
+        let deduped = client.call(
+            PdfRequest::new(vec![item])
+                .with_output_path("dedupe.pdf")
+                .with_dedupe(DedupePolicy::Exact),
+        );
+
+        let result = result.unwrap();
+        assert_eq!(result.pdf_path, PathBuf::from("/srv/output/travel_expense.pdf"));
+        assert!(!result.printed);
+        assert_eq!(result.file_size, fs.file(&result.pdf_path).unwrap().len() as u64);
+        assert!(matches!(empty, Err(PdfError::Validation(_))));
+        assert!(matches!(deduped.unwrap().warnings[..], [Warning::DuplicateRyohi { removed: true, .. }, ..]));
+    }
+
+    #[test]
+    fn test_generate_and_print_without_runtime() {
+        let fs = Arc::new(MockFs::new());
+        let (client, _font) = mock_font_client("print", fs.clone());
+        let printer = Arc::new(MockPrinter::new().with_offline("Offline"));
+        let client = client.with_printer(printer.clone());
+
+        let named = client.generate_and_print(vec![Item::default()], Some("Label"));
+        let default = client.generate_and_print(vec![Item::default()], None);
+        let failed = client.generate_and_print(vec![Item::default()], Some("Offline"));
+
+        let named = named.unwrap();
+        assert!(named.printed);
+        assert_eq!(named.printer_name, Some("Label".to_string()));
+        assert_eq!(default.unwrap().printer_name, Some("Office".to_string()));
+        assert!(matches!(failed, Err(PdfError::PrintFailed { .. })));
+        assert_eq!(printer.printed(), vec![Some("Label".to_string()), Some("Office".to_string())]);
+    }
+
+    #[test]
+    fn test_audit_sink_records_each_request() {
+        let records = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = records.clone();
+        let config = PdfConfig::new()
+            .with_audit_sink(move |entry| sink.lock().unwrap().push(entry))
+            .with_audit_redaction(NameRedaction::Masked);
+        let fs = Arc::new(MockFs::new());
+        let (client, _font) = mock_font_client_with("audit", fs.clone(), config);
+        let client = client.with_printer(Arc::new(MockPrinter::new().with_offline("Offline")));
+        let item = Item {
+            name: "山田太郎".to_string(),
+            car: "12-34".to_string(),
+            external_id: Some("V-1".to_string()),
+            ..Default::default()
+        };
+
+        client.generate_and_print(vec![item.clone()], Some("Label")).unwrap();
+        client.call(PdfRequest::new(vec![item.clone()]).with_request_id("EXP-42")).unwrap();
+        let bytes = fs.file(Path::new("/srv/output/travel_expense.pdf")).unwrap();
+        client.generate_and_print(vec![item], Some("Offline")).unwrap_err();
+        client.generate(Vec::new()).unwrap_err();
+        client
+            .call(
+                PdfRequest::new(vec![Item::default()])
+                    .with_print(true)
+                    .with_print_options(crate::PrintOptions::new().with_copies(3)),
+            )
+            .unwrap();
+
+        let records = records.lock().unwrap();
+        assert_eq!(records.len(), 5);
+        let printed = &records[0];
+        assert!(printed.is_success() && printed.print_requested);
+        assert_eq!(printed.item_count, 1);
+        assert_eq!(printed.items[0].external_id, Some("V-1".to_string()));
+        assert_eq!(printed.items[0].name, Some("山***".to_string()));
+        assert_eq!(printed.output_path, Some(PathBuf::from("/srv/output/travel_expense.pdf")));
+        assert_eq!(printed.printer_name, Some("Label".to_string()));
+        assert_eq!(printed.copies, 1);
+        assert!(!printed.request_id.is_empty());
+
+        let generated = &records[1];
+        assert!(generated.is_success() && !generated.print_requested);
+        assert_eq!(generated.request_id, "EXP-42");
+        assert_eq!(generated.copies, 0);
+        assert_eq!(generated.checksum, Some(crate::audit::sha256_hex(&bytes)));
+        assert_ne!(printed.request_id, generated.request_id);
+
+        let failed = &records[2];
+        assert_eq!(failed.printer_name, Some("Offline".to_string()));
+        assert!(matches!(failed.outcome, AuditOutcome::Failure { code, .. } if code == PdfError::Print(String::new()).code()));
+        assert_eq!(failed.copies, 0);
+        assert_eq!(failed.checksum, None);
+
+        assert!(matches!(records[3].outcome, AuditOutcome::Failure { code, .. } if code == PdfError::Validation(String::new()).code()));
+        assert_eq!(records[3].copies, 0);
+        // 印刷オプションの部数を記録
+        assert_eq!(records[4].copies, 3);
+        assert_eq!(records[4].printer_name, Some("Office".to_string()));
+    }
+}
@@ -1,24 +1,122 @@
 //! 設定管理
 
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+
+use printpdf::{DateTime, Op, ParsedFont};
+use serde::Deserialize;
+
+use crate::audit::{AuditSink, JsonlFileSink, NameRedaction, NoopSink};
+use crate::error::PdfError;
+use crate::fs::WriteRetry;
+use crate::models::Item;
+use crate::pdf::fonts::{FontRole, FontSource};
+use crate::pdf::generator::{PagePostprocess, DEFAULT_GLYPH_FALLBACK};
+use crate::pdf::layout::LayoutConfig;
+use crate::pdf::text_utils::{FormatOptions, KukanReplacement};
+use crate::print::backend::Backend;
+use crate::print::options::{Duplex, PrintOptions};
+use crate::print::sumatra::SUMATRA_PATH_ENV;
 
 /// PDF生成サービスの設定
 #[derive(Debug, Clone)]
 pub struct PdfConfig {
     /// PDF出力ディレクトリ
     pub output_path: PathBuf,
+    /// 出力ディレクトリの外の絶対パスへの出力を許可するか（既定は許可しない）
+    pub allow_absolute_output: bool,
     /// SumatraPDFの実行ファイルパス
     pub sumatra_path: Option<PathBuf>,
+    /// 印刷バックエンド
+    pub printer_backend: Backend,
+    /// IPPの論理プリンター名とURIの対応（`ipp`バックエンドで使用）
+    pub ipp_printers: BTreeMap<String, String>,
+    /// winspoolでPDFを直接印刷するプリンター名（PDFダイレクトプリント対応機のみ、`winspool`バックエンドで使用）
+    pub pdf_passthrough_printers: Vec<String>,
+    /// デフォルトプリンター（リクエストでプリンター名を指定しない場合に使用、Noneはシステムのデフォルト）
+    pub default_printer: Option<String>,
+    /// デフォルトの印刷オプション（リクエストで印刷オプションを指定しない場合に使用）
+    pub default_print_options: PrintOptions,
+    /// 出力ファイル書き込みの再試行設定（ファイルが使用中の場合）
+    pub write_retry: WriteRetry,
     /// ヘッドレスモード（印刷時にウィンドウを表示しない）
     pub headless: bool,
+    /// 印刷用データの整形オプション
+    pub format: FormatOptions,
+    /// レイアウト設定
+    pub layout: LayoutConfig,
+    /// 名前付きのレイアウト設定（事業所別など、`Item::layout_preset` でアイテムごとに選択）
+    pub layout_presets: BTreeMap<String, LayoutConfig>,
+    /// フォント候補のファイル名（Noneの場合はデフォルトの候補を使用）
+    pub font_candidates: Option<Vec<String>>,
+    /// フォントファイルのパス（設定した場合は候補を検索しない）
+    pub font_path: Option<PathBuf>,
+    /// 用途別のフォント（未設定の用途は本文フォントを使用）
+    pub role_fonts: HashMap<FontRole, FontSource>,
+    /// 本文フォントに存在しない文字（人名の外字など）のみを描画するフォールバックフォント
+    pub fallback_font: Option<FontSource>,
+    /// ページ操作を並列に構築するか（`parallel` feature有効時のみ有効）
+    pub parallel: bool,
+    /// フォントに存在しない文字の代替文字
+    pub glyph_fallback: String,
+    /// 厳格モード（フォントに存在しない文字をエラーとする）
+    pub strict: bool,
+    /// アイテムが空の場合に空白の精算書を1ページ出力するか
+    pub blank_on_empty: bool,
+    /// 重複入力の可能性がある旅費項目を警告するか（厳格モードでは常に警告）
+    pub check_duplicates: bool,
+    /// PDFの作成日時・更新日時を固定（Noneの場合は生成時の現在時刻）
+    pub fixed_date: Option<DateTime>,
+    /// リクエストの処理ごとに監査記録を渡す先（既定は記録しない）
+    pub audit_sink: Arc<dyn AuditSink>,
+    /// 監査記録の氏名の扱い
+    pub audit_redaction: NameRedaction,
+    /// 印刷に失敗した場合の生成したPDFの扱い（フォールバックプリンターを含め全て失敗した場合）
+    pub on_print_failure: PrintFailurePolicy,
+    /// リクエストの大きさの上限（生成前に検証）
+    pub limits: RequestLimits,
+    /// 描画前に文字列をNFC（合成済みの文字）に統一するか
+    pub unicode_nfc: bool,
+    /// ページごとの操作を `PdfPage` にする前に呼び出す処理
+    pub page_postprocess: Option<PagePostprocess>,
+    /// 環境変数の読み込み（`from_env`）で無視した設定（不正な値は `validate`、不明な変数は `warnings` で報告）
+    pub env_issues: Vec<ConfigIssue>,
 }
 
 impl Default for PdfConfig {
     fn default() -> Self {
         Self {
             output_path: PathBuf::from("./output"),
+            allow_absolute_output: false,
             sumatra_path: None,
+            printer_backend: Backend::Auto,
+            ipp_printers: BTreeMap::new(),
+            pdf_passthrough_printers: Vec::new(),
+            default_printer: None,
+            default_print_options: PrintOptions::default(),
+            write_retry: WriteRetry::default(),
             headless: true,
+            format: FormatOptions::default(),
+            layout: LayoutConfig::default(),
+            layout_presets: BTreeMap::new(),
+            font_candidates: None,
+            font_path: None,
+            role_fonts: HashMap::new(),
+            fallback_font: None,
+            parallel: true,
+            glyph_fallback: DEFAULT_GLYPH_FALLBACK.to_string(),
+            strict: false,
+            blank_on_empty: false,
+            check_duplicates: false,
+            fixed_date: None,
+            audit_sink: Arc::new(NoopSink),
+            audit_redaction: NameRedaction::default(),
+            on_print_failure: PrintFailurePolicy::default(),
+            limits: RequestLimits::default(),
+            unicode_nfc: true,
+            page_postprocess: None,
+            env_issues: Vec::new(),
         }
     }
 }
@@ -35,34 +133,1227 @@ impl PdfConfig {
         self
     }
 
+    /// 出力ディレクトリの外の絶対パスへの出力を許可するかを設定
+    ///
+    /// リクエストで任意の場所に書き込めるようになるため、リクエストの送信元を信頼できる場合のみ有効にする
+    pub fn with_allow_absolute_output(mut self, allow: bool) -> Self {
+        self.allow_absolute_output = allow;
+        self
+    }
+
     /// SumatraPDFのパスを設定
     pub fn with_sumatra_path(mut self, path: impl Into<PathBuf>) -> Self {
         self.sumatra_path = Some(path.into());
         self
     }
 
+    /// 印刷バックエンドを設定
+    pub fn with_printer_backend(mut self, backend: Backend) -> Self {
+        self.printer_backend = backend;
+        self
+    }
+
+    /// デフォルトプリンターを設定
+    pub fn with_default_printer(mut self, name: impl Into<String>) -> Self {
+        self.default_printer = Some(name.into());
+        self
+    }
+
+    /// デフォルトの印刷オプションを設定
+    pub fn with_default_print_options(mut self, options: PrintOptions) -> Self {
+        self.default_print_options = options;
+        self
+    }
+
+    /// IPPのプリンターを登録（例: `"倉庫"` → `ipp://192.168.1.20/ipp/print`）
+    pub fn with_ipp_printer(mut self, name: impl Into<String>, uri: impl Into<String>) -> Self {
+        self.ipp_printers.insert(name.into(), uri.into());
+        self
+    }
+
+    /// winspoolでPDFを直接印刷するプリンターを登録（PDFダイレクトプリント対応機のみ）
+    pub fn with_pdf_passthrough_printer(mut self, name: impl Into<String>) -> Self {
+        self.pdf_passthrough_printers.push(name.into());
+        self
+    }
+
+    /// 出力ファイル書き込みの再試行設定を設定
+    pub fn with_write_retry(mut self, retry: WriteRetry) -> Self {
+        self.write_retry = retry;
+        self
+    }
+
     /// ヘッドレスモードを設定
     pub fn with_headless(mut self, headless: bool) -> Self {
         self.headless = headless;
         self
     }
 
+    /// 整形オプションを設定
+    pub fn with_format_options(mut self, format: FormatOptions) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// レイアウト設定を設定
+    pub fn with_layout(mut self, layout: LayoutConfig) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// 監査記録を渡す先を設定（`Fn(AuditEntry)` のクロージャも指定できる）
+    ///
+    /// サービス・同期クライアントのリクエストの処理（生成・印刷）ごとに、成否に関わらず1回呼び出す
+    pub fn with_audit_sink(mut self, sink: impl AuditSink + 'static) -> Self {
+        self.audit_sink = Arc::new(sink);
+        self
+    }
+
+    /// 監査記録の氏名の扱いを設定
+    pub fn with_audit_redaction(mut self, redaction: NameRedaction) -> Self {
+        self.audit_redaction = redaction;
+        self
+    }
+
+    /// 印刷に失敗した場合の生成したPDFの扱いを設定
+    ///
+    /// 再試行で同じ内容を二重に印刷しないよう、失敗したPDFを削除または別のディレクトリに移動する。
+    /// 一時ファイル（`PdfRequest::with_temp_output`）は設定に関わらず削除する
+    pub fn with_on_print_failure(mut self, policy: PrintFailurePolicy) -> Self {
+        self.on_print_failure = policy;
+        self
+    }
+
+    /// リクエストの大きさの上限を設定（リクエストごとに `PdfRequest::with_limits` でさらに制限できる）
+    pub fn with_limits(mut self, limits: RequestLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// 描画前に文字列をNFC（合成済みの文字）に統一するかを設定（既定は統一する）
+    ///
+    /// 分解された濁点・半濁点（NFD）を含むデータをフォントによらず同じように描画する。
+    /// 全角英数字等も変換する場合は `PdfRequest::with_normalize_text`（NFKC）を使用する
+    pub fn with_unicode_nfc(mut self, enabled: bool) -> Self {
+        self.unicode_nfc = enabled;
+        self
+    }
+
+    /// 区間を折り返す前に順に適用する置換を設定（既定の置換を含めない場合は既定の置換も無効になる）
+    ///
+    /// 既定は `KukanReplacement::defaults`。空の場合は置換しない
+    pub fn with_kukan_replacements(mut self, replacements: Vec<KukanReplacement>) -> Self {
+        self.format.kukan_replacements = replacements;
+        self
+    }
+
+    /// ページごとの操作を `PdfPage` にする前に呼び出す処理を設定（注釈・スタンプの追加等）
+    ///
+    /// レイアウトの描画後、PDF内のページ番号（0始まり、2面付けの場合はA4横の用紙ごと）とそのページの操作で呼び出す
+    pub fn with_page_postprocess(mut self, f: impl Fn(usize, &mut Vec<Op>) + Send + Sync + 'static) -> Self {
+        self.page_postprocess = Some(PagePostprocess::new(f));
+        self
+    }
+
+    /// 名前付きのレイアウト設定を登録（同じ名前は上書き）
+    pub fn with_layout_preset(mut self, name: impl Into<String>, layout: LayoutConfig) -> Self {
+        self.layout_presets.insert(name.into(), layout);
+        self
+    }
+
+    /// フォント候補のファイル名を優先順位順に設定
+    pub fn with_font_candidates(mut self, candidates: Vec<String>) -> Self {
+        self.font_candidates = Some(candidates);
+        self
+    }
+
+    /// フォントファイルのパスを設定
+    pub fn with_font_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.font_path = Some(path.into());
+        self
+    }
+
+    /// 用途別のフォントを設定（例: タイトルに明朝体、本文にゴシック体）
+    pub fn with_role_font(mut self, role: FontRole, source: FontSource) -> Self {
+        self.role_fonts.insert(role, source);
+        self
+    }
+
+    /// フォールバックフォントを設定（本文フォントに存在しない文字のみをこのフォントで描画）
+    pub fn with_fallback_font(mut self, source: FontSource) -> Self {
+        self.fallback_font = Some(source);
+        self
+    }
+
+    /// 数値用のフォントを設定（金額を欧文フォントで表示する場合など）
+    pub fn with_numeric_font(self, path: impl Into<PathBuf>) -> Self {
+        self.with_role_font(FontRole::Numeric, FontSource::path(path))
+    }
+
+    /// ページ操作を並列に構築するかを設定（`parallel` feature有効時のみ有効）
+    pub fn with_parallel(mut self, parallel: bool) -> Self {
+        self.parallel = parallel;
+        self
+    }
+
+    /// フォントに存在しない文字の代替文字を設定
+    pub fn with_glyph_fallback(mut self, fallback: impl Into<String>) -> Self {
+        self.glyph_fallback = fallback.into();
+        self
+    }
+
+    /// 厳格モードを設定（フォントに存在しない文字があればエラーとする）
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// アイテムが空の場合に空白の精算書を1ページ出力するかを設定
+    ///
+    /// 手書き記入用の様式を印刷する場合に使用する（デフォルトはfalseで `PdfError::Validation("項目が空です")` を返す）
+    pub fn with_blank_on_empty(mut self, blank_on_empty: bool) -> Self {
+        self.blank_on_empty = blank_on_empty;
+        self
+    }
+
+    /// 重複入力の可能性がある旅費項目（直前の項目と日付・行先が同じ）を警告するかを設定
+    ///
+    /// 印刷内容は変更せず、生成レポートの警告として記録する
+    pub fn with_duplicate_check(mut self, check: bool) -> Self {
+        self.check_duplicates = check;
+        self
+    }
+
+    /// PDFの作成日時・更新日時を固定
+    ///
+    /// 再現可能なビルドやゴールデンファイルとの比較のため、同じ入力から同じバイト列のPDFを出力する
+    pub fn with_fixed_date(mut self, date: Option<DateTime>) -> Self {
+        self.fixed_date = date;
+        self
+    }
+
     /// 環境変数から設定を読み込み
+    ///
+    /// 値が不正な変数・不明な `PDF_` で始まる変数は無視し、`env_issues` に記録する
+    /// （不正な値は `validate` のエラー、不明な変数は `warnings` の警告として報告）
     pub fn from_env() -> Self {
+        Self::from_vars(std::env::vars())
+    }
+
+    /// 環境変数の一覧から設定を読み込み（`from_env` の本体）
+    pub(crate) fn from_vars(vars: impl IntoIterator<Item = (String, String)>) -> Self {
+        let vars: BTreeMap<String, String> = vars.into_iter().collect();
+        let var = |name: &str| vars.get(name).cloned();
         let mut config = Self::default();
+        let invalid = |name: &str, value: &str| {
+            tracing::warn!("環境変数{}の値が不正なため無視: {}", name, value);
+            ConfigIssue::InvalidEnvVar(name.to_string(), value.to_string())
+        };
+        let mut issues = Vec::new();
 
-        if let Ok(path) = std::env::var("PDF_OUTPUT_PATH") {
+        if let Some(path) = var("PDF_OUTPUT_PATH") {
             config.output_path = PathBuf::from(path);
         }
 
-        if let Ok(path) = std::env::var("SUMATRA_PDF_PATH") {
+        if let Some(path) = var(SUMATRA_PATH_ENV) {
             config.sumatra_path = Some(PathBuf::from(path));
         }
 
-        if let Ok(val) = std::env::var("PDF_HEADLESS") {
-            config.headless = val.to_lowercase() != "false";
+        if let Some(name) = var("PDF_PRINTER_BACKEND") {
+            match Backend::from_name(&name) {
+                Some(backend) => config.printer_backend = backend,
+                None => issues.push(invalid("PDF_PRINTER_BACKEND", &name)),
+            }
+        }
+
+        if let Some(name) = var("PDF_DEFAULT_PRINTER") {
+            if !name.trim().is_empty() {
+                config.default_printer = Some(name);
+            }
         }
 
+        // カンマ区切り（例: "Backup1,Backup2"）
+        if let Some(printers) = var("PDF_PASSTHROUGH_PRINTERS") {
+            config.pdf_passthrough_printers = printers
+                .split(',')
+                .map(|s| s.trim())
+                .filter(|s| !s.is_empty())
+                .map(|s| s.to_string())
+                .collect();
+        }
+        if let Some(printers) = var("PDF_FALLBACK_PRINTERS") {
+            config.default_print_options.fallback_printers = printers
+                .split(',')
+                .map(|s| s.trim())
+                .filter(|s| !s.is_empty())
+                .map(|s| s.to_string())
+                .collect();
+        }
+
+        // true / false（1 / 0）
+        if let Some(val) = var("PDF_HEADLESS") {
+            match val.trim().to_lowercase().as_str() {
+                "true" | "1" => config.headless = true,
+                "false" | "0" => config.headless = false,
+                _ => issues.push(invalid("PDF_HEADLESS", &val)),
+            }
+        }
+
+        if let Some(path) = var("PDF_AUDIT_LOG") {
+            config.audit_sink = Arc::new(JsonlFileSink::new(path));
+        }
+
+        // keep / delete（PDF_FAILED_PRINT_DIR を設定した場合はそのディレクトリに移動）
+        if let Some(policy) = var("PDF_ON_PRINT_FAILURE") {
+            match policy.trim().to_lowercase().as_str() {
+                "keep" => config.on_print_failure = PrintFailurePolicy::Keep,
+                "delete" => config.on_print_failure = PrintFailurePolicy::Delete,
+                _ => issues.push(invalid("PDF_ON_PRINT_FAILURE", &policy)),
+            }
+        }
+        if let Some(dir) = var("PDF_FAILED_PRINT_DIR") {
+            if !dir.trim().is_empty() {
+                config.on_print_failure = PrintFailurePolicy::MoveToFailedDir(PathBuf::from(dir));
+            }
+        }
+
+        if let Some(path) = var("PDF_FONT_PATH") {
+            config.font_path = Some(PathBuf::from(path));
+        }
+        if let Some(path) = var("PDF_FALLBACK_FONT_PATH") {
+            config.fallback_font = Some(FontSource::path(path));
+        }
+
+        // JSONの配列（例: [{"from":"_","to":"　"},{"regex":"\\s*\\(往復\\)","to":"　往復"}]、既定の置換と置き換え）
+        if let Some(json) = var("PDF_KUKAN_REPLACEMENTS") {
+            match parse_kukan_replacements(&json) {
+                Ok(replacements) => config.format.kukan_replacements = replacements,
+                Err(_) => issues.push(invalid("PDF_KUKAN_REPLACEMENTS", &json)),
+            }
+        }
+
+        // UNIX時間の秒数（作成日時と支払日の2桁の年の基準を固定する。`SOURCE_DATE_EPOCH` は読み込まない）
+        if let Some(epoch) = var("PDF_FIXED_DATE") {
+            match epoch.trim().parse().ok().and_then(|secs| DateTime::from_unix_timestamp(secs).ok()) {
+                Some(date) => config.fixed_date = Some(date),
+                None => issues.push(invalid("PDF_FIXED_DATE", &epoch)),
+            }
+        }
+
+        // 読み込まない `PDF_` で始まる変数（綴りの誤り等）
+        for name in vars.keys().filter(|name| name.starts_with("PDF_") && !ENV_VARS.contains(&name.as_str())) {
+            tracing::warn!("不明な環境変数のため無視: {}", name);
+            issues.push(ConfigIssue::UnknownEnvVar(name.clone()));
+        }
+
+        config.env_issues = issues;
         config
     }
+
+    /// 設定ファイル（TOML）を読み込み
+    ///
+    /// 記載していないキーは既定値を使用する。不明なキー・不正な値は `PdfError::Config` を返す
+    pub fn from_toml_file(path: impl AsRef<Path>) -> Result<Self, PdfError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| PdfError::Config(format!("設定ファイルを読み込めません: {:?}: {}", path, e)))?;
+        Self::from_toml_str(&text)
+    }
+
+    /// TOMLの文字列から設定を読み込み（`from_toml_file` の本体）
+    pub fn from_toml_str(text: &str) -> Result<Self, PdfError> {
+        let file: ConfigFile =
+            toml::from_str(text).map_err(|e| PdfError::Config(format!("設定ファイルが不正です: {}", e)))?;
+        let mut config = Self::default();
+
+        if let Some(path) = file.output_path {
+            config.output_path = path;
+        }
+        config.sumatra_path = file.sumatra_path;
+        if let Some(name) = file.printer_backend {
+            config.printer_backend = Backend::from_name(&name)
+                .ok_or_else(|| PdfError::Config(format!("printer_backendの値が不正です: {}", name)))?;
+        }
+        config.default_printer = file.default_printer.filter(|name| !name.trim().is_empty());
+        config.pdf_passthrough_printers = file.pdf_passthrough_printers;
+        if let Some(headless) = file.headless {
+            config.headless = headless;
+        }
+        config.font_path = file.font_path;
+        config.fallback_font = file.fallback_font_path.map(FontSource::path);
+        if let Some(entries) = file.kukan_replacements {
+            config.format.kukan_replacements = entries
+                .into_iter()
+                .map(ReplacementEntry::into_replacement)
+                .collect::<Result<_, _>>()?;
+        }
+
+        let settings = file.default_print_settings;
+        config.default_print_options = PrintOptions {
+            fallback_printers: settings.fallback_printers,
+            duplex: settings
+                .duplex
+                .map(|name| {
+                    Duplex::from_name(&name)
+                        .ok_or_else(|| PdfError::Config(format!("default_print_settings.duplexの値が不正です: {}", name)))
+                })
+                .transpose()?,
+            paper: settings.paper,
+            copies: settings.copies,
+            check_capabilities: settings.check_capabilities,
+        };
+        Ok(config)
+    }
+
+    /// 出力先のパスを絶対パスに解決
+    ///
+    /// 相対パスは `output_path` を基準ディレクトリとして解決する。
+    /// `..` やシンボリックリンクで基準ディレクトリの外を指す場合、
+    /// 基準ディレクトリの外の絶対パスの場合（`with_allow_absolute_output` で許可した場合を除く）は `PdfError::Config` を返す
+    pub fn resolve_output_path(&self, path: &Path) -> Result<PathBuf, PdfError> {
+        let outside = || PdfError::Config(format!("出力パスが出力ディレクトリの外を指しています: {:?}", path));
+        let base = std::path::absolute(&self.output_path)
+            .map_err(|e| PdfError::Config(format!("出力ディレクトリを解決できません: {:?}: {}", self.output_path, e)))?;
+        let base = normalize_path(&base)
+            .ok_or_else(|| PdfError::Config(format!("出力ディレクトリが不正です: {:?}", self.output_path)))?;
+
+        let resolved = if path.is_absolute() {
+            let resolved =
+                normalize_path(path).ok_or_else(|| PdfError::Config(format!("出力パスが不正です: {:?}", path)))?;
+            if self.allow_absolute_output {
+                return Ok(resolved);
+            }
+            resolved
+        } else {
+            base.join(normalize_path(path).ok_or_else(outside)?)
+        };
+
+        // シンボリックリンクを解決した実際の位置で確認
+        if !canonicalize_existing(&resolved).starts_with(canonicalize_existing(&base)) {
+            return Err(outside());
+        }
+        Ok(resolved)
+    }
+
+    /// 設定を検証
+    ///
+    /// ファイルやディレクトリの存在など、実際の生成時まで発覚しない問題を事前に検出する
+    ///
+    /// # Returns
+    /// 問題がある場合は全ての問題点の一覧
+    pub fn validate(&self) -> Result<(), Vec<ConfigIssue>> {
+        let mut issues: Vec<ConfigIssue> = self.env_issues.iter().filter(|issue| !issue.is_warning()).cloned().collect();
+
+        // 出力ディレクトリ（存在しない場合は作成可能か）
+        if self.output_path.exists() {
+            if !self.output_path.is_dir() {
+                issues.push(ConfigIssue::OutputPathNotDirectory(self.output_path.clone()));
+            }
+        } else if !is_creatable_dir(&self.output_path) {
+            issues.push(ConfigIssue::OutputPathNotCreatable(self.output_path.clone()));
+        }
+
+        // SumatraPDF
+        if let Some(ref path) = self.sumatra_path {
+            if !path.exists() {
+                issues.push(ConfigIssue::SumatraNotFound(path.clone()));
+            } else if !path.is_file() {
+                issues.push(ConfigIssue::SumatraNotFile(path.clone()));
+            }
+        }
+
+        // 印刷バックエンド
+        let backend_available = match self.printer_backend {
+            Backend::Winspool => cfg!(all(windows, feature = "native-print")),
+            Backend::Ipp => cfg!(feature = "ipp"),
+            Backend::Auto | Backend::Sumatra => true,
+        };
+        if !backend_available {
+            issues.push(ConfigIssue::PrinterBackendUnavailable(self.printer_backend));
+        }
+        if self.printer_backend == Backend::Ipp && self.ipp_printers.is_empty() {
+            issues.push(ConfigIssue::IppPrintersMissing);
+        }
+
+        // フォント
+        let fallback_path = self.fallback_font.as_ref().and_then(|source| source.path.as_ref());
+        for path in self.font_path.iter().chain(fallback_path) {
+            match std::fs::read(path) {
+                Ok(data) => {
+                    let mut warnings = Vec::new();
+                    if ParsedFont::from_bytes(&data, 0, &mut warnings).is_none() {
+                        issues.push(ConfigIssue::FontParse(path.clone()));
+                    }
+                }
+                Err(_) => issues.push(ConfigIssue::FontNotFound(path.clone())),
+            }
+        }
+
+        // レイアウト
+        issues.extend(self.layout.issues().into_iter().map(ConfigIssue::Layout));
+        for (name, layout) in &self.layout_presets {
+            issues.extend(
+                layout
+                    .issues()
+                    .into_iter()
+                    .map(|issue| ConfigIssue::Layout(format!("プリセット「{}」: {}", name, issue))),
+            );
+        }
+
+        if issues.is_empty() {
+            Ok(())
+        } else {
+            Err(issues)
+        }
+    }
+
+    /// 起動を妨げない設定の問題点（不明な環境変数など、`validate` では報告しない）
+    pub fn warnings(&self) -> Vec<ConfigIssue> {
+        self.env_issues.iter().filter(|issue| issue.is_warning()).cloned().collect()
+    }
+}
+
+/// 設定ファイル（TOML）のキー
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ConfigFile {
+    output_path: Option<PathBuf>,
+    sumatra_path: Option<PathBuf>,
+    printer_backend: Option<String>,
+    default_printer: Option<String>,
+    pdf_passthrough_printers: Vec<String>,
+    headless: Option<bool>,
+    font_path: Option<PathBuf>,
+    fallback_font_path: Option<PathBuf>,
+    /// 記載した場合は既定の置換と置き換え（空の配列は置換しない）
+    kukan_replacements: Option<Vec<ReplacementEntry>>,
+    default_print_settings: PrintSettingsFile,
+}
+
+/// 区間の置換の指定（`{from, to}` または `{regex, to}`、JSON・TOMLで共通）
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ReplacementEntry {
+    from: Option<String>,
+    regex: Option<String>,
+    to: String,
+}
+
+impl ReplacementEntry {
+    fn into_replacement(self) -> Result<KukanReplacement, PdfError> {
+        match (self.from, self.regex) {
+            (Some(from), None) => Ok(KukanReplacement::literal(from, self.to)),
+            (None, Some(pattern)) => KukanReplacement::regex(&pattern, self.to),
+            _ => Err(PdfError::Config(
+                "区間の置換の指定が不正です: from と regex のどちらか一方を指定してください".to_string(),
+            )),
+        }
+    }
+}
+
+/// 設定ファイルの `[default_print_settings]`
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct PrintSettingsFile {
+    fallback_printers: Vec<String>,
+    duplex: Option<String>,
+    paper: Option<String>,
+    copies: Option<u32>,
+    check_capabilities: bool,
+}
+
+/// 印刷に失敗した場合の生成したPDFの扱い
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum PrintFailurePolicy {
+    /// 出力先に残す
+    #[default]
+    Keep,
+    /// 削除する
+    Delete,
+    /// 指定したディレクトリに移動する（相対パスは出力ディレクトリが基準、ディレクトリが無い場合は作成）
+    ///
+    /// 同じ名前のファイルがある場合は連番を付けて（`job-1.pdf`）以前のPDFを残す
+    MoveToFailedDir(PathBuf),
+}
+
+/// リクエストの大きさの上限
+///
+/// 異常な入力データで生成中にメモリを使い果たさないよう、生成前に検証する。
+/// 上限ちょうどは許可し、超えた場合は `PdfError::Validation` を返す
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequestLimits {
+    /// 1リクエストのアイテム数
+    pub max_items: usize,
+    /// 1アイテムの旅費項目数
+    pub max_ryohi_per_item: usize,
+    /// リクエスト全体の文字列フィールドの合計文字数
+    pub max_total_chars: usize,
+    /// 1旅費項目の摘要の数
+    pub max_details_per_ryohi: usize,
+    /// 1つの文字列フィールドの文字数
+    pub max_string_len: usize,
+}
+
+impl RequestLimits {
+    /// 標準の上限（通常の精算書では到達しない値）
+    pub const DEFAULT: RequestLimits = RequestLimits {
+        max_items: 1_000,
+        max_ryohi_per_item: 1_000,
+        max_total_chars: 5_000_000,
+        max_details_per_ryohi: 100,
+        max_string_len: 10_000,
+    };
+
+    /// アイテム数の上限を設定
+    pub fn with_max_items(mut self, max: usize) -> Self {
+        self.max_items = max;
+        self
+    }
+
+    /// 1アイテムの旅費項目数の上限を設定
+    pub fn with_max_ryohi_per_item(mut self, max: usize) -> Self {
+        self.max_ryohi_per_item = max;
+        self
+    }
+
+    /// 合計文字数の上限を設定
+    pub fn with_max_total_chars(mut self, max: usize) -> Self {
+        self.max_total_chars = max;
+        self
+    }
+
+    /// 1旅費項目の摘要の数の上限を設定
+    pub fn with_max_details_per_ryohi(mut self, max: usize) -> Self {
+        self.max_details_per_ryohi = max;
+        self
+    }
+
+    /// 1つの文字列フィールドの文字数の上限を設定
+    pub fn with_max_string_len(mut self, max: usize) -> Self {
+        self.max_string_len = max;
+        self
+    }
+
+    /// 項目ごとに小さい方の上限（リクエストの指定で設定の上限を緩めることはできない）
+    pub fn tightened(&self, other: &RequestLimits) -> RequestLimits {
+        RequestLimits {
+            max_items: self.max_items.min(other.max_items),
+            max_ryohi_per_item: self.max_ryohi_per_item.min(other.max_ryohi_per_item),
+            max_total_chars: self.max_total_chars.min(other.max_total_chars),
+            max_details_per_ryohi: self.max_details_per_ryohi.min(other.max_details_per_ryohi),
+            max_string_len: self.max_string_len.min(other.max_string_len),
+        }
+    }
+
+    /// アイテムが上限内か検証
+    ///
+    /// 最初に超えた上限の名前とアイテムの位置（`item[0].ryohi[1].detail[0]` 等）を含む `PdfError::Validation` を返す
+    pub fn check(&self, items: &[Item]) -> Result<(), PdfError> {
+        let exceeded = |limit: &str, max: usize, actual: usize, location: String| {
+            Err(PdfError::Validation(format!(
+                "上限（{}={}）を超えています: {}（{}）",
+                limit, max, location, actual
+            )))
+        };
+
+        if items.len() > self.max_items {
+            return exceeded("max_items", self.max_items, items.len(), "アイテム数".to_string());
+        }
+
+        let mut total_chars = 0usize;
+        for (item_index, item) in items.iter().enumerate() {
+            if item.ryohi.len() > self.max_ryohi_per_item {
+                let location = format!("item[{}].ryohi", item_index);
+                return exceeded("max_ryohi_per_item", self.max_ryohi_per_item, item.ryohi.len(), location);
+            }
+            if let Some((i, ryohi)) = item
+                .ryohi
+                .iter()
+                .enumerate()
+                .find(|(_, ryohi)| ryohi.detail.len() > self.max_details_per_ryohi)
+            {
+                let location = format!("item[{}].ryohi[{}].detail", item_index, i);
+                return exceeded("max_details_per_ryohi", self.max_details_per_ryohi, ryohi.detail.len(), location);
+            }
+
+            let mut result = Ok(());
+            item.for_each_text(|field, value| {
+                if result.is_err() {
+                    return;
+                }
+                let len = value.chars().count();
+                total_chars += len;
+                if len > self.max_string_len {
+                    let location = format!("item[{}].{}", item_index, field());
+                    result = exceeded("max_string_len", self.max_string_len, len, location);
+                } else if total_chars > self.max_total_chars {
+                    let location = format!("item[{}].{}", item_index, field());
+                    result = exceeded("max_total_chars", self.max_total_chars, total_chars, location);
+                }
+            });
+            result?;
+        }
+        Ok(())
+    }
+}
+
+impl Default for RequestLimits {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// 設定の問題点
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigIssue {
+    /// 出力パスがディレクトリではない
+    OutputPathNotDirectory(PathBuf),
+    /// 出力ディレクトリを作成できない
+    OutputPathNotCreatable(PathBuf),
+    /// SumatraPDFが見つからない
+    SumatraNotFound(PathBuf),
+    /// SumatraPDFのパスがファイルではない
+    SumatraNotFile(PathBuf),
+    /// 印刷バックエンドがこのビルドでは使用できない
+    PrinterBackendUnavailable(Backend),
+    /// IPPバックエンドにプリンターが登録されていない
+    IppPrintersMissing,
+    /// フォントファイルが見つからない
+    FontNotFound(PathBuf),
+    /// フォントファイルをパースできない
+    FontParse(PathBuf),
+    /// レイアウト設定がページに収まらない
+    Layout(String),
+    /// 環境変数の値が不正なため無視した（変数名, 値）
+    InvalidEnvVar(String, String),
+    /// 不明な環境変数（`PDF_` で始まる読み込まない変数）を無視した
+    UnknownEnvVar(String),
+}
+
+impl ConfigIssue {
+    /// 警告か（`validate` の失敗とせず `PdfConfig::warnings` で報告する）
+    pub fn is_warning(&self) -> bool {
+        matches!(self, ConfigIssue::UnknownEnvVar(_))
+    }
+}
+
+impl std::fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigIssue::OutputPathNotDirectory(path) => {
+                write!(f, "出力パスがディレクトリではありません: {:?}", path)
+            }
+            ConfigIssue::OutputPathNotCreatable(path) => {
+                write!(f, "出力ディレクトリを作成できません: {:?}", path)
+            }
+            ConfigIssue::SumatraNotFound(path) => {
+                write!(f, "SumatraPDFが見つかりません: {:?}", path)
+            }
+            ConfigIssue::SumatraNotFile(path) => {
+                write!(f, "SumatraPDFのパスがファイルではありません: {:?}", path)
+            }
+            ConfigIssue::PrinterBackendUnavailable(backend) => {
+                write!(f, "印刷バックエンド{:?}はこのビルドでは使用できません", backend)
+            }
+            ConfigIssue::IppPrintersMissing => {
+                write!(f, "IPPプリンターが登録されていません")
+            }
+            ConfigIssue::FontNotFound(path) => {
+                write!(f, "フォントファイルが見つかりません: {:?}", path)
+            }
+            ConfigIssue::FontParse(path) => {
+                write!(f, "フォントファイルをパースできません: {:?}", path)
+            }
+            ConfigIssue::Layout(message) => write!(f, "レイアウト設定エラー: {}", message),
+            ConfigIssue::InvalidEnvVar(name, value) => {
+                write!(f, "環境変数{}の値が不正なため無視しました: {}", name, value)
+            }
+            ConfigIssue::UnknownEnvVar(name) => write!(f, "不明な環境変数のため無視しました: {}", name),
+        }
+    }
+}
+
+/// `PdfConfig::from_env` が読み込む `PDF_` で始まる環境変数
+const ENV_VARS: &[&str] = &[
+    "PDF_OUTPUT_PATH",
+    "PDF_PRINTER_BACKEND",
+    "PDF_DEFAULT_PRINTER",
+    "PDF_FALLBACK_PRINTERS",
+    "PDF_PASSTHROUGH_PRINTERS",
+    "PDF_HEADLESS",
+    "PDF_AUDIT_LOG",
+    "PDF_ON_PRINT_FAILURE",
+    "PDF_FAILED_PRINT_DIR",
+    "PDF_FONT_PATH",
+    "PDF_FALLBACK_FONT_PATH",
+    "PDF_KUKAN_REPLACEMENTS",
+    "PDF_FIXED_DATE",
+];
+
+/// 問題点の一覧を1つのメッセージにまとめる
+pub fn format_issues(issues: &[ConfigIssue]) -> String {
+    let messages: Vec<String> = issues.iter().map(|issue| issue.to_string()).collect();
+    format!("{}件の問題があります: {}", issues.len(), messages.join("; "))
+}
+
+/// 区間の置換の一覧をJSONから解析
+///
+/// 各要素は `{"from": 置換前, "to": 置換後}`（文字列）または `{"regex": 正規表現, "to": 置換後}`
+fn parse_kukan_replacements(json: &str) -> Result<Vec<KukanReplacement>, PdfError> {
+    let entries: Vec<ReplacementEntry> = serde_json::from_str(json)
+        .map_err(|e| PdfError::Config(format!("区間の置換の指定が不正です: {}", e)))?;
+    entries.into_iter().map(ReplacementEntry::into_replacement).collect()
+}
+
+/// `.` と `..` を字句的に除去（先頭より上に戻る場合はNone）
+fn normalize_path(path: &Path) -> Option<PathBuf> {
+    let mut normalized = PathBuf::new();
+    let mut depth = 0usize;
+    for component in path.components() {
+        match component {
+            Component::Prefix(_) | Component::RootDir => normalized.push(component),
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if depth == 0 {
+                    return None;
+                }
+                normalized.pop();
+                depth -= 1;
+            }
+            Component::Normal(name) => {
+                normalized.push(name);
+                depth += 1;
+            }
+        }
+    }
+    Some(normalized)
+}
+
+/// 存在する最も近い親までシンボリックリンクを解決したパス（残りはそのまま連結）
+fn canonicalize_existing(path: &Path) -> PathBuf {
+    for ancestor in path.ancestors() {
+        if let Ok(canonical) = std::fs::canonicalize(ancestor) {
+            return match path.strip_prefix(ancestor) {
+                Ok(rest) if !rest.as_os_str().is_empty() => canonical.join(rest),
+                _ => canonical,
+            };
+        }
+    }
+    path.to_path_buf()
+}
+
+/// 存在しないディレクトリを作成可能か（最も近い既存の親がディレクトリか）
+fn is_creatable_dir(path: &Path) -> bool {
+    path.ancestors()
+        .skip(1)
+        .find(|p| p.as_os_str().is_empty() || p.exists())
+        .map(|p| p.as_os_str().is_empty() || p.is_dir())
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Ryohi;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("print_pdf_config_{}", name))
+    }
+
+    #[test]
+    fn test_parse_kukan_replacements() {
+        let replacements = parse_kukan_replacements(
+            r#"[{"from":"_九州外空車適用","to":"　九州外空車適用"},{"regex":"\\s*\\(往復\\)","to":"　往復"}]"#,
+        )
+        .unwrap();
+        assert!(matches!(&replacements[0], KukanReplacement::Literal { from, .. } if from == "_九州外空車適用"));
+        assert!(matches!(&replacements[1], KukanReplacement::Regex { pattern, to } if pattern.as_str() == r"\s*\(往復\)" && to == "　往復"));
+        assert!(parse_kukan_replacements("[]").unwrap().is_empty());
+
+        // 置換後の文字列が無い・from と regex の両方・不正な正規表現はエラー
+        for json in [r#"[{"from":"a"}]"#, r#"[{"from":"a","regex":"b","to":"c"}]"#, r#"[{"regex":"(","to":""}]"#, "{}"] {
+            assert!(matches!(parse_kukan_replacements(json), Err(PdfError::Config(_))), "{}", json);
+        }
+
+        let config = PdfConfig::new().with_kukan_replacements(replacements);
+        assert_eq!(config.format.kukan_replacements.len(), 2);
+
+        // 設定ファイルでも指定できる（記載しない場合は既定の置換、空の配列は置換しない）
+        let config = PdfConfig::from_toml_str(
+            r#"
+[[kukan_replacements]]
+from = "_九州外空車適用"
+to = "　九州外空車適用"
+
+[[kukan_replacements]]
+regex = '\s*\(往復\)'
+to = "　往復"
+"#,
+        )
+        .unwrap();
+        assert!(matches!(&config.format.kukan_replacements[1], KukanReplacement::Regex { pattern, .. } if pattern.as_str() == r"\s*\(往復\)"));
+        assert!(PdfConfig::from_toml_str("kukan_replacements = []").unwrap().format.kukan_replacements.is_empty());
+        assert!(matches!(
+            PdfConfig::from_toml_str("[[kukan_replacements]]\nfrom = \"a\""),
+            Err(PdfError::Config(_))
+        ));
+        assert_eq!(PdfConfig::new().format.kukan_replacements.len(), KukanReplacement::defaults().len());
+    }
+
+    #[test]
+    fn test_resolve_output_path_relative() {
+        let base = temp_path("base");
+        let config = PdfConfig::new().with_output_path(&base);
+        let resolved = config.resolve_output_path(Path::new("./2025/../jobs/report.pdf")).unwrap();
+        assert_eq!(resolved, base.join("jobs").join("report.pdf"));
+        assert!(resolved.is_absolute());
+
+        // 基準ディレクトリの下の絶対パスはそのまま
+        let absolute = base.join("other.pdf");
+        assert_eq!(config.resolve_output_path(&absolute).unwrap(), absolute);
+    }
+
+    #[test]
+    fn test_resolve_output_path_absolute_outside() {
+        let config = PdfConfig::new().with_output_path(temp_path("base"));
+        let outside = temp_path("other.pdf");
+        assert!(matches!(config.resolve_output_path(&outside), Err(PdfError::Config(_))));
+
+        // 明示的に許可した場合のみ出力できる
+        let config = config.with_allow_absolute_output(true);
+        assert_eq!(config.resolve_output_path(&outside).unwrap(), outside);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_resolve_output_path_symlink_escape() {
+        let base = temp_path("symlink_base");
+        let target = temp_path("symlink_target");
+        std::fs::create_dir_all(&base).unwrap();
+        std::fs::create_dir_all(&target).unwrap();
+        let link = base.join("link");
+        let _ = std::fs::remove_file(&link);
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+
+        let config = PdfConfig::new().with_output_path(&base);
+        let result = config.resolve_output_path(Path::new("link/escape.pdf"));
+        let inside = config.resolve_output_path(Path::new("jobs/report.pdf"));
+        std::fs::remove_file(&link).unwrap();
+        std::fs::remove_dir(&base).unwrap();
+        std::fs::remove_dir(&target).unwrap();
+
+        assert!(matches!(result, Err(PdfError::Config(_))), "{:?}", result);
+        assert!(inside.is_ok());
+    }
+
+    #[test]
+    fn test_resolve_output_path_traversal() {
+        let config = PdfConfig::new().with_output_path(temp_path("base"));
+        for path in ["../escape.pdf", "jobs/../../escape.pdf"] {
+            let result = config.resolve_output_path(Path::new(path));
+            assert!(matches!(result, Err(PdfError::Config(_))), "{}", path);
+        }
+    }
+
+    #[test]
+    fn test_from_vars_reports_ignored_values() {
+        let vars = |pairs: &[(&str, &str)]| {
+            pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect::<Vec<_>>()
+        };
+        let config = PdfConfig::from_vars(vars(&[
+            ("PDF_DEFAULT_PRINTER", "Office"),
+            ("PDF_HEADLESS", "0"),
+            ("PATH", "/usr/bin"),
+        ]));
+        assert_eq!(config.default_printer, Some("Office".to_string()));
+        assert!(!config.headless);
+        assert!(config.env_issues.is_empty());
+
+        // 日時の固定は `PDF_FIXED_DATE` を指定した場合のみ（`SOURCE_DATE_EPOCH` は無視）
+        let config = PdfConfig::from_vars(vars(&[("SOURCE_DATE_EPOCH", "1711929600")]));
+        assert_eq!(config.fixed_date, None);
+        let config = PdfConfig::from_vars(vars(&[("PDF_FIXED_DATE", "1711929600")]));
+        assert_eq!(config.fixed_date, Some(DateTime::from_unix_timestamp(1_711_929_600).unwrap()));
+        assert!(config.env_issues.is_empty());
+
+        let config = PdfConfig::from_vars(vars(&[
+            ("PDF_FALLBACK_FONT_PATH", "/fonts/ipamjm.ttf"),
+            ("PDF_PASSTHROUGH_PRINTERS", "Label, Warehouse"),
+        ]));
+        assert_eq!(config.fallback_font, Some(FontSource::path("/fonts/ipamjm.ttf")));
+        assert_eq!(config.pdf_passthrough_printers, vec!["Label".to_string(), "Warehouse".to_string()]);
+        assert!(config.env_issues.is_empty());
+
+        let config = PdfConfig::from_vars(vars(&[
+            ("PDF_PRINTER_BACKEND", "fax"),
+            ("PDF_HEADLESS", "no-window"),
+            ("PDF_ON_PRINT_FAILURE", "retry"),
+            ("PDF_KUKAN_REPLACEMENTS", "not json"),
+            ("PDF_FIXED_DATE", "yesterday"),
+            ("PDF_OUTPUTPATH", "/srv/output"),
+        ]))
+        .with_output_path(temp_path("env_issues"));
+        assert_eq!(config.printer_backend, Backend::Auto);
+        assert!(config.headless);
+        assert_eq!(
+            config.env_issues,
+            vec![
+                ConfigIssue::InvalidEnvVar("PDF_PRINTER_BACKEND".to_string(), "fax".to_string()),
+                ConfigIssue::InvalidEnvVar("PDF_HEADLESS".to_string(), "no-window".to_string()),
+                ConfigIssue::InvalidEnvVar("PDF_ON_PRINT_FAILURE".to_string(), "retry".to_string()),
+                ConfigIssue::InvalidEnvVar("PDF_KUKAN_REPLACEMENTS".to_string(), "not json".to_string()),
+                ConfigIssue::InvalidEnvVar("PDF_FIXED_DATE".to_string(), "yesterday".to_string()),
+                ConfigIssue::UnknownEnvVar("PDF_OUTPUTPATH".to_string()),
+            ]
+        );
+        // 不正な値は検証時のエラー、不明な変数は警告のみ
+        assert_eq!(config.validate().unwrap_err().len(), 5);
+        assert_eq!(config.warnings(), vec![ConfigIssue::UnknownEnvVar("PDF_OUTPUTPATH".to_string())]);
+    }
+
+    #[test]
+    fn test_unknown_env_var_is_warning() {
+        let config = PdfConfig::from_vars([("PDF_OUTPUTPATH".to_string(), "/srv/output".to_string())])
+            .with_output_path(temp_path("out"));
+        assert!(config.validate().is_ok());
+        assert_eq!(config.warnings().len(), 1);
+    }
+
+    #[test]
+    fn test_from_toml_str() {
+        let config = PdfConfig::from_toml_str(
+            r#"
+output_path = "/srv/pdf"
+printer_backend = "sumatra"
+default_printer = "Label"
+headless = false
+
+[default_print_settings]
+fallback_printers = ["Backup1", "Backup2"]
+duplex = "long_edge"
+paper = "A5"
+copies = 2
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.output_path, PathBuf::from("/srv/pdf"));
+        assert_eq!(config.printer_backend, Backend::Sumatra);
+        assert_eq!(config.default_printer.as_deref(), Some("Label"));
+        assert!(!config.headless);
+        let options = &config.default_print_options;
+        assert_eq!(options.fallback_printers, vec!["Backup1".to_string(), "Backup2".to_string()]);
+        assert_eq!(options.duplex, Some(Duplex::LongEdge));
+        assert_eq!(options.paper.as_deref(), Some("A5"));
+        assert_eq!(options.copies, Some(2));
+
+        // 記載していないキーは既定値
+        let config = PdfConfig::from_toml_str("").unwrap();
+        assert_eq!(config.default_printer, None);
+        assert!(config.headless);
+
+        // 不明なキー・不正な値はエラー
+        for text in ["defualt_printer = \"Label\"", "printer_backend = \"fax\"", "[default_print_settings]\nduplex = \"both\""] {
+            assert!(matches!(PdfConfig::from_toml_str(text), Err(PdfError::Config(_))), "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_validate_default_ok() {
+        let config = PdfConfig::new().with_output_path(temp_path("out"));
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_output_path_not_directory() {
+        let path = temp_path("output_file");
+        std::fs::write(&path, b"").unwrap();
+        let result = PdfConfig::new().with_output_path(&path).validate();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(result, Err(vec![ConfigIssue::OutputPathNotDirectory(path)]));
+    }
+
+    #[test]
+    fn test_validate_output_path_not_creatable() {
+        let file = temp_path("output_parent_file");
+        std::fs::write(&file, b"").unwrap();
+        let path = file.join("out");
+        let result = PdfConfig::new().with_output_path(&path).validate();
+        std::fs::remove_file(&file).unwrap();
+        assert_eq!(result, Err(vec![ConfigIssue::OutputPathNotCreatable(path)]));
+    }
+
+    #[test]
+    fn test_validate_sumatra_not_found() {
+        let path = temp_path("missing_sumatra.exe");
+        let result = PdfConfig::new()
+            .with_output_path(temp_path("out"))
+            .with_sumatra_path(&path)
+            .validate();
+        assert_eq!(result, Err(vec![ConfigIssue::SumatraNotFound(path)]));
+    }
+
+    #[test]
+    fn test_validate_sumatra_not_file() {
+        let path = std::env::temp_dir();
+        let result = PdfConfig::new()
+            .with_output_path(temp_path("out"))
+            .with_sumatra_path(&path)
+            .validate();
+        assert_eq!(result, Err(vec![ConfigIssue::SumatraNotFile(path)]));
+    }
+
+    #[cfg(not(all(windows, feature = "native-print")))]
+    #[test]
+    fn test_validate_winspool_unavailable() {
+        let result = PdfConfig::new()
+            .with_output_path(temp_path("out"))
+            .with_printer_backend(Backend::Winspool)
+            .validate();
+        assert_eq!(
+            result,
+            Err(vec![ConfigIssue::PrinterBackendUnavailable(Backend::Winspool)])
+        );
+    }
+
+    #[test]
+    fn test_validate_ipp_printers_missing() {
+        let result = PdfConfig::new()
+            .with_output_path(temp_path("out"))
+            .with_printer_backend(Backend::Ipp)
+            .validate();
+        let issues = result.unwrap_err();
+        assert!(issues.contains(&ConfigIssue::IppPrintersMissing));
+        assert_eq!(
+            issues.contains(&ConfigIssue::PrinterBackendUnavailable(Backend::Ipp)),
+            !cfg!(feature = "ipp")
+        );
+    }
+
+    #[test]
+    fn test_validate_font_not_found() {
+        let path = temp_path("missing_font.ttf");
+        let result = PdfConfig::new()
+            .with_output_path(temp_path("out"))
+            .with_font_path(&path)
+            .validate();
+        assert_eq!(result, Err(vec![ConfigIssue::FontNotFound(path)]));
+    }
+
+    #[test]
+    fn test_validate_font_parse() {
+        let path = temp_path("broken_font.ttf");
+        std::fs::write(&path, b"not a font").unwrap();
+        let result = PdfConfig::new()
+            .with_output_path(temp_path("out"))
+            .with_font_path(&path)
+            .validate();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(result, Err(vec![ConfigIssue::FontParse(path)]));
+    }
+
+    #[test]
+    fn test_validate_layout() {
+        let result = PdfConfig::new()
+            .with_output_path(temp_path("out"))
+            .with_layout(LayoutConfig::new().with_row_height(20.0))
+            .validate();
+        assert!(matches!(result.unwrap_err()[..], [ConfigIssue::Layout(_)]));
+
+        // プリセットのレイアウトも検証
+        let result = PdfConfig::new()
+            .with_output_path(temp_path("out"))
+            .with_layout_preset("支店", LayoutConfig::new().with_row_height(20.0))
+            .validate();
+        assert!(matches!(result.unwrap_err()[..], [ConfigIssue::Layout(ref message)] if message.contains("支店")));
+    }
+
+    #[test]
+    fn test_validate_aggregate_issues() {
+        let issues = PdfConfig::new()
+            .with_output_path(temp_path("out"))
+            .with_sumatra_path(temp_path("missing_sumatra.exe"))
+            .with_font_path(temp_path("missing_font.ttf"))
+            .validate()
+            .unwrap_err();
+        assert_eq!(issues.len(), 2);
+
+        let message = format_issues(&issues);
+        assert!(message.starts_with("2件の問題があります: SumatraPDFが見つかりません"));
+        assert!(message.contains("; フォントファイルが見つかりません"));
+    }
+
+    /// 上限ちょうどは成功、1つ超えると失敗することを確認
+    fn assert_boundary(limits: RequestLimits, at_limit: Vec<Item>, over_limit: Vec<Item>, expected: &str) {
+        assert!(limits.check(&at_limit).is_ok());
+        let result = limits.check(&over_limit);
+        assert!(
+            matches!(result, Err(PdfError::Validation(ref message)) if message.contains(expected)),
+            "{:?}",
+            result
+        );
+    }
+
+    fn item_with_ryohi(ryohi: Vec<Ryohi>) -> Item {
+        Item {
+            ryohi,
+            ..Default::default()
+        }
+    }
+
+    fn ryohi_with_details(details: Vec<String>) -> Ryohi {
+        Ryohi {
+            detail: details,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_request_limits_boundaries() {
+        assert_boundary(
+            RequestLimits::DEFAULT.with_max_items(2),
+            vec![Item::default(); 2],
+            vec![Item::default(); 3],
+            "max_items=2",
+        );
+        assert_boundary(
+            RequestLimits::DEFAULT.with_max_ryohi_per_item(2),
+            vec![item_with_ryohi(vec![Ryohi::default(); 2])],
+            vec![Item::default(), item_with_ryohi(vec![Ryohi::default(); 3])],
+            "max_ryohi_per_item=2）を超えています: item[1].ryohi",
+        );
+        assert_boundary(
+            RequestLimits::DEFAULT.with_max_details_per_ryohi(2),
+            vec![item_with_ryohi(vec![ryohi_with_details(vec![String::new(); 2])])],
+            vec![item_with_ryohi(vec![Ryohi::default(), ryohi_with_details(vec![String::new(); 3])])],
+            "max_details_per_ryohi=2）を超えています: item[0].ryohi[1].detail",
+        );
+        // 文字数はバイト数ではなく文字数で数える
+        assert_boundary(
+            RequestLimits::DEFAULT.with_max_string_len(3),
+            vec![item_with_ryohi(vec![ryohi_with_details(vec!["東京駅".to_string()])])],
+            vec![item_with_ryohi(vec![ryohi_with_details(vec!["東京駅前".to_string()])])],
+            "max_string_len=3）を超えています: item[0].ryohi[0].detail[0]（4）",
+        );
+        let named = |name: &str| Item {
+            name: name.to_string(),
+            ..Default::default()
+        };
+        assert_boundary(
+            RequestLimits::DEFAULT.with_max_total_chars(5),
+            vec![named("山田"), named("佐藤花")],
+            vec![named("山田"), named("佐藤花子")],
+            "max_total_chars=5）を超えています: item[1].name（6）",
+        );
+    }
+
+    #[test]
+    fn test_request_limits_tightened() {
+        let config = RequestLimits::DEFAULT.with_max_items(10).with_max_string_len(100);
+        let request = RequestLimits::DEFAULT.with_max_items(5).with_max_string_len(1_000_000);
+        let limits = config.tightened(&request);
+        assert_eq!(limits.max_items, 5);
+        // リクエストの指定で緩めることはできない
+        assert_eq!(limits.max_string_len, 100);
+        assert_eq!(limits.max_total_chars, RequestLimits::DEFAULT.max_total_chars);
+    }
 }
@@ -1,5 +1,7 @@
 //! エラー型定義
 
+use std::path::{Path, PathBuf};
+
 use thiserror::Error;
 
 /// PDF生成サービスのエラー型
@@ -17,6 +19,19 @@ pub enum PdfError {
     #[error("印刷エラー: {0}")]
     Print(String),
 
+    /// PDFの直接印刷を許可していないプリンターへのwinspoolでの送信を中止（`PdfConfig::with_pdf_passthrough_printer`）
+    #[error("印刷エラー: PDFの直接印刷を許可していないプリンターです: {0}")]
+    PassthroughUnsupported(String),
+
+    /// 生成したPDFの印刷に失敗（PDFの最終的な場所を含む、`PdfConfig::on_print_failure`）
+    #[error("印刷エラー: {message}（PDF: {artifact}）")]
+    PrintFailed {
+        /// 印刷エラーの内容
+        message: String,
+        /// 生成したPDFの場所
+        artifact: ArtifactLocation,
+    },
+
     /// ファイルIOエラー
     #[error("ファイルIOエラー: {0}")]
     FileIO(#[from] std::io::Error),
@@ -24,4 +39,79 @@ pub enum PdfError {
     /// 設定エラー
     #[error("設定エラー: {0}")]
     Config(String),
+
+    /// 入力データ検証エラー
+    #[error("検証エラー: {0}")]
+    Validation(String),
+}
+
+impl PdfError {
+    /// エラーの種類ごとの数値コード（FFIの戻り値等に使用、0は成功を表すため使用しない）
+    ///
+    /// | 種類 | コード |
+    /// |---|---|
+    /// | `Generation` | 1 |
+    /// | `FontLoad` | 2 |
+    /// | `Print`・`PassthroughUnsupported`・`PrintFailed` | 3 |
+    /// | `FileIO` | 4 |
+    /// | `Config` | 5 |
+    /// | `Validation` | 6 |
+    pub fn code(&self) -> i32 {
+        match self {
+            PdfError::Generation(_) => 1,
+            PdfError::FontLoad(_) => 2,
+            PdfError::Print(_) | PdfError::PassthroughUnsupported(_) | PdfError::PrintFailed { .. } => 3,
+            PdfError::FileIO(_) => 4,
+            PdfError::Config(_) => 5,
+            PdfError::Validation(_) => 6,
+        }
+    }
+}
+
+/// 印刷に失敗した後の生成したPDFの場所
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArtifactLocation {
+    /// 出力先に残した
+    Kept(PathBuf),
+    /// 印刷失敗用のディレクトリに移動した
+    Moved(PathBuf),
+    /// 削除した
+    Deleted,
+}
+
+impl ArtifactLocation {
+    /// PDFのパス（削除した場合はNone）
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            ArtifactLocation::Kept(path) | ArtifactLocation::Moved(path) => Some(path),
+            ArtifactLocation::Deleted => None,
+        }
+    }
+}
+
+impl std::fmt::Display for ArtifactLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArtifactLocation::Kept(path) => write!(f, "{}", path.display()),
+            ArtifactLocation::Moved(path) => write!(f, "{} に移動", path.display()),
+            ArtifactLocation::Deleted => write!(f, "削除済み"),
+        }
+    }
+}
+
+/// 入力値の検証エラー
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ValidationError {
+    /// 車両番号の形式が不正
+    #[error("車両番号の形式が不正です: {0}")]
+    InvalidCarNumber(String),
+
+    /// 車両番号が長すぎる
+    #[error("車両番号が長すぎます: {value} ({max}文字まで)")]
+    CarNumberTooLong {
+        /// 入力値
+        value: String,
+        /// 最大文字数
+        max: usize,
+    },
 }
@@ -0,0 +1,248 @@
+//! C言語から呼び出すためのFFI（`ffi` feature）
+//!
+//! C#・Delphi等のデスクトップアプリケーションから、JSONのリクエストでPDFの生成・印刷を行う。
+//! リクエストは `PrintRequest`、結果は `PdfResult`（警告を含む）のJSON。
+//! 処理は `blocking::PdfClient` で呼び出し元のスレッドで行い、設定は環境変数（`PdfConfig::from_env`）から読み込む。
+//!
+//! ヘッダーは `include/print_pdf.h`（cbindgenで生成）:
+//! ```bash
+//! cbindgen --config cbindgen.toml --crate print-pdf-service --output include/print_pdf.h
+//! ```
+
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr;
+
+use crate::blocking::PdfClient;
+use crate::config::PdfConfig;
+use crate::error::PdfError;
+use crate::models::PrintRequest;
+use crate::service::PdfRequest;
+
+/// 成功
+pub const PPDF_OK: i32 = 0;
+/// PDF生成エラー（`PdfError::Generation`）
+pub const PPDF_ERR_GENERATION: i32 = 1;
+/// フォント読み込みエラー（`PdfError::FontLoad`）
+pub const PPDF_ERR_FONT_LOAD: i32 = 2;
+/// 印刷エラー（`PdfError::Print`・`PdfError::PrintFailed`）
+pub const PPDF_ERR_PRINT: i32 = 3;
+/// ファイルIOエラー（`PdfError::FileIO`）
+pub const PPDF_ERR_FILE_IO: i32 = 4;
+/// 設定エラー（`PdfError::Config`）
+pub const PPDF_ERR_CONFIG: i32 = 5;
+/// 入力データ検証エラー（`PdfError::Validation`、リクエストのJSONが不正な場合を含む）
+pub const PPDF_ERR_VALIDATION: i32 = 6;
+/// 引数が不正（NULLポインタ、UTF-8でない文字列）
+pub const PPDF_ERR_INVALID_ARGUMENT: i32 = -1;
+/// 処理中にパニックが発生
+pub const PPDF_ERR_PANIC: i32 = -2;
+
+thread_local! {
+    /// 直近のエラーメッセージ（スレッドごと）
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// リクエストのJSONからPDFを生成（`print` がtrueの場合は印刷）
+///
+/// 成功した場合は `out_result_json` に結果のJSONを設定し `PPDF_OK` を返す（`ppdf_free` で解放する）。
+/// 失敗した場合は `out_result_json` にNULLを設定し、エラーコードを返す（メッセージは `ppdf_last_error_message`）。
+///
+/// # Safety
+/// `json_request` はNUL終端の文字列、`out_result_json` は書き込み可能なポインタを指すこと
+#[no_mangle]
+pub unsafe extern "C" fn ppdf_generate(json_request: *const c_char, out_result_json: *mut *mut c_char) -> i32 {
+    generate_with(|| PdfClient::new(PdfConfig::from_env()), json_request, out_result_json)
+}
+
+/// `ppdf_generate` で返した文字列を解放（NULLの場合は何もしない）
+///
+/// # Safety
+/// `ptr` は `ppdf_generate` で返した文字列で、解放済みでないこと
+#[no_mangle]
+pub unsafe extern "C" fn ppdf_free(ptr: *mut c_char) {
+    if !ptr.is_null() {
+        drop(CString::from_raw(ptr));
+    }
+}
+
+/// 同じスレッドの直近のエラーメッセージ（UTF-8、エラーが無い場合はNULL）
+///
+/// 返す文字列は同じスレッドで次に `ppdf_generate` を呼び出すまで有効。解放しないこと
+#[no_mangle]
+pub extern "C" fn ppdf_last_error_message() -> *const c_char {
+    LAST_ERROR.with(|last| last.borrow().as_ref().map_or(ptr::null(), |message| message.as_ptr()))
+}
+
+/// クライアントを指定してリクエストを処理（パニックはエラーコードに変換）
+unsafe fn generate_with(
+    client: impl FnOnce() -> PdfClient,
+    json_request: *const c_char,
+    out_result_json: *mut *mut c_char,
+) -> i32 {
+    set_last_error(None);
+    if out_result_json.is_null() {
+        set_last_error(Some("out_result_json がNULLです".to_string()));
+        return PPDF_ERR_INVALID_ARGUMENT;
+    }
+    *out_result_json = ptr::null_mut();
+    if json_request.is_null() {
+        set_last_error(Some("json_request がNULLです".to_string()));
+        return PPDF_ERR_INVALID_ARGUMENT;
+    }
+    let json_request = match CStr::from_ptr(json_request).to_str() {
+        Ok(json) => json,
+        Err(e) => {
+            set_last_error(Some(format!("json_request がUTF-8ではありません: {}", e)));
+            return PPDF_ERR_INVALID_ARGUMENT;
+        }
+    };
+
+    let generated = catch_unwind(AssertUnwindSafe(|| {
+        // JSONの制御文字はエスケープされるためNULは含まれない
+        generate_json(&client(), json_request).map(|result| CString::new(result).expect("結果のJSONにNULが含まれています"))
+    }));
+    match generated {
+        Ok(Ok(result)) => {
+            *out_result_json = result.into_raw();
+            PPDF_OK
+        }
+        Ok(Err(e)) => {
+            set_last_error(Some(e.to_string()));
+            e.code()
+        }
+        Err(panic) => {
+            let message = panic
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| panic.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "不明なエラー".to_string());
+            set_last_error(Some(format!("処理中にパニックが発生しました: {}", message)));
+            PPDF_ERR_PANIC
+        }
+    }
+}
+
+/// リクエストのJSONを処理して結果のJSONを返す
+fn generate_json(client: &PdfClient, json_request: &str) -> Result<String, PdfError> {
+    let request: PrintRequest = serde_json::from_str(json_request)
+        .map_err(|e| PdfError::Validation(format!("リクエストのJSONが不正です: {}", e)))?;
+    let result = client.call(PdfRequest::from(request))?;
+    serde_json::to_string(&result).map_err(|e| PdfError::Generation(format!("結果をJSONに変換できません: {}", e)))
+}
+
+/// 直近のエラーメッセージを設定（NULを含む場合は除去）
+fn set_last_error(message: Option<String>) {
+    let message = message.map(|m| CString::new(m.replace('\0', "")).expect("NULは除去済み"));
+    LAST_ERROR.with(|last| *last.borrow_mut() = message);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use std::sync::Arc;
+
+    use crate::fs::MockFs;
+    use crate::pdf::testing::MockFont;
+    use crate::print::MockPrinter;
+
+    /// 外部の呼び出し元と同様にリクエストを渡して結果を取得
+    fn call(generate: impl FnOnce(*const c_char, *mut *mut c_char) -> i32, json: &str) -> (i32, Option<String>) {
+        let request = CString::new(json).unwrap();
+        let mut out: *mut c_char = ptr::null_mut();
+        let code = generate(request.as_ptr(), &mut out);
+        let result = (!out.is_null()).then(|| unsafe { CStr::from_ptr(out) }.to_str().unwrap().to_string());
+        unsafe { ppdf_free(out) };
+        (code, result)
+    }
+
+    fn last_error() -> Option<String> {
+        let message = ppdf_last_error_message();
+        (!message.is_null()).then(|| unsafe { CStr::from_ptr(message) }.to_str().unwrap().to_string())
+    }
+
+    #[test]
+    fn test_error_codes_match_pdf_error() {
+        let errors = [
+            (PdfError::Generation(String::new()), PPDF_ERR_GENERATION),
+            (PdfError::FontLoad(String::new()), PPDF_ERR_FONT_LOAD),
+            (PdfError::Print(String::new()), PPDF_ERR_PRINT),
+            (
+                PdfError::PrintFailed {
+                    message: String::new(),
+                    artifact: crate::error::ArtifactLocation::Deleted,
+                },
+                PPDF_ERR_PRINT,
+            ),
+            (PdfError::FileIO(std::io::Error::other("")), PPDF_ERR_FILE_IO),
+            (PdfError::Config(String::new()), PPDF_ERR_CONFIG),
+            (PdfError::Validation(String::new()), PPDF_ERR_VALIDATION),
+        ];
+        for (error, code) in errors {
+            assert_eq!(error.code(), code);
+        }
+    }
+
+    #[test]
+    fn test_generate_invalid_requests() {
+        // 不正なJSON
+        let (code, result) = call(|req, out| unsafe { ppdf_generate(req, out) }, "{");
+        assert_eq!(code, PPDF_ERR_VALIDATION);
+        assert!(result.is_none());
+        assert!(last_error().unwrap().contains("JSON"));
+
+        // 出力ディレクトリの外への出力（ファイルを作成する前に拒否）
+        let (code, _) = call(
+            |req, out| unsafe { ppdf_generate(req, out) },
+            r#"{"items":[],"printerName":null,"outputPath":"../outside.pdf"}"#,
+        );
+        assert_eq!(code, PPDF_ERR_CONFIG);
+
+        // NULLポインタ
+        let code = unsafe { ppdf_generate(ptr::null(), ptr::null_mut()) };
+        assert_eq!(code, PPDF_ERR_INVALID_ARGUMENT);
+        let mut out: *mut c_char = ptr::null_mut();
+        assert_eq!(unsafe { ppdf_generate(ptr::null(), &mut out) }, PPDF_ERR_INVALID_ARGUMENT);
+        assert!(last_error().unwrap().contains("json_request"));
+        unsafe { ppdf_free(ptr::null_mut()) };
+    }
+
+    #[test]
+    fn test_generate_result_json() {
+        let font = MockFont::new("ffi");
+        let fs = Arc::new(MockFs::new());
+        let printer = Arc::new(MockPrinter::new());
+        let client = || {
+            let config = PdfConfig::new().with_font_path(font.path()).with_output_path("/srv/output");
+            PdfClient::new(config)
+                .with_fs(fs.clone())
+                .with_printer(printer.clone())
+                .with_font_cache(font.cache())
+        };
+        let json = r#"{"items":[{"car":"","name":"山田太郎","price":0,"ryohi":[],"externalId":"V-1"}],"print":true,"printerName":"Office","outputPath":"ffi.pdf"}"#;
+        let (code, result) = call(|req, out| unsafe { generate_with(client, req, out) }, json);
+
+        assert_eq!(code, PPDF_OK);
+        assert!(last_error().is_none());
+        let result: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
+        assert_eq!(result["pdfPath"], "/srv/output/ffi.pdf");
+        assert_eq!(result["printed"], true);
+        assert_eq!(result["printerName"], "Office");
+        assert!(result["warnings"].is_array());
+        assert_eq!(result["stats"]["externalIds"]["0"][0], "V-1");
+        assert_eq!(fs.paths(), vec![PathBuf::from("/srv/output/ffi.pdf")]);
+    }
+
+    #[test]
+    fn test_panic_converted_to_error_code() {
+        let (code, result) = call(
+            |req, out| unsafe { generate_with(|| panic!("クライアントを作成できません"), req, out) },
+            "{}",
+        );
+        assert_eq!(code, PPDF_ERR_PANIC);
+        assert!(result.is_none());
+        assert!(last_error().unwrap().contains("クライアントを作成できません"));
+    }
+}
@@ -0,0 +1,439 @@
+//! ファイルシステム
+//!
+//! - `Fs`: ファイル操作の抽象化（テストではメモリ上の `MockFs` を使用、テスト時または `test-util` feature有効時のみ）
+//! - `WriteRetry`: ウイルス対策ソフトやバックアップソフトが出力直後のPDFを開いている場合に備え、
+//!   共有違反・アクセス拒否のエラーを再試行する
+
+#[cfg(any(test, feature = "test-util"))]
+use std::collections::{BTreeSet, HashMap};
+#[cfg(any(test, feature = "test-util"))]
+use std::io::Error;
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+#[cfg(any(test, feature = "test-util"))]
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::error::PdfError;
+
+/// Windowsの共有違反（ERROR_SHARING_VIOLATION）
+const ERROR_SHARING_VIOLATION: i32 = 32;
+/// Windowsのロック違反（ERROR_LOCK_VIOLATION）
+const ERROR_LOCK_VIOLATION: i32 = 33;
+
+/// ファイルのメタデータ
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileMetadata {
+    /// ファイルサイズ（バイト）
+    pub len: u64,
+    /// ファイルか
+    pub is_file: bool,
+}
+
+/// ファイル操作
+///
+/// 生成・結果の作成・印刷で使用するファイル操作をまとめる
+pub trait Fs: Send + Sync {
+    /// ファイルに書き込み（既存のファイルは上書き、ディスクへの書き出しまで待つ）
+    fn write(&self, path: &Path, data: &[u8]) -> std::io::Result<()>;
+
+    /// ファイルを読み込み
+    fn read(&self, path: &Path) -> std::io::Result<Vec<u8>>;
+
+    /// メタデータを取得
+    fn metadata(&self, path: &Path) -> std::io::Result<FileMetadata>;
+
+    /// パスが存在するか
+    fn exists(&self, path: &Path) -> bool {
+        self.metadata(path).is_ok()
+    }
+
+    /// 絶対パスを取得
+    fn canonicalize(&self, path: &Path) -> std::io::Result<PathBuf>;
+
+    /// ディレクトリを作成（親ディレクトリも作成）
+    fn create_dir_all(&self, path: &Path) -> std::io::Result<()>;
+
+    /// ファイル名を変更（既存のファイルは置き換える）
+    fn rename(&self, from: &Path, to: &Path) -> std::io::Result<()>;
+
+    /// ファイルを削除
+    fn remove_file(&self, path: &Path) -> std::io::Result<()>;
+
+    /// ディレクトリのエントリ（ファイル名の変更）をディスクに書き出す
+    fn sync_dir(&self, _path: &Path) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// 実際のファイルシステム
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RealFs;
+
+impl Fs for RealFs {
+    fn write(&self, path: &Path, data: &[u8]) -> std::io::Result<()> {
+        let mut file = std::fs::File::create(path)?;
+        file.write_all(data)?;
+        file.sync_all()
+    }
+
+    fn read(&self, path: &Path) -> std::io::Result<Vec<u8>> {
+        std::fs::read(path)
+    }
+
+    fn metadata(&self, path: &Path) -> std::io::Result<FileMetadata> {
+        let metadata = std::fs::metadata(path)?;
+        Ok(FileMetadata {
+            len: metadata.len(),
+            is_file: metadata.is_file(),
+        })
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        path.exists()
+    }
+
+    fn canonicalize(&self, path: &Path) -> std::io::Result<PathBuf> {
+        std::fs::canonicalize(path)
+    }
+
+    fn create_dir_all(&self, path: &Path) -> std::io::Result<()> {
+        std::fs::create_dir_all(path)
+    }
+
+    fn rename(&self, from: &Path, to: &Path) -> std::io::Result<()> {
+        std::fs::rename(from, to)
+    }
+
+    fn remove_file(&self, path: &Path) -> std::io::Result<()> {
+        std::fs::remove_file(path)
+    }
+
+    #[cfg(unix)]
+    fn sync_dir(&self, path: &Path) -> std::io::Result<()> {
+        std::fs::File::open(path)?.sync_all()
+    }
+}
+
+/// メモリ上のファイルシステム（テスト用）
+///
+/// 相対パスは `/` を基準とした絶対パスとして扱う
+#[cfg(any(test, feature = "test-util"))]
+#[derive(Debug, Default)]
+pub struct MockFs {
+    files: Mutex<HashMap<PathBuf, Vec<u8>>>,
+    dirs: Mutex<BTreeSet<PathBuf>>,
+    failures: Mutex<HashMap<PathBuf, Vec<ErrorKind>>>,
+    synced_dirs: Mutex<Vec<PathBuf>>,
+}
+
+#[cfg(any(test, feature = "test-util"))]
+impl MockFs {
+    /// 空のファイルシステムを作成
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// ファイルを追加
+    pub fn with_file(self, path: impl AsRef<Path>, data: impl Into<Vec<u8>>) -> Self {
+        self.files.lock().unwrap().insert(Self::key(path.as_ref()), data.into());
+        self
+    }
+
+    /// 指定したパスへの次回以降の書き込み・名前変更を指定したエラーで順番に失敗させる
+    pub fn fail_next(&self, path: impl AsRef<Path>, errors: Vec<ErrorKind>) {
+        let mut errors = errors;
+        errors.reverse();
+        self.failures.lock().unwrap().insert(Self::key(path.as_ref()), errors);
+    }
+
+    /// ファイルの内容を取得
+    pub fn file(&self, path: impl AsRef<Path>) -> Option<Vec<u8>> {
+        self.files.lock().unwrap().get(&Self::key(path.as_ref())).cloned()
+    }
+
+    /// 全てのファイルのパスを取得（ソート済み）
+    pub fn paths(&self) -> Vec<PathBuf> {
+        let mut paths: Vec<PathBuf> = self.files.lock().unwrap().keys().cloned().collect();
+        paths.sort();
+        paths
+    }
+
+    /// ディスクに書き出したディレクトリ（呼び出し順）
+    pub fn synced_dirs(&self) -> Vec<PathBuf> {
+        self.synced_dirs.lock().unwrap().clone()
+    }
+
+    fn key(path: &Path) -> PathBuf {
+        Path::new("/").join(path)
+    }
+
+    fn take_failure(&self, path: &Path) -> std::io::Result<()> {
+        match self.failures.lock().unwrap().get_mut(path).and_then(Vec::pop) {
+            Some(kind) => Err(Error::from(kind)),
+            None => Ok(()),
+        }
+    }
+
+    fn not_found(path: &Path) -> Error {
+        Error::new(ErrorKind::NotFound, format!("{:?}", path))
+    }
+}
+
+#[cfg(any(test, feature = "test-util"))]
+impl Fs for MockFs {
+    fn write(&self, path: &Path, data: &[u8]) -> std::io::Result<()> {
+        let key = Self::key(path);
+        self.take_failure(&key)?;
+        self.files.lock().unwrap().insert(key, data.to_vec());
+        Ok(())
+    }
+
+    fn read(&self, path: &Path) -> std::io::Result<Vec<u8>> {
+        self.file(path).ok_or_else(|| Self::not_found(path))
+    }
+
+    fn metadata(&self, path: &Path) -> std::io::Result<FileMetadata> {
+        let key = Self::key(path);
+        if let Some(data) = self.files.lock().unwrap().get(&key) {
+            return Ok(FileMetadata {
+                len: data.len() as u64,
+                is_file: true,
+            });
+        }
+        if self.dirs.lock().unwrap().contains(&key) {
+            return Ok(FileMetadata { len: 0, is_file: false });
+        }
+        Err(Self::not_found(path))
+    }
+
+    fn canonicalize(&self, path: &Path) -> std::io::Result<PathBuf> {
+        self.metadata(path)?;
+        Ok(Self::key(path))
+    }
+
+    fn create_dir_all(&self, path: &Path) -> std::io::Result<()> {
+        let mut dirs = self.dirs.lock().unwrap();
+        for dir in Self::key(path).ancestors() {
+            dirs.insert(dir.to_path_buf());
+        }
+        Ok(())
+    }
+
+    fn rename(&self, from: &Path, to: &Path) -> std::io::Result<()> {
+        let to = Self::key(to);
+        self.take_failure(&to)?;
+        let mut files = self.files.lock().unwrap();
+        let data = files.remove(&Self::key(from)).ok_or_else(|| Self::not_found(from))?;
+        files.insert(to, data);
+        Ok(())
+    }
+
+    fn remove_file(&self, path: &Path) -> std::io::Result<()> {
+        self.files
+            .lock()
+            .unwrap()
+            .remove(&Self::key(path))
+            .map(|_| ())
+            .ok_or_else(|| Self::not_found(path))
+    }
+
+    fn sync_dir(&self, path: &Path) -> std::io::Result<()> {
+        self.synced_dirs.lock().unwrap().push(Self::key(path));
+        Ok(())
+    }
+}
+
+/// 書き込みの再試行設定
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WriteRetry {
+    /// 最大試行回数（1の場合は再試行しない）
+    pub attempts: u32,
+    /// 再試行までの待機時間
+    pub delay: Duration,
+}
+
+impl Default for WriteRetry {
+    fn default() -> Self {
+        Self {
+            attempts: 3,
+            delay: Duration::from_millis(200),
+        }
+    }
+}
+
+impl WriteRetry {
+    /// 再試行設定を作成
+    pub fn new(attempts: u32, delay: Duration) -> Self {
+        Self { attempts, delay }
+    }
+
+    /// 共有違反・アクセス拒否の場合のみ再試行して実行
+    ///
+    /// # Returns
+    /// (実行結果, 再試行した回数)
+    pub fn run<T>(&self, mut op: impl FnMut() -> std::io::Result<T>) -> (std::io::Result<T>, usize) {
+        let mut retries = 0;
+        loop {
+            match op() {
+                Err(e) if is_retryable(&e) && retries + 1 < self.attempts.max(1) as usize => {
+                    retries += 1;
+                    tracing::warn!("ファイルが使用中のため再試行します（{}回目）: {}", retries, e);
+                    std::thread::sleep(self.delay);
+                }
+                result => return (result, retries),
+            }
+        }
+    }
+}
+
+/// 再試行で解消する可能性のあるエラーか（共有違反・ロック違反・アクセス拒否）
+pub fn is_retryable(e: &std::io::Error) -> bool {
+    e.kind() == ErrorKind::PermissionDenied
+        || (cfg!(windows) && matches!(e.raw_os_error(), Some(ERROR_SHARING_VIOLATION | ERROR_LOCK_VIOLATION)))
+}
+
+/// 一時ファイルに書き込んでから出力先に置き換える
+///
+/// 書き込みに失敗した場合も出力先に途中までのファイルを残さない。
+/// 一時ファイルをディスクに書き出してから置き換え、置き換え後に親ディレクトリも書き出す
+///
+/// # Returns
+/// 再試行した回数
+pub fn write_atomic(fs: &dyn Fs, path: &Path, retry: &WriteRetry, data: &[u8]) -> Result<usize, PdfError> {
+    let temp_path = temp_path_for(path);
+
+    let (written, mut retries) = retry.run(|| fs.write(&temp_path, data));
+    if let Err(e) = written {
+        let _ = fs.remove_file(&temp_path);
+        return Err(e.into());
+    }
+
+    let (renamed, rename_retries) = retry.run(|| fs.rename(&temp_path, path));
+    retries += rename_retries;
+    if let Err(e) = renamed {
+        let _ = fs.remove_file(&temp_path);
+        return Err(e.into());
+    }
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs.sync_dir(parent)?;
+    }
+    Ok(retries)
+}
+
+/// 出力先と同じディレクトリの一時ファイルのパス（同じボリューム内で置き換えるため）
+///
+/// 同じ出力先への同時の書き込みが衝突しないよう、呼び出しごとに一意な名前にする
+fn temp_path_for(path: &Path) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    path.with_file_name(format!(".{}.{}.tmp", name, unique_suffix()))
+}
+
+/// ファイル名に付ける一意な文字列（プロセスID・時刻・連番）
+pub(crate) fn unique_suffix() -> String {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+    format!("{}-{}-{}", std::process::id(), nanos, COUNTER.fetch_add(1, Ordering::Relaxed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn no_delay(attempts: u32) -> WriteRetry {
+        WriteRetry::new(attempts, Duration::ZERO)
+    }
+
+    #[test]
+    fn test_retry_until_success() {
+        let mut failures = 2;
+        let (result, retries) = no_delay(3).run(|| {
+            if failures > 0 {
+                failures -= 1;
+                return Err(Error::from(ErrorKind::PermissionDenied));
+            }
+            Ok(())
+        });
+        assert!(result.is_ok());
+        assert_eq!(retries, 2);
+    }
+
+    #[test]
+    fn test_retry_gives_up() {
+        let mut calls = 0;
+        let (result, retries) = no_delay(3).run(|| -> std::io::Result<()> {
+            calls += 1;
+            Err(Error::from(ErrorKind::PermissionDenied))
+        });
+        assert!(result.is_err());
+        assert_eq!((calls, retries), (3, 2));
+    }
+
+    #[test]
+    fn test_no_retry_for_other_errors() {
+        let mut calls = 0;
+        let (result, retries) = no_delay(3).run(|| -> std::io::Result<()> {
+            calls += 1;
+            Err(Error::from(ErrorKind::NotFound))
+        });
+        assert!(result.is_err());
+        assert_eq!((calls, retries), (1, 0));
+    }
+
+    #[test]
+    fn test_write_atomic_retries_sharing_violation() {
+        let fs = MockFs::new().with_file("out/report.pdf", "previous");
+        fs.fail_next("out/report.pdf", vec![ErrorKind::PermissionDenied, ErrorKind::PermissionDenied]);
+
+        let retries = write_atomic(&fs, Path::new("out/report.pdf"), &no_delay(3), b"new").unwrap();
+        assert_eq!(retries, 2);
+        assert_eq!(fs.file("out/report.pdf").unwrap(), b"new");
+        assert_eq!(fs.paths(), vec![PathBuf::from("/out/report.pdf")]);
+        // 置き換え後に親ディレクトリを書き出す
+        assert_eq!(fs.synced_dirs(), vec![PathBuf::from("/out")]);
+    }
+
+    #[test]
+    fn test_write_atomic_failure_keeps_target() {
+        let fs = MockFs::new().with_file("out/report.pdf", "previous");
+        fs.fail_next("out/report.pdf", vec![ErrorKind::PermissionDenied; 3]);
+
+        let result = write_atomic(&fs, Path::new("out/report.pdf"), &no_delay(3), b"new");
+        assert!(matches!(result, Err(PdfError::FileIO(_))));
+        // 出力先は元の内容のまま、一時ファイルも残らない
+        assert_eq!(fs.file("out/report.pdf").unwrap(), b"previous");
+        assert_eq!(fs.paths(), vec![PathBuf::from("/out/report.pdf")]);
+        assert!(fs.synced_dirs().is_empty());
+    }
+
+    #[test]
+    fn test_real_fs_write_atomic() {
+        let path = std::env::temp_dir().join("print_pdf_fs_atomic.pdf");
+        let retries = write_atomic(&RealFs, &path, &no_delay(1), b"%PDF").unwrap();
+        assert_eq!(retries, 0);
+        assert_eq!(RealFs.metadata(&path).unwrap().len, 4);
+        let leftovers = std::fs::read_dir(std::env::temp_dir())
+            .unwrap()
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_name().to_string_lossy().starts_with(".print_pdf_fs_atomic.pdf."))
+            .count();
+        assert_eq!(leftovers, 0);
+        RealFs.remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_temp_path_unique_per_call() {
+        let path = Path::new("out/report.pdf");
+        let (first, second) = (temp_path_for(path), temp_path_for(path));
+        assert_ne!(first, second);
+        assert_eq!(first.parent(), path.parent());
+        assert!(first.file_name().unwrap().to_string_lossy().starts_with(".report.pdf."));
+    }
+}
@@ -18,19 +18,62 @@
 //!     println!("PDF generated: {:?}", result.pdf_path);
 //! }
 //! ```
+//!
+//! # 同期処理からの使用例
+//!
+//! 非同期ランタイムを使用しない場合は `blocking::PdfClient`（`PdfService` と同じ検証・警告・結果）、
+//! `generate_sync` または `ReportLabStylePdfClient` を直接使用する。
+//!
+//! ```rust,ignore
+//! use print_pdf_service::{blocking, generate_sync, ReportLabStylePdfClient, PdfConfig};
+//!
+//! // サービスと同じ処理を呼び出し元のスレッドで実行
+//! let client = blocking::PdfClient::new(PdfConfig::from_env());
+//! let result = client.generate_and_print(items.clone(), Some("Office"))?;
+//!
+//! // デフォルト設定で生成
+//! let path = generate_sync(&items, "output.pdf")?;
+//!
+//! // 設定を指定して生成
+//! let mut client = ReportLabStylePdfClient::from_config(&PdfConfig::from_env())
+//!     .with_output_path("output.pdf");
+//! let path = client.generate(&items)?;
+//! ```
 
+pub mod audit;
+pub mod blocking;
 pub mod config;
 pub mod error;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "python")]
+pub mod python;
+pub mod fs;
 pub mod models;
 pub mod pdf;
+pub mod prelude;
 pub mod print;
 pub mod service;
 pub mod traits;
+pub mod warning;
 
 // 主要な型をリエクスポート
-pub use config::PdfConfig;
-pub use error::PdfError;
-pub use models::{Item, PrintRequest, Ryohi};
-pub use print::SumatraPrinter;
-pub use service::{PdfRequest, PdfResult, PdfService};
-pub use traits::PdfGenerator;
+pub use audit::{AuditEntry, AuditItem, AuditOutcome, AuditSink, JsonlFileSink, NameRedaction, NoopSink};
+pub use config::{ConfigIssue, PdfConfig, PrintFailurePolicy, RequestLimits};
+pub use error::{ArtifactLocation, PdfError, ValidationError};
+#[cfg(any(test, feature = "test-util"))]
+pub use fs::MockFs;
+pub use fs::{Fs, RealFs, WriteRetry};
+pub use models::{format_car, DedupePolicy, DuplicateRyohi, Item, NumberFormat, PrintRequest, Ryohi};
+pub use pdf::{
+    generate_pdf, generate_sync, FormatOptions, GenerationReport, GenerationStats, LayoutConfig, PageRenderer,
+    ReportLabStylePdfClient, SealImage,
+};
+pub use print::{create_printer, create_printer_with_passthrough, Backend, Duplex, PrintOptions, PrinterCapabilities, SumatraPrinter};
+#[cfg(feature = "ipp")]
+pub use print::IppPrinter;
+#[cfg(any(test, feature = "test-util"))]
+pub use print::{MockPrinter, PrintJob};
+pub use service::{PdfRequest, PdfResult, PdfService, WarmUpReport};
+pub use traits::{PdfGenerator, Printer};
+pub use warning::Warning;
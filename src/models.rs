@@ -2,8 +2,77 @@
 //!
 //! Go版のmodels.goから移植
 
+use std::collections::HashMap;
+use std::path::PathBuf;
+
 use serde::{Deserialize, Serialize};
 
+use crate::error::ValidationError;
+
+/// 車両番号のデフォルトの最大文字数（車両No.欄に収まる長さ）
+pub const DEFAULT_MAX_CAR_LENGTH: usize = 10;
+
+/// アイテムの文字列フィールドの一覧（`Item::for_each_text`・`Item::for_each_text_mut` で共有）
+///
+/// 各フィールドについて `$visit!(印刷するか, 位置, 値)` を呼び出す（独自の列のキーは `$visit!(custom_key, 位置, キー)`）。
+/// 独自の列はキーの順に走査する
+macro_rules! visit_text_fields {
+    ($item:expr, [$($r:tt)+], $iter:ident, $as:ident, $visit:ident) => {{
+        for (field, value) in [("car", $($r)+ $item.car), ("name", $($r)+ $item.name)] {
+            $visit!(true, field.to_string(), value);
+        }
+        for (field, value) in [
+            ("purpose", $($r)+ $item.purpose),
+            ("startDate", $($r)+ $item.start_date),
+            ("endDate", $($r)+ $item.end_date),
+            ("description", $($r)+ $item.description),
+            ("office", $($r)+ $item.office),
+            ("payDay", $($r)+ $item.pay_day),
+        ] {
+            if let Some(value) = value {
+                $visit!(true, field.to_string(), value);
+            }
+        }
+        // 印刷しない指定
+        for (field, value) in [("externalId", $($r)+ $item.external_id), ("layoutPreset", $($r)+ $item.layout_preset)] {
+            if let Some(value) = value {
+                $visit!(false, field.to_string(), value);
+            }
+        }
+
+        for (i, ryohi) in $item.ryohi.$iter().enumerate() {
+            for (field, value) in [("date", $($r)+ ryohi.date), ("dest", $($r)+ ryohi.dest)] {
+                if let Some(value) = value {
+                    $visit!(true, format!("ryohi[{}].{}", i, field), value);
+                }
+            }
+            for (j, value) in ryohi.detail.$iter().enumerate() {
+                $visit!(true, format!("ryohi[{}].detail[{}]", i, j), value);
+            }
+            if let Some(value) = $($r)+ ryohi.kukan {
+                $visit!(true, format!("ryohi[{}].kukan", i), value);
+            }
+            for (field, values) in [
+                ("dateAr", ryohi.date_ar.$as()),
+                ("destAr", ryohi.dest_ar.$as()),
+                ("kukanSplit", ryohi.kukan_split.$as()),
+                ("printDetail", ryohi.print_detail.$as()),
+                ("printKukan", ryohi.print_kukan.$as()),
+            ] {
+                for (j, value) in values.into_iter().flatten().enumerate() {
+                    $visit!(true, format!("ryohi[{}].{}[{}]", i, field, j), value);
+                }
+            }
+            let mut custom: Vec<_> = ryohi.custom.$iter().collect();
+            custom.sort_by(|a, b| a.0.cmp(b.0));
+            for (key, value) in custom {
+                $visit!(custom_key, format!("ryohi[{}].custom.{}", i, key), key);
+                $visit!(true, format!("ryohi[{}].custom.{}", i, key), value);
+            }
+        }
+    }};
+}
+
 /// 経費明細（旅費項目）
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Ryohi {
@@ -22,9 +91,9 @@ pub struct Ryohi {
     pub detail: Vec<String>,
     /// 区間
     pub kukan: Option<String>,
-    /// 区間分割
-    #[serde(rename = "kukanSprit")]
-    pub kukan_sprit: Option<Vec<String>>,
+    /// 区間分割（旧キー `kukanSprit` も受け付ける）
+    #[serde(rename = "kukanSplit", alias = "kukanSprit")]
+    pub kukan_split: Option<Vec<String>>,
     /// 金額
     pub price: Option<i32>,
     /// 金額配列
@@ -35,6 +104,21 @@ pub struct Ryohi {
     /// 数量配列
     #[serde(rename = "volAr")]
     pub vol_ar: Option<Vec<f64>>,
+    /// 消費税率（%、例: 8, 10）。指定した項目は消費税の内訳に集計する
+    ///
+    /// Go版に無いキーのため、未指定の場合は出力しない
+    #[serde(rename = "taxRate", skip_serializing_if = "Option::is_none")]
+    pub tax_rate: Option<u8>,
+    /// 独自の列に印刷するデータ（キーは `ColumnSource::Custom` のキー）
+    ///
+    /// Go版に無いキーのため、空の場合は出力しない
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub custom: HashMap<String, String>,
+    /// この旅費項目から新しいページに印刷するか（継続ページ有効時のみ）
+    ///
+    /// Go版に無いキーのため、未指定の場合は出力しない
+    #[serde(rename = "pageBreakBefore", skip_serializing_if = "Option::is_none")]
+    pub page_break_before: Option<bool>,
 
     // 印刷用フィールド（PDF生成時に使用）
     /// 印刷用摘要
@@ -58,7 +142,7 @@ pub struct Ryohi {
 }
 
 /// 精算書項目
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Item {
     /// 車両番号
     pub car: String,
@@ -86,23 +170,188 @@ pub struct Item {
     /// 支払日 (YYYY/MM/DD形式)
     #[serde(rename = "payDay")]
     pub pay_day: Option<String>,
+    /// 外部システムのID（ERPの伝票番号など、印刷せずにPDFのメタデータに記録）
+    #[serde(rename = "externalId", skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>,
+    /// レイアウトのプリセット名（`PdfConfig::layout_presets`、Noneの場合は既定のレイアウト）
+    #[serde(rename = "layoutPreset", skip_serializing_if = "Option::is_none")]
+    pub layout_preset: Option<String>,
+    /// 旅費項目を改ページで分割しないか（継続ページ有効時のみ）
+    ///
+    /// 分割される項目が1ページに収まる場合は、項目全体を次のページに送る
+    #[serde(rename = "keepTogether", skip_serializing_if = "Option::is_none")]
+    pub keep_together: Option<bool>,
+    /// 添付する領収書の枚数（備考欄に「領収書 N 枚」と印刷）
+    #[serde(rename = "receiptCount", skip_serializing_if = "Option::is_none")]
+    pub receipt_count: Option<u32>,
+    /// 仮払金（出張前に受け取った額、計欄に仮払金と差引支給額を印刷）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub advance: Option<i32>,
 }
 
-impl Default for Item {
-    fn default() -> Self {
-        Self {
-            car: String::new(),
-            name: String::new(),
-            purpose: None,
-            start_date: None,
-            end_date: None,
-            price: 0,
-            tax: None,
-            description: None,
-            ryohi: Vec::new(),
-            office: None,
-            pay_day: None,
+/// 重複した旅費項目の扱い
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DedupePolicy {
+    /// 重複を検出しない
+    #[default]
+    Off,
+    /// 完全に一致する項目を削除
+    Exact,
+    /// 削除せずに記録のみ行う
+    Report,
+}
+
+/// 検出された重複旅費項目
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateRyohi {
+    /// 重複した項目のインデックス（重複除去前）
+    pub index: usize,
+    /// 重複元の項目のインデックス
+    pub duplicate_of: usize,
+    /// 日付
+    pub date: Option<String>,
+    /// 金額
+    pub price: Option<i32>,
+}
+
+impl Ryohi {
+    /// 区間分割（旧フィールド名 `kukan_sprit` の互換用）
+    #[deprecated(since = "0.1.0", note = "フィールド `kukan_split` を使用")]
+    pub fn kukan_sprit(&self) -> Option<&Vec<String>> {
+        self.kukan_split.as_ref()
+    }
+
+    /// 区間分割を変更（旧フィールド名 `kukan_sprit` の互換用）
+    #[deprecated(since = "0.1.0", note = "フィールド `kukan_split` を使用")]
+    pub fn kukan_sprit_mut(&mut self) -> &mut Option<Vec<String>> {
+        &mut self.kukan_split
+    }
+
+    /// 日付・行先・摘要・区間・金額・数量が全て一致するか
+    fn is_duplicate_of(&self, other: &Ryohi) -> bool {
+        self.date == other.date
+            && self.dest == other.dest
+            && self.detail == other.detail
+            && self.kukan == other.kukan
+            && self.price == other.price
+            && self.vol.map(f64::to_bits) == other.vol.map(f64::to_bits)
+    }
+}
+
+impl Item {
+    /// 重複した旅費項目を検出・除去
+    ///
+    /// 日付・行先・摘要・区間・金額・数量が全て一致する項目を重複とみなし、
+    /// 最初に出現した項目を残す
+    ///
+    /// # Returns
+    /// 検出された重複項目（`Exact` の場合は削除された項目）
+    pub fn dedupe_ryohi(&mut self, policy: DedupePolicy) -> Vec<DuplicateRyohi> {
+        if policy == DedupePolicy::Off {
+            return Vec::new();
+        }
+
+        let mut duplicates = Vec::new();
+        for (index, ryohi) in self.ryohi.iter().enumerate() {
+            if let Some(duplicate_of) = self.ryohi[..index]
+                .iter()
+                .position(|other| ryohi.is_duplicate_of(other))
+            {
+                duplicates.push(DuplicateRyohi {
+                    index,
+                    duplicate_of,
+                    date: ryohi.date.clone(),
+                    price: ryohi.price,
+                });
+            }
         }
+
+        if policy == DedupePolicy::Exact && !duplicates.is_empty() {
+            let mut index = 0;
+            self.ryohi.retain(|_| {
+                let keep = !duplicates.iter().any(|d| d.index == index);
+                index += 1;
+                keep
+            });
+        }
+
+        duplicates
+    }
+
+    /// 重複入力の可能性がある旅費項目を検出
+    ///
+    /// 直前の項目と日付・行先が同じ項目を対象とする（日付・行先の無い項目は対象外）。
+    /// 項目は変更しない
+    ///
+    /// # Returns
+    /// 該当する項目のインデックス（2件目以降）
+    pub fn possible_duplicate_ryohi(&self) -> Vec<usize> {
+        self.ryohi
+            .windows(2)
+            .enumerate()
+            .filter(|(_, pair)| pair[0].date.is_some() && pair[0].dest.is_some())
+            .filter(|(_, pair)| pair[0].date == pair[1].date && pair[0].dest == pair[1].dest)
+            .map(|(index, _)| index + 1)
+            .collect()
+    }
+
+    /// 車両番号を正規化
+    ///
+    /// `format_car` で整形できる場合は置き換える。整形できない場合は値を変更せず、
+    /// 最大文字数を超える場合は `CarNumberTooLong`、それ以外は形式エラーを返す
+    pub fn normalize_car(&mut self, max_len: usize) -> Option<ValidationError> {
+        if self.car.trim().is_empty() {
+            return None;
+        }
+
+        match format_car(&self.car) {
+            Ok(car) => {
+                self.car = car;
+                None
+            }
+            Err(e) => {
+                let len = self.car.chars().count();
+                if len > max_len {
+                    Some(ValidationError::CarNumberTooLong {
+                        value: self.car.clone(),
+                        max: max_len,
+                    })
+                } else {
+                    Some(e)
+                }
+            }
+        }
+    }
+
+    /// 全ての文字列フィールド（外部ID・配列・印刷用の指定・独自の列のキーを含む）を順番に走査する
+    ///
+    /// コールバックにはフィールドの位置（例: `"ryohi[0].detail[1]"`）を作成する関数と値が渡される
+    pub fn for_each_text(&self, mut f: impl FnMut(&dyn Fn() -> String, &str)) {
+        macro_rules! visit {
+            (custom_key, $field:expr, $value:expr) => {
+                f(&|| $field, $value)
+            };
+            ($printed:expr, $field:expr, $value:expr) => {
+                f(&|| $field, $value)
+            };
+        }
+        visit_text_fields!(self, [&], iter, as_ref, visit);
+    }
+
+    /// 文字列フィールドを順番に走査して変更する
+    ///
+    /// 印刷する全ての文字列（配列・印刷用の指定を含む）が対象。外部ID・レイアウトのプリセット名・独自の列のキーは変更しない。
+    /// コールバックにはフィールド名（例: `"ryohi[0].detail[1]"`）と値が渡される
+    pub fn for_each_text_mut(&mut self, mut f: impl FnMut(&str, &mut String)) {
+        macro_rules! visit {
+            (custom_key, $field:expr, $value:expr) => {};
+            ($printed:expr, $field:expr, $value:expr) => {
+                if $printed {
+                    f(&$field, $value)
+                }
+            };
+        }
+        visit_text_fields!(self, [&mut], iter_mut, as_mut, visit);
     }
 }
 
@@ -117,6 +366,9 @@ pub struct PrintRequest {
     /// プリンター名
     #[serde(rename = "printerName")]
     pub printer_name: Option<String>,
+    /// 出力パス（Noneの場合はデフォルトの出力パス）
+    #[serde(rename = "outputPath", default, skip_serializing_if = "Option::is_none")]
+    pub output_path: Option<PathBuf>,
 }
 
 impl PrintRequest {
@@ -126,6 +378,7 @@ impl PrintRequest {
             items,
             print: false,
             printer_name: None,
+            output_path: None,
         }
     }
 
@@ -142,15 +395,39 @@ impl PrintRequest {
     }
 }
 
+/// 金額の表示形式
+///
+/// 数量の小数桁は `VolFormat` で指定する
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NumberFormat {
+    /// 3桁区切りの文字（Noneの場合は区切らない）
+    pub thousands_separator: Option<char>,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self {
+            thousands_separator: Some(','),
+        }
+    }
+}
+
 /// 金額をフォーマット（3桁区切り）
 pub fn format_price(price: i32) -> String {
-    let s = price.abs().to_string();
+    format_price_styled(price, &NumberFormat::default())
+}
+
+/// 表示形式を指定して金額をフォーマット
+pub fn format_price_styled(price: i32, format: &NumberFormat) -> String {
+    let s = price.unsigned_abs().to_string();
     let mut result = String::new();
     let chars: Vec<char> = s.chars().rev().collect();
 
     for (i, c) in chars.iter().enumerate() {
         if i > 0 && i % 3 == 0 {
-            result.push(',');
+            if let Some(separator) = format.thousands_separator {
+                result.push(separator);
+            }
         }
         result.push(*c);
     }
@@ -162,6 +439,37 @@ pub fn format_price(price: i32) -> String {
     result.chars().rev().collect()
 }
 
+/// 車両番号をフォーマット
+///
+/// 空白を除去し、全角数字・ハイフンを半角に変換した上で以下の形式を受け付ける
+/// - "NN-NN": そのまま
+/// - 4桁の数字: "NN-NN" に整形（例: "1234" → "12-34"）
+/// - 1〜3桁の数字: そのまま
+pub fn format_car(car: &str) -> Result<String, ValidationError> {
+    let normalized: String = car
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| match c {
+            '０'..='９' => char::from_u32(c as u32 - '０' as u32 + '0' as u32).unwrap_or(c),
+            '－' | '‐' | '−' | 'ー' => '-',
+            _ => c,
+        })
+        .collect();
+
+    let is_digits = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+
+    match normalized.split_once('-') {
+        Some((left, right)) if left.len() == 2 && right.len() == 2 && is_digits(left) && is_digits(right) => {
+            Ok(normalized)
+        }
+        None if is_digits(&normalized) && normalized.len() == 4 => {
+            Ok(format!("{}-{}", &normalized[..2], &normalized[2..]))
+        }
+        None if is_digits(&normalized) && normalized.len() <= 3 => Ok(normalized),
+        _ => Err(ValidationError::InvalidCarNumber(car.to_string())),
+    }
+}
+
 /// 日付をパース (YYYY-MM-DD → YYYY年MM月DD日)
 pub fn parse_date(date: &str) -> String {
     if date.is_empty() {
@@ -194,6 +502,346 @@ pub fn parse_pay_day(date: &str) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_print_request_output_path() {
+        let request: PrintRequest =
+            serde_json::from_str(r#"{"items":[],"print":true,"printerName":null,"outputPath":"out/job.pdf"}"#).unwrap();
+        assert_eq!(request.output_path, Some(PathBuf::from("out/job.pdf")));
+
+        // 出力パスは省略可能
+        let request: PrintRequest = serde_json::from_str(r#"{"items":[],"printerName":null}"#).unwrap();
+        assert_eq!(request.output_path, None);
+        assert!(!serde_json::to_string(&request).unwrap().contains("outputPath"));
+    }
+
+    /// Go版のキー名に合わせて作成した合成のJSON（Go版サービスの実際の出力から取得したものではない）
+    const SYNTHETIC_ITEM_JSON: &str = r#"{
+        "car": "12-34",
+        "name": "山田太郎",
+        "purpose": "配送",
+        "startDate": "2024-01-15",
+        "endDate": "2024-01-16",
+        "price": 15000,
+        "tax": 1500.0,
+        "description": "説明",
+        "ryohi": [{
+            "date": "2024-01-15",
+            "dateAr": ["2024-01-15", "2024-01-16"],
+            "dest": "大阪",
+            "destAr": ["大阪", "京都"],
+            "detail": ["高速代"],
+            "kukan": "東京-大阪",
+            "kukanSplit": ["東京", "大阪"],
+            "price": 5000,
+            "priceAr": [3000, 2000],
+            "vol": 1.5,
+            "volAr": [1.0, 0.5],
+            "printDetail": ["高速代"],
+            "printDetailRow": 1,
+            "printKukan": ["東京-大阪"],
+            "printKukanRow": 1,
+            "maxRow": 1,
+            "pageCount": 1
+        }],
+        "office": "本社",
+        "payDay": "2024/01/31"
+    }"#;
+
+    #[test]
+    fn test_item_json_round_trip() {
+        let expected: serde_json::Value = serde_json::from_str(SYNTHETIC_ITEM_JSON).unwrap();
+        let item: Item = serde_json::from_value(expected.clone()).unwrap();
+        assert_eq!(item.start_date.as_deref(), Some("2024-01-15"));
+        assert_eq!(item.pay_day.as_deref(), Some("2024/01/31"));
+        assert_eq!(item.ryohi[0].print_detail_row, Some(1));
+        assert_eq!(item.ryohi[0].kukan_split, Some(vec!["東京".to_string(), "大阪".to_string()]));
+
+        // 全てのキーが入力と同じ名前で出力される
+        assert_eq!(serde_json::to_value(&item).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_ryohi_tax_rate_json() {
+        let ryohi: Ryohi = serde_json::from_str(r#"{"price":1000,"taxRate":8}"#).unwrap();
+        assert_eq!(ryohi.tax_rate, Some(8));
+        assert_eq!(serde_json::to_value(&ryohi).unwrap()["taxRate"], 8);
+    }
+
+    #[test]
+    fn test_ryohi_kukan_sprit_alias() {
+        let ryohi: Ryohi = serde_json::from_str(r#"{"kukanSprit":["東京","大阪"]}"#).unwrap();
+        assert_eq!(ryohi.kukan_split, Some(vec!["東京".to_string(), "大阪".to_string()]));
+
+        let json = serde_json::to_value(&ryohi).unwrap();
+        assert_eq!(json["kukanSplit"], serde_json::json!(["東京", "大阪"]));
+        assert!(json.get("kukanSprit").is_none());
+
+        // 旧フィールド名の互換用アクセサー
+        #[allow(deprecated)]
+        {
+            let mut ryohi = ryohi;
+            assert_eq!(ryohi.kukan_sprit().map(Vec::len), Some(2));
+            *ryohi.kukan_sprit_mut() = None;
+            assert_eq!(ryohi.kukan_split, None);
+        }
+    }
+
+    #[test]
+    fn test_item_json_optional_fields() {
+        let item: Item = serde_json::from_str(r#"{"car":"12-34","name":"山田太郎","price":0}"#).unwrap();
+        assert!(item.ryohi.is_empty());
+        assert_eq!(item.start_date, None);
+
+        let ryohi: Ryohi = serde_json::from_str("{}").unwrap();
+        assert!(ryohi.detail.is_empty());
+        assert_eq!(ryohi.print_detail_row, None);
+
+        // 改ページの指定は未指定の場合は出力しない
+        let json = serde_json::to_value(&item).unwrap();
+        assert!(json.get("keepTogether").is_none());
+        let item: Item = serde_json::from_str(
+            r#"{"car":"","name":"","price":0,"keepTogether":true,"ryohi":[{"pageBreakBefore":true}]}"#,
+        )
+        .unwrap();
+        assert_eq!(item.keep_together, Some(true));
+        assert_eq!(item.ryohi[0].page_break_before, Some(true));
+        assert!(json.get("receiptCount").is_none());
+    }
+
+    #[test]
+    fn test_item_for_each_text_mut() {
+        let mut item = Item {
+            name: "山田".to_string(),
+            office: Some("営業部".to_string()),
+            ryohi: vec![Ryohi {
+                detail: vec!["交通費".to_string(), "宿泊費".to_string()],
+                kukan: Some("東京".to_string()),
+                print_detail: Some(vec!["交通費".to_string()]),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let mut fields = Vec::new();
+        item.for_each_text_mut(|field, value| {
+            fields.push(field.to_string());
+            value.push('!');
+        });
+
+        assert_eq!(
+            fields,
+            vec![
+                "car",
+                "name",
+                "office",
+                "ryohi[0].detail[0]",
+                "ryohi[0].detail[1]",
+                "ryohi[0].kukan",
+                "ryohi[0].printDetail[0]"
+            ]
+        );
+        assert_eq!(item.name, "山田!");
+        assert_eq!(item.ryohi[0].detail[1], "宿泊費!");
+    }
+
+    #[test]
+    fn test_item_for_each_text_matches_mut() {
+        let mut item = Item {
+            name: "山田".to_string(),
+            external_id: Some("V-001".to_string()),
+            ryohi: vec![Ryohi {
+                kukan: Some("東京".to_string()),
+                custom: HashMap::from([
+                    ("c".to_string(), "3".to_string()),
+                    ("a".to_string(), "1".to_string()),
+                    ("b".to_string(), "2".to_string()),
+                ]),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let mut fields = Vec::new();
+        item.for_each_text(|field, value| fields.push((field(), value.to_string())));
+        let expected: Vec<(String, String)> = [
+            ("car", ""),
+            ("name", "山田"),
+            ("externalId", "V-001"),
+            ("ryohi[0].kukan", "東京"),
+            ("ryohi[0].custom.a", "a"),
+            ("ryohi[0].custom.a", "1"),
+            ("ryohi[0].custom.b", "b"),
+            ("ryohi[0].custom.b", "2"),
+            ("ryohi[0].custom.c", "c"),
+            ("ryohi[0].custom.c", "3"),
+        ]
+        .iter()
+        .map(|(field, value)| (field.to_string(), value.to_string()))
+        .collect();
+        assert_eq!(fields, expected);
+
+        // 変更する走査は外部ID・独自の列のキーを除いて同じ順
+        let mut mut_fields = Vec::new();
+        item.for_each_text_mut(|field, value| mut_fields.push((field.to_string(), value.clone())));
+        let printed: Vec<_> = expected
+            .into_iter()
+            .filter(|(field, value)| field != "externalId" && !(field.contains(".custom.") && field.ends_with(value.as_str())))
+            .collect();
+        assert_eq!(mut_fields, printed);
+    }
+
+    fn toll(date: &str, kukan: &str, price: i32) -> Ryohi {
+        Ryohi {
+            date: Some(date.to_string()),
+            kukan: Some(kukan.to_string()),
+            detail: vec!["高速代".to_string()],
+            price: Some(price),
+            vol: Some(1.0),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_dedupe_ryohi_exact() {
+        let mut item = Item {
+            ryohi: vec![
+                toll("2025-01-15", "東京～大阪", 1500),
+                toll("2025-01-15", "東京～大阪", 1500),
+                toll("2025-01-16", "大阪～東京", 1500),
+                toll("2025-01-15", "東京～大阪", 1500),
+            ],
+            ..Default::default()
+        };
+
+        let duplicates = item.dedupe_ryohi(DedupePolicy::Exact);
+
+        assert_eq!(item.ryohi.len(), 2);
+        assert_eq!(
+            duplicates,
+            vec![
+                DuplicateRyohi {
+                    index: 1,
+                    duplicate_of: 0,
+                    date: Some("2025-01-15".to_string()),
+                    price: Some(1500),
+                },
+                DuplicateRyohi {
+                    index: 3,
+                    duplicate_of: 0,
+                    date: Some("2025-01-15".to_string()),
+                    price: Some(1500),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dedupe_ryohi_near_duplicates_kept() {
+        let mut near = toll("2025-01-15", "東京～大阪", 1500);
+        near.vol = Some(2.0);
+        let mut item = Item {
+            ryohi: vec![
+                toll("2025-01-15", "東京～大阪", 1500),
+                toll("2025-01-15", "東京～大阪", 1600),
+                toll("2025-01-16", "東京～大阪", 1500),
+                toll("2025-01-15", "東京～名古屋", 1500),
+                near,
+            ],
+            ..Default::default()
+        };
+
+        assert!(item.dedupe_ryohi(DedupePolicy::Exact).is_empty());
+        assert_eq!(item.ryohi.len(), 5);
+    }
+
+    #[test]
+    fn test_dedupe_ryohi_report_and_off() {
+        let ryohi = vec![
+            toll("2025-01-15", "東京～大阪", 1500),
+            toll("2025-01-15", "東京～大阪", 1500),
+        ];
+        let mut item = Item {
+            ryohi,
+            ..Default::default()
+        };
+
+        assert!(item.dedupe_ryohi(DedupePolicy::Off).is_empty());
+        assert_eq!(item.dedupe_ryohi(DedupePolicy::Report).len(), 1);
+        assert_eq!(item.ryohi.len(), 2);
+    }
+
+    #[test]
+    fn test_possible_duplicate_ryohi() {
+        let mut item = Item {
+            ryohi: vec![
+                toll("2025-01-15", "東京～大阪", 1500),
+                toll("2025-01-15", "大阪～東京", 1200),
+                toll("2025-01-16", "大阪～東京", 1200),
+                toll("2025-01-15", "東京～大阪", 1500),
+                Ryohi::default(),
+                Ryohi::default(),
+            ],
+            ..Default::default()
+        };
+        // 行先が同じ（摘要・区間・金額は問わない）連続する項目のみ
+        for ryohi in &mut item.ryohi[..4] {
+            ryohi.dest = Some("大阪".to_string());
+        }
+        assert_eq!(item.possible_duplicate_ryohi(), vec![1]);
+        assert_eq!(item.ryohi.len(), 6);
+
+        item.ryohi[2].date = Some("2025-01-15".to_string());
+        assert_eq!(item.possible_duplicate_ryohi(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_format_car_accepted() {
+        assert_eq!(format_car("12-34").unwrap(), "12-34");
+        assert_eq!(format_car("12 -34").unwrap(), "12-34");
+        assert_eq!(format_car(" 1234 ").unwrap(), "12-34");
+        assert_eq!(format_car("１２－３４").unwrap(), "12-34");
+        assert_eq!(format_car("123").unwrap(), "123");
+        assert_eq!(format_car("7").unwrap(), "7");
+    }
+
+    #[test]
+    fn test_format_car_rejected() {
+        for car in ["", "12345", "1-234", "12-3", "AB-CD", "品川300あ12-34"] {
+            assert_eq!(
+                format_car(car),
+                Err(ValidationError::InvalidCarNumber(car.to_string())),
+                "{}",
+                car
+            );
+        }
+    }
+
+    #[test]
+    fn test_item_normalize_car() {
+        let mut item = Item {
+            car: "12 34".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(item.normalize_car(DEFAULT_MAX_CAR_LENGTH), None);
+        assert_eq!(item.car, "12-34");
+
+        item.car = "品川300あ12-34".to_string();
+        assert_eq!(
+            item.normalize_car(DEFAULT_MAX_CAR_LENGTH),
+            Some(ValidationError::CarNumberTooLong {
+                value: "品川300あ12-34".to_string(),
+                max: DEFAULT_MAX_CAR_LENGTH,
+            })
+        );
+        assert_eq!(item.car, "品川300あ12-34");
+
+        item.car = "AB-CD".to_string();
+        assert!(matches!(
+            item.normalize_car(DEFAULT_MAX_CAR_LENGTH),
+            Some(ValidationError::InvalidCarNumber(_))
+        ));
+    }
+
     #[test]
     fn test_format_price() {
         assert_eq!(format_price(1000), "1,000");
@@ -203,6 +851,20 @@ mod tests {
         assert_eq!(format_price(-1000), "-1,000");
     }
 
+    #[test]
+    fn test_format_price_styled() {
+        let none = NumberFormat {
+            thousands_separator: None,
+        };
+        assert_eq!(format_price_styled(1234567, &none), "1234567");
+
+        let period = NumberFormat {
+            thousands_separator: Some('.'),
+        };
+        assert_eq!(format_price_styled(-1234567, &period), "-1.234.567");
+        assert_eq!(format_price_styled(i32::MIN, &NumberFormat::default()), "-2,147,483,648");
+    }
+
     #[test]
     fn test_parse_date() {
         assert_eq!(parse_date("2024-01-15"), "2024年01月15日");
@@ -2,53 +2,379 @@
 //!
 //! Windows環境の日本語フォントを読み込む
 
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+
+use printpdf::ParsedFont;
+use serde::Serialize;
+
 use crate::error::PdfError;
+use crate::models::Item;
+
+/// デフォルトのフォント候補（優先順位順）
+pub const DEFAULT_FONT_CANDIDATES: &[&str] = &[
+    "yumin.ttf",      // 游明朝
+    "yugothm.ttf",    // 游ゴシック Medium
+    "YuGothM.ttf",    // 游ゴシック Medium (大文字)
+    "meiryo.ttc",     // メイリオ
+    "msgothic.ttc",   // MSゴシック
+    "msmincho.ttc",   // MS明朝
+];
+
+/// グリフ収録範囲の検索対象（基本多言語面〜追加漢字面）
+const GLYPH_SCAN_RANGE: std::ops::RangeInclusive<u32> = 0..=0x2FFFF;
+
+/// フォントに収録されている文字の一覧
+#[derive(Debug, Clone, Default)]
+pub struct FontGlyphs {
+    chars: HashSet<char>,
+}
+
+impl FontGlyphs {
+    /// パース済みフォントのcmapから収録文字を取得
+    pub fn from_parsed_font(font: &ParsedFont) -> Self {
+        let chars = GLYPH_SCAN_RANGE
+            .filter_map(char::from_u32)
+            .filter(|&c| matches!(font.lookup_glyph_index(c as u32), Some(gid) if gid != 0))
+            .collect();
+        Self { chars }
+    }
+
+    /// 文字の一覧から作成
+    pub fn from_chars(chars: impl IntoIterator<Item = char>) -> Self {
+        Self {
+            chars: chars.into_iter().collect(),
+        }
+    }
+
+    /// 2つのフォントのいずれかに収録されている文字の一覧
+    pub fn union(&self, other: &FontGlyphs) -> Self {
+        Self {
+            chars: self.chars.union(&other.chars).copied().collect(),
+        }
+    }
+
+    /// 文字がフォントに収録されているか
+    ///
+    /// 制御文字と半角スペースは常に収録済みとして扱う
+    pub fn has_glyph(&self, c: char) -> bool {
+        c == ' ' || c.is_control() || self.chars.contains(&c)
+    }
+
+    /// フォントに存在しない文字を出現順（重複なし）で取得
+    pub fn unsupported_chars(&self, text: &str) -> Vec<char> {
+        let mut result = Vec::new();
+        for c in text.chars() {
+            if !self.has_glyph(c) && !result.contains(&c) {
+                result.push(c);
+            }
+        }
+        result
+    }
+
+    /// アイテム全体でフォントに存在しない文字を出現順（重複なし）で取得
+    pub fn missing_in_items(&self, items: &[Item]) -> Vec<char> {
+        let mut result = Vec::new();
+        for item in items {
+            let mut item = item.clone();
+            item.for_each_text_mut(|_, value| {
+                for c in self.unsupported_chars(value) {
+                    if !result.contains(&c) {
+                        result.push(c);
+                    }
+                }
+            });
+        }
+        result
+    }
+}
+
+/// フォントの用途
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FontRole {
+    /// タイトル
+    Title,
+    /// 見出し・ラベル・本文
+    Body,
+    /// 金額などの数値
+    Numeric,
+}
+
+impl std::fmt::Display for FontRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            FontRole::Title => "タイトル",
+            FontRole::Body => "本文",
+            FontRole::Numeric => "数値",
+        };
+        f.write_str(name)
+    }
+}
+
+/// フォントの指定（パスまたは候補のファイル名）
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FontSource {
+    /// フォントファイルのパス（設定した場合は候補を検索しない）
+    pub path: Option<PathBuf>,
+    /// フォント候補のファイル名（優先順位順）
+    pub candidates: Option<Vec<String>>,
+}
+
+impl FontSource {
+    /// フォントファイルのパスを指定
+    pub fn path(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: Some(path.into()),
+            candidates: None,
+        }
+    }
+
+    /// フォント候補のファイル名を指定
+    pub fn candidates(candidates: Vec<String>) -> Self {
+        Self {
+            path: None,
+            candidates: Some(candidates),
+        }
+    }
+}
+
+/// 読み込み済みのフォント（ファイル内容と収録文字）
+struct CachedFont {
+    data: Arc<Vec<u8>>,
+    glyphs: Arc<FontGlyphs>,
+}
+
+/// フォントデータのパーサー
+type FontParser = fn(&[u8]) -> Option<ParsedFont>;
+
+/// プロセス全体で共有するキャッシュ（`FontCache::global`）
+static GLOBAL_CACHE: OnceLock<Arc<FontCache>> = OnceLock::new();
+
+/// フォントパスごとの読み込み済みフォントのキャッシュ
+///
+/// 同じパスのフォントは1回だけファイルを読み込み、収録文字を調べる。
+/// 複数のクライアント・サービスのクローンで `Arc` を共有して使用する。
+/// `ParsedFont` はスレッド間で共有できないため、PDFに埋め込むフォントはキャッシュしたファイル内容から作成する
+pub struct FontCache {
+    /// フォントパスごとの読み込み済みフォント
+    entries: Mutex<HashMap<PathBuf, Arc<CachedFont>>>,
+    /// フォントファイルを読み込んでパースできた回数
+    loads: AtomicUsize,
+    /// フォントデータのパーサー
+    parser: FontParser,
+}
+
+impl FontCache {
+    /// 空のキャッシュを作成
+    pub fn new() -> Self {
+        Self::with_parser(|data| ParsedFont::from_bytes(data, 0, &mut Vec::new()))
+    }
+
+    /// パーサーを指定して作成（テストでフォントファイルの代わりに使用）
+    pub(crate) fn with_parser(parser: FontParser) -> Self {
+        Self {
+            entries: Mutex::new(HashMap::new()),
+            loads: AtomicUsize::new(0),
+            parser,
+        }
+    }
+
+    /// プロセス全体で共有するキャッシュ（`FontLoader` の既定）
+    pub fn global() -> Arc<FontCache> {
+        GLOBAL_CACHE.get_or_init(|| Arc::new(FontCache::new())).clone()
+    }
+
+    /// プロセス全体で共有するキャッシュを設定（使用済みの場合はfalse）
+    #[cfg(any(test, feature = "test-util"))]
+    pub(crate) fn set_global(cache: Arc<FontCache>) -> bool {
+        GLOBAL_CACHE.set(cache).is_ok()
+    }
+
+    /// フォントファイルを読み込んで収録文字を調べた回数（キャッシュにあるフォント・パースできないフォントは数えない）
+    pub fn load_count(&self) -> usize {
+        self.loads.load(Ordering::SeqCst)
+    }
+
+    /// フォントを読み込んでパースする（ファイル内容と収録文字はキャッシュを使用）
+    ///
+    /// `ParsedFont` はキャッシュできないため、パースは呼び出しごとに行う。
+    /// 同時に同じフォントを要求された場合も1回だけ読み込むよう、読み込み中はロックを保持する
+    pub(crate) fn load(&self, font_path: &Path) -> Result<(ParsedFont, Arc<FontGlyphs>), PdfError> {
+        let parse_error = || PdfError::FontLoad("フォントパースエラー".to_string());
+        let cached = {
+            let mut entries = self
+                .entries
+                .lock()
+                .map_err(|_| PdfError::FontLoad("フォントキャッシュを利用できません".to_string()))?;
+            match entries.get(font_path) {
+                Some(cached) => cached.clone(),
+                None => {
+                    let font_data = std::fs::read(font_path).map_err(|e| {
+                        PdfError::FontLoad(format!("フォント読み込みエラー: {}", e))
+                    })?;
+                    let font = (self.parser)(&font_data).ok_or_else(parse_error)?;
+                    self.loads.fetch_add(1, Ordering::SeqCst);
+                    let glyphs = Arc::new(FontGlyphs::from_parsed_font(&font));
+                    entries.insert(
+                        font_path.to_path_buf(),
+                        Arc::new(CachedFont {
+                            data: Arc::new(font_data),
+                            glyphs: glyphs.clone(),
+                        }),
+                    );
+                    return Ok((font, glyphs));
+                }
+            }
+        };
+
+        let font = (self.parser)(&cached.data).ok_or_else(parse_error)?;
+        Ok((font, cached.glyphs.clone()))
+    }
+}
+
+impl Default for FontCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for FontCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FontCache")
+            .field("loads", &self.load_count())
+            .finish_non_exhaustive()
+    }
+}
 
 /// フォントローダー
 pub struct FontLoader {
     /// フォントファイルパス
     font_path: Option<PathBuf>,
+    /// フォント候補のファイル名（Noneの場合はデフォルトの候補を使用）
+    candidates: Option<Vec<String>>,
+    /// 読み込んだフォントの収録文字
+    glyphs: Option<Arc<FontGlyphs>>,
+    /// 本文以外の用途のフォント指定
+    role_sources: HashMap<FontRole, FontSource>,
+    /// 読み込んだ用途別フォントの収録文字
+    role_glyphs: HashMap<FontRole, Arc<FontGlyphs>>,
+    /// 本文フォントに存在しない文字を描画するフォールバックフォントの指定
+    fallback_source: Option<FontSource>,
+    /// 読み込んだフォールバックフォントの収録文字
+    fallback_glyphs: Option<Arc<FontGlyphs>>,
+    /// パース済みフォントのキャッシュ
+    cache: Arc<FontCache>,
 }
 
 impl FontLoader {
     /// 新しいフォントローダーを作成
     pub fn new() -> Self {
-        Self { font_path: None }
+        Self {
+            font_path: None,
+            candidates: None,
+            glyphs: None,
+            role_sources: HashMap::new(),
+            role_glyphs: HashMap::new(),
+            fallback_source: None,
+            fallback_glyphs: None,
+            cache: FontCache::global(),
+        }
+    }
+
+    /// パース済みフォントのキャッシュを設定（既定はプロセス全体で共有するキャッシュ）
+    pub fn with_cache(mut self, cache: Arc<FontCache>) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// パース済みフォントのキャッシュを取得
+    pub(crate) fn cache(&self) -> &Arc<FontCache> {
+        &self.cache
+    }
+
+    /// フォント候補のファイル名を優先順位順に設定
+    ///
+    /// 設定した場合はデフォルトの候補の代わりに使用される
+    pub fn with_candidates(mut self, candidates: Vec<String>) -> Self {
+        self.candidates = Some(candidates);
+        self
+    }
+
+    /// フォントファイルのパスを直接設定（設定した場合は検索しない）
+    pub fn with_font_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.font_path = Some(path.into());
+        self
+    }
+
+    /// 数値用のフォントを設定（金額を欧文フォントで表示する場合など）
+    pub fn with_numeric_font(self, path: impl Into<PathBuf>) -> Self {
+        self.with_role_font(FontRole::Numeric, FontSource::path(path))
+    }
+
+    /// 用途別のフォントを設定
+    ///
+    /// `FontRole::Body` を指定した場合は本文フォントのパス・候補を置き換える
+    pub fn with_role_font(mut self, role: FontRole, source: FontSource) -> Self {
+        if role == FontRole::Body {
+            if let Some(path) = source.path {
+                self.font_path = Some(path);
+            }
+            if let Some(candidates) = source.candidates {
+                self.candidates = Some(candidates);
+            }
+        } else {
+            self.role_sources.insert(role, source);
+        }
+        self
+    }
+
+    /// フォールバックフォントを設定
+    ///
+    /// 本文フォントに存在しない文字（人名の外字など）のみをこのフォントで描画する
+    pub fn with_fallback_font(mut self, source: FontSource) -> Self {
+        self.fallback_source = Some(source);
+        self
+    }
+
+    /// フォールバックフォントが設定されているか
+    pub fn has_fallback_font(&self) -> bool {
+        self.fallback_source.is_some()
+    }
+
+    /// 用途別のフォントが設定されているか（本文フォントは常に使用する）
+    pub fn has_role_font(&self, role: FontRole) -> bool {
+        role == FontRole::Body || self.role_sources.contains_key(&role)
+    }
+
+    /// 検索するフォント候補のファイル名を取得
+    pub fn candidates(&self) -> Vec<String> {
+        match self.candidates {
+            Some(ref candidates) => candidates.clone(),
+            None => DEFAULT_FONT_CANDIDATES.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    /// `find_font` が検索するフォントファイルのパスを優先順位順に取得（診断用）
+    ///
+    /// フォントディレクトリが見つからない場合は `C:\Windows\Fonts` を基準にする
+    pub fn candidate_paths(&self) -> Vec<PathBuf> {
+        candidate_paths(&self.candidates())
     }
 
     /// フォントを検索して読み込む
     ///
-    /// 優先順位:
+    /// デフォルトの優先順位:
     /// 1. yumin.ttf (游明朝)
     /// 2. yugothm.ttf (游ゴシック)
     /// 3. meiryo.ttc (メイリオ)
     /// 4. msgothic.ttc (MSゴシック)
     pub fn find_font(&mut self) -> Result<PathBuf, PdfError> {
-        let fonts_dir = get_windows_fonts_dir()?;
-
-        // 優先順位順にフォントを検索
-        let candidates = [
-            "yumin.ttf",      // 游明朝
-            "yugothm.ttf",    // 游ゴシック Medium
-            "YuGothM.ttf",    // 游ゴシック Medium (大文字)
-            "meiryo.ttc",     // メイリオ
-            "msgothic.ttc",   // MSゴシック
-            "msmincho.ttc",   // MS明朝
-        ];
-
-        for candidate in &candidates {
-            let font_path = fonts_dir.join(candidate);
-            if font_path.exists() {
-                tracing::info!("フォント発見: {:?}", font_path);
-                self.font_path = Some(font_path.clone());
-                return Ok(font_path);
-            }
-        }
-
-        Err(PdfError::FontLoad(
-            "日本語フォントが見つかりません".to_string(),
-        ))
+        let font_path = find_in_fonts_dir(&self.candidates())?;
+        self.font_path = Some(font_path.clone());
+        Ok(font_path)
     }
 
     /// フォントデータを読み込む
@@ -62,6 +388,102 @@ impl FontLoader {
         })
     }
 
+    /// フォントを検索・読み込み・パースする
+    ///
+    /// 収録文字の一覧もあわせて取得し、`unsupported_chars` で利用できるようにする。
+    /// フォントファイルの内容と収録文字はキャッシュされ、2回目以降はファイルを再読み込みしない
+    /// （パース済みフォントはキャッシュできないため、パースは毎回行う）。
+    pub fn load_font(&mut self) -> Result<ParsedFont, PdfError> {
+        let font_path = match self.font_path {
+            Some(ref path) => path.clone(),
+            None => self.find_font()?,
+        };
+
+        let (font, glyphs) = self.cache.load(&font_path)?;
+        self.glyphs = Some(glyphs);
+        Ok(font)
+    }
+
+    /// 用途別のフォントを読み込む
+    ///
+    /// # Returns
+    /// (パース済みフォント, フォントファイルのパス)。
+    /// 本文以外で用途別のフォントが設定されていない場合はNone
+    pub fn load_role_font(&mut self, role: FontRole) -> Option<Result<(ParsedFont, PathBuf), PdfError>> {
+        if role == FontRole::Body {
+            return Some(self.load_font().and_then(|font| {
+                let path = self
+                    .font_path
+                    .clone()
+                    .ok_or_else(|| PdfError::FontLoad("フォントが設定されていません".to_string()))?;
+                Ok((font, path))
+            }));
+        }
+
+        let source = self.role_sources.get(&role)?;
+        let result = self.load_source(source).map(|(font, path, glyphs)| {
+            self.role_glyphs.insert(role, glyphs);
+            (font, path)
+        });
+        Some(result)
+    }
+
+    /// フォールバックフォントを読み込む
+    ///
+    /// # Returns
+    /// (パース済みフォント, フォントファイルのパス)。設定されていない場合はNone
+    pub fn load_fallback_font(&mut self) -> Option<Result<(ParsedFont, PathBuf), PdfError>> {
+        let source = self.fallback_source.as_ref()?;
+        let result = self.load_source(source).map(|(font, path, glyphs)| {
+            self.fallback_glyphs = Some(glyphs);
+            (font, path)
+        });
+        Some(result)
+    }
+
+    /// フォントの指定からフォントを検索・読み込む（候補の指定が無い場合は本文フォントの候補を使用）
+    fn load_source(&self, source: &FontSource) -> Result<(ParsedFont, PathBuf, Arc<FontGlyphs>), PdfError> {
+        let path = match source.path {
+            Some(ref path) => path.clone(),
+            None => {
+                let candidates = source.candidates.clone().unwrap_or_else(|| self.candidates());
+                find_in_fonts_dir(&candidates)?
+            }
+        };
+        let (font, glyphs) = self.cache.load(&path)?;
+        Ok((font, path, glyphs))
+    }
+
+    /// 読み込んだフォントの収録文字を取得（未読み込みの場合はNone）
+    pub fn glyphs(&self) -> Option<&FontGlyphs> {
+        self.glyphs.as_deref()
+    }
+
+    /// 用途別フォントの収録文字を取得（未読み込みの場合はNone）
+    ///
+    /// 用途別のフォントが無い用途は本文フォントの収録文字を返す
+    pub fn role_glyphs(&self, role: FontRole) -> Option<Arc<FontGlyphs>> {
+        match self.role_glyphs.get(&role) {
+            Some(glyphs) => Some(glyphs.clone()),
+            None => self.glyphs.clone(),
+        }
+    }
+
+    /// フォールバックフォントの収録文字を取得（未読み込みの場合はNone）
+    pub fn fallback_glyphs(&self) -> Option<Arc<FontGlyphs>> {
+        self.fallback_glyphs.clone()
+    }
+
+    /// フォントに存在しない文字を取得
+    ///
+    /// フォント未読み込みの場合は判定できないため空を返す
+    pub fn unsupported_chars(&self, text: &str) -> Vec<char> {
+        self.glyphs
+            .as_ref()
+            .map(|glyphs| glyphs.unsupported_chars(text))
+            .unwrap_or_default()
+    }
+
     /// 現在のフォントパスを取得
     pub fn font_path(&self) -> Option<&PathBuf> {
         self.font_path.as_ref()
@@ -74,6 +496,34 @@ impl Default for FontLoader {
     }
 }
 
+/// フォントディレクトリから候補を優先順位順に検索
+fn find_in_fonts_dir(candidates: &[String]) -> Result<PathBuf, PdfError> {
+    get_windows_fonts_dir()?;
+    let paths = candidate_paths(candidates);
+
+    for font_path in &paths {
+        if font_path.exists() {
+            tracing::info!("フォント発見: {:?}", font_path);
+            return Ok(font_path.clone());
+        }
+    }
+
+    let searched: Vec<String> = paths.iter().map(|p| p.display().to_string()).collect();
+    Err(PdfError::FontLoad(format!(
+        "日本語フォントが見つかりません（検索したパス: {}）",
+        searched.join(", ")
+    )))
+}
+
+/// フォントディレクトリと候補のファイル名から検索パスを構築
+fn candidate_paths(candidates: &[String]) -> Vec<PathBuf> {
+    let fonts_dir = get_windows_fonts_dir().unwrap_or_else(|_| PathBuf::from(DEFAULT_WINDOWS_FONTS_DIR));
+    candidates.iter().map(|candidate| fonts_dir.join(candidate)).collect()
+}
+
+/// 標準のWindowsフォントディレクトリ
+const DEFAULT_WINDOWS_FONTS_DIR: &str = "C:\\Windows\\Fonts";
+
 /// Windowsのフォントディレクトリを取得
 fn get_windows_fonts_dir() -> Result<PathBuf, PdfError> {
     // WINDIR環境変数からフォントディレクトリを構築
@@ -85,7 +535,7 @@ fn get_windows_fonts_dir() -> Result<PathBuf, PdfError> {
     }
 
     // フォールバック: C:\Windows\Fonts
-    let default_path = PathBuf::from("C:\\Windows\\Fonts");
+    let default_path = PathBuf::from(DEFAULT_WINDOWS_FONTS_DIR);
     if default_path.exists() {
         return Ok(default_path);
     }
@@ -98,6 +548,7 @@ fn get_windows_fonts_dir() -> Result<PathBuf, PdfError> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::pdf::testing::MockFont;
 
     #[test]
     fn test_get_windows_fonts_dir() {
@@ -108,6 +559,138 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_font_loader_candidates() {
+        let loader = FontLoader::new();
+        assert_eq!(loader.candidates()[0], "yumin.ttf");
+
+        let loader = FontLoader::new().with_candidates(vec!["msgothic.ttc".to_string()]);
+        assert_eq!(loader.candidates(), vec!["msgothic.ttc".to_string()]);
+    }
+
+    #[test]
+    fn test_unsupported_chars() {
+        let glyphs = FontGlyphs::from_chars("東京大阪".chars());
+        // 絵文字と収録されていない漢字（𠮷）を検出
+        assert_eq!(glyphs.unsupported_chars("東京😀𠮷大阪😀"), vec!['😀', '𠮷']);
+        assert!(glyphs.unsupported_chars("東京 大阪").is_empty());
+    }
+
+    #[test]
+    fn test_missing_in_items() {
+        let glyphs = FontGlyphs::from_chars("山田太郎".chars());
+        let items = vec![
+            Item {
+                name: "髙田太郎".to_string(),
+                ..Default::default()
+            },
+            Item {
+                name: "山田".to_string(),
+                office: Some("栁髙".to_string()),
+                ..Default::default()
+            },
+        ];
+        assert_eq!(glyphs.missing_in_items(&items), vec!['髙', '栁']);
+    }
+
+    #[test]
+    fn test_font_loader_load_font_glyphs() {
+        if cfg!(windows) {
+            let mut loader = FontLoader::new();
+            // フォントが見つかるかどうかは環境依存
+            if loader.load_font().is_ok() {
+                assert!(loader.unsupported_chars("東京").is_empty());
+                assert_eq!(loader.unsupported_chars("😀"), vec!['😀']);
+            }
+        }
+    }
+
+    #[test]
+    fn test_font_loader_unsupported_chars_without_font() {
+        let loader = FontLoader::new();
+        assert!(loader.unsupported_chars("😀").is_empty());
+    }
+
+    #[test]
+    fn test_font_loader_role_fonts() {
+        let loader = FontLoader::new()
+            .with_role_font(FontRole::Title, FontSource::candidates(vec!["yumin.ttf".to_string()]))
+            .with_role_font(FontRole::Body, FontSource::path("C:\\Windows\\Fonts\\yugothm.ttf"));
+
+        assert!(loader.has_role_font(FontRole::Title));
+        assert!(loader.has_role_font(FontRole::Body));
+        assert!(!loader.has_role_font(FontRole::Numeric));
+        assert_eq!(loader.font_path(), Some(&PathBuf::from("C:\\Windows\\Fonts\\yugothm.ttf")));
+
+        let loader = FontLoader::new().with_numeric_font("C:\\Windows\\Fonts\\arial.ttf");
+        assert!(loader.has_role_font(FontRole::Numeric));
+        assert!(loader.role_glyphs(FontRole::Numeric).is_none());
+    }
+
+    #[test]
+    fn test_font_loader_fallback_font() {
+        let mut loader = FontLoader::new();
+        assert!(!loader.has_fallback_font());
+        assert!(loader.load_fallback_font().is_none());
+
+        let mut loader = loader.with_fallback_font(FontSource::path("/nonexistent/ipamjm.ttf"));
+        assert!(loader.has_fallback_font());
+        assert!(matches!(loader.load_fallback_font(), Some(Err(PdfError::FontLoad(_)))));
+        assert!(loader.fallback_glyphs().is_none());
+    }
+
+    #[test]
+    fn test_glyphs_union() {
+        let glyphs = FontGlyphs::from_chars("山田".chars()).union(&FontGlyphs::from_chars("𠮷".chars()));
+        assert!(glyphs.unsupported_chars("𠮷山田").is_empty());
+        assert_eq!(glyphs.unsupported_chars("髙"), vec!['髙']);
+    }
+
+    #[test]
+    fn test_candidate_paths() {
+        let loader = FontLoader::new().with_candidates(vec!["a.ttf".to_string(), "b.ttc".to_string()]);
+        let paths = loader.candidate_paths();
+
+        assert_eq!(paths.len(), 2);
+        assert!(paths[0].ends_with("a.ttf"));
+        assert!(paths[1].ends_with("b.ttc"));
+        assert_eq!(paths[0].parent(), paths[1].parent());
+    }
+
+    #[test]
+    fn test_load_role_font_missing() {
+        let mut loader = FontLoader::new()
+            .with_role_font(FontRole::Numeric, FontSource::path("/nonexistent/font.ttf"));
+        assert!(loader.load_role_font(FontRole::Title).is_none());
+        assert!(matches!(loader.load_role_font(FontRole::Numeric), Some(Err(PdfError::FontLoad(_)))));
+    }
+
+    #[test]
+    fn test_font_cache_loads_once() {
+        let font = MockFont::new("fonts_cache");
+        let cache = font.cache();
+
+        for _ in 0..3 {
+            let mut loader = FontLoader::new().with_font_path(font.path()).with_cache(cache.clone());
+            loader.load_font().unwrap();
+            assert!(loader.glyphs().is_some());
+        }
+        assert_eq!(cache.load_count(), 1);
+
+        // 読み込めないフォントは数えない
+        let mut loader = FontLoader::new().with_font_path("/nonexistent/font.ttf").with_cache(cache.clone());
+        assert!(matches!(loader.load_font(), Err(PdfError::FontLoad(_))));
+        assert_eq!(cache.load_count(), 1);
+
+        // パースできないフォントも数えない（キャッシュもしない）
+        let broken = Arc::new(FontCache::with_parser(|_| None));
+        for _ in 0..2 {
+            let mut loader = FontLoader::new().with_font_path(font.path()).with_cache(broken.clone());
+            assert!(matches!(loader.load_font(), Err(PdfError::FontLoad(_))));
+        }
+        assert_eq!(broken.load_count(), 0);
+    }
+
     #[test]
     fn test_font_loader_find_font() {
         if cfg!(windows) {
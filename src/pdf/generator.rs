@@ -3,15 +3,67 @@
 //! Go版のreportlab_style_pdf.goから移植
 //! printpdf 0.8クレートを使用してPDFを生成
 
-use std::path::PathBuf;
-
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use async_trait::async_trait;
 use printpdf::*;
+use serde::Serialize;
+use tokio_util::sync::CancellationToken;
 
+use crate::config::PdfConfig;
 use crate::error::PdfError;
-use crate::models::{format_price, Item};
-use crate::pdf::fonts::FontLoader;
+use crate::audit::sha256_hex;
+use crate::fs::{write_atomic, Fs, RealFs, WriteRetry};
+use crate::models::Item;
+use crate::pdf::fonts::{FontCache, FontGlyphs, FontLoader, FontRole};
 use crate::pdf::layout::*;
-use crate::pdf::text_utils::prepare_ryohi_for_print;
+use crate::pdf::metadata::{external_id_keywords, external_ids_by_page};
+use crate::pdf::renderer::{EmbeddedSeal, PageRenderer};
+use crate::pdf::seal::SealImage;
+use crate::pdf::svg::ops_to_svg;
+use crate::pdf::tax::check_item_taxes;
+use crate::pdf::text_utils::{is_nfc_item, nfc_item, truncated_detail_warnings, DetailOverflow, FormatOptions, WrapMode};
+use crate::print::backend::{create_printer_with_passthrough, Backend};
+use crate::traits::PdfGenerator;
+use crate::warning::Warning;
+
+/// フォントに存在しない文字のデフォルトの代替文字
+pub const DEFAULT_GLYPH_FALLBACK: &str = "〓";
+
+/// アイテムが空の場合のエラーメッセージ
+pub const EMPTY_ITEMS_MESSAGE: &str = "項目が空です";
+
+/// ページごとの操作を `PdfPage` にする前に呼び出す処理（注釈・スタンプの追加等）
+///
+/// 引数はPDF内のページ番号（0始まり、面付け後の用紙ごと）とそのページの操作
+#[derive(Clone)]
+pub struct PagePostprocess(Arc<PagePostprocessFn>);
+
+/// ページの操作の後処理（PDF内のページ番号, ページの操作）
+type PagePostprocessFn = dyn Fn(usize, &mut Vec<Op>) + Send + Sync;
+
+impl PagePostprocess {
+    /// 処理を指定して作成
+    pub fn new(f: impl Fn(usize, &mut Vec<Op>) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(f))
+    }
+
+    /// ページの操作に処理を適用
+    fn apply(&self, page: usize, ops: &mut Vec<Op>) {
+        (self.0)(page, ops)
+    }
+}
+
+impl std::fmt::Debug for PagePostprocess {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("PagePostprocess")
+    }
+}
 
 /// ReportLabスタイルのPDF生成クライアント
 pub struct ReportLabStylePdfClient {
@@ -19,6 +71,248 @@ pub struct ReportLabStylePdfClient {
     output_path: PathBuf,
     /// フォントローダー
     font_loader: FontLoader,
+    /// SumatraPDFの実行ファイルパス（印刷時に使用）
+    sumatra_path: Option<PathBuf>,
+    /// 印刷バックエンド
+    printer_backend: Backend,
+    /// IPPの論理プリンター名とURIの対応
+    ipp_printers: BTreeMap<String, String>,
+    /// winspoolでPDFを直接印刷するプリンター名
+    pdf_passthrough_printers: Vec<String>,
+    /// 出力ファイル書き込みの再試行設定
+    write_retry: WriteRetry,
+    /// ファイル操作
+    fs: Arc<dyn Fs>,
+    /// 印刷用データの整形オプション
+    format: FormatOptions,
+    /// レイアウト設定
+    layout: LayoutConfig,
+    /// 名前付きのレイアウト設定（アイテムごとに選択）
+    layout_presets: BTreeMap<String, LayoutConfig>,
+    /// 直近の生成レポート
+    last_report: GenerationReport,
+    /// 最後の生成で出力したページの統計
+    last_stats: GenerationStats,
+    /// ページ操作を並列に構築するか（`parallel` feature有効時のみ）
+    parallel: bool,
+    /// フォントに存在しない文字の代替文字
+    glyph_fallback: String,
+    /// 厳格モード（フォントに存在しない文字をエラーとする）
+    strict: bool,
+    /// アイテムが空の場合に空白の精算書を1ページ出力するか
+    blank_on_empty: bool,
+    /// 重複入力の可能性がある旅費項目を警告するか（厳格モードでは常に警告）
+    check_duplicates: bool,
+    /// 描画前に文字列をNFC（合成済みの文字）に統一するか
+    unicode_nfc: bool,
+    /// 生成を中断するためのトークン（アイテムごとに確認）
+    cancel: Option<CancellationToken>,
+    /// 承認欄の列ごとの印影画像
+    approval_seals: Vec<Option<SealImage>>,
+    /// ドキュメントに埋め込んだ印影（承認欄の列順）
+    embedded_seals: Vec<Option<EmbeddedSeal>>,
+    /// PDFの作成日時・更新日時（Noneの場合は生成時の現在時刻）
+    fixed_date: Option<DateTime>,
+    /// ページごとの操作の後処理
+    page_postprocess: Option<PagePostprocess>,
+}
+
+/// ページを追加する前のドキュメントと全ページの操作
+struct PreparedDocument {
+    /// フォント・印影を追加したドキュメント
+    doc: PdfDocument,
+    /// 全ページの操作（面付け前）
+    page_ops: Vec<Vec<Op>>,
+    /// アイテムごとのページ範囲（面付け前）
+    pages_per_item: Vec<Range<usize>>,
+    /// 描画したアイテム（代替文字に置換済み）
+    items: Vec<Item>,
+}
+
+/// ページ操作を作成中のドキュメント
+///
+/// 入力の確認とフォントの読み込み（`begin_document`）、アイテムの範囲ごとのページ操作の作成（`render_chunk`）、
+/// 生成レポートの確定（`finish_document`）を別々に呼び出すために使用する。
+/// `PdfDocument`（パース済みフォント）はスレッド間で移動できないため含めない
+pub(crate) struct PendingDocument {
+    /// 用途別のフォントID
+    fonts: FontSet,
+    /// 描画するアイテム（代替文字に置換済み）
+    items: Vec<Item>,
+    /// 作成済みのページの操作（面付け前）
+    page_ops: Vec<Vec<Op>>,
+    /// 作成済みのアイテムごとのページ範囲（面付け前）
+    pages_per_item: Vec<Range<usize>>,
+    /// 作成済みのアイテムの生成レポート
+    report: GenerationReport,
+    /// 描画前の確認で検出した警告（税額・重複・代替文字・フォント）
+    warnings: Vec<Warning>,
+    /// 埋め込むフォントのパス（フォントIDの順）
+    embedded_fonts: Vec<PathBuf>,
+    /// 空白の精算書の部数
+    blank_copies: usize,
+}
+
+impl PendingDocument {
+    /// 描画するアイテム数
+    pub(crate) fn item_count(&self) -> usize {
+        self.items.len()
+    }
+}
+
+/// 用途別のフォントID
+///
+/// 用途別のフォントが無い場合は本文フォントと同じIDを使用する
+#[derive(Debug, Clone)]
+pub struct FontSet {
+    /// タイトル
+    pub title: FontId,
+    /// 見出し・ラベル・本文
+    pub body: FontId,
+    /// 金額などの数値
+    pub numeric: FontId,
+    /// 用途別フォントの収録文字（未設定の用途は判定せずに使用する）
+    coverage: HashMap<FontRole, Arc<FontGlyphs>>,
+    /// フォールバックフォント（フォントID, 本文フォントの収録文字, フォールバックフォントの収録文字）
+    fallback: Option<(FontId, Arc<FontGlyphs>, Arc<FontGlyphs>)>,
+}
+
+impl FontSet {
+    /// 全ての用途で同じフォントを使用
+    pub fn single(font_id: FontId) -> Self {
+        Self {
+            title: font_id.clone(),
+            body: font_id.clone(),
+            numeric: font_id,
+            coverage: HashMap::new(),
+            fallback: None,
+        }
+    }
+
+    /// 用途のフォントIDを取得
+    pub fn get(&self, role: FontRole) -> &FontId {
+        match role {
+            FontRole::Title => &self.title,
+            FontRole::Body => &self.body,
+            FontRole::Numeric => &self.numeric,
+        }
+    }
+
+    /// 用途別フォントの収録文字を設定
+    pub fn set_coverage(&mut self, role: FontRole, glyphs: Arc<FontGlyphs>) {
+        self.coverage.insert(role, glyphs);
+    }
+
+    /// テキストを描画するフォントIDを選択
+    ///
+    /// 用途別フォントに存在しない文字を含む場合は本文フォントを使用する
+    /// （例: 欧文の数値フォントに「円」が無い場合）
+    pub fn for_text(&self, role: FontRole, text: &str) -> &FontId {
+        match self.coverage.get(&role) {
+            Some(glyphs) if !glyphs.unsupported_chars(text).is_empty() => &self.body,
+            _ => self.get(role),
+        }
+    }
+
+    /// フォールバックフォントを設定
+    ///
+    /// 本文フォント（`body_glyphs`）に存在せずフォールバックフォントに存在する文字は
+    /// フォールバックフォントで描画する
+    pub fn set_fallback(&mut self, font_id: FontId, body_glyphs: Arc<FontGlyphs>, fallback_glyphs: Arc<FontGlyphs>) {
+        self.fallback = Some((font_id, body_glyphs, fallback_glyphs));
+    }
+
+    /// テキストを描画するフォントごとの区間に分割
+    ///
+    /// 本文フォントで描画する場合のみフォールバックフォントに切り替える。
+    /// 切り替えが無い場合は `font_id` の1区間を返す
+    pub fn runs<'a, 't>(&'a self, font_id: &'a FontId, text: &'t str) -> Vec<(&'a FontId, &'t str)> {
+        let Some((fallback_id, body_glyphs, fallback_glyphs)) = self.fallback.as_ref().filter(|_| *font_id == self.body) else {
+            return vec![(font_id, text)];
+        };
+
+        let mut runs: Vec<(&FontId, &str)> = Vec::new();
+        let mut start = 0;
+        let mut current = font_id;
+        for (i, c) in text.char_indices() {
+            let font = if !body_glyphs.has_glyph(c) && fallback_glyphs.has_glyph(c) {
+                fallback_id
+            } else {
+                font_id
+            };
+            if font != current && i > start {
+                runs.push((current, &text[start..i]));
+                start = i;
+            }
+            current = font;
+        }
+        runs.push((current, &text[start..]));
+        runs
+    }
+
+    /// 用途のフォントIDを設定
+    pub fn set(&mut self, role: FontRole, font_id: FontId) {
+        match role {
+            FontRole::Title => self.title = font_id,
+            FontRole::Body => self.body = font_id,
+            FontRole::Numeric => self.numeric = font_id,
+        }
+    }
+}
+
+/// 生成したページの統計
+///
+/// 実際に出力したページから集計する（連番の用紙への印刷などに使用）
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GenerationStats {
+    /// 総ページ数（2面付けの場合は用紙の枚数）
+    pub total_pages: usize,
+    /// アイテムごとのページ範囲（0始まり、アイテム順。2面付けの場合は用紙の番号）
+    pub pages_per_item: Vec<Range<usize>>,
+    /// ページごとの外部ID（0始まりのページ番号 → `Item::external_id`、IDの無いページは含まない）
+    pub external_ids: BTreeMap<usize, Vec<String>>,
+}
+
+/// PDF生成レポート
+///
+/// 行数上限による欠落や折り返し時の切り詰めを記録する（0ならデータ欠落なし）
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GenerationReport {
+    /// 行数上限を超えて印刷されなかった行数
+    pub dropped_rows: usize,
+    /// 切り詰められた項目数（摘要・区間）
+    pub truncated_fields: usize,
+    /// 警告
+    pub warnings: Vec<Warning>,
+    /// 1ページに収まらない旅費データを印刷した継続ページ数
+    pub continuation_pages: usize,
+    /// 埋め込んだフォント数
+    pub embedded_fonts: usize,
+    /// 埋め込んだフォントファイルの合計サイズ（サブセット化前、バイト）
+    pub font_data_bytes: u64,
+    /// ファイルが使用中のため書き込みを再試行した回数
+    pub write_retries: usize,
+    /// 出力パスに書き込んだPDFのSHA-256（16進数、ファイルに出力しなかった場合はNone）
+    pub checksum: Option<String>,
+}
+
+impl GenerationReport {
+    /// データ欠落・警告がないか
+    pub fn is_clean(&self) -> bool {
+        self.dropped_rows == 0 && self.truncated_fields == 0 && self.warnings.is_empty()
+    }
+
+    /// 別のレポートを合算
+    pub fn merge(&mut self, other: &GenerationReport) {
+        self.dropped_rows += other.dropped_rows;
+        self.truncated_fields += other.truncated_fields;
+        self.warnings.extend(other.warnings.iter().cloned());
+        self.continuation_pages += other.continuation_pages;
+        self.embedded_fonts += other.embedded_fonts;
+        self.font_data_bytes += other.font_data_bytes;
+        self.write_retries += other.write_retries;
+    }
 }
 
 impl ReportLabStylePdfClient {
@@ -27,7 +321,65 @@ impl ReportLabStylePdfClient {
         Self {
             output_path: PathBuf::from("travel_expense_reportlab_style.pdf"),
             font_loader: FontLoader::new(),
+            sumatra_path: None,
+            printer_backend: Backend::Auto,
+            ipp_printers: BTreeMap::new(),
+            pdf_passthrough_printers: Vec::new(),
+            write_retry: WriteRetry::default(),
+            fs: Arc::new(RealFs),
+            format: FormatOptions::default(),
+            layout: LayoutConfig::default(),
+            layout_presets: BTreeMap::new(),
+            last_report: GenerationReport::default(),
+            last_stats: GenerationStats::default(),
+            parallel: true,
+            glyph_fallback: DEFAULT_GLYPH_FALLBACK.to_string(),
+            strict: false,
+            blank_on_empty: false,
+            check_duplicates: false,
+            unicode_nfc: true,
+            cancel: None,
+            approval_seals: Vec::new(),
+            embedded_seals: Vec::new(),
+            fixed_date: None,
+            page_postprocess: None,
+        }
+    }
+
+    /// 設定からPDFクライアントを作成
+    pub fn from_config(config: &PdfConfig) -> Self {
+        let mut client = Self::new();
+        client.sumatra_path = config.sumatra_path.clone();
+        client.printer_backend = config.printer_backend;
+        client.ipp_printers = config.ipp_printers.clone();
+        client.pdf_passthrough_printers = config.pdf_passthrough_printers.clone();
+        client.write_retry = config.write_retry;
+        client.format = config.format.clone();
+        client.layout = config.layout.clone();
+        client.layout_presets = config.layout_presets.clone();
+        client.parallel = config.parallel;
+        client.glyph_fallback = config.glyph_fallback.clone();
+        client.strict = config.strict;
+        client.blank_on_empty = config.blank_on_empty;
+        client.check_duplicates = config.check_duplicates;
+        client.unicode_nfc = config.unicode_nfc;
+        client.fixed_date = config.fixed_date;
+        client.page_postprocess = config.page_postprocess.clone();
+        let mut font_loader = FontLoader::new();
+        if let Some(ref candidates) = config.font_candidates {
+            font_loader = font_loader.with_candidates(candidates.clone());
+        }
+        if let Some(ref path) = config.font_path {
+            font_loader = font_loader.with_font_path(path);
+        }
+        for (role, source) in &config.role_fonts {
+            font_loader = font_loader.with_role_font(*role, source.clone());
         }
+        if let Some(ref source) = config.fallback_font {
+            font_loader = font_loader.with_fallback_font(source.clone());
+        }
+        client.font_loader = font_loader;
+        client
     }
 
     /// 出力パスを設定
@@ -36,6 +388,161 @@ impl ReportLabStylePdfClient {
         self
     }
 
+    /// 整形オプションを設定
+    pub fn with_format_options(mut self, format: FormatOptions) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// レイアウト設定を設定
+    pub fn with_layout(mut self, layout: LayoutConfig) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// 名前付きのレイアウト設定を登録（`Item::layout_preset` で選択）
+    ///
+    /// 面付けや印影の埋め込みなど文書全体の設定は既定のレイアウトに従う
+    pub fn with_layout_preset(mut self, name: impl Into<String>, layout: LayoutConfig) -> Self {
+        self.layout_presets.insert(name.into(), layout);
+        self
+    }
+
+    /// ページ操作を並列に構築するかを設定（`parallel` feature有効時のみ有効）
+    pub fn with_parallel(mut self, parallel: bool) -> Self {
+        self.parallel = parallel;
+        self
+    }
+
+    /// フォントに存在しない文字の代替文字を設定
+    pub fn with_glyph_fallback(mut self, fallback: impl Into<String>) -> Self {
+        self.glyph_fallback = fallback.into();
+        self
+    }
+
+    /// 厳格モードを設定（フォントに存在しない文字があればエラーとする）
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// アイテムが空の場合に空白の精算書を1ページ出力するかを設定
+    ///
+    /// falseの場合、アイテムが空なら `PdfError::Validation` を返す
+    pub fn with_blank_on_empty(mut self, blank_on_empty: bool) -> Self {
+        self.blank_on_empty = blank_on_empty;
+        self
+    }
+
+    /// 重複入力の可能性がある旅費項目を警告するかを設定
+    ///
+    /// 直前の項目と日付・行先が同じ旅費項目を `Warning::PossibleDuplicateRyohi` として生成レポートに記録する。
+    /// 印刷内容は変更せず、厳格モードでもエラーにしない（厳格モードでは設定に関わらず検出する）
+    pub fn with_duplicate_check(mut self, check: bool) -> Self {
+        self.check_duplicates = check;
+        self
+    }
+
+    /// 描画前に文字列をNFC（合成済みの文字）に統一するかを設定（既定は統一する）
+    pub fn with_unicode_nfc(mut self, enabled: bool) -> Self {
+        self.unicode_nfc = enabled;
+        self
+    }
+
+    /// ページごとの操作を `PdfPage` にする前に呼び出す処理を設定
+    ///
+    /// 引数はPDF内のページ番号（0始まり、2面付けの場合はA4横の用紙ごと）とそのページの操作。
+    /// アイテムごとのPDFでは各PDF内のページ番号で呼び出す
+    pub fn with_page_postprocess(mut self, f: impl Fn(usize, &mut Vec<Op>) + Send + Sync + 'static) -> Self {
+        self.page_postprocess = Some(PagePostprocess::new(f));
+        self
+    }
+
+    /// PDFの作成日時・更新日時を固定
+    ///
+    /// 設定した場合は同じ入力から同じバイト列のPDFを出力する（ドキュメントIDも内容から決定する）。
+    /// Noneの場合は生成時の現在時刻を記録する
+    pub fn with_fixed_date(mut self, date: Option<DateTime>) -> Self {
+        self.fixed_date = date;
+        self
+    }
+
+    /// 承認欄の印影画像を設定
+    ///
+    /// 承認欄の列（`LayoutConfig::approval_labels`）の順に指定し、Noneの列は空欄のままとする
+    pub fn with_approval_seals(mut self, seals: Vec<Option<SealImage>>) -> Self {
+        self.approval_seals = seals;
+        self
+    }
+
+    /// 生成を中断するためのトークンを設定
+    ///
+    /// 生成は同期処理のため、キャンセルはアイテムの処理の合間に確認する
+    pub fn with_cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancel = Some(token);
+        self
+    }
+
+    /// キャンセルされているか
+    pub(crate) fn is_cancelled(&self) -> bool {
+        self.cancel.as_ref().is_some_and(|token| token.is_cancelled())
+    }
+
+    /// キャンセルされている場合はエラーを返す
+    fn check_cancelled(&self) -> Result<(), PdfError> {
+        if self.is_cancelled() {
+            return Err(PdfError::Generation("キャンセルされました".to_string()));
+        }
+        Ok(())
+    }
+
+    /// フォントローダーを設定
+    pub fn with_font_loader(mut self, font_loader: FontLoader) -> Self {
+        self.font_loader = font_loader;
+        self
+    }
+
+    /// フォントのキャッシュを設定（サービスのクローン間でフォントの読み込みを共有する場合など）
+    pub fn with_font_cache(mut self, cache: Arc<FontCache>) -> Self {
+        self.font_loader = std::mem::take(&mut self.font_loader).with_cache(cache);
+        self
+    }
+
+    /// フォントローダーを取得
+    pub fn font_loader_mut(&mut self) -> &mut FontLoader {
+        &mut self.font_loader
+    }
+
+    /// SumatraPDFのパスを設定
+    pub fn with_sumatra_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.sumatra_path = Some(path.into());
+        self
+    }
+
+    /// 印刷バックエンドを設定
+    pub fn with_printer_backend(mut self, backend: Backend) -> Self {
+        self.printer_backend = backend;
+        self
+    }
+
+    /// 出力ファイル書き込みの再試行設定を設定
+    pub fn with_write_retry(mut self, retry: WriteRetry) -> Self {
+        self.write_retry = retry;
+        self
+    }
+
+    /// ファイル操作を設定（テストではメモリ上のファイルシステムを使用）
+    pub fn with_fs(mut self, fs: Arc<dyn Fs>) -> Self {
+        self.fs = fs;
+        self
+    }
+
+    /// IPPのプリンターを登録
+    pub fn with_ipp_printer(mut self, name: impl Into<String>, uri: impl Into<String>) -> Self {
+        self.ipp_printers.insert(name.into(), uri.into());
+        self
+    }
+
     /// PDFを生成
     ///
     /// # Arguments
@@ -44,509 +551,2185 @@ impl ReportLabStylePdfClient {
     /// # Returns
     /// 生成されたPDFファイルのパス
     pub fn generate(&mut self, items: &[Item]) -> Result<PathBuf, PdfError> {
-        tracing::info!("Creating ReportLab Style PDF client...");
-
-        // フォントを検索して読み込む
-        self.font_loader.find_font()?;
-        let font_data = self.font_loader.load_font_data()?;
-
-        // ドキュメントを作成
-        let mut doc = PdfDocument::new("出張旅費精算書");
-
-        // フォントを追加
-        let mut warnings = Vec::new();
-        let font = ParsedFont::from_bytes(&font_data, 0, &mut warnings)
-            .ok_or_else(|| PdfError::FontLoad("フォントパースエラー".to_string()))?;
-        let font_id = doc.add_font(&font);
+        self.check_cancelled()?;
+        let bytes = self.generate_to_vec(items)?;
+        self.write_output(&bytes)
+    }
 
-        // 各アイテムをページとして追加
-        let mut pages = Vec::new();
-        for (index, item) in items.iter().enumerate() {
-            tracing::info!("Processing item {}/{}", index + 1, items.len());
-            let ops = self.create_page_operations(&font_id, item);
-            let page = PdfPage::new(Mm(A5_WIDTH), Mm(A5_HEIGHT), ops);
-            pages.push(page);
+    /// 空白の精算書（枠・表・見出しのみ）を指定部数生成
+    ///
+    /// 手書き用の用紙として、アイテムのデータを含まない同一のページを `copies` ページ出力する。
+    /// 描画は通常のページと同じ処理を使用し、設定したラベル・レイアウト・用紙サイズに従う
+    ///
+    /// # Arguments
+    /// * `copies` - 部数（1以上）
+    ///
+    /// # Returns
+    /// 生成されたPDFファイルのパス
+    pub fn generate_blank_forms(&mut self, copies: usize) -> Result<PathBuf, PdfError> {
+        self.check_cancelled()?;
+        if copies == 0 {
+            return Err(PdfError::Validation("空白の精算書の部数は1以上を指定してください".to_string()));
         }
+        let doc = self.build_document(&[], copies)?;
+        let mut bytes = Vec::new();
+        write_document(&doc, &mut bytes, self.fixed_date.is_some())?;
+        self.write_output(&bytes)
+    }
 
-        // PDFを保存
-        let bytes = doc
-            .with_pages(pages)
-            .save(&PdfSaveOptions::default(), &mut Vec::new());
-
-        std::fs::write(&self.output_path, bytes)?;
+    /// 生成したPDFを出力パスに書き込む
+    fn write_output(&mut self, bytes: &[u8]) -> Result<PathBuf, PdfError> {
+        let retries = write_atomic(self.fs.as_ref(), &self.output_path, &self.write_retry, bytes)?;
+        self.last_report.write_retries = retries;
+        // 監査記録用に書き込んだ内容のハッシュを記録（ファイルを読み直さない）
+        self.last_report.checksum = Some(sha256_hex(bytes));
 
         tracing::info!("ReportLab Style PDF saved successfully!");
 
         Ok(self.output_path.clone())
     }
 
-    /// ページの操作を作成
-    fn create_page_operations(&self, font_id: &FontId, item: &Item) -> Vec<Op> {
-        let mut ops = Vec::new();
+    /// 全アイテムをまとめたPDFと、アイテムごとのPDFを生成
+    ///
+    /// まとめたPDFは出力パスに、アイテムごとのPDFは `item_output_path` のパスに出力する。
+    /// フォントの読み込みとページ操作の作成は1回のみで、どちらのPDFも同じページ操作から組み立てる
+    ///
+    /// # Returns
+    /// (まとめたPDFのパス, アイテムごとのPDFのパス（アイテム順）)
+    pub fn generate_with_item_files(&mut self, items: &[Item]) -> Result<(PathBuf, Vec<PathBuf>), PdfError> {
+        self.check_cancelled()?;
+        let prepared = self.prepare_document(items, 0)?;
+        self.write_prepared(prepared, true)
+    }
 
-        // 外枠を描画
-        self.add_outer_frame(&mut ops);
+    /// 全アイテムのページ操作を作成したドキュメントを出力パスに書き込む（アイテムを分割して生成する場合の最後の処理）
+    ///
+    /// # Returns
+    /// (まとめたPDFのパス, アイテムごとのPDFのパス（`item_files` の場合のみ、アイテム順）)
+    pub(crate) fn write_pending(
+        &mut self,
+        pending: PendingDocument,
+        item_files: bool,
+    ) -> Result<(PathBuf, Vec<PathBuf>), PdfError> {
+        let doc = self.rebuild_document(&pending.embedded_fonts)?;
+        let prepared = self.finish_document(doc, pending)?;
+        self.write_prepared(prepared, item_files)
+    }
 
-        // 承認テーブル（右上）
-        self.add_approval_table(&mut ops, font_id);
+    /// ページ操作を作成済みのドキュメントを出力パスに書き込む（`item_files` の場合はアイテムごとのPDFも出力）
+    ///
+    /// # Returns
+    /// (まとめたPDFのパス, アイテムごとのPDFのパス（アイテム順）)
+    fn write_prepared(&mut self, prepared: PreparedDocument, item_files: bool) -> Result<(PathBuf, Vec<PathBuf>), PdfError> {
+        let reproducible = self.fixed_date.is_some();
+
+        let mut item_paths = Vec::new();
+        let mut write_retries = 0;
+        let item_pages = if item_files { prepared.pages_per_item.as_slice() } else { &[] };
+        for (index, pages) in item_pages.iter().enumerate() {
+            self.check_cancelled()?;
+            let (doc, _) = self.assemble_document(
+                prepared.doc.clone(),
+                prepared.page_ops[pages.clone()].to_vec(),
+                std::iter::once(0..pages.len()).collect(),
+                &prepared.items[index..=index],
+            );
+            let mut bytes = Vec::new();
+            write_document(&doc, &mut bytes, reproducible)?;
+            let path = item_output_path(&self.output_path, index);
+            write_retries += write_atomic(self.fs.as_ref(), &path, &self.write_retry, &bytes)?;
+            item_paths.push(path);
+        }
 
-        // 基本情報テーブル
-        self.add_basic_info_table(&mut ops, font_id);
+        let (doc, stats) =
+            self.assemble_document(prepared.doc, prepared.page_ops, prepared.pages_per_item, &prepared.items);
+        self.last_stats = stats;
+        let mut bytes = Vec::new();
+        write_document(&doc, &mut bytes, reproducible)?;
+        let pdf_path = self.write_output(&bytes)?;
+        self.last_report.write_retries += write_retries;
+        Ok((pdf_path, item_paths))
+    }
 
-        // メインデータテーブル
-        self.add_main_data_table(&mut ops, font_id);
+    /// PDFを生成して任意の書き込み先に出力
+    ///
+    /// HTTPレスポンスやオブジェクトストレージへのアップロードなど、
+    /// 一時ファイルを経由せずに出力する場合に使用する
+    ///
+    /// # Arguments
+    /// * `items` - 精算書項目リスト
+    /// * `w` - 書き込み先
+    ///
+    /// # Returns
+    /// 書き込んだバイト数
+    pub fn generate_to_writer<W: Write>(&mut self, items: &[Item], w: &mut W) -> Result<u64, PdfError> {
+        let doc = self.build_document(items, 0)?;
+        write_document(&doc, w, self.fixed_date.is_some())
+    }
 
-        // 備考・計テーブル
-        self.add_summary_table(&mut ops, font_id);
+    /// PDFを生成してメモリ上のバイト列として取得
+    pub fn generate_to_vec(&mut self, items: &[Item]) -> Result<Vec<u8>, PdfError> {
+        let mut bytes = Vec::new();
+        self.generate_to_writer(items, &mut bytes)?;
+        Ok(bytes)
+    }
 
-        // アイテム情報を印刷
-        self.add_item_data(&mut ops, font_id, item);
+    /// 直近の生成レポートを取得
+    pub fn last_report(&self) -> &GenerationReport {
+        &self.last_report
+    }
 
-        ops
+    /// 最後の生成で出力したページの統計を取得
+    pub fn last_stats(&self) -> &GenerationStats {
+        &self.last_stats
     }
 
-    /// 外枠を描画
-    fn add_outer_frame(&self, ops: &mut Vec<Op>) {
-        let start_x = 10.0;
-        let start_y = 15.0;
-        let end_x = A5_WIDTH - 10.0;
-        let end_y = A5_HEIGHT - 10.0;
+    /// PDFドキュメントを構築
+    ///
+    /// アイテムが空の場合は `blank_copies` 部の空白の精算書を出力する
+    /// （`blank_on_empty` 有効時は最低1部）
+    fn build_document(&mut self, items: &[Item], blank_copies: usize) -> Result<PdfDocument, PdfError> {
+        let prepared = self.prepare_document(items, blank_copies)?;
+        let (doc, stats) =
+            self.assemble_document(prepared.doc, prepared.page_ops, prepared.pages_per_item, &prepared.items);
+        self.last_stats = stats;
+        Ok(doc)
+    }
 
-        ops.push(Op::SetOutlineThickness { pt: Pt(0.5) });
-        ops.push(Op::SetOutlineColor {
-            col: Color::Rgb(Rgb { r: 0.0, g: 0.0, b: 0.0, icc_profile: None }),
-        });
+    /// フォント・印影を追加したドキュメント（ページなし）と全ページの操作を作成
+    fn prepare_document(&mut self, items: &[Item], blank_copies: usize) -> Result<PreparedDocument, PdfError> {
+        let (doc, mut pending) = self.begin_document(items, blank_copies)?;
+        let count = pending.item_count();
+        self.render_chunk(&mut pending, 0..count);
+        self.finish_document(doc, pending)
+    }
 
-        // 外枠を描画
-        ops.push(Op::DrawPolygon {
-            polygon: Polygon {
-                rings: vec![PolygonRing {
-                    points: vec![
-                        LinePoint { p: Point::new(Mm(start_x), Mm(A5_HEIGHT - start_y)), bezier: false },
-                        LinePoint { p: Point::new(Mm(end_x), Mm(A5_HEIGHT - start_y)), bezier: false },
-                        LinePoint { p: Point::new(Mm(end_x), Mm(A5_HEIGHT - end_y)), bezier: false },
-                        LinePoint { p: Point::new(Mm(start_x), Mm(A5_HEIGHT - end_y)), bezier: false },
-                    ],
-                }],
-                mode: PaintMode::Stroke,
-                winding_order: WindingOrder::NonZero,
-            },
-        });
+    /// アイテムを分割してPDFを生成する場合の最初の処理（入力の確認とフォントの読み込み）
+    ///
+    /// ドキュメントは書き込み時に `write_pending` で作成し直す
+    pub(crate) fn begin_chunked(&mut self, items: &[Item]) -> Result<PendingDocument, PdfError> {
+        let (_, pending) = self.begin_document(items, 0)?;
+        Ok(pending)
     }
 
-    /// 承認テーブルを描画
-    fn add_approval_table(&self, ops: &mut Vec<Op>, font_id: &FontId) {
-        let start_x = 155.0;
-        let start_y = 25.0;
-        let col_width = 15.0;
-        let row_height1 = 5.0;
-        let row_height2 = 15.0;
+    /// 入力の確認・フォントと印影の追加を行い、ドキュメント（ページなし）とページ操作を作成する前の状態を返す
+    fn begin_document(&mut self, items: &[Item], blank_copies: usize) -> Result<(PdfDocument, PendingDocument), PdfError> {
+        tracing::info!("Creating ReportLab Style PDF client...");
+        self.check_cancelled()?;
+        if items.is_empty() && blank_copies == 0 && !self.blank_on_empty {
+            return Err(PdfError::Validation(EMPTY_ITEMS_MESSAGE.to_string()));
+        }
+        self.check_layout_presets(items)?;
 
-        ops.push(Op::SetOutlineThickness { pt: Pt(0.2) });
+        // 記載の税額と旅費項目から計算した税額を照合
+        let tax_warnings = check_item_taxes(items, self.format.rounding, &self.format.tax);
+        if self.strict && !tax_warnings.is_empty() {
+            let messages: Vec<String> = tax_warnings.iter().map(|w| w.to_string()).collect();
+            return Err(PdfError::Validation(messages.join(", ")));
+        }
 
-        // ヘッダー行
-        let headers = ["社　長", "会　計", "所　属"];
-        for (i, header) in headers.iter().enumerate() {
-            let x = start_x + (i as f32) * col_width;
+        // 重複入力の可能性がある旅費項目（確認用の警告のみ）
+        let duplicate_warnings = if self.strict || self.check_duplicates {
+            possible_duplicate_warnings(items)
+        } else {
+            Vec::new()
+        };
 
-            // 矩形を描画
-            self.add_rect(ops, x, start_y, col_width, row_height1);
+        // フォントを検索して読み込む
+        let (font, font_path) = self
+            .font_loader
+            .load_role_font(FontRole::Body)
+            .unwrap_or_else(|| Err(PdfError::FontLoad("フォントが設定されていません".to_string())))?;
+
+        // フォールバックフォントを読み込む（読み込めない場合は本文フォントのみで描画）
+        let mut font_warnings = Vec::new();
+        let fallback_font = match self.font_loader.load_fallback_font() {
+            None => None,
+            Some(Ok(fallback)) => Some(fallback),
+            Some(Err(e)) => {
+                tracing::warn!("フォールバックフォントを読み込めません: {}", e);
+                font_warnings.push(Warning::FallbackFontUnavailable { message: e.to_string() });
+                None
+            }
+        };
+
+        // 描画する文字列をNFCに統一（分解された濁点を収録文字の確認の前に合成する。描画時の統一は `PageRenderer` が行う）
+        let nfc_items = self.nfc_items(items);
+        let items = &*nfc_items;
+
+        // 1行に収まらず切り詰める摘要（切り詰めの設定の場合のみ、NFCに合成した後の文字数で確認）
+        let detail_warnings =
+            if self.format.wrap_mode == WrapMode::JoinSegments && self.format.detail_overflow == DetailOverflow::Truncate {
+                truncated_detail_warnings(items, self.layout.detail_max_len())
+            } else {
+                Vec::new()
+            };
+
+        // 本文・フォールバックフォントのどちらにも存在しない文字を代替文字に置換
+        let glyphs = match (self.font_loader.glyphs(), self.font_loader.fallback_glyphs()) {
+            (Some(glyphs), Some(fallback)) if fallback_font.is_some() => Some(Cow::Owned(glyphs.union(&fallback))),
+            (glyphs, _) => glyphs.map(Cow::Borrowed),
+        };
+        let (items, glyph_warnings) = match glyphs.as_deref() {
+            Some(glyphs) => {
+                let missing = glyphs.missing_in_items(items);
+                if !missing.is_empty() {
+                    tracing::warn!(
+                        "フォントに存在しない文字があります: {}",
+                        missing.iter().collect::<String>()
+                    );
+                }
+                replace_unsupported_glyphs(items, glyphs, &self.glyph_fallback)
+            }
+            None => (items.to_vec(), Vec::new()),
+        };
+        if self.strict && !glyph_warnings.is_empty() {
+            let messages: Vec<String> = glyph_warnings.iter().map(|w| w.to_string()).collect();
+            return Err(PdfError::Validation(messages.join(", ")));
+        }
 
-            // テキストを描画
-            self.add_text(ops, font_id, header, 9.0, x + 1.0, start_y + 4.0);
+        // ドキュメントを作成
+        let mut doc = self.new_document();
+
+        // フォントを追加（用途別のフォントが無い場合は本文フォントを使用）
+        let mut embedded = vec![(font_path, add_font(&mut doc, &font))];
+        let mut fonts = FontSet::single(embedded[0].1.clone());
+        for role in [FontRole::Title, FontRole::Numeric] {
+            match self.font_loader.load_role_font(role) {
+                None => {}
+                Some(Ok((role_font, path))) => {
+                    let font_id = embed_font(&mut doc, &mut embedded, &role_font, path);
+                    fonts.set(role, font_id);
+                    if let Some(glyphs) = self.font_loader.role_glyphs(role) {
+                        fonts.set_coverage(role, glyphs);
+                    }
+                }
+                Some(Err(e)) => {
+                    tracing::warn!("{}フォントを読み込めないため本文フォントを使用: {}", role, e);
+                    font_warnings.push(Warning::FontFallback {
+                        role,
+                        message: e.to_string(),
+                    });
+                }
+            }
         }
 
-        // データ行（空）
-        for i in 0..3 {
-            let x = start_x + (i as f32) * col_width;
-            self.add_rect(ops, x, start_y + row_height1, col_width, row_height2);
+        // フォールバックフォントを追加（本文フォントに存在しない文字のみ描画）
+        if let (Some((fallback, path)), Some(body_glyphs), Some(fallback_glyphs)) =
+            (fallback_font, self.font_loader.role_glyphs(FontRole::Body), self.font_loader.fallback_glyphs())
+        {
+            let font_id = embed_font(&mut doc, &mut embedded, &fallback, path);
+            fonts.set_fallback(font_id, body_glyphs, fallback_glyphs);
         }
+
+        // 承認欄の印影を追加
+        self.embed_seals(&mut doc)?;
+
+        let mut warnings = tax_warnings;
+        warnings.extend(duplicate_warnings);
+        warnings.extend(detail_warnings);
+        warnings.extend(glyph_warnings);
+        warnings.extend(font_warnings);
+        let pending = PendingDocument {
+            fonts,
+            items,
+            page_ops: Vec::new(),
+            pages_per_item: Vec::new(),
+            report: GenerationReport::default(),
+            warnings,
+            embedded_fonts: embedded.into_iter().map(|(path, _)| path).collect(),
+            blank_copies,
+        };
+        Ok((doc, pending))
     }
 
-    /// 基本情報テーブルを描画
-    fn add_basic_info_table(&self, ops: &mut Vec<Op>, font_id: &FontId) {
-        let start_x = 10.0;
-        let start_y = 30.0;
+    /// `begin_document` と同じフォント（同じフォントID）・印影を追加したドキュメントを作成
+    fn rebuild_document(&mut self, font_paths: &[PathBuf]) -> Result<PdfDocument, PdfError> {
+        let mut doc = self.new_document();
+        for path in font_paths {
+            let (font, _) = self.font_loader.cache().load(path)?;
+            add_font(&mut doc, &font);
+        }
+        self.embed_seals(&mut doc)?;
+        Ok(doc)
+    }
 
-        ops.push(Op::SetOutlineThickness { pt: Pt(0.2) });
+    /// 指定した範囲のアイテムのページ操作を作成してドキュメントに追加（アイテム順に呼び出す）
+    pub(crate) fn render_chunk(&self, pending: &mut PendingDocument, range: Range<usize>) {
+        let total = pending.items.len();
+        let rendered = self.render_items(&pending.fonts, &pending.items[range.clone()]);
+        append_rendered(
+            rendered,
+            range.start,
+            total,
+            &mut pending.page_ops,
+            &mut pending.report,
+            &mut pending.pages_per_item,
+        );
+    }
 
-        // 出発・帰着ラベル
-        let row_height = 3.5;
-        let diff_start_y = 3.0;
+    /// 全アイテムのページ操作の作成後に生成レポートを確定
+    fn finish_document(&mut self, doc: PdfDocument, pending: PendingDocument) -> Result<PreparedDocument, PdfError> {
+        self.check_cancelled()?;
+        let PendingDocument {
+            fonts,
+            items,
+            mut page_ops,
+            pages_per_item,
+            mut report,
+            warnings,
+            embedded_fonts,
+            blank_copies,
+        } = pending;
+        report.warnings.extend(warnings);
+        report.embedded_fonts = embedded_fonts.len();
+        report.font_data_bytes = embedded_fonts
+            .iter()
+            .filter_map(|path| self.fs.metadata(path).ok())
+            .map(|m| m.len)
+            .sum();
+        if page_ops.is_empty() {
+            let copies = if self.blank_on_empty { blank_copies.max(1) } else { blank_copies };
+            tracing::info!("空白の精算書を出力します: {}部", copies);
+            page_ops = self.renderer(&fonts).blank_forms(copies);
+        }
+        if !report.is_clean() {
+            tracing::warn!(
+                "データ欠落あり: 欠落行数={}, 切り詰め項目数={}, 警告数={}",
+                report.dropped_rows,
+                report.truncated_fields,
+                report.warnings.len()
+            );
+        }
+        self.last_report = report;
+
+        Ok(PreparedDocument {
+            doc,
+            page_ops,
+            pages_per_item,
+            items,
+        })
+    }
 
-        self.add_text(ops, font_id, "出発", 9.0, start_x + 1.0, start_y + diff_start_y);
-        self.add_text(ops, font_id, "　　月　　日", 9.0, start_x + 2.0, start_y + diff_start_y + row_height);
-        self.add_text(ops, font_id, "帰着", 9.0, start_x + 1.0, start_y + diff_start_y + row_height * 2.0);
-        self.add_text(ops, font_id, "　　月　　日", 9.0, start_x + 2.0, start_y + diff_start_y + row_height * 3.0);
+    /// ページ操作を面付けしてドキュメントに追加
+    ///
+    /// # Returns
+    /// (ページを追加したドキュメント, 出力したページの統計)
+    fn assemble_document(
+        &self,
+        mut doc: PdfDocument,
+        page_ops: Vec<Vec<Op>>,
+        pages_per_item: Vec<Range<usize>>,
+        items: &[Item],
+    ) -> (PdfDocument, GenerationStats) {
+        let pages = self.impose_pages(page_ops);
+        let pages_per_item = match self.layout.imposition {
+            Imposition::OneUp => pages_per_item,
+            Imposition::TwoUpA4 => pages_per_item.into_iter().map(two_up_sheets).collect(),
+        };
+        // 外部IDをドキュメント情報のキーワードに記録（ページには描画しない）
+        let external_ids = external_ids_by_page(items, &pages_per_item);
+        doc.metadata.info.keywords = external_id_keywords(&external_ids);
+        let stats = GenerationStats {
+            total_pages: pages.len(),
+            pages_per_item,
+            external_ids,
+        };
+        doc.with_pages(pages);
+        (doc, stats)
+    }
 
-        // テーブルヘッダー
-        let headers = ["", "出張目的", "車両No.", "氏　名", "サイン"];
-        let col_widths = [31.0, 25.0, 28.75, 30.0, 30.0];
+    /// 描画するアイテム（NFCに統一する場合、合成が必要なアイテムがあれば合成したコピー）
+    fn nfc_items<'a>(&self, items: &'a [Item]) -> Cow<'a, [Item]> {
+        if self.unicode_nfc && !items.iter().all(is_nfc_item) {
+            Cow::Owned(items.iter().map(nfc_item).collect())
+        } else {
+            Cow::Borrowed(items)
+        }
+    }
 
-        let mut current_x = start_x;
-        for (i, header) in headers.iter().enumerate() {
-            self.add_rect(ops, current_x, start_y, col_widths[i], 15.0);
-            if !header.is_empty() {
-                self.add_text(ops, font_id, header, 9.0, current_x + 1.0, start_y + 4.0);
-            }
-            current_x += col_widths[i];
+    /// アイテムのレイアウトのプリセット名が登録済みか確認
+    fn check_layout_presets(&self, items: &[Item]) -> Result<(), PdfError> {
+        let unknown: Vec<String> = items
+            .iter()
+            .enumerate()
+            .filter_map(|(index, item)| {
+                let name = item.layout_preset.as_ref()?;
+                (!self.layout_presets.contains_key(name))
+                    .then(|| format!("未登録のレイアウトプリセットです: {}（アイテム{}）", name, index + 1))
+            })
+            .collect();
+        if unknown.is_empty() {
+            Ok(())
+        } else {
+            Err(PdfError::Validation(unknown.join(", ")))
+        }
+    }
+
+    /// アイテムに適用するレイアウト設定（プリセットが未指定・未登録の場合は既定のレイアウト）
+    pub fn layout_for(&self, item: &Item) -> &LayoutConfig {
+        item.layout_preset
+            .as_ref()
+            .and_then(|name| self.layout_presets.get(name))
+            .unwrap_or(&self.layout)
+    }
+
+    /// 作成日時・更新日時を設定した空のドキュメントを作成
+    fn new_document(&self) -> PdfDocument {
+        let mut doc = PdfDocument::new("出張旅費精算書");
+        let date = self.fixed_date.unwrap_or_else(DateTime::now_utc);
+        doc.metadata.info.creation_date = date;
+        doc.metadata.info.modification_date = date;
+        doc.metadata.info.metadata_date = date;
+        doc
+    }
+
+    /// 印影画像をドキュメントに埋め込む
+    fn embed_seals(&mut self, doc: &mut PdfDocument) -> Result<(), PdfError> {
+        let labels = &self.layout.approval_labels;
+        if self.approval_seals.len() > labels.len() {
+            tracing::warn!(
+                "承認欄の列数を超える印影画像は無視します: 列数={}, 画像数={}",
+                labels.len(),
+                self.approval_seals.len()
+            );
+        }
+        let mut embedded = Vec::new();
+        for (index, (seal, label)) in self.approval_seals.iter().zip(labels).enumerate() {
+            let seal = match seal {
+                Some(seal) => seal,
+                None => {
+                    embedded.push(None);
+                    continue;
+                }
+            };
+            let image = seal
+                .decode()
+                .map_err(|e| PdfError::Validation(format!("{}（{}）", e, label)))?;
+            // 生成のたびに変わらないよう列番号からIDを決める
+            let id = XObjectId(format!("SEAL{}", index + 1));
+            doc.resources.xobjects.map.insert(id.clone(), XObject::Image(image.clone()));
+            embedded.push(Some(EmbeddedSeal {
+                id,
+                width: image.width,
+                height: image.height,
+            }));
         }
+        self.embedded_seals = embedded;
+        Ok(())
     }
 
-    /// メインデータテーブルを描画
-    fn add_main_data_table(&self, ops: &mut Vec<Op>, font_id: &FontId) {
-        let start_x = 10.0;
-        let start_y = 45.0;
+    /// 全アイテムのページ操作を作成
+    ///
+    /// `parallel` feature有効時はアイテムごとに並列で構築する。
+    /// 結果とログはアイテムの順番通りに並ぶ。
+    ///
+    /// # Returns
+    /// (ページ操作（アイテム順、継続ページはアイテムの直後）, 生成レポート)
+    pub fn render_page_operations_with_fonts(&self, fonts: &FontSet, items: &[Item]) -> (Vec<Vec<Op>>, GenerationReport) {
+        let (page_ops, report, _) = self.render_pages(fonts, items);
+        (page_ops, report)
+    }
 
-        ops.push(Op::SetOutlineThickness { pt: Pt(0.2) });
+    /// 全アイテムのページ操作を1つのフォントで作成
+    #[deprecated(since = "0.1.0", note = "用途別のフォントを指定する `render_page_operations_with_fonts` を使用")]
+    pub fn render_page_operations(&self, font_id: &FontId, items: &[Item]) -> (Vec<Vec<Op>>, GenerationReport) {
+        self.render_page_operations_with_fonts(&FontSet::single(font_id.clone()), items)
+    }
 
-        // 列幅
-        let col_widths = [10.0, 17.0, 40.0, 30.0, 15.0, 15.0, 15.0, 25.0, 23.0];
-        let row_height = 10.0;
-        let header_height = 4.0;
+    /// 全アイテムのページ操作とアイテムごとのページ範囲を作成
+    fn render_pages(&self, fonts: &FontSet, items: &[Item]) -> (Vec<Vec<Op>>, GenerationReport, Vec<Range<usize>>) {
+        let rendered = self.render_items(fonts, items);
 
-        // ヘッダー
-        let headers = ["日付", "行　先", "摘　　要", "区　　間", "交通機関", "運　賃", "特別料金", "旅費日当", "計"];
+        let mut report = GenerationReport::default();
+        let mut page_ops = Vec::with_capacity(rendered.len());
+        let mut pages_per_item = Vec::with_capacity(rendered.len());
+        append_rendered(rendered, 0, items.len(), &mut page_ops, &mut report, &mut pages_per_item);
+        (page_ops, report, pages_per_item)
+    }
 
-        let mut current_x = start_x;
-        for (i, header) in headers.iter().enumerate() {
-            self.add_rect(ops, current_x, start_y, col_widths[i], header_height);
-            self.add_text(ops, font_id, header, 8.0, current_x + 1.0, start_y + 3.0);
-            current_x += col_widths[i];
+    /// 1件のアイテムのページ操作を作成
+    ///
+    /// 独自の `PdfPage` や文書に精算書を組み込む場合に使用する。
+    /// 座標はA5横（`A5_WIDTH` × `A5_HEIGHT`）のページを前提とする。
+    /// `fonts` は組み込み先の文書に追加した日本語フォントを指定する。
+    /// 継続ページを含める場合は `render_item_pages` を使用する。
+    pub fn render_item_ops_with_fonts(&self, fonts: &FontSet, item: &Item) -> Vec<Op> {
+        let mut report = GenerationReport::default();
+        self.item_renderer(fonts, item).page_operations(item, &mut report)
+    }
+
+    /// 1件のアイテムのページ操作を1つのフォントで作成
+    #[deprecated(since = "0.1.0", note = "用途別のフォントを指定する `render_item_ops_with_fonts` を使用")]
+    pub fn render_item_ops(&self, font_id: &FontId, item: &Item) -> Vec<Op> {
+        self.render_item_ops_with_fonts(&FontSet::single(font_id.clone()), item)
+    }
+
+    /// 1件のアイテムの継続ページを含む全ページの操作を作成
+    pub fn render_item_pages(&self, fonts: &FontSet, item: &Item) -> Vec<Vec<Op>> {
+        let mut report = GenerationReport::default();
+        self.item_renderer(fonts, item).item_pages(item, &mut report)
+    }
+
+    /// 1件のアイテムの1ページ目をSVGで作成（Webのプレビュー用）
+    ///
+    /// PDFと同じページ操作の矩形・線・テキストを変換する。
+    /// フォントは埋め込まないため文字幅は近似になり、印影画像は描画しない
+    pub fn render_svg(&self, item: &Item) -> String {
+        let fonts = FontSet::single(FontId("F1".to_string()));
+        ops_to_svg(&self.render_item_ops_with_fonts(&fonts, item), A5_WIDTH, A5_HEIGHT)
+    }
+
+    /// 現在の設定で描画するレンダラーを作成
+    ///
+    /// 生成中は直近に埋め込んだ印影を承認欄に描画する
+    pub fn renderer<'a>(&'a self, fonts: &'a FontSet) -> PageRenderer<'a> {
+        PageRenderer::new(&self.layout, &self.format, fonts)
+            .with_seals(&self.embedded_seals)
+            .with_reference_year(self.reference_year())
+            .with_unicode_nfc(self.unicode_nfc)
+    }
+
+    /// アイテムのレイアウトのプリセットで描画するレンダラーを作成
+    fn item_renderer<'a>(&'a self, fonts: &'a FontSet, item: &Item) -> PageRenderer<'a> {
+        PageRenderer::new(self.layout_for(item), &self.format, fonts)
+            .with_seals(&self.embedded_seals)
+            .with_reference_year(self.reference_year())
+            .with_unicode_nfc(self.unicode_nfc)
+    }
+
+    /// 支払日の2桁の年を展開する基準の年（日時を固定した場合はその年）
+    fn reference_year(&self) -> i32 {
+        self.fixed_date.unwrap_or_else(DateTime::now_utc).year()
+    }
+
+    /// アイテムごとにページ操作とレポートを作成
+    ///
+    /// キャンセルされた場合、以降のアイテムはページ操作を作成しない
+    fn render_items(&self, fonts: &FontSet, items: &[Item]) -> Vec<(Vec<Vec<Op>>, GenerationReport)> {
+        let render = |item: &Item| {
+            let mut report = GenerationReport::default();
+            // キャンセル後のアイテムは処理しない
+            if self.is_cancelled() {
+                return (Vec::new(), report);
+            }
+            let pages = self.item_renderer(fonts, item).item_pages(item, &mut report);
+            (pages, report)
+        };
+
+        #[cfg(feature = "parallel")]
+        if self.parallel {
+            use rayon::prelude::*;
+            return items.par_iter().map(render).collect();
         }
 
-        // データ行（7行）
-        for row in 0..7 {
-            current_x = start_x;
-            let current_y = start_y + header_height + (row as f32) * row_height;
+        items.iter().map(render).collect()
+    }
 
-            for (col, &width) in col_widths.iter().enumerate() {
-                if col == 2 {
-                    // 摘要欄は左右の線のみ描画
-                    self.add_vertical_line(ops, current_x, current_y, row_height);
-                    self.add_vertical_line(ops, current_x + width, current_y, row_height);
-                } else {
-                    self.add_rect(ops, current_x, current_y, width, row_height);
+    /// 面付けの設定に合わせて用紙ごとのページを作成
+    ///
+    /// 2面付けの場合は連続する2ページをA4横の左右に配置する。
+    /// 後処理が設定されている場合は用紙ごとの操作に適用してから `PdfPage` にする
+    fn impose_pages(&self, page_ops: Vec<Vec<Op>>) -> Vec<PdfPage> {
+        let sheets: Vec<(f32, f32, Vec<Op>)> = match self.layout.imposition {
+            Imposition::OneUp => page_ops.into_iter().map(|ops| (A5_WIDTH, A5_HEIGHT, ops)).collect(),
+            Imposition::TwoUpA4 => {
+                let mut sheets = Vec::with_capacity(page_ops.len().div_ceil(2));
+                let mut page_ops = page_ops.into_iter();
+                while let Some(left) = page_ops.next() {
+                    match page_ops.next() {
+                        Some(right) => {
+                            let mut ops = place_rotated(left, 0);
+                            ops.extend(place_rotated(right, 1));
+                            sheets.push((A4_LANDSCAPE_WIDTH, A4_LANDSCAPE_HEIGHT, ops));
+                        }
+                        None => sheets.push((A5_WIDTH, A5_HEIGHT, left)),
+                    }
                 }
-                current_x += width;
+                sheets
             }
-        }
+        };
+        sheets
+            .into_iter()
+            .enumerate()
+            .map(|(index, (width, height, mut ops))| {
+                if let Some(ref postprocess) = self.page_postprocess {
+                    postprocess.apply(index, &mut ops);
+                }
+                PdfPage::new(Mm(width), Mm(height), ops)
+            })
+            .collect()
     }
 
-    /// 備考・計テーブルを描画
-    fn add_summary_table(&self, ops: &mut Vec<Op>, font_id: &FontId) {
-        let start_x = 10.0;
-        let start_y = 119.0;
+}
 
-        ops.push(Op::SetOutlineThickness { pt: Pt(0.2) });
+impl Default for ReportLabStylePdfClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
-        let col_widths = [145.0, 45.0];
-        let row_height = 19.0;
-        let headers = ["備考", "計"];
+#[async_trait]
+impl PdfGenerator for ReportLabStylePdfClient {
+    async fn generate(&mut self, items: Vec<Item>) -> Result<PathBuf, PdfError> {
+        ReportLabStylePdfClient::generate(self, &items)
+    }
 
-        let mut current_x = start_x;
-        for (i, header) in headers.iter().enumerate() {
-            self.add_rect(ops, current_x, start_y, col_widths[i], row_height);
-            self.add_text(ops, font_id, header, 8.0, current_x + 2.0, start_y + 4.0);
-            current_x += col_widths[i];
-        }
+    async fn generate_and_print(
+        &mut self,
+        items: Vec<Item>,
+        printer: Option<&str>,
+    ) -> Result<PathBuf, PdfError> {
+        let pdf_path = ReportLabStylePdfClient::generate(self, &items)?;
+
+        let backend = create_printer_with_passthrough(
+            self.printer_backend,
+            self.sumatra_path.as_deref(),
+            &self.ipp_printers,
+            &self.pdf_passthrough_printers,
+        )?;
+        backend.print(&pdf_path, printer)?;
+
+        Ok(pdf_path)
     }
+}
+
+/// アイテムごとに作成したページ操作を追加（`offset` は最初のアイテムの番号、`total` はログ用の全アイテム数）
+fn append_rendered(
+    rendered: Vec<(Vec<Vec<Op>>, GenerationReport)>,
+    offset: usize,
+    total: usize,
+    page_ops: &mut Vec<Vec<Op>>,
+    report: &mut GenerationReport,
+    pages_per_item: &mut Vec<Range<usize>>,
+) {
+    for (index, (pages, item_report)) in rendered.into_iter().enumerate() {
+        tracing::info!("Processing item {}/{}", offset + index + 1, total);
+        report.merge(&item_report);
+        let start = page_ops.len();
+        page_ops.extend(pages);
+        pages_per_item.push(start..page_ops.len());
+    }
+}
+
+/// PDFを同期的に生成（tokioランタイム不要）
+///
+/// CLIツールなど非同期ランタイムを持たない呼び出し元向けの簡易関数
+///
+/// # Arguments
+/// * `items` - 精算書項目リスト
+/// * `path` - 出力先PDFファイルのパス
+/// * `config` - 設定
+///
+/// # Returns
+/// 生成されたPDFファイルのパス
+pub fn generate_pdf(items: &[Item], path: &Path, config: &PdfConfig) -> Result<PathBuf, PdfError> {
+    ReportLabStylePdfClient::from_config(config)
+        .with_output_path(path)
+        .generate(items)
+}
+
+/// デフォルト設定でPDFを同期的に生成
+///
+/// 非同期ランタイムを必要としないため、同期処理のみのアプリケーションから利用できる
+///
+/// # Arguments
+/// * `items` - 精算書項目リスト
+/// * `output` - 出力先PDFファイルのパス
+///
+/// # Returns
+/// 生成されたPDFファイルのパス
+pub fn generate_sync(items: &[Item], output: impl AsRef<Path>) -> Result<PathBuf, PdfError> {
+    generate_pdf(items, output.as_ref(), &PdfConfig::default())
+}
+
+/// アイテムごとのPDFのパス（まとめたPDFと同じディレクトリに `<ファイル名>_<連番3桁>.pdf`）
+pub fn item_output_path(output_path: &Path, index: usize) -> PathBuf {
+    let stem = output_path.file_stem().and_then(|s| s.to_str()).unwrap_or("travel_expense");
+    output_path.with_file_name(format!("{}_{:03}.pdf", stem, index + 1))
+}
+
+/// フォントをドキュメントに追加
+///
+/// printpdfのフォントIDは生成のたびに変わるため、追加順の連番（F1, F2, ...）を使用する
+fn add_font(doc: &mut PdfDocument, font: &ParsedFont) -> FontId {
+    let id = FontId(format!("F{}", doc.resources.fonts.map.len() + 1));
+    doc.resources.fonts.map.insert(id.clone(), font.clone());
+    id
+}
+
+/// フォントをドキュメントに追加（同じファイルを追加済みの場合はそのフォントIDを使用）
+fn embed_font(doc: &mut PdfDocument, embedded: &mut Vec<(PathBuf, FontId)>, font: &ParsedFont, path: PathBuf) -> FontId {
+    if let Some((_, id)) = embedded.iter().find(|(p, _)| *p == path) {
+        return id.clone();
+    }
+    let id = add_font(doc, font);
+    embedded.push((path, id.clone()));
+    id
+}
+
+/// PDFドキュメントを書き込み先に出力
+///
+/// printpdfは書き込みエラーを返さないため、ラッパーで最初のエラーを記録して返す。
+/// `reproducible` の場合はドキュメントIDを内容から決定する（一度メモリ上に出力する）
+fn write_document<W: Write>(doc: &PdfDocument, w: &mut W, reproducible: bool) -> Result<u64, PdfError> {
+    if reproducible {
+        let mut bytes = doc.save(&PdfSaveOptions::default(), &mut Vec::new());
+        pin_document_id(&mut bytes);
+        w.write_all(&bytes).map_err(write_error)?;
+        return Ok(bytes.len() as u64);
+    }
+
+    let mut writer = CountingWriter {
+        inner: w,
+        written: 0,
+        error: None,
+    };
+    doc.save_writer(&mut writer, &PdfSaveOptions::default(), &mut Vec::new());
+
+    if let Some(e) = writer.error {
+        return Err(write_error(e));
+    }
+    Ok(writer.written)
+}
+
+/// トレーラーのドキュメントID（`/ID[(...)(...)]`）を内容のハッシュで置き換える
+///
+/// printpdfは保存のたびにランダムなIDを出力するため、同じ長さの文字列で上書きする
+/// （相互参照表のオフセットは変わらない）
+fn pin_document_id(bytes: &mut [u8]) {
+    const MARKER: &[u8] = b"/ID[(";
+    const ID_LEN: usize = 32;
+    let Some(start) = bytes.windows(MARKER.len()).rposition(|w| w == MARKER).map(|i| i + MARKER.len()) else {
+        return;
+    };
+    let second = start + ID_LEN + 2;
+    if bytes.len() < second + ID_LEN || &bytes[start + ID_LEN..second] != b")(" {
+        return;
+    }
+
+    // ID より前の内容（全オブジェクト）のFNV-1aハッシュ（初期値を変えた2つで128bit）
+    let hash = |seed: u64| {
+        bytes[..start].iter().fold(seed, |hash, &byte| (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3))
+    };
+    let id = format!("{:016X}{:016X}", hash(0xcbf2_9ce4_8422_2325), hash(0x8422_2325_cbf2_9ce4));
+    bytes[start..start + ID_LEN].copy_from_slice(id.as_bytes());
+    bytes[second..second + ID_LEN].copy_from_slice(id.as_bytes());
+}
 
-    /// アイテムデータを追加
-    fn add_item_data(&self, ops: &mut Vec<Op>, font_id: &FontId, item: &Item) {
-        self.add_base_data(ops, font_id, item);
+/// 書き込みエラーにコンテキストを付与
+fn write_error(e: std::io::Error) -> PdfError {
+    PdfError::FileIO(std::io::Error::new(
+        e.kind(),
+        format!("PDF書き込みエラー: {}", e),
+    ))
+}
 
-        let start_x = 14.0;
-        let start_y = 36.8;
+/// 書き込みバイト数と最初のエラーを記録するライター
+struct CountingWriter<'a, W: Write> {
+    inner: &'a mut W,
+    written: u64,
+    error: Option<std::io::Error>,
+}
 
-        // 出発日
-        if let Some(ref start_date) = item.start_date {
-            if let Some(formatted) = format_date_mmdd(start_date) {
-                self.add_text(ops, font_id, &formatted, 10.0, start_x, start_y);
+impl<W: Write> Write for CountingWriter<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if self.error.is_some() {
+            return Err(std::io::Error::other("書き込みは既に失敗しています"));
+        }
+        match self.inner.write(buf) {
+            Ok(n) => {
+                self.written += n as u64;
+                Ok(n)
+            }
+            Err(e) => {
+                let err = std::io::Error::new(e.kind(), e.to_string());
+                self.error = Some(e);
+                Err(err)
             }
         }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// 重複入力の可能性がある旅費項目の警告（`Item::possible_duplicate_ryohi`）
+fn possible_duplicate_warnings(items: &[Item]) -> Vec<Warning> {
+    items
+        .iter()
+        .enumerate()
+        .flat_map(|(item_index, item)| {
+            item.possible_duplicate_ryohi().into_iter().map(move |ryohi_index| {
+                let ryohi = &item.ryohi[ryohi_index];
+                Warning::PossibleDuplicateRyohi {
+                    item_index,
+                    ryohi_index,
+                    date: ryohi.date.clone().unwrap_or_default(),
+                    dest: ryohi.dest.clone().unwrap_or_default(),
+                }
+            })
+        })
+        .collect()
+}
+
+/// フォントに存在しない文字を代替文字に置換
+///
+/// 置換したフィールドごとに `Warning::UnsupportedGlyph` を返す
+fn replace_unsupported_glyphs(items: &[Item], glyphs: &FontGlyphs, fallback: &str) -> (Vec<Item>, Vec<Warning>) {
+    let mut warnings = Vec::new();
+    let items = items
+        .iter()
+        .enumerate()
+        .map(|(item_index, item)| {
+            let mut item = item.clone();
+            item.for_each_text_mut(|field, value| {
+                let chars = glyphs.unsupported_chars(value);
+                if chars.is_empty() {
+                    return;
+                }
+                *value = value
+                    .chars()
+                    .map(|c| if chars.contains(&c) { fallback.to_string() } else { c.to_string() })
+                    .collect();
+                warnings.push(Warning::UnsupportedGlyph {
+                    item_index,
+                    field: field.to_string(),
+                    chars,
+                });
+            });
+            item
+        })
+        .collect();
+    (items, warnings)
+}
+
+/// A5横のページをA4横の左右の面に配置（反時計回りに90度回転）
+///
+/// `slot` は0が左、1が右。面の中で左右中央に配置する
+fn place_rotated(ops: Vec<Op>, slot: usize) -> Vec<Op> {
+    let slot_width = A4_LANDSCAPE_WIDTH / 2.0;
+    let x = slot as f32 * slot_width + (slot_width - A5_HEIGHT) / 2.0;
+
+    let mut placed = Vec::with_capacity(ops.len() + 3);
+    placed.push(Op::SaveGraphicsState);
+    placed.push(Op::SetTransformationMatrix {
+        matrix: CurTransMat::Raw([0.0, 1.0, -1.0, 0.0, mm_to_pt(x + A5_HEIGHT), 0.0]),
+    });
+    placed.extend(ops);
+    placed.push(Op::RestoreGraphicsState);
+    placed
+}
+
+/// 2面付けの場合のアイテムのページ範囲（用紙の番号）
+fn two_up_sheets(pages: Range<usize>) -> Range<usize> {
+    if pages.is_empty() {
+        return pages.start / 2..pages.start / 2;
+    }
+    pages.start / 2..(pages.end - 1) / 2 + 1
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Ryohi;
+    use crate::pdf::fonts::FontSource;
+    use crate::pdf::renderer::{estimate_text_width, RYOHI_START_X, RYOHI_START_Y};
+    use crate::pdf::seal::tests::TEST_PNG;
+    use crate::pdf::tax::{TaxOptions, TaxRoundingUnit};
+    use crate::pdf::testing::{self, MockFont};
+
+    #[test]
+    fn test_from_config_sets_sumatra_path() {
+        let config = PdfConfig::new().with_sumatra_path("C:\\SumatraPDF.exe");
+        let client = ReportLabStylePdfClient::from_config(&config);
+        assert_eq!(client.sumatra_path, Some(PathBuf::from("C:\\SumatraPDF.exe")));
+    }
+
+    #[test]
+    fn test_from_config_font_candidates() {
+        let config = PdfConfig::new().with_font_candidates(vec!["msgothic.ttc".to_string()]);
+        let client = ReportLabStylePdfClient::from_config(&config);
+        assert_eq!(client.font_loader.candidates(), vec!["msgothic.ttc".to_string()]);
+    }
+
+    #[test]
+    fn test_from_config_fallback_font() {
+        let client = ReportLabStylePdfClient::from_config(&PdfConfig::new());
+        assert!(!client.font_loader.has_fallback_font());
+
+        let config = PdfConfig::new().with_fallback_font(FontSource::path("/fonts/ipamjm.ttf"));
+        let client = ReportLabStylePdfClient::from_config(&config);
+        assert!(client.font_loader.has_fallback_font());
+    }
+
+    /// 指定バイト数を書き込んだ後に失敗するライター
+    struct FailingWriter {
+        limit: usize,
+        written: usize,
+    }
 
-        // 帰着日
-        if let Some(ref end_date) = item.end_date {
-            if let Some(formatted) = format_date_mmdd(end_date) {
-                self.add_text(ops, font_id, &formatted, 10.0, start_x, start_y + 7.0);
+    impl Write for FailingWriter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            if self.written >= self.limit {
+                return Err(std::io::Error::other("disk full"));
             }
+            let n = buf.len().min(self.limit - self.written);
+            self.written += n;
+            Ok(n)
         }
 
-        // 出張目的
-        if let Some(ref purpose) = item.purpose {
-            self.add_text(ops, font_id, purpose, 10.0, start_x + 32.0, start_y + 7.0);
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
         }
+    }
 
-        // 車両
-        if !item.car.is_empty() {
-            self.add_text(ops, font_id, &item.car, 10.0, start_x + 52.0, start_y + 7.0);
-        }
+    fn blank_document() -> PdfDocument {
+        let mut doc = PdfDocument::new("test");
+        doc.with_pages(vec![PdfPage::new(Mm(A5_WIDTH), Mm(A5_HEIGHT), Vec::new())]);
+        doc
+    }
+
+    #[test]
+    fn test_write_document_to_vec() {
+        let mut buf: Vec<u8> = Vec::new();
+        let written = write_document(&blank_document(), &mut buf, false).unwrap();
+        assert_eq!(written, buf.len() as u64);
+        assert!(buf.starts_with(b"%PDF"));
+    }
 
-        // 氏名
-        if !item.name.is_empty() {
-            self.add_text(ops, font_id, &item.name, 10.0, start_x + 85.0, start_y + 7.0);
+    #[test]
+    fn test_write_document_failing_writer() {
+        let mut writer = FailingWriter { limit: 16, written: 0 };
+        let result = write_document(&blank_document(), &mut writer, false);
+        match result {
+            Err(PdfError::FileIO(e)) => assert!(e.to_string().contains("disk full")),
+            other => panic!("FileIOエラーを期待: {:?}", other.map(|_| ())),
         }
+    }
 
-        // 合計金額（上部の計欄）
-        let price_str = format_price(item.price);
-        self.add_text(ops, font_id, &price_str, 12.0, MARGIN_RIGHT - 30.0, MARGIN_TOP - 12.0);
+    #[test]
+    fn test_fixed_date_reproducible_output() {
+        let date = DateTime::from_unix_timestamp(1_711_929_600).unwrap();
+        let client = ReportLabStylePdfClient::new().with_fixed_date(Some(date));
+        let save = || {
+            let mut doc = client.new_document();
+            doc.with_pages(vec![PdfPage::new(Mm(A5_WIDTH), Mm(A5_HEIGHT), Vec::new())]);
+            let mut buf = Vec::new();
+            write_document(&doc, &mut buf, true).unwrap();
+            buf
+        };
+        let first = save();
+        assert_eq!(first, save());
+        let text = String::from_utf8_lossy(&first);
+        assert!(text.contains("/CreationDate(D:20240401000000+00'00')"));
+        assert!(text.contains("/ModDate(D:20240401000000+00'00')"));
+
+        // 固定しない場合はドキュメントIDが保存のたびに変わる
+        let mut a = Vec::new();
+        let mut b = Vec::new();
+        write_document(&blank_document(), &mut a, false).unwrap();
+        write_document(&blank_document(), &mut b, false).unwrap();
+        assert_ne!(a, b);
+    }
 
-        // 旅費データを処理
-        self.add_ryohi_items(ops, font_id, &item.ryohi);
+    #[test]
+    fn test_fixed_date_pay_day_year() {
+        let fonts = FontSet::single(FontId("F1".to_string()));
+        let item = Item {
+            pay_day: Some("24/01/25".to_string()),
+            ..Default::default()
+        };
+        let pay_day = |client: &ReportLabStylePdfClient| {
+            testing::texts_in(&client.render_item_ops_with_fonts(&fonts, &item))
+                .into_iter()
+                .find(|t| t.text.starts_with("清算日"))
+                .unwrap()
+                .text
+        };
+
+        // 2桁の年は固定した日時の年を基準に展開する（1990年基準: 1910年〜2009年）
+        let date = DateTime::from_unix_timestamp(631_152_000).unwrap();
+        let client = ReportLabStylePdfClient::new().with_fixed_date(Some(date));
+        assert_eq!(pay_day(&client), "清算日　1924年 01月 25日");
+        assert_eq!(pay_day(&ReportLabStylePdfClient::new()), "清算日　2024年 01月 25日");
     }
 
-    /// 基本データを描画
-    fn add_base_data(&self, ops: &mut Vec<Op>, font_id: &FontId, item: &Item) {
-        let start_x = 10.0;
-        let start_y = 15.0;
+    #[test]
+    fn test_pin_document_id() {
+        let mut bytes = b"1 0 obj<<>>endobj trailer<</ID[(ABCDEFGHIJABCDEFGHIJABCDEFGHIJAB)(BBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBB)]>>".to_vec();
+        let len = bytes.len();
+        pin_document_id(&mut bytes);
+        assert_eq!(bytes.len(), len);
+        let text = String::from_utf8(bytes).unwrap();
+        let start = text.find("/ID[(").unwrap() + 5;
+        let (first, second) = (&text[start..start + 32], &text[start + 34..start + 66]);
+        assert_eq!(first, second);
+        assert!(first.chars().all(|c| c.is_ascii_hexdigit()));
+
+        // IDが無い場合は変更しない
+        let mut plain = b"%PDF-1.3".to_vec();
+        pin_document_id(&mut plain);
+        assert_eq!(plain, b"%PDF-1.3");
+    }
 
-        // タイトル
-        let title = "出 張 旅 費 日 当 駐 車 料 込 精 算 書";
-        self.add_text(ops, font_id, title, 14.0, start_x + 13.0, start_y + 5.0);
+    #[test]
+    fn test_generation_report_dropped_rows() {
+        let client = ReportLabStylePdfClient::new();
+        let fonts = FontSet::single(FontId("test".to_string()));
+        let ryohi = crate::models::Ryohi {
+            date: Some("2024-01-15".to_string()),
+            price: Some(1000),
+            ..Default::default()
+        };
+        let item = Item {
+            ryohi: vec![ryohi; 20],
+            ..Default::default()
+        };
+
+        let mut report = GenerationReport::default();
+        client.renderer(&fonts).page_operations(&item, &mut report);
+        assert_eq!(report.dropped_rows, 6);
+        assert_eq!(report.truncated_fields, 0);
+        assert!(!report.is_clean());
+    }
 
-        // タイトル下線（2本）
-        let title_width = 130.0;
-        ops.push(Op::SetOutlineThickness { pt: Pt(0.3) });
-        self.add_horizontal_line(ops, start_x + 13.0, start_y + 6.0, title_width);
-        self.add_horizontal_line(ops, start_x + 13.0, start_y + 7.0, title_width);
+    #[test]
+    fn test_render_page_operations_order() {
+        let fonts = FontSet::single(FontId("test".to_string()));
+        let items: Vec<Item> = (0..8)
+            .map(|i| Item {
+                name: format!("社員{}", i),
+                ..Default::default()
+            })
+            .collect();
+
+        let serial = ReportLabStylePdfClient::new()
+            .with_parallel(false)
+            .render_page_operations_with_fonts(&fonts, &items);
+        let parallel = ReportLabStylePdfClient::new()
+            .with_parallel(true)
+            .render_page_operations_with_fonts(&fonts, &items);
+
+        assert_eq!(serial.0.len(), 8);
+        assert_eq!(serial, parallel);
+    }
 
-        // 精算日
-        if let Some(ref pay_day) = item.pay_day {
-            if let Some(formatted) = format_pay_day_full(pay_day) {
-                self.add_text(ops, font_id, &formatted, 9.0, start_x + 100.0, start_y + 5.0);
-            }
-        }
+    #[test]
+    fn test_cancelled_generation() {
+        let token = CancellationToken::new();
+        token.cancel();
+        let mut client = ReportLabStylePdfClient::new().with_cancellation_token(token);
+        let fonts = FontSet::single(FontId("test".to_string()));
+
+        // キャンセル後のアイテムはページ操作を作成しない
+        let (pages, _, _) = client.render_pages(&fonts, &[Item::default(), Item::default()]);
+        assert!(pages.is_empty());
+
+        let result = client.generate_to_vec(&[Item::default()]);
+        assert!(matches!(result, Err(PdfError::Generation(ref message)) if message == "キャンセルされました"));
+    }
 
-        // 所属（右上）
-        if let Some(ref office) = item.office {
-            self.add_text(ops, font_id, office, 10.0, start_x + 175.0, start_y + 5.0);
-        }
+    #[test]
+    fn test_render_pages_per_item() {
+        let fonts = FontSet::single(FontId("test".to_string()));
+        let items = vec![Item::default(), Item::default(), Item::default()];
+
+        let (pages, _, ranges) = ReportLabStylePdfClient::new().render_pages(&fonts, &items);
+        assert_eq!(pages.len(), 3);
+        assert_eq!(ranges, vec![0..1, 1..2, 2..3]);
+
+        // 継続ページはアイテムのページ範囲に含まれる
+        let long = Item {
+            ryohi: vec![
+                Ryohi {
+                    price: Some(1000),
+                    ..Default::default()
+                };
+                20
+            ],
+            ..Default::default()
+        };
+        let client = ReportLabStylePdfClient::new()
+            .with_layout(LayoutConfig::default().with_continuation_pages(true));
+        let (pages, _, ranges) = client.render_pages(&fonts, &[Item::default(), long, Item::default()]);
+        assert_eq!(pages.len(), 4);
+        assert_eq!(ranges, vec![0..1, 1..3, 3..4]);
     }
 
-    /// 旅費データを印刷
-    fn add_ryohi_items(
-        &self,
-        ops: &mut Vec<Op>,
-        font_id: &FontId,
-        ryohi_list: &[crate::models::Ryohi],
-    ) {
-        let start_x = 10.0;
-        let start_y = 47.0;
-        let col_widths = [10.0, 17.0, 40.0, 30.0, 15.0, 15.0, 15.0, 25.0, 23.0];
-        let row_height = 10.0;
-
-        let mut current_row: usize = 0;
-
-        for (i, ryohi) in ryohi_list.iter().enumerate() {
-            if current_row >= 14 {
-                break;
-            }
+    #[test]
+    fn test_truncated_detail_warnings_after_nfc() {
+        let font = MockFont::new("generator_detail_nfc");
+        let format = FormatOptions {
+            detail_overflow: DetailOverflow::Truncate,
+            ..Default::default()
+        };
+        let mut client = ReportLabStylePdfClient::new()
+            .with_font_loader(FontLoader::new().with_font_path(font.path()))
+            .with_font_cache(font.cache())
+            .with_format_options(format);
+
+        // 分解された濁点を含めると11文字以上だが、合成後は10文字に収まる
+        let detail = "か\u{3099}き\u{3099}く\u{3099}け\u{3099}こ\u{3099}あいうえお".to_string();
+        assert!(detail.chars().count() > MAX_DETAIL_LENGTH);
+        let item = Item {
+            ryohi: vec![Ryohi {
+                detail: vec![detail],
+                price: Some(1000),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        client.generate_to_vec(std::slice::from_ref(&item)).unwrap();
+        assert!(!client
+            .last_report()
+            .warnings
+            .iter()
+            .any(|w| matches!(w, Warning::DetailTruncated { .. })));
+    }
 
-            // 旅費データを印刷用に準備
-            let print_data = prepare_ryohi_for_print(ryohi, MAX_DETAIL_LENGTH, MAX_KUKAN_LENGTH);
+    #[test]
+    fn test_external_ids_in_document_info() {
+        let font = MockFont::new("generator_external_ids");
+        let mut client = ReportLabStylePdfClient::new()
+            .with_font_loader(FontLoader::new().with_font_path(font.path()))
+            .with_font_cache(font.cache())
+            .with_layout(LayoutConfig::default().with_continuation_pages(true));
+
+        let long = Item {
+            external_id: Some("V-002".to_string()),
+            ryohi: vec![
+                Ryohi {
+                    price: Some(1000),
+                    ..Default::default()
+                };
+                20
+            ],
+            ..Default::default()
+        };
+        let first = Item {
+            external_id: Some("V-001".to_string()),
+            ..Default::default()
+        };
+        let bytes = client.generate_to_vec(&[first, long, Item::default()]).unwrap();
+
+        // ドキュメント情報のキーワードからページごとのIDを取得
+        let doc = lopdf::Document::load_mem(&bytes).unwrap();
+        let info = doc.trailer.get(b"Info").and_then(|info| doc.dereference(info)).unwrap().1;
+        let keywords = info.as_dict().unwrap().get(b"Keywords").unwrap().as_str().unwrap();
+        let by_page = crate::pdf::metadata::parse_external_id_keywords(&String::from_utf8_lossy(keywords));
+
+        let expected = BTreeMap::from([
+            (0, vec!["V-001".to_string()]),
+            (1, vec!["V-002".to_string()]),
+            (2, vec!["V-002".to_string()]),
+        ]);
+        assert_eq!(by_page, expected);
+        assert_eq!(client.last_stats().external_ids, expected);
+        assert_eq!(doc.get_pages().len(), 4);
+    }
 
-            let remaining_rows = 14 - current_row;
-            let actual_rows = print_data.max_rows.min(remaining_rows);
+    #[test]
+    fn test_two_up_imposition() {
+        let fonts = FontSet::single(FontId("test".to_string()));
+        let client = ReportLabStylePdfClient::new()
+            .with_layout(LayoutConfig::default().with_imposition(Imposition::TwoUpA4));
+        let (page_ops, _, ranges) = client.render_pages(&fonts, &[Item::default(), Item::default(), Item::default()]);
+        let one_up_len = page_ops[0].len();
+
+        let pages = client.impose_pages(page_ops);
+        assert_eq!(pages.len(), 2);
+
+        // 1枚目はA4横に2面、それぞれ回転して左右に配置
+        assert_eq!(pages[0].media_box.width, Pt::from(Mm(A4_LANDSCAPE_WIDTH)));
+        assert_eq!(pages[0].media_box.height, Pt::from(Mm(A4_LANDSCAPE_HEIGHT)));
+        assert_eq!(pages[0].ops.len(), (one_up_len + 3) * 2);
+        let offsets: Vec<f32> = pages[0]
+            .ops
+            .iter()
+            .filter_map(|op| match op {
+                Op::SetTransformationMatrix {
+                    matrix: CurTransMat::Raw(m),
+                } => Some(m[4]),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(offsets.len(), 2);
+        assert!(offsets[0] >= mm_to_pt(A5_HEIGHT) && offsets[1] <= mm_to_pt(A4_LANDSCAPE_WIDTH));
+        assert!((offsets[1] - offsets[0] - mm_to_pt(A4_LANDSCAPE_WIDTH / 2.0)).abs() < 0.01);
+
+        // 最後の奇数ページはA5に1面
+        assert_eq!(pages[1].media_box.width, Pt::from(Mm(A5_WIDTH)));
+        assert_eq!(pages[1].ops.len(), one_up_len);
+
+        let sheets: Vec<_> = ranges.into_iter().map(two_up_sheets).collect();
+        assert_eq!(sheets, vec![0..1, 0..1, 1..2]);
+        assert_eq!(two_up_sheets(1..3), 0..2);
+    }
 
-            let mut drawn_rows = 0;
+    #[test]
+    fn test_page_postprocess() {
+        let fonts = FontSet::single(FontId("test".to_string()));
+        let stamp = |page: usize, ops: &mut Vec<Op>| {
+            ops.push(Op::WriteText {
+                items: vec![TextItem::Text(format!("STAMP{}", page))],
+                font: FontId("test".to_string()),
+            })
+        };
+        let items = [Item::default(), Item::default(), Item::default()];
+
+        // 用紙ごとにPDF内のページ番号で呼び出し、操作の最後に追加できる
+        let client = ReportLabStylePdfClient::from_config(&PdfConfig::new().with_page_postprocess(stamp));
+        let (page_ops, _, _) = client.render_pages(&fonts, &items);
+        let pages = client.impose_pages(page_ops);
+        assert_eq!(pages.len(), 3);
+        for (index, page) in pages.iter().enumerate() {
+            assert_eq!(text_count(&page.ops, &format!("STAMP{}", index)), 1);
+            assert!(matches!(page.ops.last(), Some(Op::WriteText { .. })));
+        }
 
-            for row in 0..actual_rows {
-                if !print_data.has_content_in_row(row) {
-                    continue;
-                }
+        // 2面付けの場合は面付け後の用紙ごと
+        let client = ReportLabStylePdfClient::new()
+            .with_layout(LayoutConfig::default().with_imposition(Imposition::TwoUpA4))
+            .with_page_postprocess(stamp);
+        let (page_ops, _, _) = client.render_pages(&fonts, &items);
+        let pages = client.impose_pages(page_ops);
+        assert_eq!(pages.len(), 2);
+        assert_eq!(text_count(&pages[0].ops, "STAMP0"), 1);
+        assert_eq!(text_count(&pages[1].ops, "STAMP1"), 1);
+        assert_eq!(text_count(&pages[0].ops, "STAMP1"), 0);
+    }
 
-                let logical_row = current_row + drawn_rows;
-                let physical_row = logical_row / 2;
-                let sub_row = logical_row % 2;
-                let y_offset = (sub_row as f32) * 5.0;
+    #[test]
+    fn test_replace_unsupported_glyphs() {
+        let glyphs = FontGlyphs::from_chars("東京大阪交通費".chars());
+        let items = vec![
+            Item::default(),
+            Item {
+                ryohi: vec![Ryohi {
+                    dest: Some("東京".to_string()),
+                    detail: vec!["交通費😀".to_string(), "𠮷".to_string()],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+        ];
 
-                let current_y = start_y + (physical_row as f32) * row_height + y_offset;
-                let mut current_x = start_x;
+        let (items, warnings) = replace_unsupported_glyphs(&items, &glyphs, DEFAULT_GLYPH_FALLBACK);
 
-                // 日付
-                let date = print_data.get_date(row);
-                if !date.is_empty() {
-                    self.add_text(ops, font_id, date, 10.0, current_x + 1.0, current_y + 6.0);
-                }
-                current_x += col_widths[0];
+        assert_eq!(items[1].ryohi[0].dest.as_deref(), Some("東京"));
+        assert_eq!(items[1].ryohi[0].detail, vec!["交通費〓", "〓"]);
+        assert_eq!(
+            warnings,
+            vec![
+                Warning::UnsupportedGlyph {
+                    item_index: 1,
+                    field: "ryohi[0].detail[0]".to_string(),
+                    chars: vec!['😀'],
+                },
+                Warning::UnsupportedGlyph {
+                    item_index: 1,
+                    field: "ryohi[0].detail[1]".to_string(),
+                    chars: vec!['𠮷'],
+                },
+            ]
+        );
+    }
 
-                // 行先
-                let dest = print_data.get_dest(row);
-                if !dest.is_empty() {
-                    self.add_text(ops, font_id, dest, 10.0, current_x + 1.0, current_y + 6.0);
-                }
-                current_x += col_widths[1];
+    #[test]
+    fn test_render_item_ops_matches_page_operations() {
+        let client = ReportLabStylePdfClient::new();
+        let fonts = FontSet::single(FontId("test".to_string()));
+        let item = Item {
+            name: "山田".to_string(),
+            ..Default::default()
+        };
+        let (pages, _) = client.render_page_operations_with_fonts(&fonts, std::slice::from_ref(&item));
+        assert_eq!(client.render_item_ops_with_fonts(&fonts, &item), pages[0]);
+    }
 
-                // 摘要
-                let detail = print_data.get_detail(row);
-                if !detail.is_empty() {
-                    self.add_text(ops, font_id, detail, 10.0, current_x + 1.0, current_y + 6.0);
-                }
-                current_x += col_widths[2];
+    #[test]
+    #[allow(deprecated)]
+    fn test_single_font_render_functions_deprecated() {
+        let client = ReportLabStylePdfClient::new();
+        let font_id = FontId("test".to_string());
+        let fonts = FontSet::single(font_id.clone());
+        let item = Item {
+            name: "山田".to_string(),
+            price: 1000,
+            ..Default::default()
+        };
+
+        // 1つのフォントを全ての用途に使用した場合と同じ
+        assert_eq!(client.render_item_ops(&font_id, &item), client.render_item_ops_with_fonts(&fonts, &item));
+        let items = vec![item];
+        assert_eq!(
+            client.render_page_operations(&font_id, &items).0,
+            client.render_page_operations_with_fonts(&fonts, &items).0
+        );
+    }
 
-                // 区間
-                let kukan = print_data.get_kukan(row);
-                if !kukan.is_empty() {
-                    self.add_text(ops, font_id, kukan, 10.0, current_x + 1.0, current_y + 6.0);
-                }
-                current_x += col_widths[3];
+    #[test]
+    fn test_role_fonts_in_page_operations() {
+        let client = ReportLabStylePdfClient::new();
+        let mut fonts = FontSet::single(FontId("body".to_string()));
+        fonts.set(FontRole::Title, FontId("title".to_string()));
+        let item = Item {
+            name: "山田".to_string(),
+            price: 1000,
+            ..Default::default()
+        };
+        let ops = client.render_item_ops_with_fonts(&fonts, &item);
+        let used: std::collections::HashSet<_> = ops
+            .iter()
+            .filter_map(|op| match op {
+                Op::WriteText { font, .. } => Some(font.0.clone()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(used.len(), 2);
+        assert!(used.contains("title"));
+        assert!(used.contains("body"));
+    }
 
-                // 交通機関（空）
-                current_x += col_widths[4];
+    #[test]
+    fn test_nfc_items() {
+        let items = vec![Item {
+            name: "山田ひ\u{309A}ろ".to_string(),
+            ..Default::default()
+        }];
+
+        let client = ReportLabStylePdfClient::new();
+        assert_eq!(client.nfc_items(&items)[0].name, "山田ぴろ");
+        assert!(matches!(client.nfc_items(&[Item::default()]), Cow::Borrowed(_)));
+
+        // 公開の描画関数でも合成する
+        let fonts = FontSet::single(FontId("test".to_string()));
+        let (pages, _) = client.render_page_operations_with_fonts(&fonts, &items);
+        assert_eq!(text_count(&pages[0], "山田ぴろ"), 1);
+        assert_eq!(text_count(&client.render_item_ops_with_fonts(&fonts, &items[0]), "山田ぴろ"), 1);
+        assert_eq!(text_count(&client.render_item_pages(&fonts, &items[0])[0], "山田ぴろ"), 1);
+        assert!(client.render_svg(&items[0]).contains("山田ぴろ"));
+
+        // 無効の場合はそのまま
+        let client = ReportLabStylePdfClient::from_config(&PdfConfig::new().with_unicode_nfc(false));
+        assert!(matches!(client.nfc_items(&items), Cow::Borrowed(_)));
+    }
 
-                // 運賃（空）
-                current_x += col_widths[5];
+    #[test]
+    fn test_numeric_font_falls_back_for_unsupported_text() {
+        let mut fonts = FontSet::single(FontId("body".to_string()));
+        fonts.set(FontRole::Numeric, FontId("latin".to_string()));
+        fonts.set_coverage(FontRole::Numeric, Arc::new(FontGlyphs::from_chars("0123456789,".chars())));
+
+        assert_eq!(fonts.for_text(FontRole::Numeric, "1,000").0, "latin");
+        assert_eq!(fonts.for_text(FontRole::Numeric, "1,000円").0, "body");
+        // 収録文字が未設定の用途はそのまま使用
+        assert_eq!(fonts.for_text(FontRole::Title, "出張旅費").0, "body");
+    }
 
-                // 特別料金（空）
-                current_x += col_widths[6];
+    /// 本文フォントに無い「髙」「𠮷」をフォールバックフォントで描画するフォント設定
+    fn fonts_with_fallback() -> FontSet {
+        let mut fonts = FontSet::single(FontId("body".to_string()));
+        fonts.set_fallback(
+            FontId("fallback".to_string()),
+            Arc::new(FontGlyphs::from_chars("山田太郎".chars())),
+            Arc::new(FontGlyphs::from_chars("髙𠮷田".chars())),
+        );
+        fonts
+    }
 
-                // 旅費日当
-                let price = print_data.get_price(row);
-                if !price.is_empty() {
-                    self.add_text(ops, font_id, price, 10.0, current_x + col_widths[7] - 15.0, current_y + 6.0);
-                }
-                current_x += col_widths[7];
+    #[test]
+    fn test_font_runs_split_by_coverage() {
+        let fonts = fonts_with_fallback();
+        let runs: Vec<(&str, &str)> = fonts
+            .runs(&fonts.body, "髙田𠮷郎")
+            .into_iter()
+            .map(|(font, run)| (font.0.as_str(), run))
+            .collect();
+        assert_eq!(runs, vec![("fallback", "髙"), ("body", "田"), ("fallback", "𠮷"), ("body", "郎")]);
+
+        // どちらにも無い文字は本文フォントのまま
+        assert_eq!(fonts.runs(&fonts.body, "山﨑").len(), 1);
+        // 本文フォント以外・フォールバック未設定の場合は分割しない
+        assert_eq!(fonts.runs(&FontId("title".to_string()), "髙田").len(), 1);
+        let plain = FontSet::single(FontId("body".to_string()));
+        assert_eq!(plain.runs(&plain.body, "髙田").len(), 1);
+    }
 
-                // 計
-                let vol = print_data.get_vol(row);
-                if !vol.is_empty() {
-                    self.add_text(ops, font_id, vol, 10.0, current_x + col_widths[8] - 10.0, current_y + 6.0);
-                }
+    #[test]
+    fn test_fallback_runs_in_one_text_section() {
+        let item = Item {
+            name: "髙田太郎".to_string(),
+            ..Default::default()
+        };
+        let ops = ReportLabStylePdfClient::new().render_item_ops_with_fonts(&fonts_with_fallback(), &item);
+        let start = ops
+            .iter()
+            .position(|op| matches!(op, Op::WriteText { items, .. } if items == &vec![TextItem::Text("髙".to_string())]))
+            .expect("フォールバックフォントの区間が描画されていない");
+
+        assert!(matches!(&ops[start], Op::WriteText { font, .. } if font.0 == "fallback"));
+        assert!(matches!(&ops[start + 1], Op::SetFontSize { font, .. } if font.0 == "body"));
+        assert!(
+            matches!(&ops[start + 2], Op::WriteText { font, items } if font.0 == "body" && items == &vec![TextItem::Text("田太郎".to_string())])
+        );
+        assert!(matches!(ops[start + 3], Op::EndTextSection));
+        // 区間の先頭のフォントで文字サイズを設定
+        let font_size = ops[..start]
+            .iter()
+            .rev()
+            .find_map(|op| match op {
+                Op::SetFontSize { font, .. } => Some(font.0.as_str()),
+                _ => None,
+            });
+        assert_eq!(font_size, Some("fallback"));
+    }
 
-                drawn_rows += 1;
+    #[test]
+    fn test_custom_margins_wrap_page() {
+        let fonts = FontSet::single(FontId("test".to_string()));
+        let item = Item::default();
+        let default_ops = ReportLabStylePdfClient::new().render_item_ops_with_fonts(&fonts, &item);
+
+        let layout = LayoutConfig::new().with_margins(Margins {
+            left: 15.0,
+            ..Margins::DEFAULT
+        });
+        let ops = ReportLabStylePdfClient::new()
+            .with_layout(layout.clone())
+            .render_item_ops_with_fonts(&fonts, &item);
+
+        assert_eq!(ops.len(), default_ops.len() + 3);
+        assert_eq!(ops[0], Op::SaveGraphicsState);
+        assert_eq!(
+            ops[1],
+            Op::SetTransformationMatrix {
+                matrix: CurTransMat::Raw(layout.content_matrix().unwrap()),
             }
+        );
+        assert_eq!(ops[2..ops.len() - 1], default_ops[..]);
+        assert_eq!(ops.last(), Some(&Op::RestoreGraphicsState));
+    }
 
-            current_row += drawn_rows;
-            tracing::debug!(
-                "旅費項目 {}: 最大行数={}, 実際印刷行数={}, 現在行={}",
-                i + 1,
-                print_data.max_rows,
-                drawn_rows,
-                current_row
-            );
+    #[test]
+    fn test_approval_table_labels() {
+        let fonts = FontSet::single(FontId("test".to_string()));
+        let count_rects = |ops: &[Op]| ops.iter().filter(|op| matches!(op, Op::DrawPolygon { .. })).count();
+
+        let mut default_ops = Vec::new();
+        ReportLabStylePdfClient::new().renderer(&fonts).add_approval_table(&mut default_ops);
+        assert_eq!(count_rects(&default_ops), 6);
+
+        let labels = vec!["部長".to_string(), "課長".to_string(), "係長".to_string(), "担当".to_string()];
+        let client = ReportLabStylePdfClient::new().with_layout(LayoutConfig::new().with_approval_labels(labels));
+        let mut ops = Vec::new();
+        client.renderer(&fonts).add_approval_table(&mut ops);
+        assert_eq!(count_rects(&ops), 8);
+        assert!(ops.iter().any(|op| matches!(op, Op::WriteText { items, .. } if items == &vec![TextItem::Text("担当".to_string())])));
+    }
+
+    #[test]
+    fn test_approval_seals_drawn_in_cells() {
+        let seal = SealImage::from_png(TEST_PNG);
+        let mut client = ReportLabStylePdfClient::new().with_approval_seals(vec![Some(seal.clone()), None, Some(seal)]);
+        let mut doc = PdfDocument::new("test");
+        client.embed_seals(&mut doc).unwrap();
+        assert_eq!(doc.resources.xobjects.map.len(), 2);
+
+        let fonts = FontSet::single(FontId("test".to_string()));
+        let mut ops = Vec::new();
+        client.renderer(&fonts).add_approval_table(&mut ops);
+        let placed: Vec<&XObjectTransform> = ops
+            .iter()
+            .filter_map(|op| match op {
+                Op::UseXobject { transform, .. } => Some(transform),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(placed.len(), 2);
+
+        // 2×1pxの画像は列幅に合わせて拡大し、データ行（上端30mm、高さ15mm）の中央に配置
+        let col_width = client.layout.approval_col_width();
+        let start_x = 200.0 - col_width * 3.0;
+        let scale = placed[0].scale_x.unwrap();
+        let drawn_width = pt_to_mm(2.0 * scale);
+        let drawn_height = pt_to_mm(scale);
+        assert!((drawn_width - (col_width - 2.0)).abs() < 0.01);
+        let left = pt_to_mm(placed[0].translate_x.unwrap().0);
+        assert!((left - (start_x + 1.0)).abs() < 0.01);
+        let top = A5_HEIGHT - pt_to_mm(placed[0].translate_y.unwrap().0) - drawn_height;
+        assert!((top - (30.0 + (15.0 - drawn_height) / 2.0)).abs() < 0.01);
+        let third = pt_to_mm(placed[1].translate_x.unwrap().0);
+        assert!((third - (start_x + col_width * 2.0 + 1.0)).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_invalid_approval_seal_rejected() {
+        let mut client = ReportLabStylePdfClient::new()
+            .with_approval_seals(vec![None, Some(SealImage::from_png(b"broken".to_vec()))]);
+        let result = client.embed_seals(&mut PdfDocument::new("test"));
+        assert!(matches!(result, Err(PdfError::Validation(ref message)) if message.contains("会　計")));
+    }
+
+    #[test]
+    fn test_blank_page_operations() {
+        let client = ReportLabStylePdfClient::new().with_blank_on_empty(true);
+        let fonts = FontSet::single(FontId("test".to_string()));
+        let blank = client.renderer(&fonts).blank_page();
+
+        let mut report = GenerationReport::default();
+        let page = client.renderer(&fonts).page_operations(&Item::default(), &mut report);
+
+        // 空白の精算書は合計金額を含まない（金額0のアイテムは「0」を印刷する）
+        assert!(!blank.is_empty());
+        assert_eq!(text_count(&blank, "0"), 0);
+        assert_eq!(text_count(&page, "0"), 1);
+
+        let priced = Item {
+            price: 1000,
+            ..Default::default()
+        };
+        let page = client.renderer(&fonts).page_operations(&priced, &mut report);
+        assert_eq!(text_count(&page, "1,000"), 1);
+    }
+
+    /// レイアウト検証用のページ操作（所属・旅費1件）
+    fn sample_page_operations(client: &ReportLabStylePdfClient) -> Vec<Op> {
+        let item = Item {
+            office: Some("本社".to_string()),
+            ryohi: vec![Ryohi {
+                date: Some("2024-01-15".to_string()),
+                detail: vec!["高速代".to_string()],
+                price: Some(1000),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let fonts = FontSet::single(FontId("test".to_string()));
+        let mut report = GenerationReport::default();
+        client.renderer(&fonts).page_operations(&item, &mut report)
+    }
+
+    #[test]
+    fn test_render_svg() {
+        let client = ReportLabStylePdfClient::new();
+        let item = Item {
+            name: "山田&太郎".to_string(),
+            ..Default::default()
+        };
+        let svg = client.render_svg(&item);
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains(&format!(r#"viewBox="0 0 {} {}""#, A5_WIDTH, A5_HEIGHT)));
+        assert!(svg.contains(TITLE));
+        assert!(svg.contains("山田&amp;太郎"));
+
+        // ページ操作の矩形・テキストと同じ数の要素
+        let fonts = FontSet::single(FontId("F1".to_string()));
+        let ops = client.render_item_ops_with_fonts(&fonts, &item);
+        assert!(svg.matches("<path").count() >= testing::rects_in(&ops).len());
+        let texts: Vec<String> = testing::texts_in(&ops).into_iter().map(|text| text.text).collect();
+        assert!(svg.matches("<text").count() <= texts.len());
+        for text in texts.iter().filter(|text| !text.contains('&')) {
+            assert!(svg.contains(text.as_str()), "{}がありません", text);
         }
     }
 
-    /// テキストを追加
-    fn add_text(&self, ops: &mut Vec<Op>, font_id: &FontId, text: &str, size: f32, x: f32, y: f32) {
-        ops.push(Op::StartTextSection);
-        ops.push(Op::SetTextCursor {
-            pos: Point::new(Mm(x), Mm(A5_HEIGHT - y)),
-        });
-        ops.push(Op::SetFontSize {
-            font: font_id.clone(),
-            size: Pt(size),
-        });
-        ops.push(Op::SetLineHeight { lh: Pt(size) });
-        ops.push(Op::SetFillColor {
-            col: Color::Rgb(Rgb { r: 0.0, g: 0.0, b: 0.0, icc_profile: None }),
-        });
-        ops.push(Op::WriteText {
-            items: vec![TextItem::Text(text.to_string())],
-            font: font_id.clone(),
-        });
-        ops.push(Op::EndTextSection);
-    }
-
-    /// 矩形を描画
-    fn add_rect(&self, ops: &mut Vec<Op>, x: f32, y: f32, width: f32, height: f32) {
-        ops.push(Op::DrawPolygon {
-            polygon: Polygon {
-                rings: vec![PolygonRing {
-                    points: vec![
-                        LinePoint { p: Point::new(Mm(x), Mm(A5_HEIGHT - y)), bezier: false },
-                        LinePoint { p: Point::new(Mm(x + width), Mm(A5_HEIGHT - y)), bezier: false },
-                        LinePoint { p: Point::new(Mm(x + width), Mm(A5_HEIGHT - y - height)), bezier: false },
-                        LinePoint { p: Point::new(Mm(x), Mm(A5_HEIGHT - y - height)), bezier: false },
-                    ],
-                }],
-                mode: PaintMode::Stroke,
-                winding_order: WindingOrder::NonZero,
-            },
+    #[test]
+    fn test_page_title_position() {
+        let ops = sample_page_operations(&ReportLabStylePdfClient::new());
+        let title = testing::find_text(&ops, TITLE).unwrap();
+        assert!((title.x_mm - 23.0).abs() < 0.01);
+        assert!((title.top_mm(A5_HEIGHT) - 20.0).abs() < 0.01);
+        assert_eq!(title.size, FONT_SIZE_TITLE);
+    }
+
+    #[test]
+    fn test_page_approval_headers_inside_boxes() {
+        let client = ReportLabStylePdfClient::new();
+        let ops = sample_page_operations(&client);
+        let rects = testing::rects_in(&ops);
+        for label in DEFAULT_APPROVAL_LABELS {
+            let text = testing::find_text(&ops, label).unwrap();
+            let top = text.top_mm(A5_HEIGHT);
+            let header = rects.iter().find(|rect| {
+                (rect.top_mm(A5_HEIGHT) - 25.0).abs() < 0.01
+                    && (rect.height_mm - 5.0).abs() < 0.01
+                    && rect.x_mm <= text.x_mm
+                    && text.x_mm < rect.x_mm + rect.width_mm
+            });
+            assert!(header.is_some(), "{}の見出し枠がありません", label);
+            assert!(top > 25.0 && top <= 30.0);
+        }
+    }
+
+    #[test]
+    fn test_page_first_ryohi_amounts_within_columns() {
+        let ops = sample_page_operations(&ReportLabStylePdfClient::new());
+        let amounts: Vec<testing::PlacedText> =
+            testing::texts_in(&ops).into_iter().filter(|t| t.text == "1,000").collect();
+        assert_eq!(amounts.len(), 2);
+        let (price, total) = (&amounts[0], &amounts[1]);
+        assert!((price.top_mm(A5_HEIGHT) - (RYOHI_START_Y + 6.0)).abs() < 0.01);
+
+        // 旅費日当は列内に収まる
+        let layout = LayoutConfig::default();
+        let price_left = RYOHI_START_X + layout.column_offset(7);
+        let price_right = RYOHI_START_X + layout.column_offset(8);
+        assert!(price.x_mm > price_left);
+        assert!(price.x_mm + estimate_text_width(&price.text, price.size) < price_right);
+
+        // 計は列の右端から1mmの位置に右揃え
+        let total_right = RYOHI_START_X + layout.columns_width() - 1.0;
+        let total_end = total.x_mm + estimate_text_width(&total.text, total.size);
+        assert!((total_end - total_right).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_page_office_position() {
+        let ops = sample_page_operations(&ReportLabStylePdfClient::new());
+        let office = testing::find_text(&ops, "本社").unwrap();
+        assert!((office.x_mm - 185.0).abs() < 0.01);
+        assert!((office.top_mm(A5_HEIGHT) - 20.0).abs() < 0.01);
+        assert_eq!(office.size, 10.0);
+    }
+
+    #[test]
+    fn test_page_positions_follow_margins() {
+        let layout = LayoutConfig::new().with_margins(Margins {
+            left: 20.0,
+            ..Margins::DEFAULT
         });
+        let shifted = sample_page_operations(&ReportLabStylePdfClient::new().with_layout(layout));
+        let title = testing::find_text(&shifted, TITLE).unwrap();
+        let original = testing::find_text(&sample_page_operations(&ReportLabStylePdfClient::new()), TITLE).unwrap();
+        assert!(title.x_mm > original.x_mm);
     }
 
-    /// 垂直線を描画
-    fn add_vertical_line(&self, ops: &mut Vec<Op>, x: f32, y: f32, height: f32) {
-        ops.push(Op::DrawLine {
-            line: Line {
-                points: vec![
-                    LinePoint { p: Point::new(Mm(x), Mm(A5_HEIGHT - y)), bezier: false },
-                    LinePoint { p: Point::new(Mm(x), Mm(A5_HEIGHT - y - height)), bezier: false },
-                ],
-                is_closed: false,
+    #[test]
+    fn test_tax_summary_in_remarks() {
+        let item = Item {
+            ryohi: vec![
+                Ryohi {
+                    price: Some(105),
+                    tax_rate: Some(10),
+                    ..Default::default()
+                },
+                Ryohi {
+                    price: Some(105),
+                    tax_rate: Some(10),
+                    ..Default::default()
+                },
+                Ryohi {
+                    price: Some(2500),
+                    tax_rate: Some(8),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        let fonts = FontSet::single(FontId("test".to_string()));
+        let mut report = GenerationReport::default();
+        let ops = ReportLabStylePdfClient::new().renderer(&fonts).page_operations(&item, &mut report);
+
+        let eight = testing::find_text(&ops, "8%対象").unwrap();
+        let ten = testing::find_text(&ops, "10%対象").unwrap();
+        let total = testing::find_text(&ops, "消費税計").unwrap();
+        // 備考欄（上端119mm、高さ19mm）の中に税率の昇順で並ぶ
+        assert!(eight.top_mm(A5_HEIGHT) < ten.top_mm(A5_HEIGHT));
+        assert!(ten.top_mm(A5_HEIGHT) < total.top_mm(A5_HEIGHT));
+        assert!(eight.top_mm(A5_HEIGHT) > 119.0 && total.top_mm(A5_HEIGHT) < 138.0);
+
+        // 合計に対して端数処理（210円×10% = 21円、2,500円×8% = 200円）
+        let texts: Vec<String> = testing::texts_in(&ops).into_iter().map(|t| t.text).collect();
+        for expected in ["2,500", "200", "210", "21", "221"] {
+            assert!(texts.iter().any(|t| t == expected), "{}がありません", expected);
+        }
+
+        let per_line = ReportLabStylePdfClient::new().with_format_options(FormatOptions {
+            tax: TaxOptions {
+                unit: TaxRoundingUnit::PerLine,
+                ..Default::default()
             },
+            ..Default::default()
         });
+        let ops = per_line.renderer(&fonts).page_operations(&item, &mut report);
+        let texts: Vec<String> = testing::texts_in(&ops).into_iter().map(|t| t.text).collect();
+        assert!(texts.iter().any(|t| t == "220"));
+    }
+
+    #[test]
+    fn test_stated_tax_printed_without_rates() {
+        let fonts = FontSet::single(FontId("test".to_string()));
+        let mut report = GenerationReport::default();
+        let client = ReportLabStylePdfClient::new();
+
+        let item = Item {
+            tax: Some(2500.0),
+            ..Default::default()
+        };
+        let ops = client.renderer(&fonts).page_operations(&item, &mut report);
+        assert!(testing::find_text(&ops, "消費税計").is_some());
+        assert_eq!(text_count(&ops, "2,500"), 1);
+
+        let ops = client.renderer(&fonts).page_operations(&Item::default(), &mut report);
+        assert!(testing::find_text(&ops, "消費税").is_none());
     }
 
-    /// 水平線を描画
-    fn add_horizontal_line(&self, ops: &mut Vec<Op>, x: f32, y: f32, width: f32) {
-        ops.push(Op::DrawLine {
-            line: Line {
-                points: vec![
-                    LinePoint { p: Point::new(Mm(x), Mm(A5_HEIGHT - y)), bezier: false },
-                    LinePoint { p: Point::new(Mm(x + width), Mm(A5_HEIGHT - y)), bezier: false },
-                ],
-                is_closed: false,
+    #[test]
+    fn test_tax_mismatch_strict() {
+        let items = vec![Item {
+            tax: Some(500.0),
+            ryohi: vec![Ryohi {
+                price: Some(1000),
+                tax_rate: Some(10),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }];
+        let mut client = ReportLabStylePdfClient::new().with_strict(true);
+        let result = client.generate_to_vec(&items);
+        assert!(matches!(result, Err(PdfError::Validation(ref message)) if message.contains("税額が一致しません")));
+
+        // 厳格モードでなければ警告として扱い生成を継続する
+        let mut client = ReportLabStylePdfClient::new().with_font_loader(FontLoader::new().with_candidates(Vec::new()));
+        assert!(!matches!(client.generate_to_vec(&items), Err(PdfError::Validation(_))));
+    }
+
+    #[test]
+    fn test_possible_duplicate_warnings() {
+        let trip = |date: &str, price: i32| Ryohi {
+            date: Some(date.to_string()),
+            dest: Some("東京".to_string()),
+            price: Some(price),
+            ..Default::default()
+        };
+        let items = vec![
+            Item {
+                ryohi: vec![trip("2024-01-15", 15000), trip("2024-01-16", 10000)],
+                ..Default::default()
             },
+            Item {
+                ryohi: vec![trip("2024-01-20", 8000), trip("2024-01-20", 8000)],
+                ..Default::default()
+            },
+        ];
+        let warnings = possible_duplicate_warnings(&items);
+        assert_eq!(
+            warnings,
+            vec![Warning::PossibleDuplicateRyohi {
+                item_index: 1,
+                ryohi_index: 1,
+                date: "2024-01-20".to_string(),
+                dest: "東京".to_string(),
+            }]
+        );
+        assert_eq!(
+            warnings[0].to_string(),
+            "重複の可能性がある旅費項目: item[1].ryohi[1] (ryohi[0]と日付・行先が同じ, 日付=2024-01-20, 行先=東京)"
+        );
+
+        // 厳格モードでもエラーにしない（フォントが無いためフォントの読み込みで失敗する）
+        let mut client = ReportLabStylePdfClient::new()
+            .with_strict(true)
+            .with_font_loader(FontLoader::new().with_candidates(Vec::new()));
+        assert!(matches!(client.generate_to_vec(&items), Err(PdfError::FontLoad(_))));
+    }
+
+    #[test]
+    fn test_blank_forms_pages() {
+        let client = ReportLabStylePdfClient::new();
+        let fonts = FontSet::single(FontId("test".to_string()));
+        let forms = client.renderer(&fonts).blank_forms(3);
+        assert_eq!(forms.len(), 3);
+        assert!(forms.iter().all(|ops| *ops == forms[0]));
+        assert_eq!(client.impose_pages(forms.clone()).len(), 3);
+
+        // 日付・金額などのアイテムのデータを含まない
+        let texts: Vec<String> = forms[0]
+            .iter()
+            .filter_map(|op| match op {
+                Op::WriteText { items, .. } => Some(
+                    items
+                        .iter()
+                        .filter_map(|item| match item {
+                            TextItem::Text(text) => Some(text.clone()),
+                            _ => None,
+                        })
+                        .collect::<String>(),
+                ),
+                _ => None,
+            })
+            .collect();
+        assert!(!texts.is_empty());
+        assert!(texts.iter().all(|text| !text.chars().any(|c| c.is_ascii_digit())));
+    }
+
+    #[test]
+    fn test_blank_forms_requires_copies() {
+        let mut client = ReportLabStylePdfClient::new();
+        assert!(matches!(client.generate_blank_forms(0), Err(PdfError::Validation(_))));
+    }
+
+    #[test]
+    fn test_empty_items_rejected() {
+        let mut client = ReportLabStylePdfClient::new();
+        let result = client.generate_to_vec(&[]);
+        assert!(matches!(result, Err(PdfError::Validation(ref message)) if message == EMPTY_ITEMS_MESSAGE));
+    }
+
+    #[test]
+    fn test_layout_presets_per_item() {
+        let branch = LayoutConfig::new().with_approval_labels(vec!["所長".to_string(), "担当".to_string()]);
+        let client = ReportLabStylePdfClient::new()
+            .with_layout_preset("本社", LayoutConfig::default())
+            .with_layout_preset("支店", branch.clone());
+        let item = |preset: &str| Item {
+            layout_preset: Some(preset.to_string()),
+            ..Default::default()
+        };
+        let fonts = FontSet::single(FontId("test".to_string()));
+        let (pages, _) = client.render_page_operations_with_fonts(&fonts, &[item("本社"), item("支店")]);
+        assert_eq!(pages.len(), 2);
+
+        // 承認欄の見出し枠（上端25mm、高さ5mm）の左端
+        let approval_left = |ops: &[Op]| {
+            testing::rects_in(ops)
+                .iter()
+                .filter(|rect| (rect.top_mm(A5_HEIGHT) - 25.0).abs() < 0.01 && (rect.height_mm - 5.0).abs() < 0.01)
+                .map(|rect| rect.x_mm)
+                .fold(f32::INFINITY, f32::min)
+        };
+        let head_office = LayoutConfig::default();
+        let expected = |layout: &LayoutConfig| 200.0 - layout.approval_col_width() * layout.approval_labels.len() as f32;
+        assert!((approval_left(&pages[0]) - expected(&head_office)).abs() < 0.01);
+        assert!((approval_left(&pages[1]) - expected(&branch)).abs() < 0.01);
+        assert!(testing::find_text(&pages[1], "所長").is_some());
+        assert!(testing::find_text(&pages[0], "所長").is_none());
+    }
+
+    #[test]
+    fn test_unknown_layout_preset_rejected() {
+        let mut client = ReportLabStylePdfClient::new().with_layout_preset("本社", LayoutConfig::default());
+        let item = Item {
+            layout_preset: Some("倉庫".to_string()),
+            ..Default::default()
+        };
+        let result = client.generate_to_vec(&[Item::default(), item]);
+        assert!(matches!(result, Err(PdfError::Validation(ref message)) if message.contains("倉庫") && message.contains("アイテム2")));
+    }
+
+    /// 区切り線（破線）のY座標を取得
+    fn separator_positions(ops: &[Op]) -> Vec<f32> {
+        ops.windows(2)
+            .filter_map(|w| match (&w[0], &w[1]) {
+                (Op::SetLineDashPattern { .. }, Op::DrawLine { line }) => Some(A5_HEIGHT - pt_to_mm(line.points[0].p.y.0)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_entry_separators_between_entries() {
+        let items = vec![Item {
+            ryohi: vec![
+                Ryohi {
+                    detail: vec!["高速代\nETC割引適用".to_string()],
+                    price: Some(1000),
+                    ..Default::default()
+                },
+                Ryohi {
+                    detail: vec!["駐車場".to_string()],
+                    price: Some(500),
+                    ..Default::default()
+                },
+                Ryohi {
+                    detail: vec!["日当".to_string()],
+                    price: Some(2000),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        }];
+        let fonts = FontSet::single(FontId("test".to_string()));
+
+        // デフォルトでは描画しない
+        let (pages, _) = ReportLabStylePdfClient::new().render_page_operations_with_fonts(&fonts, &items);
+        assert!(separator_positions(&pages[0]).is_empty());
+
+        let layout = LayoutConfig::new().with_entry_separator(EntrySeparator {
+            enabled: true,
+            ..Default::default()
         });
+        let (pages, _) = ReportLabStylePdfClient::new()
+            .with_layout(layout)
+            .render_page_operations_with_fonts(&fonts, &items);
+        let positions = separator_positions(&pages[0]);
+
+        // 1件目（2行）の後と2件目（1行）の後のみ
+        assert_eq!(positions.len(), 2);
+        assert!((positions[0] - 59.0).abs() < 0.01);
+        assert!((positions[1] - 64.0).abs() < 0.01);
     }
-}
 
-impl Default for ReportLabStylePdfClient {
-    fn default() -> Self {
-        Self::new()
+    /// テキストのY座標（ベースライン）を取得
+    fn text_positions(ops: &[Op]) -> Vec<f32> {
+        ops.iter()
+            .filter_map(|op| match op {
+                Op::SetTextCursor { pos } => Some(A5_HEIGHT - pt_to_mm(pos.y.0)),
+                _ => None,
+            })
+            .collect()
     }
-}
 
-/// 日付をMM　DD形式にフォーマット
-fn format_date_mmdd(date: &str) -> Option<String> {
-    // YYYY-MM-DD形式を想定
-    if date.len() >= 10 && date.chars().nth(4) == Some('-') && date.chars().nth(7) == Some('-') {
-        let month = &date[5..7];
-        let day = &date[8..10];
-        Some(format!("{}　 {}", month, day))
-    } else {
-        Some(date.to_string())
+    #[test]
+    fn test_ryohi_lines_do_not_overlap() {
+        let ryohi: Vec<Ryohi> = (0..6)
+            .map(|i| Ryohi {
+                detail: vec![format!("明細{}", i)],
+                ..Default::default()
+            })
+            .collect();
+        let layout = LayoutConfig::default();
+        let client = ReportLabStylePdfClient::new();
+        let fonts = FontSet::single(FontId("test".to_string()));
+
+        let mut ops = Vec::new();
+        let mut report = GenerationReport::default();
+        client.renderer(&fonts).add_ryohi_items(&mut ops, &ryohi, false, &mut report);
+
+        let positions = text_positions(&ops);
+        assert_eq!(positions.len(), 6);
+        for pair in positions.windows(2) {
+            // 次の行のベースラインとの間隔が文字の高さ以上
+            assert!(pair[1] - pair[0] >= pt_to_mm(layout.body_font_size));
+        }
     }
-}
 
-/// 支払日をフルフォーマット
-fn format_pay_day_full(pay_day: &str) -> Option<String> {
-    // YYYY/MM/DD or YYYY-MM-DD形式を想定
-    let parts: Vec<&str> = if pay_day.contains('/') {
-        pay_day.split('/').collect()
-    } else {
-        pay_day.split('-').collect()
-    };
+    /// テキストのY座標（ベースライン）を取得
+    fn text_position(ops: &[Op], text: &str) -> Option<f32> {
+        let index = ops.iter().position(
+            |op| matches!(op, Op::WriteText { items, .. } if items == &vec![TextItem::Text(text.to_string())]),
+        )?;
+        ops[..index].iter().rev().find_map(|op| match op {
+            Op::SetTextCursor { pos } => Some(A5_HEIGHT - pt_to_mm(pos.y.0)),
+            _ => None,
+        })
+    }
 
-    if parts.len() == 3 {
-        Some(format!("清算日　{}年 {}月 {}日", parts[0], parts[1], parts[2]))
-    } else {
-        None
+    /// 指定したテキストの描画回数
+    fn text_count(ops: &[Op], text: &str) -> usize {
+        ops.iter()
+            .filter(|op| matches!(op, Op::WriteText { items, .. } if items == &vec![TextItem::Text(text.to_string())]))
+            .count()
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn test_continuation_pages_subtotal_and_carry_forward() {
+        let ryohi = Ryohi {
+            date: Some("2024-01-15".to_string()),
+            price: Some(1000),
+            ..Default::default()
+        };
+        let client = ReportLabStylePdfClient::new()
+            .with_layout(LayoutConfig::default().with_continuation_pages(true));
+        let fonts = FontSet::single(FontId("test".to_string()));
+
+        let mut ops = Vec::new();
+        let mut report = GenerationReport::default();
+        let continuation = client.renderer(&fonts).add_ryohi_items(&mut ops, &vec![ryohi; 20], false, &mut report);
+
+        assert_eq!(continuation.len(), 1);
+        assert_eq!(report.continuation_pages, 1);
+        assert_eq!(report.dropped_rows, 0);
+
+        // 1ページ目: 13行 + 最終行に小計
+        let layout = LayoutConfig::default();
+        let last_row_y = RYOHI_START_Y + layout.sub_line_offset(13) + 6.0;
+        assert!((text_position(&ops, SUBTOTAL_LABEL).unwrap() - last_row_y).abs() < 0.01);
+        assert!((text_position(&ops, "13,000").unwrap() - last_row_y).abs() < 0.01);
+        assert_eq!(text_count(&ops, "01/15"), 13);
+
+        // 2ページ目: 先頭行に繰越 + 残りの7行
+        let page = &continuation[0];
+        let first_row_y = RYOHI_START_Y + 6.0;
+        assert!((text_position(page, CARRY_FORWARD_LABEL).unwrap() - first_row_y).abs() < 0.01);
+        assert!((text_position(page, "13,000").unwrap() - first_row_y).abs() < 0.01);
+        assert_eq!(text_count(page, "01/15"), 7);
+        assert_eq!(text_count(page, SUBTOTAL_LABEL), 0);
+    }
 
     #[test]
-    fn test_format_date_mmdd() {
-        assert_eq!(format_date_mmdd("2024-01-15"), Some("01　 15".to_string()));
-        assert_eq!(format_date_mmdd("invalid"), Some("invalid".to_string()));
+    fn test_continuation_subtotal_when_entry_is_split() {
+        let single = Ryohi {
+            price: Some(1000),
+            ..Default::default()
+        };
+        let multi = Ryohi {
+            detail: vec!["一行目\n二行目\n三行目".to_string()],
+            price: Some(500),
+            ..Default::default()
+        };
+        let mut ryohi = vec![single; 12];
+        ryohi.push(multi);
+        let client = ReportLabStylePdfClient::new()
+            .with_layout(LayoutConfig::default().with_continuation_pages(true));
+        let fonts = FontSet::single(FontId("test".to_string()));
+
+        let mut ops = Vec::new();
+        let mut report = GenerationReport::default();
+        let continuation = client.renderer(&fonts).add_ryohi_items(&mut ops, &ryohi, false, &mut report);
+
+        // 複数行の項目の1行目（金額）は1ページ目、残りは継続ページに印刷
+        assert_eq!(continuation.len(), 1);
+        assert_eq!(text_count(&ops, "12,500"), 1);
+        assert_eq!(text_count(&continuation[0], "12,500"), 1);
+        assert_eq!(text_count(&continuation[0], "二行目"), 1);
+        assert_eq!(text_count(&continuation[0], "三行目"), 1);
     }
 
     #[test]
-    fn test_format_pay_day_full() {
-        assert_eq!(
-            format_pay_day_full("2024/01/25"),
-            Some("清算日　2024年 01月 25日".to_string())
-        );
-        assert_eq!(
-            format_pay_day_full("2024-01-25"),
-            Some("清算日　2024年 01月 25日".to_string())
-        );
+    fn test_page_break_before() {
+        let single = |date: &str| Ryohi {
+            date: Some(date.to_string()),
+            price: Some(1000),
+            ..Default::default()
+        };
+        let mut ryohi: Vec<Ryohi> = ["2024-01-01", "2024-01-02", "2024-01-03", "2024-01-04", "2024-01-05"]
+            .iter()
+            .map(|date| single(date))
+            .collect();
+        ryohi[2].page_break_before = Some(true);
+        let client = ReportLabStylePdfClient::new()
+            .with_layout(LayoutConfig::default().with_continuation_pages(true));
+        let fonts = FontSet::single(FontId("test".to_string()));
+        let layout = LayoutConfig::default();
+
+        let mut ops = Vec::new();
+        let mut report = GenerationReport::default();
+        let continuation = client.renderer(&fonts).add_ryohi_items(&mut ops, &ryohi, false, &mut report);
+
+        // 1ページ目: 2件 + 最終行に小計、2ページ目: 繰越 + 3件
+        assert_eq!(continuation.len(), 1);
+        assert_eq!(report.continuation_pages, 1);
+        assert_eq!(text_count(&ops, "01/01") + text_count(&ops, "01/02"), 2);
+        assert_eq!(text_count(&ops, "01/03"), 0);
+        let last_row_y = RYOHI_START_Y + layout.sub_line_offset(13) + 6.0;
+        assert!((text_position(&ops, SUBTOTAL_LABEL).unwrap() - last_row_y).abs() < 0.01);
+        assert!((text_position(&ops, "2,000").unwrap() - last_row_y).abs() < 0.01);
+        let page = &continuation[0];
+        assert!((text_position(page, CARRY_FORWARD_LABEL).unwrap() - (RYOHI_START_Y + 6.0)).abs() < 0.01);
+        assert_eq!(text_count(page, "2,000"), 1);
+        let second_row_y = RYOHI_START_Y + layout.sub_line_offset(1) + 6.0;
+        assert!((text_position(page, "01/03").unwrap() - second_row_y).abs() < 0.01);
+        assert_eq!(text_count(page, "01/04") + text_count(page, "01/05"), 2);
+
+        // 先頭の項目・継続ページを無効にした場合は改ページしない
+        ryohi[2].page_break_before = None;
+        ryohi[0].page_break_before = Some(true);
+        let mut report = GenerationReport::default();
+        assert!(client.renderer(&fonts).add_ryohi_items(&mut Vec::new(), &ryohi, false, &mut report).is_empty());
+        ryohi[2].page_break_before = Some(true);
+        let mut report = GenerationReport::default();
+        let single_page = ReportLabStylePdfClient::new().renderer(&fonts).add_ryohi_items(&mut Vec::new(), &ryohi, false, &mut report);
+        assert!(single_page.is_empty());
+        assert_eq!(report.continuation_pages, 0);
+    }
+
+    #[test]
+    fn test_keep_together_moves_split_entry() {
+        let single = Ryohi {
+            price: Some(1000),
+            ..Default::default()
+        };
+        let multi = Ryohi {
+            detail: vec!["一行目\n二行目\n三行目".to_string()],
+            price: Some(500),
+            ..Default::default()
+        };
+        let mut ryohi = vec![single.clone(); 12];
+        ryohi.push(multi);
+        ryohi.push(single);
+        let client = ReportLabStylePdfClient::new()
+            .with_layout(LayoutConfig::default().with_continuation_pages(true));
+        let fonts = FontSet::single(FontId("test".to_string()));
+        let layout = LayoutConfig::default();
+
+        let mut ops = Vec::new();
+        let mut report = GenerationReport::default();
+        let continuation = client.renderer(&fonts).add_ryohi_items(&mut ops, &ryohi, true, &mut report);
+
+        // 複数行の項目は全体を継続ページに印刷し、小計・繰越は送る前までの累計
+        assert_eq!(continuation.len(), 1);
+        assert_eq!(report.continuation_pages, 1);
+        assert_eq!(text_count(&ops, "一行目"), 0);
+        let last_row_y = RYOHI_START_Y + layout.sub_line_offset(13) + 6.0;
+        assert!((text_position(&ops, SUBTOTAL_LABEL).unwrap() - last_row_y).abs() < 0.01);
+        assert!((text_position(&ops, "12,000").unwrap() - last_row_y).abs() < 0.01);
+        let page = &continuation[0];
+        assert_eq!(text_count(page, "12,000"), 1);
+        for line in ["一行目", "二行目", "三行目"] {
+            assert_eq!(text_count(page, line), 1);
+        }
+        let second_row_y = RYOHI_START_Y + layout.sub_line_offset(1) + 6.0;
+        assert!((text_position(page, "一行目").unwrap() - second_row_y).abs() < 0.01);
+
+        // 1ページに収まらない項目は送らない
+        let long = Ryohi {
+            detail: vec![(1..=14).map(|n| format!("行{}", n)).collect::<Vec<_>>().join("\n")],
+            price: Some(500),
+            ..Default::default()
+        };
+        let mut ryohi = vec![ryohi[0].clone(); 12];
+        ryohi.push(long);
+        let mut ops = Vec::new();
+        let mut report = GenerationReport::default();
+        client.renderer(&fonts).add_ryohi_items(&mut ops, &ryohi, true, &mut report);
+        assert_eq!(text_count(&ops, "行1"), 1);
+    }
+
+    #[test]
+    fn test_continuation_total_printed_once() {
+        let item = Item {
+            price: 20000,
+            ryohi: vec![
+                Ryohi {
+                    price: Some(1000),
+                    ..Default::default()
+                };
+                20
+            ],
+            ..Default::default()
+        };
+        let fonts = FontSet::single(FontId("test".to_string()));
+
+        let single = ReportLabStylePdfClient::new().render_item_pages(&fonts, &item);
+        assert_eq!(single.len(), 1);
+
+        let client = ReportLabStylePdfClient::new()
+            .with_layout(LayoutConfig::default().with_continuation_pages(true));
+        let pages = client.render_item_pages(&fonts, &item);
+        assert_eq!(pages.len(), 2);
+        assert_eq!(text_count(&pages[0], "20,000"), 1);
+        assert_eq!(text_count(&pages[1], "20,000"), 0);
+        assert_eq!(text_count(&pages[1], TITLE), 1);
+    }
+
+    #[test]
+    fn test_title_uses_character_spacing() {
+        let client = ReportLabStylePdfClient::new();
+        let fonts = FontSet::single(FontId("test".to_string()));
+        let ops = client.render_item_ops_with_fonts(&fonts, &Item::default());
+
+        let title_pos = ops
+            .iter()
+            .position(|op| matches!(op, Op::WriteText { items, .. } if items == &vec![TextItem::Text(TITLE.to_string())]))
+            .expect("タイトルが描画されていない");
+        let spacing = mm_to_pt(DEFAULT_TITLE_TRACKING_MM);
+        assert!(matches!(ops[title_pos - 1], Op::SetCharacterSpacing { multiplier } if multiplier == spacing));
+        assert!(matches!(ops[title_pos + 1], Op::SetCharacterSpacing { multiplier } if multiplier == 0.0));
+        assert!(!TITLE.contains(' '));
     }
 }
@@ -0,0 +1,93 @@
+//! ページ操作の解釈
+//!
+//! printpdfのページ操作（`Op`）の列を順に解釈し、変換行列とテキストの状態を追跡する。
+//! 検証用ヘルパー（`testing`）とSVGへの変換（`svg`）で共通に使用する
+
+use printpdf::Op;
+
+/// 単位行列
+const IDENTITY: [f32; 6] = [1.0, 0.0, 0.0, 1.0, 0.0, 0.0];
+
+/// 操作列の解釈中のグラフィックス・テキスト状態
+#[derive(Debug)]
+pub(crate) struct State {
+    /// 現在の変換行列
+    ctm: [f32; 6],
+    /// `SaveGraphicsState` で保存した変換行列
+    saved: Vec<[f32; 6]>,
+    /// 現在行の開始位置 (pt、テキスト空間)
+    pub(crate) line: (f32, f32),
+    /// 行送り (pt)
+    leading: f32,
+    /// フォントサイズ (pt)
+    pub(crate) font_size: f32,
+    /// 文字間隔 (pt)
+    pub(crate) char_spacing: f32,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            ctm: IDENTITY,
+            saved: Vec::new(),
+            line: (0.0, 0.0),
+            leading: 0.0,
+            font_size: 0.0,
+            char_spacing: 0.0,
+        }
+    }
+}
+
+impl State {
+    /// 描画以外の操作を状態に反映
+    pub(crate) fn apply(&mut self, op: &Op) {
+        match op {
+            Op::SaveGraphicsState => self.saved.push(self.ctm),
+            Op::RestoreGraphicsState => self.ctm = self.saved.pop().unwrap_or(IDENTITY),
+            Op::SetTransformationMatrix { matrix } => self.ctm = concat(matrix.as_array(), self.ctm),
+            Op::StartTextSection => self.line = (0.0, 0.0),
+            Op::SetTextCursor { pos } => {
+                self.line = (self.line.0 + pos.x.0, self.line.1 + pos.y.0);
+            }
+            Op::MoveTextCursorAndSetLeading { tx, ty } => {
+                self.line = (self.line.0 + tx, self.line.1 + ty);
+                self.leading = -ty;
+            }
+            Op::SetTextMatrix { matrix } => {
+                let m = matrix.as_array();
+                self.line = (m[4], m[5]);
+            }
+            Op::AddLineBreak => self.line.1 -= self.leading,
+            Op::SetLineHeight { lh } => self.leading = lh.0,
+            Op::SetFontSize { size, .. } => self.font_size = size.0,
+            Op::SetCharacterSpacing { multiplier } => self.char_spacing = *multiplier,
+            _ => {}
+        }
+    }
+
+    /// テキスト空間・ユーザー空間の点をページ座標 (pt) に変換
+    pub(crate) fn transform(&self, (x, y): (f32, f32)) -> (f32, f32) {
+        let m = self.ctm;
+        (m[0] * x + m[2] * y + m[4], m[1] * x + m[3] * y + m[5])
+    }
+
+    /// 変換行列の拡大率（線の太さ・文字の大きさの換算に使用）
+    pub(crate) fn scale(&self) -> f32 {
+        let m = self.ctm;
+        (m[0] * m[3] - m[1] * m[2]).abs().sqrt()
+    }
+}
+
+/// `inner` を適用してから `outer` を適用する変換行列（PDFの `cm` 演算子と同じ順序）
+fn concat(inner: [f32; 6], outer: [f32; 6]) -> [f32; 6] {
+    let [a, b, c, d, e, f] = inner;
+    let [oa, ob, oc, od, oe, of] = outer;
+    [
+        a * oa + b * oc,
+        a * ob + b * od,
+        c * oa + d * oc,
+        c * ob + d * od,
+        e * oa + f * oc + oe,
+        e * ob + f * od + of,
+    ]
+}
@@ -2,66 +2,889 @@
 //!
 //! Go版のreportlab_style_pdf.goから移植した座標定数
 
+use crate::error::PdfError;
+
 /// A5横サイズ (mm)
 pub const A5_WIDTH: f32 = 210.0;
 pub const A5_HEIGHT: f32 = 148.0;
 
-/// マージン (mm)
-pub const MARGIN_LEFT: f32 = 10.0;
-pub const MARGIN_TOP: f32 = 138.0;
-pub const MARGIN_RIGHT: f32 = 200.0;
-pub const MARGIN_BOTTOM: f32 = 10.0;
-
-/// テーブル列幅 (mm)
-pub const COL_WIDTH_DATE: f32 = 15.0;      // 月日
-pub const COL_WIDTH_DEST: f32 = 14.0;      // 行先
-pub const COL_WIDTH_DETAIL: f32 = 26.0;    // 摘要
-pub const COL_WIDTH_KUKAN: f32 = 57.5;     // 区間
-pub const COL_WIDTH_PRICE: f32 = 22.0;     // 金額
-pub const COL_WIDTH_VOL: f32 = 13.0;       // 数量
-
-/// テーブル列のX座標 (左端からの累積)
-pub const COL_X_DATE: f32 = MARGIN_LEFT;
-pub const COL_X_DEST: f32 = COL_X_DATE + COL_WIDTH_DATE;
-pub const COL_X_DETAIL: f32 = COL_X_DEST + COL_WIDTH_DEST;
-pub const COL_X_KUKAN: f32 = COL_X_DETAIL + COL_WIDTH_DETAIL;
-pub const COL_X_PRICE: f32 = COL_X_KUKAN + COL_WIDTH_KUKAN;
-pub const COL_X_VOL: f32 = COL_X_PRICE + COL_WIDTH_PRICE;
-
-/// 行の高さ (mm)
-pub const ROW_HEIGHT: f32 = 5.5;
-pub const HEADER_ROW_HEIGHT: f32 = 6.0;
-
-/// フォントサイズ (pt)
-pub const FONT_SIZE_TITLE: f32 = 14.0;
-pub const FONT_SIZE_HEADER: f32 = 10.0;
-pub const FONT_SIZE_BODY: f32 = 8.0;
-pub const FONT_SIZE_SMALL: f32 = 6.0;
+/// A4横サイズ (mm)（2面付け用）
+pub const A4_LANDSCAPE_WIDTH: f32 = 297.0;
+pub const A4_LANDSCAPE_HEIGHT: f32 = 210.0;
 
-/// 承認欄サイズ (mm)
-pub const APPROVAL_WIDTH: f32 = 20.0;
-pub const APPROVAL_HEIGHT: f32 = 15.0;
+/// データ表の上端 (mm)
+///
+/// 列の位置・行の高さ・フォントサイズ・承認欄・備考欄の寸法は `LayoutConfig` で設定する
+pub const DATA_TABLE_TOP: f32 = 45.0;
 
-/// 承認欄のX座標 (右揃え)
-pub const APPROVAL_X_PRESIDENT: f32 = MARGIN_RIGHT - APPROVAL_WIDTH;
-pub const APPROVAL_X_ACCOUNTING: f32 = APPROVAL_X_PRESIDENT - APPROVAL_WIDTH;
-pub const APPROVAL_X_DEPARTMENT: f32 = APPROVAL_X_ACCOUNTING - APPROVAL_WIDTH;
+/// データ表・備考欄の左端 (mm)
+pub const DATA_TABLE_LEFT: f32 = 10.0;
 
-/// 基本情報テーブルのY座標
-pub const INFO_TABLE_Y: f32 = 125.0;
+/// データ表の見出し行の高さ (mm)
+pub const DATA_TABLE_HEADER_HEIGHT: f32 = 4.0;
 
-/// データテーブルのY座標開始位置
-pub const DATA_TABLE_Y_START: f32 = 105.0;
+/// タイトルのフォントサイズ (pt)
+pub const FONT_SIZE_TITLE: f32 = 14.0;
 
 /// 1ページあたりの最大データ行数
 pub const MAX_DATA_ROWS_PER_PAGE: usize = 7;
 
-/// 摘要の最大文字数
+/// データ表（ヘッダーを除く）の高さ (mm)
+pub const DATA_TABLE_BODY_HEIGHT: f32 = 70.0;
+
+/// 摘要の最大文字数（標準の摘要欄の幅の場合）
 pub const MAX_DETAIL_LENGTH: usize = 10;
 
-/// 区間の最大文字数
+/// 区間の最大文字数（標準の区間欄の幅の場合）
 pub const MAX_KUKAN_LENGTH: usize = 22;
 
+/// 標準の摘要欄の幅 (mm)
+pub const DETAIL_COLUMN_WIDTH: f32 = 40.0;
+
+/// 標準の区間欄の幅 (mm)
+pub const KUKAN_COLUMN_WIDTH: f32 = 30.0;
+
+/// 枠に収まらないテキストの扱い
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CellOverflow {
+    /// 最小フォントサイズまで縮小し、それでも収まらない部分は切り詰める
+    #[default]
+    Shrink,
+    /// 縮小せずに収まらない部分を切り詰める（末尾に「…」）
+    Truncate,
+}
+
+/// 縮小時の最小フォントサイズ (pt)
+pub const MIN_FIT_FONT_SIZE: f32 = 6.0;
+
+/// 線の太さの上限 (pt)
+pub const MAX_LINE_THICKNESS: f32 = 5.0;
+
+/// 摘要・区間欄の旅費項目間の区切り線
+///
+/// 線の太さ・破線は `LineStyles::separator` で設定する
+#[derive(Debug, Clone, PartialEq)]
+pub struct EntrySeparator {
+    /// 区切り線を描画するか
+    pub enabled: bool,
+    /// 破線の線の長さ (pt)
+    #[deprecated(since = "0.1.0", note = "`LineStyles::separator` を使用")]
+    pub dash: i64,
+    /// 破線の間隔 (pt)
+    #[deprecated(since = "0.1.0", note = "`LineStyles::separator` を使用")]
+    pub gap: i64,
+    /// 線の太さ (pt)
+    #[deprecated(since = "0.1.0", note = "`LineStyles::separator` を使用")]
+    pub thickness: f32,
+}
+
+#[allow(deprecated)]
+impl Default for EntrySeparator {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            dash: 2,
+            gap: 2,
+            thickness: 0.1,
+        }
+    }
+}
+
+impl EntrySeparator {
+    /// 非推奨のフィールドで指定した線（既定値のままの場合はNone）
+    #[allow(deprecated)]
+    fn legacy_style(&self) -> Option<LineStyle> {
+        let default = Self::default();
+        (self.dash != default.dash || self.gap != default.gap || self.thickness != default.thickness)
+            .then_some(LineStyle::dashed(self.thickness, self.dash, self.gap))
+    }
+}
+
+/// 線の太さと破線
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LineStyle {
+    /// 線の太さ (pt)
+    pub thickness: f32,
+    /// 破線の線の長さと間隔 (pt)。Noneの場合は実線
+    pub dash: Option<(i64, i64)>,
+}
+
+impl LineStyle {
+    /// 実線
+    pub const fn solid(thickness: f32) -> Self {
+        Self { thickness, dash: None }
+    }
+
+    /// 破線
+    pub const fn dashed(thickness: f32, dash: i64, gap: i64) -> Self {
+        Self {
+            thickness,
+            dash: Some((dash, gap)),
+        }
+    }
+}
+
+/// 描画する要素ごとの線の太さと破線
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LineStyles {
+    /// 外枠
+    pub outer_frame: LineStyle,
+    /// 承認欄・基本情報・データ表・備考欄の罫線
+    pub table_grid: LineStyle,
+    /// タイトルの下線
+    pub title_underline: LineStyle,
+    /// 旅費項目間の区切り線（`EntrySeparator` が有効な場合のみ描画）
+    pub separator: LineStyle,
+}
+
+impl Default for LineStyles {
+    fn default() -> Self {
+        Self {
+            outer_frame: LineStyle::solid(0.5),
+            table_grid: LineStyle::solid(0.2),
+            title_underline: LineStyle::solid(0.3),
+            separator: LineStyle::dashed(0.1, 2, 2),
+        }
+    }
+}
+
+impl LineStyles {
+    /// 全ての要素（名前, 線）
+    fn entries(&self) -> [(&'static str, LineStyle); 4] {
+        [
+            ("外枠", self.outer_frame),
+            ("罫線", self.table_grid),
+            ("タイトルの下線", self.title_underline),
+            ("区切り線", self.separator),
+        ]
+    }
+
+    /// 外枠・罫線・タイトルの下線に破線があるか
+    ///
+    /// 破線はグラフィックス状態に残るため、ある場合は実線の要素で破線を解除する
+    /// （区切り線はグラフィックス状態を保存して描画するため含めない）
+    pub fn has_dashed_frame(&self) -> bool {
+        [self.outer_frame, self.table_grid, self.title_underline]
+            .iter()
+            .any(|style| style.dash.is_some())
+    }
+}
+
+/// データ表の行の網掛け
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Shading {
+    /// 塗りつぶしの色（RGB、各0.0〜1.0）
+    pub color_rgb: [f32; 3],
+    /// 網掛けする行の間隔（2の場合は2・4・6行目）
+    pub every_n_rows: usize,
+}
+
+impl Default for Shading {
+    fn default() -> Self {
+        Self {
+            color_rgb: [0.9, 0.9, 0.9],
+            every_n_rows: 2,
+        }
+    }
+}
+
+/// RGBの色（各0.0〜1.0）
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct RgbColor {
+    /// 赤
+    pub r: f32,
+    /// 緑
+    pub g: f32,
+    /// 青
+    pub b: f32,
+}
+
+impl RgbColor {
+    /// 黒
+    pub const BLACK: RgbColor = RgbColor::new(0.0, 0.0, 0.0);
+    /// 赤（負の金額の印刷に使用）
+    pub const RED: RgbColor = RgbColor::new(1.0, 0.0, 0.0);
+
+    /// 色を作成
+    pub const fn new(r: f32, g: f32, b: f32) -> Self {
+        Self { r, g, b }
+    }
+
+    /// 各成分が0.0〜1.0の範囲か
+    pub fn is_valid(&self) -> bool {
+        [self.r, self.g, self.b].iter().all(|c| (0.0..=1.0).contains(c))
+    }
+}
+
+/// 罫線・文字の色（標準は全て黒）
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ColorScheme {
+    /// 外枠・表の罫線
+    pub grid: RgbColor,
+    /// 見出し・明細の文字
+    pub text: RgbColor,
+    /// タイトル
+    pub title: RgbColor,
+    /// 負の金額（例: 赤字の合計を赤で印刷する）
+    pub negative_amount: RgbColor,
+}
+
+impl ColorScheme {
+    /// 全ての色（用途, 色）
+    fn entries(&self) -> [(&'static str, RgbColor); 4] {
+        [
+            ("罫線", self.grid),
+            ("文字", self.text),
+            ("タイトル", self.title),
+            ("負の金額", self.negative_amount),
+        ]
+    }
+}
+
+/// 外枠の余白 (mm)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Margins {
+    /// 左
+    pub left: f32,
+    /// 上
+    pub top: f32,
+    /// 右
+    pub right: f32,
+    /// 下
+    pub bottom: f32,
+}
+
+impl Margins {
+    /// 標準の余白（外枠の描画位置）
+    pub const DEFAULT: Margins = Margins {
+        left: 10.0,
+        top: 15.0,
+        right: 10.0,
+        bottom: 10.0,
+    };
+
+    /// 外枠の幅 (mm)
+    pub fn content_width(&self) -> f32 {
+        A5_WIDTH - self.left - self.right
+    }
+
+    /// 外枠の高さ (mm)
+    pub fn content_height(&self) -> f32 {
+        A5_HEIGHT - self.top - self.bottom
+    }
+}
+
+impl Default for Margins {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// 備考・計テーブルの寸法 (mm)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SummaryTable {
+    /// 行の高さ
+    pub row_height: f32,
+    /// 備考欄の幅
+    pub remarks_width: f32,
+    /// 計欄の幅
+    pub total_width: f32,
+    /// データ表の下端との間隔
+    pub gap: f32,
+}
+
+impl SummaryTable {
+    /// 標準の寸法（データ表の直下から外枠の下端まで）
+    pub const DEFAULT: SummaryTable = SummaryTable {
+        row_height: 19.0,
+        remarks_width: 145.0,
+        total_width: 45.0,
+        gap: 0.0,
+    };
+
+    /// 表全体の幅 (mm)
+    pub fn width(&self) -> f32 {
+        self.remarks_width + self.total_width
+    }
+}
+
+impl Default for SummaryTable {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// デフォルトの承認欄の見出し
+pub const DEFAULT_APPROVAL_LABELS: &[&str] = &["社　長", "会　計", "所　属"];
+
+/// 承認欄1列の標準の幅 (mm)
+pub const APPROVAL_COL_WIDTH: f32 = 15.0;
+
+/// 承認欄全体の最大幅 (mm)
+pub const APPROVAL_TABLE_MAX_WIDTH: f32 = 45.0;
+
+/// 行間（フォントサイズに対する倍率）
+pub const LINE_SPACING: f32 = 1.2;
+
+/// 継続ページがある場合にページ最終行に印刷する見出し
+pub const SUBTOTAL_LABEL: &str = "小計";
+
+/// 継続ページの先頭行に印刷する見出し
+pub const CARRY_FORWARD_LABEL: &str = "繰越";
+
+/// 精算書のタイトル
+pub const TITLE: &str = "出張旅費日当駐車料込精算書";
+
+/// タイトルの標準の字間 (mm)。半角スペース1文字分に相当
+pub const DEFAULT_TITLE_TRACKING_MM: f32 = 2.5;
+
+/// 交通機関の列に印刷する旅費項目の独自データのキー（`Ryohi::custom`）
+pub const TRANSPORT_KEY: &str = "transport";
+
+/// データ表の列の文字揃え
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum ColumnAlign {
+    /// 左揃え（左端から1mm）
+    #[default]
+    Left,
+    /// 右揃え（右端から1mm）
+    Right,
+    /// 左端から指定した位置 (mm) に左揃え（金額の桁位置を揃える場合など）
+    Offset(f32),
+}
+
+/// データ表の列に印刷する旅費項目のデータ
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ColumnSource {
+    /// 日付
+    Date,
+    /// 行先
+    Dest,
+    /// 摘要（折り返して複数行に印刷）
+    Detail,
+    /// 区間（折り返して複数行に印刷）
+    Kukan,
+    /// 金額
+    Price,
+    /// 数量
+    Vol,
+    /// 計（単価×数量）
+    LineTotal,
+    /// 交通機関（独自データの `TRANSPORT_KEY`）
+    Transport,
+    /// 独自データ（`Ryohi::custom` のキー）
+    Custom(String),
+    /// 印刷しない（手書き用の空欄）
+    Blank,
+}
+
+impl ColumnSource {
+    /// 数値用のフォントで印刷するか
+    pub fn is_numeric(&self) -> bool {
+        matches!(self, ColumnSource::Price | ColumnSource::Vol | ColumnSource::LineTotal)
+    }
+
+    /// 金額の列か（負の金額は `ColorScheme::negative_amount` の色で印刷する）
+    pub fn is_amount(&self) -> bool {
+        matches!(self, ColumnSource::Price | ColumnSource::LineTotal)
+    }
+}
+
+/// データ表の列の定義
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnSpec {
+    /// 列の識別子
+    pub key: String,
+    /// 見出し
+    pub header: String,
+    /// 列幅 (mm)
+    pub width_mm: f32,
+    /// 文字揃え
+    pub align: ColumnAlign,
+    /// 印刷するデータ
+    pub source: ColumnSource,
+}
+
+impl ColumnSpec {
+    /// 新しい列を作成（左揃え）
+    pub fn new(key: impl Into<String>, header: impl Into<String>, width_mm: f32, source: ColumnSource) -> Self {
+        Self {
+            key: key.into(),
+            header: header.into(),
+            width_mm,
+            align: ColumnAlign::Left,
+            source,
+        }
+    }
+
+    /// 文字揃えを設定
+    pub fn with_align(mut self, align: ColumnAlign) -> Self {
+        self.align = align;
+        self
+    }
+}
+
+/// 標準のデータ表の列（日付・行先・摘要・区間・交通機関・運賃・特別料金・旅費日当・計）
+pub fn default_columns() -> Vec<ColumnSpec> {
+    vec![
+        ColumnSpec::new("date", "日付", 10.0, ColumnSource::Date),
+        ColumnSpec::new("dest", "行　先", 17.0, ColumnSource::Dest),
+        ColumnSpec::new("detail", "摘　　要", DETAIL_COLUMN_WIDTH, ColumnSource::Detail),
+        ColumnSpec::new("kukan", "区　　間", KUKAN_COLUMN_WIDTH, ColumnSource::Kukan),
+        ColumnSpec::new("transport", "交通機関", 15.0, ColumnSource::Transport),
+        ColumnSpec::new("fare", "運　賃", 15.0, ColumnSource::Blank),
+        ColumnSpec::new("special", "特別料金", 15.0, ColumnSource::Blank),
+        ColumnSpec::new("allowance", "旅費日当", 25.0, ColumnSource::Price).with_align(ColumnAlign::Offset(10.0)),
+        ColumnSpec::new("total", "計", 23.0, ColumnSource::LineTotal).with_align(ColumnAlign::Right),
+    ]
+}
+
+/// 面付け
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Imposition {
+    /// A5横に1面
+    #[default]
+    OneUp,
+    /// A4横に2面（A5の精算書を90度回転して左右に配置、奇数ページの最後はA5に1面）
+    TwoUpA4,
+}
+
+/// レイアウト設定
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayoutConfig {
+    /// 旅費項目間の区切り線
+    pub entry_separator: EntrySeparator,
+    /// データ表の1行の高さ (mm)。1行に2行分の明細を印刷する
+    pub row_height: f32,
+    /// 明細のフォントサイズ (pt)
+    pub body_font_size: f32,
+    /// 外枠の余白。標準より広い場合は印刷内容を縮小して収める
+    pub margins: Margins,
+    /// 承認欄の見出し（左から順に）
+    pub approval_labels: Vec<String>,
+    /// タイトルの字間 (mm)
+    pub title_tracking_mm: f32,
+    /// 1ページに収まらない旅費データを継続ページに印刷するか（無効の場合は印刷せずに欠落として記録）
+    pub continuation_pages: bool,
+    /// 面付け
+    pub imposition: Imposition,
+    /// データ表の列（左から順に）
+    pub columns: Vec<ColumnSpec>,
+    /// データ表の行の網掛け（Noneの場合は網掛けしない）
+    pub row_shading: Option<Shading>,
+    /// 罫線・文字の色
+    pub colors: ColorScheme,
+    /// 要素ごとの線の太さと破線
+    pub line_styles: LineStyles,
+    /// データ表の列を右から順に配置するか（左右反転の様式用、列と印刷するデータの対応は変わらない）
+    pub reverse_columns: bool,
+    /// 基本情報欄（出張目的・氏名）・所属・清算日の収まらないテキストの扱い
+    pub cell_overflow: CellOverflow,
+    /// 氏名を1行に収めるために縮小する最小のフォントサイズ (pt)（既定は `MIN_FIT_FONT_SIZE`、Noneの場合は縮小せずに折り返す）
+    pub name_min_font_size: Option<f32>,
+    /// 領収書の枚数（`Item::receipt_count`）を印刷する位置（左端のX座標, ベースラインのY座標、mm）
+    ///
+    /// Noneの場合は備考欄の左下
+    pub receipt_count_position: Option<(f32, f32)>,
+    /// 備考・計テーブルの寸法
+    pub summary_table: SummaryTable,
+}
+
+impl Default for LayoutConfig {
+    fn default() -> Self {
+        Self {
+            entry_separator: EntrySeparator::default(),
+            row_height: 10.0,
+            body_font_size: 10.0,
+            margins: Margins::DEFAULT,
+            approval_labels: DEFAULT_APPROVAL_LABELS.iter().map(|s| s.to_string()).collect(),
+            title_tracking_mm: DEFAULT_TITLE_TRACKING_MM,
+            continuation_pages: false,
+            imposition: Imposition::OneUp,
+            columns: default_columns(),
+            row_shading: None,
+            colors: ColorScheme::default(),
+            line_styles: LineStyles::default(),
+            reverse_columns: false,
+            cell_overflow: CellOverflow::Shrink,
+            name_min_font_size: Some(MIN_FIT_FONT_SIZE),
+            receipt_count_position: None,
+            summary_table: SummaryTable::DEFAULT,
+        }
+    }
+}
+
+impl LayoutConfig {
+    /// 新しいレイアウト設定を作成
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// データ表の1行の高さを設定
+    pub fn with_row_height(mut self, row_height: f32) -> Self {
+        self.row_height = row_height;
+        self
+    }
+
+    /// 明細のフォントサイズを設定
+    pub fn with_body_font_size(mut self, size: f32) -> Self {
+        self.body_font_size = size;
+        self
+    }
+
+    /// 承認欄の見出しを設定（例: 部長・課長・担当）
+    pub fn with_approval_labels(mut self, labels: Vec<String>) -> Self {
+        self.approval_labels = labels;
+        self
+    }
+
+    /// タイトルの字間を設定 (mm)
+    pub fn with_title_tracking(mut self, tracking_mm: f32) -> Self {
+        self.title_tracking_mm = tracking_mm;
+        self
+    }
+
+    /// 1ページに収まらない旅費データを継続ページに印刷するかを設定
+    pub fn with_continuation_pages(mut self, enabled: bool) -> Self {
+        self.continuation_pages = enabled;
+        self
+    }
+
+    /// 面付けを設定
+    pub fn with_imposition(mut self, imposition: Imposition) -> Self {
+        self.imposition = imposition;
+        self
+    }
+
+    /// データ表の列を設定
+    pub fn with_columns(mut self, columns: Vec<ColumnSpec>) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    /// データ表の行の網掛けを設定
+    pub fn with_row_shading(mut self, shading: Shading) -> Self {
+        self.row_shading = Some(shading);
+        self
+    }
+
+    /// 罫線・文字の色を設定
+    pub fn with_colors(mut self, colors: ColorScheme) -> Self {
+        self.colors = colors;
+        self
+    }
+
+    /// 要素ごとの線の太さと破線を設定
+    pub fn with_line_styles(mut self, line_styles: LineStyles) -> Self {
+        self.line_styles = line_styles;
+        self
+    }
+
+    /// データ表の列を右から順に配置するかを設定
+    pub fn with_reverse_columns(mut self, reverse: bool) -> Self {
+        self.reverse_columns = reverse;
+        self
+    }
+
+    /// 基本情報欄の折り返しても収まらないテキストの扱いを設定
+    pub fn with_cell_overflow(mut self, overflow: CellOverflow) -> Self {
+        self.cell_overflow = overflow;
+        self
+    }
+
+    /// 氏名を縮小して1行に収める場合の最小のフォントサイズ (pt) を設定
+    ///
+    /// 最小サイズでも氏名欄に収まらない場合は折り返す（`cell_overflow` に従う）
+    pub fn with_name_min_font_size(mut self, size: f32) -> Self {
+        self.name_min_font_size = Some(size);
+        self
+    }
+
+    /// 領収書の枚数を印刷する位置を設定（左端のX座標, ベースラインのY座標、mm）
+    ///
+    /// 既定は備考欄の左下。外枠の余白を変更した場合は他の内容と同様に縮小される
+    pub fn with_receipt_count_position(mut self, x: f32, y: f32) -> Self {
+        self.receipt_count_position = Some((x, y));
+        self
+    }
+
+    /// 領収書の枚数を印刷する位置（未設定の場合は備考欄の左下）
+    pub fn receipt_count_origin(&self) -> (f32, f32) {
+        self.receipt_count_position
+            .unwrap_or((DATA_TABLE_LEFT + 2.0, self.summary_table_top() + self.summary_table.row_height - 3.0))
+    }
+
+    /// 備考・計テーブルの寸法を設定
+    pub fn with_summary_table(mut self, table: SummaryTable) -> Self {
+        self.summary_table = table;
+        self
+    }
+
+    /// データ表の下端のY座標 (mm)（見出しと最大行数分の行を含む）
+    pub fn data_table_bottom(&self) -> f32 {
+        DATA_TABLE_TOP + DATA_TABLE_HEADER_HEIGHT + self.row_height * MAX_DATA_ROWS_PER_PAGE as f32
+    }
+
+    /// 備考・計テーブルの上端のY座標 (mm)
+    pub fn summary_table_top(&self) -> f32 {
+        self.data_table_bottom() + self.summary_table.gap
+    }
+
+    /// データ表の見出しを列の順にまとめて設定（例: 英語の見出し）
+    ///
+    /// 各列の `ColumnSpec::header` を置き換える。見出しの数が列の数と一致しない場合はエラー
+    pub fn with_header_labels(mut self, labels: Vec<String>) -> Result<Self, PdfError> {
+        if labels.len() != self.columns.len() {
+            return Err(PdfError::Config(format!(
+                "データ表の見出しの数（{}）が列の数（{}）と一致しません",
+                labels.len(),
+                self.columns.len()
+            )));
+        }
+        for (column, label) in self.columns.iter_mut().zip(labels) {
+            column.header = label;
+        }
+        Ok(self)
+    }
+
+    /// 左から配置する順の列（`reverse_columns` の場合は逆順）
+    pub fn placed_columns(&self) -> Vec<&ColumnSpec> {
+        let mut columns: Vec<&ColumnSpec> = self.columns.iter().collect();
+        if self.reverse_columns {
+            columns.reverse();
+        }
+        columns
+    }
+
+    /// 配置した列の左端のデータ表の左端からの距離 (mm)
+    pub fn column_left(&self, index: usize) -> f32 {
+        if self.reverse_columns {
+            self.columns_width() - self.column_offset(index + 1)
+        } else {
+            self.column_offset(index)
+        }
+    }
+
+    /// 配置した列の右端のデータ表の左端からの距離 (mm)
+    pub fn column_right(&self, index: usize) -> f32 {
+        if self.reverse_columns {
+            self.columns_width() - self.column_offset(index)
+        } else {
+            self.column_offset(index + 1)
+        }
+    }
+
+    /// データ表の幅 (mm)
+    pub fn columns_width(&self) -> f32 {
+        self.columns.iter().map(|column| column.width_mm).sum()
+    }
+
+    /// 列の左端のデータ表の左端からの距離 (mm)
+    ///
+    /// 列数以上の位置はデータ表の右端（列の構成を変更しても範囲外の参照でパニックしない）
+    pub fn column_offset(&self, index: usize) -> f32 {
+        self.columns.iter().take(index).map(|column| column.width_mm).sum()
+    }
+
+    /// 指定したデータを印刷する最初の列の位置
+    pub fn column_index(&self, source: &ColumnSource) -> Option<usize> {
+        self.columns.iter().position(|column| column.source == *source)
+    }
+
+    /// 摘要の1行の最大文字数（摘要欄の幅に比例、標準の幅で `MAX_DETAIL_LENGTH`）
+    pub fn detail_max_len(&self) -> usize {
+        self.max_len_for(&ColumnSource::Detail, MAX_DETAIL_LENGTH, DETAIL_COLUMN_WIDTH)
+    }
+
+    /// 区間の1行の最大文字数（区間欄の幅に比例、標準の幅で `MAX_KUKAN_LENGTH`）
+    pub fn kukan_max_len(&self) -> usize {
+        self.max_len_for(&ColumnSource::Kukan, MAX_KUKAN_LENGTH, KUKAN_COLUMN_WIDTH)
+    }
+
+    /// 列幅に比例した1行の最大文字数（1文字以上、列が無い場合は標準の文字数）
+    fn max_len_for(&self, source: &ColumnSource, default_len: usize, default_width: f32) -> usize {
+        match self.column_index(source) {
+            Some(index) => ((self.columns[index].width_mm / default_width * default_len as f32).floor() as usize).max(1),
+            None => default_len,
+        }
+    }
+
+    /// 承認欄1列の幅 (mm)
+    ///
+    /// 列数が多い場合は承認欄全体の最大幅に収まるよう狭める
+    pub fn approval_col_width(&self) -> f32 {
+        let count = self.approval_labels.len().max(1) as f32;
+        APPROVAL_COL_WIDTH.min(APPROVAL_TABLE_MAX_WIDTH / count)
+    }
+
+    /// 外枠の余白を設定
+    pub fn with_margins(mut self, margins: Margins) -> Self {
+        self.margins = margins;
+        self
+    }
+
+    /// 標準の余白で配置した内容を設定した余白に収めるための変換行列
+    ///
+    /// 縦横比を保ったまま縮小（拡大）し、外枠の左上を設定した余白の位置に合わせる。
+    /// 標準の余白の場合はNone
+    pub fn content_matrix(&self) -> Option<[f32; 6]> {
+        if self.margins == Margins::DEFAULT {
+            return None;
+        }
+
+        let default = Margins::DEFAULT;
+        let scale = (self.margins.content_width() / default.content_width())
+            .min(self.margins.content_height() / default.content_height());
+
+        // PDF座標（左下原点）での外枠左上の位置を合わせる
+        let tx = self.margins.left - default.left * scale;
+        let ty = (A5_HEIGHT - self.margins.top) - (A5_HEIGHT - default.top) * scale;
+        Some([scale, 0.0, 0.0, scale, mm_to_pt(tx), mm_to_pt(ty)])
+    }
+
+    /// 明細1行分の高さ (mm)
+    ///
+    /// 表の1行の半分を基本とし、フォントサイズに対して狭い場合は行間を広げる
+    pub fn sub_line_height(&self) -> f32 {
+        (self.row_height / 2.0).max(pt_to_mm(self.body_font_size) * LINE_SPACING)
+    }
+
+    /// 設定値がページに収まるか検証
+    ///
+    /// # Returns
+    /// 問題点の一覧（空なら問題なし）
+    pub fn issues(&self) -> Vec<String> {
+        let mut issues = Vec::new();
+
+        if self.row_height.is_nan() || self.row_height <= 0.0 {
+            issues.push(format!("行の高さが不正です: {}", self.row_height));
+        } else if self.row_height * MAX_DATA_ROWS_PER_PAGE as f32 > DATA_TABLE_BODY_HEIGHT {
+            issues.push(format!(
+                "行の高さ{}mmではデータ表（{}mm）に{}行収まりません",
+                self.row_height, DATA_TABLE_BODY_HEIGHT, MAX_DATA_ROWS_PER_PAGE
+            ));
+        }
+
+        if self.body_font_size.is_nan() || self.body_font_size <= 0.0 {
+            issues.push(format!("フォントサイズが不正です: {}", self.body_font_size));
+        } else if self.row_height > 0.0 && self.sub_line_height() * 2.0 > self.row_height {
+            issues.push(format!(
+                "フォントサイズ{}ptでは1行（{}mm）に明細が2行収まりません",
+                self.body_font_size, self.row_height
+            ));
+        }
+
+        let margins = &self.margins;
+        if [margins.left, margins.top, margins.right, margins.bottom]
+            .iter()
+            .any(|m| m.is_nan() || *m < 0.0)
+        {
+            issues.push(format!("余白が不正です: {:?}", margins));
+        } else if margins.content_width() <= 0.0 || margins.content_height() <= 0.0 {
+            issues.push(format!("余白が大きすぎてページに収まりません: {:?}", margins));
+        }
+
+        if self.columns.is_empty() {
+            issues.push("データ表の列がありません".to_string());
+        } else if let Some(column) = self.columns.iter().find(|c| c.width_mm.is_nan() || c.width_mm <= 0.0) {
+            issues.push(format!("列幅が不正です: {} ({})", column.key, column.width_mm));
+        } else if self.columns_width() > Margins::DEFAULT.content_width() + 0.01 {
+            issues.push(format!(
+                "データ表の幅{}mmが外枠（{}mm）に収まりません",
+                self.columns_width(),
+                Margins::DEFAULT.content_width()
+            ));
+        }
+
+        if let Some(size) = self.name_min_font_size {
+            if size.is_nan() || size <= 0.0 {
+                issues.push(format!("氏名の最小フォントサイズが不正です: {}", size));
+            }
+        }
+
+        if let Some(shading) = self.row_shading {
+            if shading.every_n_rows == 0 {
+                issues.push("網掛けの行の間隔は1以上を指定してください".to_string());
+            }
+            if shading.color_rgb.iter().any(|c| !(0.0..=1.0).contains(c)) {
+                issues.push(format!("網掛けの色が不正です: {:?}", shading.color_rgb));
+            }
+        }
+
+        for (name, color) in self.colors.entries() {
+            if !color.is_valid() {
+                issues.push(format!("{}の色が不正です: {:?}", name, color));
+            }
+        }
+
+        let line_styles = LineStyles {
+            separator: self.separator_style(),
+            ..self.line_styles
+        };
+        for (name, style) in line_styles.entries() {
+            if style.thickness.is_nan() || style.thickness <= 0.0 || style.thickness > MAX_LINE_THICKNESS {
+                issues.push(format!(
+                    "{}の線の太さが不正です（0より大きく{}pt以下）: {}",
+                    name, MAX_LINE_THICKNESS, style.thickness
+                ));
+            }
+            if let Some((dash, gap)) = style.dash {
+                if dash <= 0 || gap <= 0 {
+                    issues.push(format!("{}の破線が不正です: 線={}, 間隔={}", name, dash, gap));
+                }
+            }
+        }
+
+        let summary = &self.summary_table;
+        if [summary.row_height, summary.remarks_width, summary.total_width]
+            .iter()
+            .any(|v| v.is_nan() || *v <= 0.0)
+            || summary.gap.is_nan()
+            || summary.gap < 0.0
+        {
+            issues.push(format!("備考欄の寸法が不正です: {:?}", summary));
+        } else {
+            if summary.width() > Margins::DEFAULT.content_width() + 0.01 {
+                issues.push(format!(
+                    "備考欄の幅{}mmが外枠（{}mm）に収まりません",
+                    summary.width(),
+                    Margins::DEFAULT.content_width()
+                ));
+            }
+            // データ表が収まらない場合は行の高さの問題として報告済み
+            let frame_bottom = A5_HEIGHT - Margins::DEFAULT.bottom;
+            let bottom = self.summary_table_top() + summary.row_height;
+            if self.row_height * MAX_DATA_ROWS_PER_PAGE as f32 <= DATA_TABLE_BODY_HEIGHT && bottom > frame_bottom + 0.01 {
+                issues.push(format!("備考欄の下端{}mmが外枠の下端（{}mm）を超えています", bottom, frame_bottom));
+            }
+        }
+
+        if self.title_tracking_mm.is_nan() || self.title_tracking_mm < 0.0 {
+            issues.push(format!("タイトルの字間が不正です: {}", self.title_tracking_mm));
+        }
+
+        issues
+    }
+
+    /// 明細の論理行の上端からのオフセット (mm)
+    pub fn sub_line_offset(&self, logical_row: usize) -> f32 {
+        logical_row as f32 * self.sub_line_height()
+    }
+
+    /// 旅費項目間の区切り線を設定
+    pub fn with_entry_separator(mut self, separator: EntrySeparator) -> Self {
+        self.entry_separator = separator;
+        self
+    }
+
+    /// 旅費項目間の区切り線の太さと破線
+    ///
+    /// `EntrySeparator` の非推奨のフィールドを既定値から変更した場合はそちらを優先する
+    pub fn separator_style(&self) -> LineStyle {
+        self.entry_separator.legacy_style().unwrap_or(self.line_styles.separator)
+    }
+}
+
 /// ポイントをmmに変換
 pub fn pt_to_mm(pt: f32) -> f32 {
     pt * 0.352778
@@ -84,13 +907,293 @@ mod tests {
         assert!((mm - back_to_mm).abs() < 0.001);
     }
 
+    #[test]
+    fn test_sub_line_height_default() {
+        let layout = LayoutConfig::default();
+        assert_eq!(layout.sub_line_height(), 5.0);
+        assert_eq!(layout.sub_line_offset(3), 15.0);
+    }
+
+    #[test]
+    fn test_content_matrix() {
+        assert!(LayoutConfig::default().content_matrix().is_none());
+
+        let margins = Margins {
+            left: 20.0,
+            top: 20.0,
+            right: 20.0,
+            bottom: 20.0,
+        };
+        let matrix = LayoutConfig::new().with_margins(margins).content_matrix().unwrap();
+        let transform = |x: f32, y: f32| {
+            (
+                pt_to_mm(matrix[0] * mm_to_pt(x) + matrix[4]),
+                pt_to_mm(matrix[3] * mm_to_pt(y) + matrix[5]),
+            )
+        };
+
+        // 外枠の左上が余白の位置に移動し、右下が余白の内側に収まる
+        let (left, top) = transform(10.0, A5_HEIGHT - 15.0);
+        assert!((left - 20.0).abs() < 0.01);
+        assert!((top - (A5_HEIGHT - 20.0)).abs() < 0.01);
+        let (right, bottom) = transform(A5_WIDTH - 10.0, 10.0);
+        assert!(right <= A5_WIDTH - 20.0 + 0.01);
+        assert!(bottom >= 20.0 - 0.01);
+    }
+
+    #[test]
+    fn test_approval_col_width() {
+        assert_eq!(LayoutConfig::default().approval_col_width(), 15.0);
+
+        let labels = ["部長", "課長", "係長", "担当", "経理"].iter().map(|s| s.to_string()).collect();
+        let layout = LayoutConfig::new().with_approval_labels(labels);
+        assert_eq!(layout.approval_col_width(), 9.0);
+    }
+
+    #[test]
+    fn test_layout_issues() {
+        assert!(LayoutConfig::default().issues().is_empty());
+        assert_eq!(LayoutConfig::new().with_row_height(12.0).issues().len(), 1);
+        assert_eq!(LayoutConfig::new().with_row_height(0.0).issues().len(), 1);
+        assert_eq!(LayoutConfig::new().with_body_font_size(16.0).issues().len(), 1);
+        let margins = Margins {
+            left: 120.0,
+            right: 120.0,
+            ..Margins::DEFAULT
+        };
+        assert_eq!(LayoutConfig::new().with_margins(margins).issues().len(), 1);
+    }
+
+    #[test]
+    fn test_default_columns() {
+        let layout = LayoutConfig::default();
+        assert_eq!(layout.columns.len(), 9);
+        assert_eq!(layout.columns_width(), Margins::DEFAULT.content_width());
+        assert_eq!(layout.column_index(&ColumnSource::Kukan), Some(3));
+        assert_eq!(layout.column_offset(3), 67.0);
+        assert!(layout.column_index(&ColumnSource::Vol).is_none());
+    }
+
+    #[test]
+    fn test_max_len_from_column_width() {
+        let layout = LayoutConfig::default();
+        assert_eq!(layout.detail_max_len(), MAX_DETAIL_LENGTH);
+        assert_eq!(layout.kukan_max_len(), MAX_KUKAN_LENGTH);
+
+        // 列幅に比例（狭い列は1文字以上、列が無い場合は標準）
+        let mut columns = default_columns();
+        columns[2].width_mm = 60.0;
+        columns[3].width_mm = 15.0;
+        let layout = LayoutConfig::new().with_columns(columns.clone());
+        assert_eq!(layout.detail_max_len(), 15);
+        assert_eq!(layout.kukan_max_len(), 11);
+        columns[2].width_mm = 1.0;
+        columns.remove(3);
+        let layout = LayoutConfig::new().with_columns(columns);
+        assert_eq!(layout.detail_max_len(), 1);
+        assert_eq!(layout.kukan_max_len(), MAX_KUKAN_LENGTH);
+    }
+
+    #[test]
+    fn test_reverse_columns_positions() {
+        let layout = LayoutConfig::new().with_reverse_columns(true);
+        let width = layout.columns_width();
+        // 日付（10mm）は右端、計（23mm）は左端
+        assert_eq!(layout.column_left(0), width - 10.0);
+        assert_eq!(layout.column_right(0), width);
+        assert_eq!(layout.column_left(8), 0.0);
+        assert_eq!(layout.column_right(8), 23.0);
+        assert_eq!(layout.placed_columns()[0].source, ColumnSource::LineTotal);
+
+        let layout = LayoutConfig::default();
+        assert_eq!(layout.column_left(3), layout.column_offset(3));
+        assert_eq!(layout.column_right(3), layout.column_offset(4));
+        assert_eq!(layout.placed_columns()[0].source, ColumnSource::Date);
+    }
+
+    #[test]
+    fn test_column_positions_out_of_range() {
+        // 列を減らした場合も範囲外の位置はデータ表の右端
+        let layout = LayoutConfig::new().with_columns(default_columns().into_iter().take(3).collect());
+        let width = layout.columns_width();
+        assert_eq!(width, 67.0);
+        assert_eq!(layout.column_offset(8), width);
+        assert_eq!(layout.column_left(8), width);
+        assert_eq!(layout.column_right(8), width);
+
+        let layout = layout.with_reverse_columns(true);
+        assert_eq!(layout.column_left(8), 0.0);
+        assert_eq!(layout.column_right(8), 0.0);
+    }
+
+    #[test]
+    fn test_header_labels() {
+        let layout = LayoutConfig::default();
+        assert_eq!(layout.placed_columns()[0].header, "日付");
+        assert_eq!(layout.placed_columns()[8].header, "計");
+
+        let labels: Vec<String> = ["Date", "Destination", "Details", "Route", "Transport", "Fare", "Surcharge", "Per diem", "Total"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let layout = LayoutConfig::new().with_header_labels(labels.clone()).unwrap();
+        assert!(layout.issues().is_empty());
+        assert_eq!(layout.columns[8].header, "Total");
+        assert_eq!(layout.with_reverse_columns(true).placed_columns()[0].header, "Total");
+
+        // 見出しの数が列の数と一致しない
+        assert!(matches!(
+            LayoutConfig::new().with_header_labels(labels[..8].to_vec()),
+            Err(PdfError::Config(_))
+        ));
+    }
+
+    #[test]
+    fn test_summary_table_position() {
+        // 既定ではデータ表の直下から外枠の下端まで
+        let layout = LayoutConfig::default();
+        assert!((layout.data_table_bottom() - 119.0).abs() < 0.01);
+        assert!((layout.summary_table_top() + layout.summary_table.row_height - (A5_HEIGHT - Margins::DEFAULT.bottom)).abs() < 0.01);
+        assert_eq!(layout.receipt_count_origin(), (12.0, 135.0));
+
+        // 行の高さに合わせて移動する
+        let layout = LayoutConfig::new().with_row_height(9.0);
+        assert!((layout.summary_table_top() - 112.0).abs() < 0.01);
+        assert!((layout.receipt_count_origin().1 - 128.0).abs() < 0.01);
+
+        let table = SummaryTable {
+            gap: 2.0,
+            ..SummaryTable::DEFAULT
+        };
+        assert!((layout.clone().with_summary_table(table).summary_table_top() - 114.0).abs() < 0.01);
+        assert!(layout.with_summary_table(table).issues().is_empty());
+    }
+
+    #[test]
+    fn test_summary_table_issues() {
+        let with_table = |table: SummaryTable| LayoutConfig::new().with_summary_table(table).issues().len();
+        assert_eq!(with_table(SummaryTable { row_height: 0.0, ..SummaryTable::DEFAULT }), 1);
+        assert_eq!(with_table(SummaryTable { gap: f32::NAN, ..SummaryTable::DEFAULT }), 1);
+        // 外枠の幅・下端を超える
+        assert_eq!(with_table(SummaryTable { remarks_width: 160.0, ..SummaryTable::DEFAULT }), 1);
+        assert_eq!(with_table(SummaryTable { gap: 1.0, ..SummaryTable::DEFAULT }), 1);
+        // データ表が収まらない場合は行の高さの問題のみ
+        assert_eq!(LayoutConfig::new().with_row_height(12.0).issues().len(), 1);
+    }
+
+    #[test]
+    fn test_name_min_font_size_issues() {
+        assert!(LayoutConfig::new().with_name_min_font_size(6.0).issues().is_empty());
+        assert_eq!(LayoutConfig::new().with_name_min_font_size(0.0).issues().len(), 1);
+        assert_eq!(LayoutConfig::new().with_name_min_font_size(f32::NAN).issues().len(), 1);
+    }
+
+    #[test]
+    fn test_column_issues() {
+        assert_eq!(LayoutConfig::new().with_columns(Vec::new()).issues().len(), 1);
+
+        let mut columns = default_columns();
+        columns.push(ColumnSpec::new("receipt", "領収書", 10.0, ColumnSource::Custom("receipt".to_string())));
+        assert_eq!(LayoutConfig::new().with_columns(columns.clone()).issues().len(), 1);
+
+        columns[3].width_mm = 20.0;
+        assert!(LayoutConfig::new().with_columns(columns.clone()).issues().is_empty());
+        columns[0].width_mm = 0.0;
+        assert_eq!(LayoutConfig::new().with_columns(columns).issues().len(), 1);
+    }
+
+    #[test]
+    fn test_row_shading_issues() {
+        assert!(LayoutConfig::new().with_row_shading(Shading::default()).issues().is_empty());
+        let shading = Shading {
+            color_rgb: [1.5, 0.9, 0.9],
+            every_n_rows: 0,
+        };
+        assert_eq!(LayoutConfig::new().with_row_shading(shading).issues().len(), 2);
+    }
+
+    #[test]
+    fn test_color_scheme_issues() {
+        let colors = ColorScheme {
+            negative_amount: RgbColor::RED,
+            grid: RgbColor::new(0.0, 0.0, 0.4),
+            ..Default::default()
+        };
+        assert!(LayoutConfig::new().with_colors(colors).issues().is_empty());
+        assert_eq!(ColorScheme::default().text, RgbColor::BLACK);
+
+        let colors = ColorScheme {
+            title: RgbColor::new(0.0, 0.0, 255.0),
+            ..Default::default()
+        };
+        assert_eq!(LayoutConfig::new().with_colors(colors).issues(), vec!["タイトルの色が不正です: RgbColor { r: 0.0, g: 0.0, b: 255.0 }"]);
+    }
+
+    #[test]
+    fn test_line_style_issues() {
+        let styles = LineStyles {
+            outer_frame: LineStyle::solid(1.0),
+            table_grid: LineStyle::dashed(0.1, 1, 1),
+            ..Default::default()
+        };
+        assert!(LayoutConfig::new().with_line_styles(styles).issues().is_empty());
+        assert!(styles.has_dashed_frame());
+        assert!(!LineStyles::default().has_dashed_frame());
+
+        let styles = LineStyles {
+            outer_frame: LineStyle::solid(0.0),
+            table_grid: LineStyle::solid(MAX_LINE_THICKNESS + 1.0),
+            separator: LineStyle::dashed(0.1, 2, 0),
+            ..Default::default()
+        };
+        assert_eq!(LayoutConfig::new().with_line_styles(styles).issues().len(), 3);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_entry_separator_legacy_fields() {
+        // 既定値のままの場合は `LineStyles::separator` を使用
+        let styles = LineStyles {
+            separator: LineStyle::solid(0.3),
+            ..Default::default()
+        };
+        let layout = LayoutConfig::new().with_line_styles(styles).with_entry_separator(EntrySeparator {
+            enabled: true,
+            ..Default::default()
+        });
+        assert_eq!(layout.separator_style(), LineStyle::solid(0.3));
+
+        // 非推奨のフィールドを変更した場合はそちらを優先
+        let layout = LayoutConfig::new().with_entry_separator(EntrySeparator {
+            enabled: true,
+            dash: 3,
+            gap: 1,
+            thickness: 0.2,
+        });
+        assert_eq!(layout.separator_style(), LineStyle::dashed(0.2, 3, 1));
+        assert!(layout.issues().is_empty());
+
+        let layout = LayoutConfig::new().with_entry_separator(EntrySeparator {
+            thickness: MAX_LINE_THICKNESS + 1.0,
+            ..Default::default()
+        });
+        assert_eq!(layout.issues().len(), 1);
+    }
+
+    #[test]
+    fn test_sub_line_height_large_font() {
+        let layout = LayoutConfig::new().with_body_font_size(16.0);
+        // 行の高さがフォントサイズより小さくならない
+        assert!(layout.sub_line_height() >= pt_to_mm(16.0));
+    }
+
     #[test]
     fn test_column_positions() {
         // 列位置が正しく連続していることを確認
-        assert!(COL_X_DEST > COL_X_DATE);
-        assert!(COL_X_DETAIL > COL_X_DEST);
-        assert!(COL_X_KUKAN > COL_X_DETAIL);
-        assert!(COL_X_PRICE > COL_X_KUKAN);
-        assert!(COL_X_VOL > COL_X_PRICE);
+        let layout = LayoutConfig::default();
+        for index in 1..layout.columns.len() {
+            assert!(layout.column_left(index) > layout.column_left(index - 1));
+            assert_eq!(layout.column_left(index), layout.column_right(index - 1));
+        }
     }
 }
@@ -0,0 +1,110 @@
+//! ページごとの外部IDのメタデータ
+//!
+//! アイテムの外部ID（`Item::external_id`、ERPの伝票番号など）をドキュメント情報のキーワード（`/Keywords`）に
+//! `external-id:ページ番号=ID` の形式で記録する。ページに描画しないため印刷結果は変わらない。
+//! ページ番号は1始まり、IDの `%` `,` はパーセントエンコードする（キーワードの区切りは `,`）
+
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+use crate::models::Item;
+
+/// 外部IDのキーワードの接頭辞
+pub const EXTERNAL_ID_KEYWORD_PREFIX: &str = "external-id:";
+
+/// ページごとの外部ID（0始まりのページ番号 → アイテム順の外部ID）
+///
+/// 2面付けでは1枚の用紙に2件のアイテムが載るため、1ページに複数のIDが対応する
+pub fn external_ids_by_page(items: &[Item], pages_per_item: &[Range<usize>]) -> BTreeMap<usize, Vec<String>> {
+    let mut by_page: BTreeMap<usize, Vec<String>> = BTreeMap::new();
+    for (item, pages) in items.iter().zip(pages_per_item) {
+        let Some(ref id) = item.external_id else {
+            continue;
+        };
+        for page in pages.clone() {
+            let ids = by_page.entry(page).or_default();
+            if !ids.contains(id) {
+                ids.push(id.clone());
+            }
+        }
+    }
+    by_page
+}
+
+/// ページごとの外部IDをドキュメント情報のキーワードに変換
+pub fn external_id_keywords(by_page: &BTreeMap<usize, Vec<String>>) -> Vec<String> {
+    by_page
+        .iter()
+        .flat_map(|(page, ids)| {
+            ids.iter()
+                .map(move |id| format!("{}{}={}", EXTERNAL_ID_KEYWORD_PREFIX, page + 1, encode_id(id)))
+        })
+        .collect()
+}
+
+/// ドキュメント情報のキーワード（`,` 区切り）からページごとの外部IDを取得
+///
+/// `external_id_keywords` の逆変換。外部ID以外のキーワードは無視する
+pub fn parse_external_id_keywords(keywords: &str) -> BTreeMap<usize, Vec<String>> {
+    let mut by_page: BTreeMap<usize, Vec<String>> = BTreeMap::new();
+    for keyword in keywords.split(',') {
+        let Some((page, id)) = keyword
+            .trim()
+            .strip_prefix(EXTERNAL_ID_KEYWORD_PREFIX)
+            .and_then(|entry| entry.split_once('='))
+        else {
+            continue;
+        };
+        if let Some(page) = page.parse::<usize>().ok().and_then(|page| page.checked_sub(1)) {
+            by_page.entry(page).or_default().push(decode_id(id));
+        }
+    }
+    by_page
+}
+
+/// キーワードの区切り文字とエスケープ文字をパーセントエンコード
+fn encode_id(id: &str) -> String {
+    id.replace('%', "%25").replace(',', "%2C")
+}
+
+/// `encode_id` の逆変換
+fn decode_id(id: &str) -> String {
+    id.replace("%2C", ",").replace("%25", "%")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(external_id: Option<&str>) -> Item {
+        Item {
+            external_id: external_id.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_external_ids_by_page() {
+        let items = vec![item(Some("V-001")), item(None), item(Some("V-003"))];
+        let by_page = external_ids_by_page(&items, &[0..2, 2..3, 3..4]);
+        assert_eq!(by_page.len(), 3);
+        assert_eq!(by_page[&0], vec!["V-001".to_string()]);
+        assert_eq!(by_page[&1], vec!["V-001".to_string()]);
+        assert_eq!(by_page[&3], vec!["V-003".to_string()]);
+
+        // 2面付けでは同じ用紙に2件
+        let by_page = external_ids_by_page(&[item(Some("A")), item(Some("B"))], &[0..1, 0..1]);
+        assert_eq!(by_page[&0], vec!["A".to_string(), "B".to_string()]);
+    }
+
+    #[test]
+    fn test_keywords_round_trip() {
+        let by_page = external_ids_by_page(&[item(Some("V-001")), item(Some("50%,off"))], &[0..1, 1..2]);
+        let keywords = external_id_keywords(&by_page);
+        assert_eq!(keywords, vec!["external-id:1=V-001", "external-id:2=50%25%2Coff"]);
+
+        let joined = format!("精算書,{}", keywords.join(","));
+        assert_eq!(parse_external_id_keywords(&joined), by_page);
+        assert!(parse_external_id_keywords("external-id:0=X,external-id:x=Y").is_empty());
+    }
+}
@@ -4,13 +4,32 @@
 //! - fonts: 日本語フォント読み込み
 //! - layout: レイアウト定数
 //! - generator: PDF生成ロジック
+//! - renderer: ページ操作の描画
+//! - seal: 承認欄の印影画像
+//! - tax: 消費税の内訳計算
+//! - metadata: ページごとの外部IDのメタデータ
+//! - svg: ページ操作のSVG変換（Webプレビュー用）
+//! - testing: ページ操作の検証用ヘルパー（テスト時または `test-util` feature）
 
 pub mod text_utils;
 pub mod fonts;
 pub mod layout;
 pub mod generator;
+pub mod renderer;
+pub mod seal;
+pub mod tax;
+pub mod metadata;
+pub mod svg;
+mod interpret;
+#[cfg(any(test, feature = "test-util"))]
+pub mod testing;
 
-pub use text_utils::{wrap_detail, wrap_kukan, align_rows, prepare_ryohi_for_print, RyohiPrintData, TextWrapResult};
-pub use fonts::FontLoader;
+pub use text_utils::{wrap_detail, wrap_kukan, align_rows, line_total, normalize, normalize_item, prepare_ryohi_for_print, DecimalsMode, DetailOverflow, FormatOptions, KukanReplacement, NormalizeOptions, RowLimits, RowOverflow, Rounding, RyohiPrintData, TextWrapResult, VolFormat, WrapMode};
+pub use fonts::{FontCache, FontGlyphs, FontLoader, FontRole, FontSource, DEFAULT_FONT_CANDIDATES};
 pub use layout::*;
-pub use generator::ReportLabStylePdfClient;
+pub use renderer::PageRenderer;
+pub use seal::SealImage;
+pub use metadata::{external_id_keywords, external_ids_by_page, parse_external_id_keywords, EXTERNAL_ID_KEYWORD_PREFIX};
+pub use svg::ops_to_svg;
+pub use tax::{check_item_taxes, TaxBreakdown, TaxLine, TaxOptions, TaxRoundingUnit};
+pub use generator::{generate_pdf, generate_sync, item_output_path, FontSet, GenerationReport, GenerationStats, PagePostprocess, ReportLabStylePdfClient, DEFAULT_GLYPH_FALLBACK, EMPTY_ITEMS_MESSAGE};
@@ -0,0 +1,2003 @@
+//! ページ操作の描画
+//!
+//! 精算書1ページ分の枠・表・アイテムデータの描画処理。
+//! 描画に必要な設定は全て参照で受け取り、状態を持たないため
+//! 同じアイテムを異なるレイアウトで並行して描画できる
+
+use std::borrow::Cow;
+use std::ops::Range;
+
+use printpdf::*;
+
+use crate::models::{format_price_styled, Item, NumberFormat, Ryohi};
+use crate::pdf::fonts::FontRole;
+use crate::pdf::generator::{FontSet, GenerationReport};
+use crate::pdf::layout::*;
+use crate::pdf::tax::TaxBreakdown;
+use crate::pdf::text_utils::{is_nfc_item, line_total, nfc_item, nfc_text, prepare_ryohi_for_print, FormatOptions, RyohiPrintData};
+
+/// ドキュメントに埋め込んだ印影画像
+#[derive(Debug, Clone)]
+pub(crate) struct EmbeddedSeal {
+    /// 画像のXObject ID
+    pub(crate) id: XObjectId,
+    /// 幅 (px)
+    pub(crate) width: usize,
+    /// 高さ (px)
+    pub(crate) height: usize,
+}
+
+/// 精算書のページ操作を作成するレンダラー
+///
+/// `ReportLabStylePdfClient` は生成ごとにレンダラーを作成する。
+/// 独自のレイアウトで描画する場合は `new` で直接作成する
+#[derive(Debug, Clone, Copy)]
+pub struct PageRenderer<'a> {
+    /// レイアウト設定
+    layout: &'a LayoutConfig,
+    /// 印刷用データの整形オプション
+    format: &'a FormatOptions,
+    /// 用途別のフォントID
+    fonts: &'a FontSet,
+    /// ドキュメントに埋め込んだ印影（承認欄の列順）
+    seals: &'a [Option<EmbeddedSeal>],
+    /// 空白の精算書として描画するか（合計金額を印刷しない）
+    blank_form: bool,
+    /// 2桁の年を展開する基準の年
+    reference_year: i32,
+    /// 描画する文字列をNFCに統一するか
+    unicode_nfc: bool,
+}
+
+impl<'a> PageRenderer<'a> {
+    /// 新しいレンダラーを作成（印影なし）
+    pub fn new(layout: &'a LayoutConfig, format: &'a FormatOptions, fonts: &'a FontSet) -> Self {
+        Self {
+            layout,
+            format,
+            fonts,
+            seals: &[],
+            blank_form: false,
+            reference_year: DateTime::now_utc().year(),
+            unicode_nfc: true,
+        }
+    }
+
+    /// 描画する文字列（アイテム・見出し・ラベル）をNFCに統一するか設定（既定は有効）
+    pub fn with_unicode_nfc(mut self, enabled: bool) -> Self {
+        self.unicode_nfc = enabled;
+        self
+    }
+
+    /// 支払日の2桁の年を展開する基準の年を設定（既定は現在の年）
+    pub fn with_reference_year(mut self, year: i32) -> Self {
+        self.reference_year = year;
+        self
+    }
+
+    /// 承認欄に描画する印影を設定
+    pub(crate) fn with_seals(mut self, seals: &'a [Option<EmbeddedSeal>]) -> Self {
+        self.seals = seals;
+        self
+    }
+
+    /// ページの操作を作成（継続ページは含まない）
+    pub fn page_operations(&self, item: &Item, report: &mut GenerationReport) -> Vec<Op> {
+        self.item_pages(item, report)
+            .into_iter()
+            .next()
+            .unwrap_or_default()
+    }
+
+    /// 1件のアイテムの全ページの操作を作成
+    pub fn item_pages(&self, item: &Item, report: &mut GenerationReport) -> Vec<Vec<Op>> {
+        // 分解された濁点を折り返し・文字幅の計算の前に合成する
+        let item = if self.unicode_nfc && !is_nfc_item(item) {
+            Cow::Owned(nfc_item(item))
+        } else {
+            Cow::Borrowed(item)
+        };
+        let item = &*item;
+        let mut ops = Vec::new();
+
+        // 枠・表を描画
+        self.add_form_template(&mut ops);
+
+        // アイテム情報を印刷
+        let continuation = self.add_item_data(&mut ops, item, report);
+
+        let mut pages = vec![self.apply_margins(ops)];
+        for body in continuation {
+            // 継続ページには合計金額を印刷しない（合計は1ページ目のみ）
+            let mut ops = Vec::new();
+            self.add_form_template(&mut ops);
+            // 切り詰めは1ページ目で記録済み
+            self.add_base_data(&mut ops, item, &mut GenerationReport::default());
+            ops.extend(body);
+            pages.push(self.apply_margins(ops));
+        }
+        pages
+    }
+
+    /// 空白の精算書（枠・表・見出しのみ）のページ操作を作成
+    ///
+    /// 金額0のアイテムは `page_operations` で合計金額（¥0）を印刷するが、空白の精算書には印刷しない
+    pub fn blank_page(&self) -> Vec<Op> {
+        let renderer = Self {
+            blank_form: true,
+            ..*self
+        };
+        renderer.page_operations(&Item::default(), &mut GenerationReport::default())
+    }
+
+    /// 空白の精算書を指定部数分作成（全ページ同一の内容）
+    pub fn blank_forms(&self, copies: usize) -> Vec<Vec<Op>> {
+        vec![self.blank_page(); copies]
+    }
+
+    /// 設定した余白に合わせてページ全体を移動・縮小
+    ///
+    /// 各描画処理は標準の余白（`Margins::DEFAULT`）の座標で配置しているため、
+    /// 変換行列で外枠・全ての表の原点をまとめて余白の位置に合わせる
+    fn apply_margins(&self, ops: Vec<Op>) -> Vec<Op> {
+        let Some(matrix) = self.layout.content_matrix() else {
+            return ops;
+        };
+
+        let mut transformed = Vec::with_capacity(ops.len() + 3);
+        transformed.push(Op::SaveGraphicsState);
+        transformed.push(Op::SetTransformationMatrix {
+            matrix: CurTransMat::Raw(matrix),
+        });
+        transformed.extend(ops);
+        transformed.push(Op::RestoreGraphicsState);
+        transformed
+    }
+
+    /// 精算書の枠・表を描画
+    fn add_form_template(&self, ops: &mut Vec<Op>) {
+        // 外枠を描画
+        self.add_outer_frame(ops);
+
+        // 承認テーブル（右上）
+        self.add_approval_table(ops);
+
+        // 基本情報テーブル
+        self.add_basic_info_table(ops);
+
+        // メインデータテーブル
+        self.add_main_data_table(ops);
+
+        // 備考・計テーブル
+        self.add_summary_table(ops);
+    }
+
+    /// 外枠を描画
+    fn add_outer_frame(&self, ops: &mut Vec<Op>) {
+        let margins = Margins::DEFAULT;
+        let start_x = margins.left;
+        let start_y = margins.top;
+        let end_x = A5_WIDTH - margins.right;
+        let end_y = A5_HEIGHT - margins.bottom;
+
+        self.set_line_style(ops, &self.layout.line_styles.outer_frame);
+        ops.push(Op::SetOutlineColor {
+            col: pdf_color(self.layout.colors.grid),
+        });
+
+        // 外枠を描画
+        ops.push(Op::DrawPolygon {
+            polygon: Polygon {
+                rings: vec![PolygonRing {
+                    points: vec![
+                        LinePoint { p: Point::new(Mm(start_x), Mm(A5_HEIGHT - start_y)), bezier: false },
+                        LinePoint { p: Point::new(Mm(end_x), Mm(A5_HEIGHT - start_y)), bezier: false },
+                        LinePoint { p: Point::new(Mm(end_x), Mm(A5_HEIGHT - end_y)), bezier: false },
+                        LinePoint { p: Point::new(Mm(start_x), Mm(A5_HEIGHT - end_y)), bezier: false },
+                    ],
+                }],
+                mode: PaintMode::Stroke,
+                winding_order: WindingOrder::NonZero,
+            },
+        });
+    }
+
+    /// 承認テーブルを描画
+    pub(crate) fn add_approval_table(&self, ops: &mut Vec<Op>) {
+        let headers = &self.layout.approval_labels;
+        let col_width = self.layout.approval_col_width();
+        // 外枠の右端（200.0）に揃えて列数分だけ左に広げる
+        let start_x = self.approval_table_left();
+        let start_y = 25.0;
+        let row_height1 = 5.0;
+        let row_height2 = 15.0;
+
+        self.set_line_style(ops, &self.layout.line_styles.table_grid);
+
+        // ヘッダー行
+        for (i, header) in headers.iter().enumerate() {
+            let x = start_x + (i as f32) * col_width;
+
+            // 矩形を描画
+            self.add_rect(ops, x, start_y, col_width, row_height1);
+
+            // テキストを描画
+            self.add_text(ops, &self.fonts.body, header, 9.0, x + 1.0, start_y + 4.0);
+        }
+
+        // データ行（空欄、印影画像があれば描画）
+        for i in 0..headers.len() {
+            let x = start_x + (i as f32) * col_width;
+            self.add_rect(ops, x, start_y + row_height1, col_width, row_height2);
+            if let Some(Some(seal)) = self.seals.get(i) {
+                self.add_seal(ops, seal, x, start_y + row_height1, col_width, row_height2);
+            }
+        }
+    }
+
+    /// 印影画像を縦横比を保って枠の中央に描画（枠の内側1mmの余白を空ける）
+    fn add_seal(&self, ops: &mut Vec<Op>, seal: &EmbeddedSeal, x: f32, y: f32, width: f32, height: f32) {
+        let padding = 1.0;
+        // dpi=72で1px=1ptとして倍率を計算
+        let scale = (mm_to_pt(width - padding * 2.0) / seal.width.max(1) as f32)
+            .min(mm_to_pt(height - padding * 2.0) / seal.height.max(1) as f32);
+        let drawn_width = pt_to_mm(seal.width as f32 * scale);
+        let drawn_height = pt_to_mm(seal.height as f32 * scale);
+        let left = x + (width - drawn_width) / 2.0;
+        let bottom = y + (height + drawn_height) / 2.0;
+
+        ops.push(Op::UseXobject {
+            id: seal.id.clone(),
+            transform: XObjectTransform {
+                translate_x: Some(Pt(mm_to_pt(left))),
+                translate_y: Some(Pt(mm_to_pt(A5_HEIGHT - bottom))),
+                rotate: None,
+                scale_x: Some(scale),
+                scale_y: Some(scale),
+                dpi: Some(72.0),
+            },
+        });
+    }
+
+    /// 基本情報テーブルを描画
+    fn add_basic_info_table(&self, ops: &mut Vec<Op>) {
+        let start_x = 10.0;
+        let start_y = 30.0;
+
+        self.set_line_style(ops, &self.layout.line_styles.table_grid);
+
+        // 出発・帰着ラベル
+        let row_height = 3.5;
+        let diff_start_y = 3.0;
+
+        self.add_text(ops, &self.fonts.body, "出発", 9.0, start_x + 1.0, start_y + diff_start_y);
+        self.add_text(ops, &self.fonts.body, "　　月　　日", 9.0, start_x + 2.0, start_y + diff_start_y + row_height);
+        self.add_text(ops, &self.fonts.body, "帰着", 9.0, start_x + 1.0, start_y + diff_start_y + row_height * 2.0);
+        self.add_text(ops, &self.fonts.body, "　　月　　日", 9.0, start_x + 2.0, start_y + diff_start_y + row_height * 3.0);
+
+        // テーブルヘッダー
+        let headers = ["", "出張目的", "車両No.", "氏　名", "サイン"];
+
+        let mut current_x = start_x;
+        for (i, header) in headers.iter().enumerate() {
+            self.add_rect(ops, current_x, start_y, BASIC_INFO_COL_WIDTHS[i], 15.0);
+            if !header.is_empty() {
+                self.add_text(ops, &self.fonts.body, header, 9.0, current_x + 1.0, start_y + 4.0);
+            }
+            current_x += BASIC_INFO_COL_WIDTHS[i];
+        }
+    }
+
+    /// メインデータテーブルを描画
+    fn add_main_data_table(&self, ops: &mut Vec<Op>) {
+        let start_x = DATA_TABLE_LEFT;
+        let start_y = DATA_TABLE_TOP;
+
+        self.set_line_style(ops, &self.layout.line_styles.table_grid);
+
+        let columns = self.layout.placed_columns();
+        let row_height = self.layout.row_height;
+        let header_height = DATA_TABLE_HEADER_HEIGHT;
+
+        // ヘッダー
+        let mut current_x = start_x;
+        for column in &columns {
+            self.add_rect(ops, current_x, start_y, column.width_mm, header_height);
+            self.add_text(ops, &self.fonts.body, &column.header, 8.0, current_x + 1.0, start_y + 3.0);
+            current_x += column.width_mm;
+        }
+
+        // 行の網掛け（罫線・文字より先に塗りつぶす）
+        if let Some(ref shading) = self.layout.row_shading {
+            self.add_row_shading(ops, shading, start_x, start_y + header_height, row_height);
+        }
+
+        // データ行（7行）
+        for row in 0..MAX_DATA_ROWS_PER_PAGE {
+            current_x = start_x;
+            let current_y = start_y + header_height + (row as f32) * row_height;
+
+            for column in &columns {
+                let width = column.width_mm;
+                if column.source == ColumnSource::Detail {
+                    // 摘要欄は左右の線のみ描画
+                    self.add_vertical_line(ops, current_x, current_y, row_height);
+                    self.add_vertical_line(ops, current_x + width, current_y, row_height);
+                } else {
+                    self.add_rect(ops, current_x, current_y, width, row_height);
+                }
+                current_x += width;
+            }
+        }
+    }
+
+    /// データ行の網掛けを描画（`top` はデータ行の上端）
+    ///
+    /// 摘要欄を含む行全体を塗りつぶし、外枠からはみ出す部分は描画しない
+    fn add_row_shading(&self, ops: &mut Vec<Op>, shading: &Shading, x: f32, top: f32, row_height: f32) {
+        if shading.every_n_rows == 0 {
+            return;
+        }
+        let margins = Margins::DEFAULT;
+        let right = (x + self.layout.columns_width()).min(A5_WIDTH - margins.right);
+        let bottom = A5_HEIGHT - margins.bottom;
+
+        let [r, g, b] = shading.color_rgb;
+        ops.push(Op::SaveGraphicsState);
+        ops.push(Op::SetFillColor {
+            col: Color::Rgb(Rgb { r, g, b, icc_profile: None }),
+        });
+        for row in (shading.every_n_rows - 1..MAX_DATA_ROWS_PER_PAGE).step_by(shading.every_n_rows) {
+            let y = top + row as f32 * row_height;
+            let height = row_height.min(bottom - y);
+            if right > x && height > 0.0 {
+                self.push_rect(ops, x, y, right - x, height, PaintMode::Fill);
+            }
+        }
+        ops.push(Op::RestoreGraphicsState);
+    }
+
+    /// 備考・計テーブルを描画（データ表の直下）
+    fn add_summary_table(&self, ops: &mut Vec<Op>) {
+        let table = &self.layout.summary_table;
+        let start_x = DATA_TABLE_LEFT;
+        let start_y = self.layout.summary_table_top();
+
+        self.set_line_style(ops, &self.layout.line_styles.table_grid);
+
+        // 見出しと幅を組にして定義（配列の長さがずれないように）
+        let columns = [("備考", table.remarks_width), ("計", table.total_width)];
+        let row_height = table.row_height;
+
+        let mut current_x = start_x;
+        for (header, width) in columns {
+            self.add_rect(ops, current_x, start_y, width, row_height);
+            self.add_text(ops, &self.fonts.body, header, 8.0, current_x + 2.0, start_y + 4.0);
+            current_x += width;
+        }
+    }
+
+    /// アイテムデータを追加
+    ///
+    /// # Returns
+    /// 継続ページに印刷する旅費データの操作
+    fn add_item_data(&self, ops: &mut Vec<Op>, item: &Item, report: &mut GenerationReport) -> Vec<Vec<Op>> {
+        self.add_base_data(ops, item, report);
+
+        let start_x = 14.0;
+        let start_y = 36.8;
+
+        // 出発日
+        if let Some(ref start_date) = item.start_date {
+            if let Some(formatted) = format_date_mmdd(start_date) {
+                self.add_text(ops, &self.fonts.body, &formatted, 10.0, start_x, start_y);
+            }
+        }
+
+        // 帰着日
+        if let Some(ref end_date) = item.end_date {
+            if let Some(formatted) = format_date_mmdd(end_date) {
+                self.add_text(ops, &self.fonts.body, &formatted, 10.0, start_x, start_y + 7.0);
+            }
+        }
+
+        // 出張目的（出張目的欄に収まらない場合は欄の左端から折り返す）
+        if let Some(ref purpose) = item.purpose {
+            let x = start_x + 32.0;
+            let right = basic_info_cell_right(1) - 1.0;
+            if estimate_text_width(purpose, 10.0) <= right - x {
+                self.add_text(ops, &self.fonts.body, purpose, 10.0, x, start_y + 7.0);
+            } else {
+                let left = basic_info_cell_right(0) + 1.0;
+                self.add_cell_text(ops, purpose, left, start_y + 7.0, right - left, report);
+            }
+        }
+
+        // 車両（車両No.欄に収まらない場合は縮小）
+        if !item.car.is_empty() {
+            let size = fit_font_size(&item.car, 10.0, CAR_CELL_WIDTH - 2.0);
+            self.add_text(ops, &self.fonts.body, &item.car, size, start_x + 52.0, start_y + 7.0);
+        }
+
+        // 氏名（氏名欄に収まらない場合は最小サイズまで縮小、それでも収まらない場合は折り返す）
+        if !item.name.is_empty() {
+            let x = start_x + 85.0;
+            let max_width = basic_info_cell_right(3) - x - 1.0;
+            let shrunk = self
+                .layout
+                .name_min_font_size
+                .and_then(|min_size| shrink_to_fit(&item.name, 10.0, max_width, min_size));
+            match shrunk {
+                Some(size) => self.add_text(ops, &self.fonts.body, &item.name, size, x, start_y + 7.0),
+                None => self.add_cell_text(ops, &item.name, x, start_y + 7.0, max_width, report),
+            }
+        }
+
+        // 合計金額（上部の計欄）。空白の精算書には印刷しない
+        if !self.blank_form {
+            let price_str = format_price_styled(item.price, &self.format.number_format);
+            let font_id = self.fonts.for_text(FontRole::Numeric, &price_str);
+            let right = DATA_TABLE_LEFT + self.layout.summary_table.width();
+            self.add_amount(ops, font_id, &price_str, 12.0, right - 30.0, self.layout.summary_table_top() + 7.0);
+        }
+
+        // 仮払金と差引支給額（計欄の下部）
+        if let Some(advance) = item.advance {
+            self.add_advance_balance(ops, item.price, advance);
+        }
+
+        // 消費税の内訳（備考欄）
+        self.add_tax_summary(ops, item, report);
+
+        // 添付する領収書の枚数
+        if let Some(count) = item.receipt_count {
+            let (x, y) = self.layout.receipt_count_origin();
+            self.add_text(ops, &self.fonts.body, &format!("領収書 {} 枚", count), 9.0, x, y);
+        }
+
+        // 旅費データを処理
+        self.add_ryohi_items(ops, &item.ryohi, item.keep_together == Some(true), report)
+    }
+
+    /// 基本情報欄の値を枠内に折り返して印刷
+    ///
+    /// 1行に収まる場合は10ptで1行（ベースラインは `baseline_y`）。収まらない場合は縮小して2〜3行に折り返し、
+    /// 1行目を行数によらず同じ位置に置いて下へ並べる（3行の場合の最終行が `baseline_y` になる位置）。
+    /// 3行に収まらない場合は `LayoutConfig::cell_overflow` に従い、切り詰めた場合は `truncated_fields` に記録する
+    fn add_cell_text(&self, ops: &mut Vec<Op>, text: &str, x: f32, baseline_y: f32, max_width: f32, report: &mut GenerationReport) {
+        const FONT_SIZE: f32 = 10.0;
+        if estimate_text_width(text, FONT_SIZE) <= max_width {
+            self.add_text(ops, &self.fonts.body, text, FONT_SIZE, x, baseline_y);
+            return;
+        }
+
+        let wrapped = CELL_WRAP_SIZES.iter().find_map(|&(max_lines, size)| {
+            let lines = wrap_to_width(text, size, max_width);
+            (lines.len() <= max_lines).then_some((lines, size))
+        });
+        let (lines, size) = match wrapped {
+            Some(wrapped) => wrapped,
+            None => {
+                let (max_lines, size) = CELL_WRAP_SIZES[CELL_WRAP_SIZES.len() - 1];
+                let shrunk = match self.layout.cell_overflow {
+                    CellOverflow::Shrink => (1..)
+                        .map(|step| size - 0.5 * step as f32)
+                        .take_while(|&size| size >= MIN_FIT_FONT_SIZE)
+                        .map(|size| (wrap_to_width(text, size, max_width), size))
+                        .find(|(lines, _)| lines.len() <= max_lines),
+                    CellOverflow::Truncate => None,
+                };
+                shrunk.unwrap_or_else(|| {
+                    let size = match self.layout.cell_overflow {
+                        CellOverflow::Shrink => MIN_FIT_FONT_SIZE,
+                        CellOverflow::Truncate => size,
+                    };
+                    tracing::warn!("基本情報欄に収まらないため切り詰め: {}", text);
+                    report.truncated_fields += 1;
+                    (truncate_lines(wrap_to_width(text, size, max_width), max_lines, size, max_width), size)
+                })
+            }
+        };
+
+        let line_pitch = pt_to_mm(size) * LINE_SPACING;
+        let first_y = baseline_y - cell_wrap_height();
+        for (i, line) in lines.iter().enumerate() {
+            self.add_text(ops, &self.fonts.body, line, size, x, first_y + line_pitch * i as f32);
+        }
+    }
+
+    /// 消費税の内訳を備考欄の右側に印刷
+    ///
+    /// 税率ごとの対象額・消費税額と消費税の合計を印刷する。
+    /// 税率の指定された旅費項目が無い場合は記載の税額（`Item::tax`）のみ印刷する
+    fn add_tax_summary(&self, ops: &mut Vec<Op>, item: &Item, report: &mut GenerationReport) {
+        let font_size = 7.0;
+        let line_height = 4.0;
+        // 備考欄の右端に揃える
+        let remarks_right = DATA_TABLE_LEFT + self.layout.summary_table.remarks_width;
+        let start_y = self.layout.summary_table_top() + 4.0;
+        let label_x = remarks_right - 75.0;
+        let base_right = remarks_right - 40.0;
+        let tax_label_x = remarks_right - 37.0;
+        let tax_right = remarks_right - 2.0;
+
+        let breakdown = TaxBreakdown::compute(&item.ryohi, self.format.rounding, &self.format.tax);
+        let total_tax = match (breakdown.is_empty(), item.tax) {
+            (false, _) => breakdown.total_tax,
+            (true, Some(tax)) => tax.round() as i64,
+            (true, None) => return,
+        };
+
+        let mut lines = breakdown.lines.as_slice();
+        if lines.len() > MAX_TAX_RATE_LINES {
+            tracing::warn!("税率の数が多いため内訳の一部を省略: {}件", lines.len());
+            report.truncated_fields += lines.len() - MAX_TAX_RATE_LINES;
+            lines = &lines[..MAX_TAX_RATE_LINES];
+        }
+
+        let number_format = &self.format.number_format;
+        let format_amount = |amount: i64| format_price_styled(amount.clamp(i32::MIN as i64, i32::MAX as i64) as i32, number_format);
+        for (i, line) in lines.iter().enumerate() {
+            let y = start_y + line_height * i as f32;
+            let base = format_amount(line.base);
+            let tax = format_amount(line.tax);
+            self.add_text(ops, &self.fonts.body, &format!("{}%対象", line.rate), font_size, label_x, y);
+            self.add_amount_right(ops, self.fonts.for_text(FontRole::Numeric, &base), &base, font_size, base_right, y);
+            self.add_text(ops, &self.fonts.body, "消費税", font_size, tax_label_x, y);
+            self.add_amount_right(ops, self.fonts.for_text(FontRole::Numeric, &tax), &tax, font_size, tax_right, y);
+        }
+
+        let y = start_y + line_height * lines.len() as f32;
+        let total = format_amount(total_tax);
+        self.add_text(ops, &self.fonts.body, "消費税計", font_size, tax_label_x, y);
+        self.add_amount_right(ops, self.fonts.for_text(FontRole::Numeric, &total), &total, font_size, tax_right, y);
+    }
+
+    /// 仮払金と差引支給額（合計金額 − 仮払金）を計欄の下部に印刷
+    ///
+    /// 差引支給額が負の場合（返納する場合）は括弧で囲み、負の金額の色で印刷する
+    fn add_advance_balance(&self, ops: &mut Vec<Op>, price: i32, advance: i32) {
+        let font_size = 7.0;
+        let table = &self.layout.summary_table;
+        let label_x = DATA_TABLE_LEFT + table.remarks_width + 2.0;
+        let amount_right = DATA_TABLE_LEFT + table.width() - 2.0;
+        let balance_y = self.layout.summary_table_top() + table.row_height - 2.5;
+
+        let number_format = &self.format.number_format;
+        let balance = (price as i64 - advance as i64).clamp(i32::MIN as i64, i32::MAX as i64) as i32;
+        let rows = [
+            ("仮払金", format_price_styled(advance, number_format), balance_y - 4.0),
+            ("差引支給額", format_balance(balance, number_format), balance_y),
+        ];
+        for (label, amount, y) in rows {
+            self.add_text(ops, &self.fonts.body, label, font_size, label_x, y);
+            self.add_amount_right(ops, self.fonts.for_text(FontRole::Numeric, &amount), &amount, font_size, amount_right, y);
+        }
+    }
+
+    /// 基本データを描画
+    fn add_base_data(&self, ops: &mut Vec<Op>, item: &Item, report: &mut GenerationReport) {
+        let start_x = 10.0;
+        let start_y = 15.0;
+        let approval_left = self.approval_table_left();
+
+        // タイトル（字間を空けて描画し、テキスト抽出時は空白を含まない）
+        let tracking = self.layout.title_tracking_mm;
+        let title_font = self.fonts.for_text(FontRole::Title, TITLE);
+        let (x, y) = (start_x + 13.0, start_y + 5.0);
+        self.add_text_tracked(ops, title_font, TITLE, FONT_SIZE_TITLE, x, y, tracking, self.layout.colors.title);
+
+        // タイトル下線（2本）
+        let title_width = tracked_text_width(TITLE, FONT_SIZE_TITLE, tracking);
+        self.set_line_style(ops, &self.layout.line_styles.title_underline);
+        self.add_horizontal_line(ops, start_x + 13.0, start_y + 6.0, title_width);
+        self.add_horizontal_line(ops, start_x + 13.0, start_y + 7.0, title_width);
+
+        // 精算日（承認欄の左まで。タイトルに近づかないよう印刷開始位置より左には寄せない）
+        if let Some(ref pay_day) = item.pay_day {
+            if let Some(formatted) = format_pay_day_full(pay_day, self.reference_year) {
+                let x = start_x + 100.0;
+                self.add_header_text(ops, &formatted, 9.0, x, x..approval_left - 2.0, start_y + 5.0, report);
+            }
+        }
+
+        // 所属（右上、承認欄の上から外枠の右端まで）
+        if let Some(ref office) = item.office {
+            let bounds = approval_left..A5_WIDTH - Margins::DEFAULT.right - 2.0;
+            self.add_header_text(ops, office, 10.0, start_x + 175.0, bounds, start_y + 5.0, report);
+        }
+    }
+
+    /// 承認欄の左端のX座標 (mm)
+    fn approval_table_left(&self) -> f32 {
+        let count = self.layout.approval_labels.len() as f32;
+        A5_WIDTH - Margins::DEFAULT.right - self.layout.approval_col_width() * count
+    }
+
+    /// 見出し行のテキストを `bounds` の範囲に印刷
+    ///
+    /// 範囲に収まる場合は `x` に印刷し、右端を超える場合は右端に揃えて左に寄せる。
+    /// 範囲の幅に収まらない場合は `cell_overflow` に従って縮小・切り詰める
+    #[allow(clippy::too_many_arguments)]
+    fn add_header_text(
+        &self,
+        ops: &mut Vec<Op>,
+        text: &str,
+        size: f32,
+        x: f32,
+        bounds: Range<f32>,
+        y: f32,
+        report: &mut GenerationReport,
+    ) {
+        let text = &*self.nfc(text);
+        let max_width = bounds.end - bounds.start;
+        let min_size = match self.layout.cell_overflow {
+            CellOverflow::Shrink => MIN_FIT_FONT_SIZE,
+            CellOverflow::Truncate => size,
+        };
+        let (text, size) = match shrink_to_fit(text, size, max_width, min_size) {
+            Some(fitted) => (text.to_string(), fitted),
+            None => {
+                tracing::warn!("見出しに収まらないため切り詰め: {}", text);
+                report.truncated_fields += 1;
+                let lines = truncate_lines(wrap_to_width(text, min_size, max_width), 1, min_size, max_width);
+                (lines.into_iter().next().unwrap_or_default(), min_size)
+            }
+        };
+        let x = x.min(bounds.end - estimate_text_width(&text, size)).max(bounds.start);
+        self.add_text(ops, &self.fonts.body, &text, size, x, y);
+    }
+
+    /// 旅費データを印刷
+    ///
+    /// `LayoutConfig::continuation_pages` が有効な場合は1ページに収まらない行を継続ページに印刷する。
+    /// ページの最終行に小計（前ページからの繰越を含む累計）、継続ページの先頭行に繰越を印刷する。
+    /// `Ryohi::page_break_before` の項目は新しいページから印刷し、`keep_together` の場合は
+    /// ページをまたぐ項目を（1ページに収まれば）次のページに送る
+    ///
+    /// # Returns
+    /// 継続ページに印刷する旅費データの操作（ページごと、枠・表は含まない）
+    pub(crate) fn add_ryohi_items(
+        &self,
+        ops: &mut Vec<Op>,
+        ryohi_list: &[Ryohi],
+        keep_together: bool,
+        report: &mut GenerationReport,
+    ) -> Vec<Vec<Op>> {
+        let capacity = MAX_DATA_ROWS_PER_PAGE * 2;
+        let continuation = self.layout.continuation_pages;
+
+        let prepared: Vec<_> = ryohi_list
+            .iter()
+            .map(|ryohi| prepare_ryohi_for_print(ryohi, self.layout.detail_max_len(), self.layout.kukan_max_len(), self.format))
+            .collect();
+        let mut remaining_rows: usize = prepared
+            .iter()
+            .map(|data| (0..data.max_rows).filter(|&row| data.has_content_in_row(row)).count())
+            .sum();
+
+        // 区切り線を引く列の範囲（摘要・区間欄）
+        let separator_columns = {
+            let mut indices = self
+                .layout
+                .columns
+                .iter()
+                .enumerate()
+                .filter(|(_, column)| matches!(column.source, ColumnSource::Detail | ColumnSource::Kukan))
+                .map(|(index, _)| index);
+            indices.next().map(|first| (first, indices.next_back().unwrap_or(first)))
+        };
+
+        // 指定した行から印刷する項目が途中で改ページされるか
+        let splits = |start_row: usize, entry_rows: usize, remaining_rows: usize| {
+            let break_at = (capacity - 1).saturating_sub(start_row);
+            break_at >= 1 && break_at < entry_rows && remaining_rows - break_at > 1
+        };
+
+        let mut continuation_pages: Vec<Vec<Op>> = Vec::new();
+        let mut current_row: usize = 0;
+        // 印刷済みの計の累計（小計・繰越の金額）
+        let mut running_total: i64 = 0;
+
+        for (i, (ryohi, print_data)) in ryohi_list.iter().zip(&prepared).enumerate() {
+            report.truncated_fields += print_data.truncated_fields;
+
+            let row_limit = if continuation {
+                print_data.max_rows
+            } else {
+                print_data.max_rows.min(capacity.saturating_sub(current_row))
+            };
+
+            // 行数上限を超えて印刷できない行を記録
+            report.dropped_rows += (row_limit..print_data.max_rows)
+                .filter(|&row| print_data.has_content_in_row(row))
+                .count();
+
+            // 改ページの指定（ページの先頭で始まる項目はそのまま印刷）
+            let entry_rows = (0..row_limit).filter(|&row| print_data.has_content_in_row(row)).count();
+            let page_top = if continuation_pages.is_empty() { 0 } else { 1 };
+            if continuation && entry_rows > 0 && current_row > page_top {
+                let forced = ryohi.page_break_before == Some(true);
+                let moved = keep_together
+                    && splits(current_row, entry_rows, remaining_rows)
+                    && !splits(1, entry_rows, remaining_rows);
+                if forced || moved {
+                    current_row = self.break_page(ops, &mut continuation_pages, running_total);
+                }
+            }
+
+            let mut first_row = None;
+            let mut drawn_rows = 0;
+
+            for row in 0..row_limit {
+                if !print_data.has_content_in_row(row) {
+                    continue;
+                }
+
+                // ページの最終行を小計に使い、残りの行は継続ページに印刷
+                if continuation && current_row == capacity - 1 && remaining_rows > 1 {
+                    current_row = self.break_page(ops, &mut continuation_pages, running_total);
+                }
+
+                let page = continuation_pages.last_mut().unwrap_or(&mut *ops);
+                self.add_ryohi_row(page, print_data, row, current_row);
+                if first_row.is_none() {
+                    first_row = Some((continuation_pages.len(), current_row));
+                }
+                if row == 0 {
+                    if let Some(price) = ryohi.price {
+                        running_total += line_total(price, ryohi.vol, self.format.rounding) as i64;
+                    }
+                }
+
+                current_row += 1;
+                remaining_rows -= 1;
+                drawn_rows += 1;
+            }
+
+            // 前の旅費項目との区切り線（摘要・区間欄）
+            // 継続ページで始まった項目は繰越の行との間を区切る
+            if let (Some((page_index, first_row)), Some((first_col, last_col))) =
+                (first_row.filter(|&(_, row)| row > 0), separator_columns)
+            {
+                let line_y = RYOHI_START_Y + self.layout.sub_line_offset(first_row) + 2.0;
+                let line_x = RYOHI_START_X + self.layout.column_left(first_col).min(self.layout.column_left(last_col));
+                let width = self.layout.column_offset(last_col + 1) - self.layout.column_offset(first_col);
+                let page = match page_index {
+                    0 => &mut *ops,
+                    index => &mut continuation_pages[index - 1],
+                };
+                self.add_entry_separator(page, line_x, line_y, width);
+            }
+
+            tracing::debug!(
+                "旅費項目 {}: 最大行数={}, 実際印刷行数={}, 現在行={}",
+                i + 1,
+                print_data.max_rows,
+                drawn_rows,
+                current_row
+            );
+        }
+
+        report.continuation_pages += continuation_pages.len();
+        continuation_pages
+    }
+
+    /// 現在のページの最終行に小計、新しい継続ページの先頭行に繰越を印刷
+    ///
+    /// # Returns
+    /// 新しいページで次に印刷する行
+    fn break_page(&self, ops: &mut Vec<Op>, continuation_pages: &mut Vec<Vec<Op>>, running_total: i64) -> usize {
+        let last_row = MAX_DATA_ROWS_PER_PAGE * 2 - 1;
+        let page = continuation_pages.last_mut().unwrap_or(ops);
+        self.add_carry_row(page, SUBTOTAL_LABEL, running_total, last_row);
+        let mut next_page = Vec::new();
+        self.add_carry_row(&mut next_page, CARRY_FORWARD_LABEL, running_total, 0);
+        continuation_pages.push(next_page);
+        1
+    }
+
+    /// 旅費データの1行を印刷
+    ///
+    /// `logical_row` はページ内の明細の論理行（1行に2行分の明細を印刷する）
+    fn add_ryohi_row(&self, ops: &mut Vec<Op>, print_data: &RyohiPrintData, row: usize, logical_row: usize) {
+        let font_size = self.layout.body_font_size;
+        let current_y = RYOHI_START_Y + self.layout.sub_line_offset(logical_row);
+        let mut current_x = RYOHI_START_X;
+
+        for column in self.layout.placed_columns() {
+            let text = match column.source {
+                ColumnSource::Date => print_data.get_date(row),
+                ColumnSource::Dest => print_data.get_dest(row),
+                ColumnSource::Detail => print_data.get_detail(row),
+                ColumnSource::Kukan => print_data.get_kukan(row),
+                ColumnSource::Price => print_data.get_price(row),
+                ColumnSource::Vol => print_data.get_vol(row),
+                ColumnSource::LineTotal => print_data.get_total(row),
+                ColumnSource::Transport => print_data.get_custom(TRANSPORT_KEY, row),
+                ColumnSource::Custom(ref key) => print_data.get_custom(key, row),
+                ColumnSource::Blank => "",
+            };
+            if !text.is_empty() {
+                let role = if column.source.is_numeric() { FontRole::Numeric } else { FontRole::Body };
+                let font_id = self.fonts.for_text(role, text);
+                let x = match column.align {
+                    ColumnAlign::Left => current_x + 1.0,
+                    ColumnAlign::Offset(offset) => current_x + offset,
+                    ColumnAlign::Right => (current_x + column.width_mm - 1.0) - estimate_text_width(text, font_size),
+                };
+                if column.source.is_amount() {
+                    self.add_amount(ops, font_id, text, font_size, x, current_y + 6.0);
+                } else {
+                    self.add_text(ops, font_id, text, font_size, x, current_y + 6.0);
+                }
+            }
+            current_x += column.width_mm;
+        }
+    }
+
+    /// 小計・繰越の行を印刷（見出しは摘要欄、金額は計欄）
+    fn add_carry_row(&self, ops: &mut Vec<Op>, label: &str, amount: i64, logical_row: usize) {
+        let font_size = self.layout.body_font_size;
+        let current_y = RYOHI_START_Y + self.layout.sub_line_offset(logical_row) + 6.0;
+
+        // 摘要欄が無い場合は先頭の列、計欄が無い場合は表の右端に印刷
+        let layout = self.layout;
+        let label_column = layout.column_index(&ColumnSource::Detail).unwrap_or(0);
+        let label_x = RYOHI_START_X + layout.column_left(label_column) + 1.0;
+        self.add_text(ops, &self.fonts.body, label, font_size, label_x, current_y);
+
+        let amount = format_price_styled(amount.clamp(i32::MIN as i64, i32::MAX as i64) as i32, &self.format.number_format);
+        let right_edge = match layout.column_index(&ColumnSource::LineTotal) {
+            Some(index) => layout.column_right(index),
+            None => layout.columns_width(),
+        };
+        let right_x = RYOHI_START_X + right_edge - 1.0;
+        self.add_amount_right(ops, self.fonts.for_text(FontRole::Numeric, &amount), &amount, font_size, right_x, current_y);
+    }
+
+    /// 旅費項目間の区切り線を描画
+    fn add_entry_separator(&self, ops: &mut Vec<Op>, x: f32, y: f32, width: f32) {
+        if !self.layout.entry_separator.enabled {
+            return;
+        }
+
+        ops.push(Op::SaveGraphicsState);
+        self.set_line_style(ops, &self.layout.separator_style());
+        self.add_horizontal_line(ops, x, y, width);
+        ops.push(Op::RestoreGraphicsState);
+    }
+
+    /// 線の太さと破線を設定
+    ///
+    /// 実線の場合、外枠・罫線・タイトルの下線のいずれかが破線なら破線を解除する
+    fn set_line_style(&self, ops: &mut Vec<Op>, style: &LineStyle) {
+        ops.push(Op::SetOutlineThickness { pt: Pt(style.thickness) });
+        match style.dash {
+            Some((dash, gap)) => ops.push(Op::SetLineDashPattern {
+                dash: LineDashPattern {
+                    dash_1: Some(dash),
+                    gap_1: Some(gap),
+                    ..Default::default()
+                },
+            }),
+            None if self.layout.line_styles.has_dashed_frame() => ops.push(Op::SetLineDashPattern {
+                dash: LineDashPattern::default(),
+            }),
+            None => {}
+        }
+    }
+
+    /// テキストを追加
+    fn add_text(&self, ops: &mut Vec<Op>, font_id: &FontId, text: &str, size: f32, x: f32, y: f32) {
+        self.add_text_tracked(ops, font_id, text, size, x, y, 0.0, self.layout.colors.text);
+    }
+
+    /// 描画する文字列（NFCに統一する設定の場合は合成）
+    fn nfc<'t>(&self, text: &'t str) -> Cow<'t, str> {
+        if self.unicode_nfc {
+            nfc_text(text)
+        } else {
+            Cow::Borrowed(text)
+        }
+    }
+
+    /// 金額を追加（負の金額は `ColorScheme::negative_amount` の色）
+    fn add_amount(&self, ops: &mut Vec<Op>, font_id: &FontId, amount: &str, size: f32, x: f32, y: f32) {
+        self.add_text_tracked(ops, font_id, amount, size, x, y, 0.0, self.amount_color(amount));
+    }
+
+    /// 金額の文字色
+    ///
+    /// `format_price_styled` は負の金額の先頭に「-」を付け、差引支給額は負の場合に括弧で囲む
+    fn amount_color(&self, amount: &str) -> RgbColor {
+        let colors = &self.layout.colors;
+        if amount.starts_with(['-', '(']) {
+            colors.negative_amount
+        } else {
+            colors.text
+        }
+    }
+
+    /// 字間 (mm) と色を指定してテキストを追加
+    ///
+    /// 字間・塗りつぶしの色はグラフィックス状態に残るため、描画後に0・文字色へ戻す
+    #[allow(clippy::too_many_arguments)]
+    fn add_text_tracked(
+        &self,
+        ops: &mut Vec<Op>,
+        font_id: &FontId,
+        text: &str,
+        size: f32,
+        x: f32,
+        y: f32,
+        tracking_mm: f32,
+        color: RgbColor,
+    ) {
+        // 本文フォントに無い文字はフォールバックフォントの区間に分割
+        let text = self.nfc(text);
+        let runs = self.fonts.runs(font_id, &text);
+
+        ops.push(Op::StartTextSection);
+        ops.push(Op::SetTextCursor {
+            pos: Point::new(Mm(x), Mm(A5_HEIGHT - y)),
+        });
+        ops.push(Op::SetFontSize {
+            font: runs[0].0.clone(),
+            size: Pt(size),
+        });
+        ops.push(Op::SetLineHeight { lh: Pt(size) });
+        ops.push(Op::SetFillColor { col: pdf_color(color) });
+        if tracking_mm != 0.0 {
+            ops.push(Op::SetCharacterSpacing { multiplier: mm_to_pt(tracking_mm) });
+        }
+        // 区間ごとにフォントを切り替えて続けて描画
+        for (i, &(run_font, run)) in runs.iter().enumerate() {
+            if i > 0 {
+                ops.push(Op::SetFontSize {
+                    font: run_font.clone(),
+                    size: Pt(size),
+                });
+            }
+            ops.push(Op::WriteText {
+                items: vec![TextItem::Text(run.to_string())],
+                font: run_font.clone(),
+            });
+        }
+        if color != self.layout.colors.text {
+            ops.push(Op::SetFillColor {
+                col: pdf_color(self.layout.colors.text),
+            });
+        }
+        if tracking_mm != 0.0 {
+            ops.push(Op::SetCharacterSpacing { multiplier: 0.0 });
+        }
+        ops.push(Op::EndTextSection);
+    }
+
+    /// 金額を右揃えで追加
+    ///
+    /// `right_x` はテキスト右端のX座標
+    fn add_amount_right(&self, ops: &mut Vec<Op>, font_id: &FontId, amount: &str, size: f32, right_x: f32, y: f32) {
+        let width = estimate_text_width(amount, size);
+        self.add_amount(ops, font_id, amount, size, right_x - width, y);
+    }
+
+    /// 矩形を描画
+    fn add_rect(&self, ops: &mut Vec<Op>, x: f32, y: f32, width: f32, height: f32) {
+        self.push_rect(ops, x, y, width, height, PaintMode::Stroke);
+    }
+
+    /// 矩形を指定した描画方法で追加
+    fn push_rect(&self, ops: &mut Vec<Op>, x: f32, y: f32, width: f32, height: f32, mode: PaintMode) {
+        ops.push(Op::DrawPolygon {
+            polygon: Polygon {
+                rings: vec![PolygonRing {
+                    points: vec![
+                        LinePoint { p: Point::new(Mm(x), Mm(A5_HEIGHT - y)), bezier: false },
+                        LinePoint { p: Point::new(Mm(x + width), Mm(A5_HEIGHT - y)), bezier: false },
+                        LinePoint { p: Point::new(Mm(x + width), Mm(A5_HEIGHT - y - height)), bezier: false },
+                        LinePoint { p: Point::new(Mm(x), Mm(A5_HEIGHT - y - height)), bezier: false },
+                    ],
+                }],
+                mode,
+                winding_order: WindingOrder::NonZero,
+            },
+        });
+    }
+
+    /// 垂直線を描画
+    fn add_vertical_line(&self, ops: &mut Vec<Op>, x: f32, y: f32, height: f32) {
+        ops.push(Op::DrawLine {
+            line: Line {
+                points: vec![
+                    LinePoint { p: Point::new(Mm(x), Mm(A5_HEIGHT - y)), bezier: false },
+                    LinePoint { p: Point::new(Mm(x), Mm(A5_HEIGHT - y - height)), bezier: false },
+                ],
+                is_closed: false,
+            },
+        });
+    }
+
+    /// 水平線を描画
+    fn add_horizontal_line(&self, ops: &mut Vec<Op>, x: f32, y: f32, width: f32) {
+        ops.push(Op::DrawLine {
+            line: Line {
+                points: vec![
+                    LinePoint { p: Point::new(Mm(x), Mm(A5_HEIGHT - y)), bezier: false },
+                    LinePoint { p: Point::new(Mm(x + width), Mm(A5_HEIGHT - y)), bezier: false },
+                ],
+                is_closed: false,
+            },
+        });
+    }
+}
+
+
+/// 備考欄に印刷する消費税の内訳の最大行数（合計行を除く）
+const MAX_TAX_RATE_LINES: usize = 3;
+
+/// 旅費データの印刷開始位置（左上、mm）
+pub(crate) const RYOHI_START_X: f32 = 10.0;
+pub(crate) const RYOHI_START_Y: f32 = 47.0;
+
+/// 車両No.欄の幅 (mm)
+const CAR_CELL_WIDTH: f32 = 28.75;
+
+/// 基本情報テーブルの列幅 (mm)（出発・帰着, 出張目的, 車両No., 氏名, サイン）
+const BASIC_INFO_COL_WIDTHS: [f32; 5] = [31.0, 25.0, CAR_CELL_WIDTH, 30.0, 30.0];
+
+/// 基本情報欄の折り返し（最大行数, フォントサイズ pt）。行数の少ない順に試す
+const CELL_WRAP_SIZES: [(usize, f32); 2] = [(2, 8.0), (3, 7.0)];
+
+/// 基本情報欄で折り返した1行目から最大行数の最終行までのベースラインの間隔 (mm)
+fn cell_wrap_height() -> f32 {
+    let (max_lines, size) = CELL_WRAP_SIZES[CELL_WRAP_SIZES.len() - 1];
+    pt_to_mm(size) * LINE_SPACING * (max_lines - 1) as f32
+}
+
+/// 基本情報テーブルの列の右端のX座標 (mm)
+fn basic_info_cell_right(index: usize) -> f32 {
+    10.0 + BASIC_INFO_COL_WIDTHS[..=index].iter().sum::<f32>()
+}
+
+/// テキストを指定幅 (mm) ごとに折り返す（文字単位）
+fn wrap_to_width(text: &str, size: f32, max_width: f32) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    for c in text.chars() {
+        let mut candidate = current.clone();
+        candidate.push(c);
+        if !current.is_empty() && estimate_text_width(&candidate, size) > max_width {
+            lines.push(std::mem::replace(&mut current, c.to_string()));
+        } else {
+            current = candidate;
+        }
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+/// 折り返した行を最大行数に切り詰め、最終行の末尾に「…」を付ける
+fn truncate_lines(mut lines: Vec<String>, max_lines: usize, size: f32, max_width: f32) -> Vec<String> {
+    if lines.len() <= max_lines {
+        return lines;
+    }
+    lines.truncate(max_lines);
+    if let Some(last) = lines.last_mut() {
+        while !last.is_empty() && estimate_text_width(&format!("{}…", last), size) > max_width {
+            last.pop();
+        }
+        last.push('…');
+    }
+    lines
+}
+
+/// 設定の色をPDFの色に変換
+fn pdf_color(color: RgbColor) -> Color {
+    Color::Rgb(Rgb {
+        r: color.r,
+        g: color.g,
+        b: color.b,
+        icc_profile: None,
+    })
+}
+
+/// テキストが指定幅に収まるフォントサイズを取得（最小 `MIN_FIT_FONT_SIZE`）
+fn fit_font_size(text: &str, size: f32, max_width: f32) -> f32 {
+    shrink_to_fit(text, size, max_width, MIN_FIT_FONT_SIZE).unwrap_or(MIN_FIT_FONT_SIZE)
+}
+
+/// テキストが指定幅に収まるまで縮小したフォントサイズを取得
+///
+/// テキスト幅（`estimate_text_width`）はフォントサイズに比例するため、収まるサイズを直接求める。
+/// `min_size` でも収まらない場合はNone
+fn shrink_to_fit(text: &str, size: f32, max_width: f32, min_size: f32) -> Option<f32> {
+    let width = estimate_text_width(text, size);
+    if width <= max_width {
+        return Some(size);
+    }
+    let fitted = size * max_width / width;
+    (fitted >= min_size).then_some(fitted)
+}
+
+/// テキスト幅の概算 (mm)
+///
+/// 全角文字は1em、半角文字は0.5emとして計算
+pub(crate) fn estimate_text_width(text: &str, size: f32) -> f32 {
+    let em = pt_to_mm(size);
+    text.chars()
+        .map(|c| if c.is_ascii() { em * 0.5 } else { em })
+        .sum()
+}
+
+/// 字間を含むテキスト幅を推定（mm）
+///
+/// 字間は文字の間にのみ数える（最後の文字の後ろは含めない）
+fn tracked_text_width(text: &str, size: f32, tracking_mm: f32) -> f32 {
+    let gaps = text.chars().count().saturating_sub(1) as f32;
+    estimate_text_width(text, size) + tracking_mm * gaps
+}
+
+/// 日付をMM　DD形式にフォーマット
+fn format_date_mmdd(date: &str) -> Option<String> {
+    // YYYY-MM-DD形式を想定
+    if date.len() >= 10 && date.chars().nth(4) == Some('-') && date.chars().nth(7) == Some('-') {
+        let month = &date[5..7];
+        let day = &date[8..10];
+        Some(format!("{}　 {}", month, day))
+    } else {
+        Some(date.to_string())
+    }
+}
+
+/// 差引支給額をフォーマット（負の場合は「-」の代わりに括弧で囲む）
+fn format_balance(balance: i32, format: &NumberFormat) -> String {
+    let formatted = format_price_styled(balance, format);
+    match formatted.strip_prefix('-') {
+        Some(amount) => format!("({})", amount),
+        None => formatted,
+    }
+}
+
+/// 2桁の年を展開する範囲のうち基準の年より前の年数（基準の年の80年前〜19年後）
+///
+/// 支払日は過去の日付が大半のため、過去に寄せた範囲とする
+const TWO_DIGIT_YEAR_PAST_YEARS: i32 = 80;
+
+/// 2桁の年を基準の年の80年前〜19年後の範囲の4桁の年に展開
+fn expand_two_digit_year(short: i32, reference_year: i32) -> i32 {
+    let earliest = reference_year - TWO_DIGIT_YEAR_PAST_YEARS;
+    let year = earliest - earliest.rem_euclid(100) + short;
+    if year < earliest {
+        year + 100
+    } else {
+        year
+    }
+}
+
+/// 支払日をフルフォーマット
+///
+/// YYYY/MM/DD・YYYY-MM-DD形式を想定（月日は1桁も可）。2桁の数字の年は基準の年の80年前〜19年後の範囲の年とする。
+/// 年月日の3つに分割できない・空の部分がある場合はNone
+fn format_pay_day_full(pay_day: &str, reference_year: i32) -> Option<String> {
+    let separator = if pay_day.contains('/') { '/' } else { '-' };
+    let parts: Vec<&str> = pay_day.trim().split(separator).map(str::trim).collect();
+    let [year, month, day] = parts[..] else {
+        return None;
+    };
+    if parts.iter().any(|part| part.is_empty()) {
+        return None;
+    }
+
+    let year = match year.parse::<i32>() {
+        Ok(short) if year.len() == 2 && year.bytes().all(|b| b.is_ascii_digit()) => {
+            expand_two_digit_year(short, reference_year).to_string()
+        }
+        _ => year.to_string(),
+    };
+    Some(format!("清算日　{}年 {}月 {}日", year, month, day))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pdf::generator::ReportLabStylePdfClient;
+    use crate::pdf::testing::{self, example_item};
+
+    /// 登録済みのサンプルの1件目のページ操作（tests/layout_snapshot.rs と共通）
+    const EXAMPLE_ITEM_OPS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/example_items_page1.json");
+
+    #[test]
+    fn test_example_item_ops_unchanged() {
+        let fonts = FontSet::single(FontId("F1".to_string()));
+        let item = example_item();
+
+        let client_ops = ReportLabStylePdfClient::new().render_item_ops_with_fonts(&fonts, &item);
+        testing::assert_ops_snapshot(EXAMPLE_ITEM_OPS, &client_ops);
+
+        let layout = LayoutConfig::default();
+        let format = FormatOptions::default();
+        let mut report = GenerationReport::default();
+        let ops = PageRenderer::new(&layout, &format, &fonts).page_operations(&item, &mut report);
+        assert_eq!(ops, client_ops);
+    }
+
+    #[test]
+    fn test_custom_column_spec() {
+        // 特別料金を駐車料に置き換え、区間を狭めて領収書欄を追加
+        let mut columns = default_columns();
+        columns[3].width_mm = 20.0;
+        columns[6] = ColumnSpec::new("parking", "駐車料", 15.0, ColumnSource::Custom("parking".to_string()))
+            .with_align(ColumnAlign::Right);
+        columns.insert(
+            7,
+            ColumnSpec::new("receipt", "領収書", 10.0, ColumnSource::Custom("receipt".to_string())),
+        );
+        let layout = LayoutConfig::new().with_columns(columns);
+        assert!(layout.issues().is_empty());
+
+        let mut item = example_item();
+        item.ryohi[0].custom = [
+            ("parking".to_string(), "800".to_string()),
+            ("receipt".to_string(), "有".to_string()),
+        ]
+        .into_iter()
+        .collect();
+        let fonts = FontSet::single(FontId("F1".to_string()));
+        let format = FormatOptions::default();
+        let ops = PageRenderer::new(&layout, &format, &fonts).page_operations(&item, &mut GenerationReport::default());
+
+        assert!(testing::find_text(&ops, "駐車料").is_some());
+        assert!(testing::find_text(&ops, "特別料金").is_none());
+
+        // 駐車料は列の右端に右揃え、領収書は列の左端から1mm
+        let parking = testing::find_text(&ops, "800").unwrap();
+        let parking_right = RYOHI_START_X + layout.column_offset(7) - 1.0;
+        assert!((parking.x_mm + estimate_text_width("800", parking.size) - parking_right).abs() < 0.01);
+        let receipt = testing::find_text(&ops, "有").unwrap();
+        assert!((receipt.x_mm - (RYOHI_START_X + layout.column_offset(7) + 1.0)).abs() < 0.01);
+        assert!((receipt.top_mm(A5_HEIGHT) - (RYOHI_START_Y + 6.0)).abs() < 0.01);
+
+        // 旅費日当・計の列は右にずれない（表の幅は同じ）
+        let total = testing::find_text(&ops, "15,000").unwrap();
+        assert!(total.x_mm > RYOHI_START_X + layout.column_offset(8));
+    }
+
+    #[test]
+    fn test_wrap_length_follows_column_width() {
+        let fonts = FontSet::single(FontId("F1".to_string()));
+        let format = FormatOptions::default();
+        let mut item = example_item();
+        item.ryohi[0].detail = vec!["あいうえおかきく".to_string()];
+        item.ryohi[0].kukan = Some("福岡　北九州　下関　広島".to_string());
+        let texts = |layout: &LayoutConfig| {
+            let ops = PageRenderer::new(layout, &format, &fonts).page_operations(&item, &mut GenerationReport::default());
+            testing::texts_in(&ops).into_iter().map(|t| t.text).collect::<Vec<_>>()
+        };
+
+        // 標準の列幅では1行
+        let normal = texts(&LayoutConfig::default());
+        assert!(normal.contains(&"あいうえおかきく".to_string()));
+        assert!(normal.contains(&"福岡　北九州　下関　広島".to_string()));
+
+        // 摘要欄・区間欄を狭めると列幅に合わせて折り返す
+        let mut columns = default_columns();
+        columns[2].width_mm = 20.0;
+        columns[3].width_mm = 12.0;
+        columns[4].width_mm += 38.0;
+        let narrowed = texts(&LayoutConfig::new().with_columns(columns));
+        assert!(narrowed.contains(&"あいうえお".to_string()));
+        assert!(narrowed.contains(&"かきく".to_string()));
+        assert!(narrowed.contains(&"福岡　北九州".to_string()));
+        assert!(narrowed.contains(&"下関　広島".to_string()));
+    }
+
+    #[test]
+    fn test_receipt_count() {
+        let fonts = FontSet::single(FontId("F1".to_string()));
+        let format = FormatOptions::default();
+        let item = Item {
+            receipt_count: Some(3),
+            ..example_item()
+        };
+
+        // 既定は備考欄の左下
+        let layout = LayoutConfig::default();
+        let ops = PageRenderer::new(&layout, &format, &fonts).page_operations(&item, &mut GenerationReport::default());
+        let receipt = testing::find_text(&ops, "領収書 3 枚").unwrap();
+        assert!((receipt.x_mm - 12.0).abs() < 0.01);
+        assert!((receipt.top_mm(A5_HEIGHT) - 135.0).abs() < 0.01);
+
+        let layout = LayoutConfig::new().with_receipt_count_position(150.0, 125.0);
+        let ops = PageRenderer::new(&layout, &format, &fonts).page_operations(&item, &mut GenerationReport::default());
+        let receipt = testing::find_text(&ops, "領収書 3 枚").unwrap();
+        assert!((receipt.x_mm - 150.0).abs() < 0.01);
+        assert!((receipt.top_mm(A5_HEIGHT) - 125.0).abs() < 0.01);
+
+        // 未指定の場合は印刷しない
+        let ops = PageRenderer::new(&layout, &format, &fonts).page_operations(&example_item(), &mut GenerationReport::default());
+        assert!(testing::find_text(&ops, "領収書").is_none());
+    }
+
+    #[test]
+    fn test_advance_and_balance() {
+        let fonts = FontSet::single(FontId("F1".to_string()));
+        let format = FormatOptions::default();
+        let layout = LayoutConfig::default();
+        let render = |item: &Item| PageRenderer::new(&layout, &format, &fonts).page_operations(item, &mut GenerationReport::default());
+        let top = layout.summary_table_top();
+        let total_left = DATA_TABLE_LEFT + layout.summary_table.remarks_width;
+        let total_right = DATA_TABLE_LEFT + layout.summary_table.width();
+
+        // 計欄の下部に仮払金と差引支給額（合計金額 − 仮払金）を右揃えで印刷
+        let item = Item {
+            advance: Some(20000),
+            ..example_item()
+        };
+        let ops = render(&item);
+        let label = testing::find_text(&ops, "仮払金").unwrap();
+        let advance = testing::find_text(&ops, "20,000").unwrap();
+        let balance_label = testing::find_text(&ops, "差引支給額").unwrap();
+        let balance = testing::texts_in(&ops).into_iter().find(|text| text.text == "5,000").unwrap();
+        assert!((label.x_mm - (total_left + 2.0)).abs() < 0.01);
+        assert!((balance_label.x_mm - (total_left + 2.0)).abs() < 0.01);
+        assert!((advance.x_mm + estimate_text_width(&advance.text, advance.size) - (total_right - 2.0)).abs() < 0.01);
+        assert!((balance.x_mm + estimate_text_width(&balance.text, balance.size) - (total_right - 2.0)).abs() < 0.01);
+        assert!((balance.top_mm(A5_HEIGHT) - (top + layout.summary_table.row_height - 2.5)).abs() < 0.01);
+        assert!((advance.top_mm(A5_HEIGHT) - (balance.top_mm(A5_HEIGHT) - 4.0)).abs() < 0.01);
+        // 合計金額の下に収まる
+        assert!(advance.top_mm(A5_HEIGHT) > top + 7.0 + 2.0);
+
+        // 仮払金が合計金額を超える場合は括弧で囲み、負の金額の色
+        let item = Item {
+            advance: Some(30000),
+            ..example_item()
+        };
+        let ops = render(&item);
+        assert!(testing::find_text(&ops, "(5,000)").is_some());
+        let index = write_text_index(&ops, "(5,000)");
+        assert_eq!(ops[index - 1], Op::SetFillColor { col: pdf_color(layout.colors.negative_amount) });
+        assert_eq!(format_balance(-1234567, &NumberFormat::default()), "(1,234,567)");
+        assert_eq!(format_balance(0, &NumberFormat::default()), "0");
+
+        // 未指定の場合は印刷しない
+        let ops = render(&example_item());
+        assert!(testing::find_text(&ops, "仮払金").is_none());
+        assert!(testing::find_text(&ops, "差引支給額").is_none());
+    }
+
+    #[test]
+    fn test_header_office_and_pay_day_placement() {
+        let fonts = FontSet::single(FontId("F1".to_string()));
+        let format = FormatOptions::default();
+        let layout = LayoutConfig::default();
+        let render = |item: &Item, layout: &LayoutConfig| {
+            let mut report = GenerationReport::default();
+            let ops = PageRenderer::new(layout, &format, &fonts).page_operations(item, &mut report);
+            (ops, report)
+        };
+
+        // 短い値は従来の位置のまま
+        let (ops, _) = render(&example_item(), &layout);
+        let office = testing::find_text(&ops, "営業部").unwrap();
+        assert!((office.x_mm - 185.0).abs() <= 1.0);
+        assert_eq!(office.size, 10.0);
+        let pay_day = testing::find_text(&ops, "清算日").unwrap();
+        assert!((pay_day.x_mm - 110.0).abs() <= 1.0);
+
+        // 10文字の所属は外枠の右端から2mm内側に右揃え、承認欄の左端より右
+        let item = Item {
+            office: Some("九州第二営業部販売課".to_string()),
+            pay_day: Some("令和10/12/31".to_string()),
+            ..example_item()
+        };
+        let (ops, report) = render(&item, &layout);
+        let office = testing::find_text(&ops, "九州第二営業部販売課").unwrap();
+        let right = office.x_mm + estimate_text_width(&office.text, office.size);
+        assert!((right - 198.0).abs() < 0.01);
+        assert!(office.x_mm >= 155.0);
+        assert_eq!(office.size, 10.0);
+        // 清算日は承認欄の左に収まる
+        let pay_day = testing::find_text(&ops, "清算日").unwrap();
+        assert!(pay_day.x_mm + estimate_text_width(&pay_day.text, pay_day.size) <= 153.0 + 0.01);
+        assert_eq!(report.truncated_fields, 0);
+
+        // 範囲に収まらない場合は縮小、切り詰めの設定では「…」で切り詰める
+        let item = Item {
+            office: Some("九州第二営業部販売課第三係".to_string()),
+            ..example_item()
+        };
+        let (ops, _) = render(&item, &layout);
+        let office = testing::find_text(&ops, "九州第二営業部販売課第三係").unwrap();
+        assert!(office.size < 10.0);
+        assert!(office.x_mm >= 155.0 - 0.01);
+        let layout = LayoutConfig::new().with_cell_overflow(CellOverflow::Truncate);
+        let (ops, report) = render(&item, &layout);
+        let office = testing::find_text(&ops, "九州第二営業部販売").unwrap();
+        assert!(office.text.ends_with('…'));
+        assert_eq!(report.truncated_fields, 1);
+    }
+
+    #[test]
+    fn test_summary_table_follows_data_table() {
+        let fonts = FontSet::single(FontId("F1".to_string()));
+        let format = FormatOptions::default();
+        let item = Item {
+            tax: Some(1000.0),
+            receipt_count: Some(2),
+            ..example_item()
+        };
+
+        let layout = LayoutConfig::default();
+        let ops = PageRenderer::new(&layout, &format, &fonts).page_operations(&item, &mut GenerationReport::default());
+        let remarks = testing::find_text(&ops, "備考").unwrap();
+        assert!((remarks.top_mm(A5_HEIGHT) - 123.0).abs() < 0.01);
+
+        // 行の高さを縮めると備考欄・消費税・領収書の枚数が上に移動する
+        let table = SummaryTable {
+            row_height: 20.0,
+            remarks_width: 130.0,
+            total_width: 60.0,
+            gap: 5.0,
+        };
+        let layout = LayoutConfig::new().with_row_height(9.0).with_summary_table(table);
+        assert!(layout.issues().is_empty());
+        let ops = PageRenderer::new(&layout, &format, &fonts).page_operations(&item, &mut GenerationReport::default());
+        let top = 45.0 + 4.0 + 63.0 + 5.0;
+        let remarks = testing::find_text(&ops, "備考").unwrap();
+        assert!((remarks.top_mm(A5_HEIGHT) - (top + 4.0)).abs() < 0.01);
+        // 計欄の見出し（データ表の見出しと区別する）
+        let total = testing::texts_in(&ops)
+            .into_iter()
+            .find(|text| text.text == "計" && (text.top_mm(A5_HEIGHT) - (top + 4.0)).abs() < 0.01)
+            .unwrap();
+        assert!((total.x_mm - (10.0 + 130.0 + 2.0)).abs() < 0.01);
+        let tax = testing::find_text(&ops, "消費税計").unwrap();
+        assert!((tax.top_mm(A5_HEIGHT) - (top + 4.0)).abs() < 0.01);
+        assert!((tax.x_mm - (10.0 + 130.0 - 37.0)).abs() < 0.01);
+        let receipt = testing::find_text(&ops, "領収書 2 枚").unwrap();
+        assert!((receipt.top_mm(A5_HEIGHT) - (top + 20.0 - 3.0)).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_header_labels() {
+        let fonts = FontSet::single(FontId("F1".to_string()));
+        let format = FormatOptions::default();
+        let labels = ["Date", "Dest.", "Details", "Route", "Transport", "Fare", "Extra", "Per diem", "Total"];
+        let layout = LayoutConfig::new().with_header_labels(labels.iter().map(|s| s.to_string()).collect()).unwrap();
+        let ops = PageRenderer::new(&layout, &format, &fonts).page_operations(&example_item(), &mut GenerationReport::default());
+
+        let total = testing::find_text(&ops, "Total").unwrap();
+        assert!((total.x_mm - (RYOHI_START_X + layout.column_offset(8) + 1.0)).abs() < 0.01);
+        assert!(testing::find_text(&ops, "Per diem").is_some());
+        assert!(!testing::texts_in(&ops).iter().any(|t| t.text == "旅費日当"));
+    }
+
+    #[test]
+    fn test_page_renderer_unicode_nfc() {
+        let fonts = FontSet::single(FontId("F1".to_string()));
+        let format = FormatOptions::default();
+        let mut labels: Vec<String> = default_columns().into_iter().map(|c| c.header).collect();
+        labels[8] = "こ\u{3099}うけい".to_string();
+        let layout = LayoutConfig::new().with_header_labels(labels).unwrap();
+        let item = Item {
+            name: "山田ひ\u{309a}ろ".to_string(),
+            ..example_item()
+        };
+
+        let ops = PageRenderer::new(&layout, &format, &fonts).page_operations(&item, &mut GenerationReport::default());
+        assert!(testing::find_text(&ops, "山田ぴろ").is_some());
+        assert!(testing::find_text(&ops, "ごうけい").is_some());
+
+        // 無効の場合はそのまま描画する
+        let ops = PageRenderer::new(&layout, &format, &fonts)
+            .with_unicode_nfc(false)
+            .page_operations(&item, &mut GenerationReport::default());
+        assert!(testing::find_text(&ops, "山田ひ\u{309a}ろ").is_some());
+        assert!(testing::find_text(&ops, "こ\u{3099}うけい").is_some());
+    }
+
+    #[test]
+    fn test_reverse_columns() {
+        let fonts = FontSet::single(FontId("F1".to_string()));
+        let format = FormatOptions::default();
+        let item = example_item();
+        let normal = PageRenderer::new(&LayoutConfig::default(), &format, &fonts).page_operations(&item, &mut GenerationReport::default());
+        let layout = LayoutConfig::new().with_reverse_columns(true);
+        let ops = PageRenderer::new(&layout, &format, &fonts).page_operations(&item, &mut GenerationReport::default());
+        let width = layout.columns_width();
+
+        // 見出し・日付は右端の列、計は左端の列に印刷する
+        let header = testing::find_text(&ops, "日付").unwrap();
+        assert!((header.x_mm - (RYOHI_START_X + width - 10.0 + 1.0)).abs() < 0.01);
+        let print_data = prepare_ryohi_for_print(&item.ryohi[0], MAX_DETAIL_LENGTH, MAX_KUKAN_LENGTH, &format);
+        let date = testing::texts_in(&ops)
+            .into_iter()
+            .filter(|t| t.text == print_data.get_date(0))
+            .max_by(|a, b| a.x_mm.total_cmp(&b.x_mm))
+            .unwrap();
+        assert!((date.x_mm - (RYOHI_START_X + width - 10.0 + 1.0)).abs() < 0.01);
+        let total = testing::texts_in(&ops)
+            .into_iter()
+            .filter(|t| t.text == "15,000")
+            .min_by(|a, b| a.x_mm.total_cmp(&b.x_mm))
+            .unwrap();
+        assert!(total.x_mm + estimate_text_width("15,000", total.size) <= RYOHI_START_X + 23.0 - 1.0 + 0.01);
+
+        // 行先と摘要の並びも反転する（データの対応は変わらない）
+        let dest = testing::texts_in(&ops).into_iter().find(|t| t.text == "東京").unwrap();
+        let detail = testing::find_text(&ops, "交通費").unwrap();
+        assert!(dest.x_mm > detail.x_mm);
+        assert_eq!(testing::texts_in(&ops).len(), testing::texts_in(&normal).len());
+
+        // データ表の罫線は左右対称（同じ矩形の集合）
+        let mirrored = |rects: Vec<testing::PlacedRect>| {
+            let mut edges: Vec<(i64, i64)> = rects
+                .iter()
+                .filter(|r| (45.0 - 0.01..118.0).contains(&r.top_mm(A5_HEIGHT)))
+                .map(|r| (((r.x_mm - RYOHI_START_X) * 100.0).round() as i64, ((r.x_mm + r.width_mm - RYOHI_START_X) * 100.0).round() as i64))
+                .collect();
+            edges.sort();
+            edges
+        };
+        let flipped: Vec<(i64, i64)> = {
+            let total = (width * 100.0).round() as i64;
+            let mut edges: Vec<(i64, i64)> = mirrored(testing::rects_in(&normal)).into_iter().map(|(l, r)| (total - r, total - l)).collect();
+            edges.sort();
+            edges
+        };
+        assert_eq!(mirrored(testing::rects_in(&ops)), flipped);
+    }
+
+    /// 折り返した出張目的の描画開始位置（出張目的欄の左端）
+    const PURPOSE_WRAP_X: f32 = 42.0;
+
+    /// 基本情報欄の値の印刷位置（上の行から順）
+    fn cell_lines(ops: &[Op], x_mm: f32) -> Vec<testing::PlacedText> {
+        let mut lines: Vec<testing::PlacedText> = testing::texts_in(ops)
+            .into_iter()
+            .filter(|t| (t.x_mm - x_mm).abs() < 0.01 && t.top_mm(A5_HEIGHT) > 36.8)
+            .collect();
+        lines.sort_by(|a, b| a.top_mm(A5_HEIGHT).total_cmp(&b.top_mm(A5_HEIGHT)));
+        lines
+    }
+
+    #[test]
+    fn test_wrap_basic_info_cells() {
+        let fonts = FontSet::single(FontId("F1".to_string()));
+        let format = FormatOptions::default();
+        let render = |item: &Item, layout: &LayoutConfig| {
+            let mut report = GenerationReport::default();
+            let ops = PageRenderer::new(layout, &format, &fonts).page_operations(item, &mut report);
+            (ops, report)
+        };
+
+        // 短い値は1行・10ptのまま
+        let (ops, _) = render(&example_item(), &LayoutConfig::default());
+        let purpose = cell_lines(&ops, 46.0);
+        assert_eq!(purpose.len(), 1);
+        assert_eq!(purpose[0].size, 10.0);
+        assert!((purpose[0].top_mm(A5_HEIGHT) - 43.8).abs() < 0.01);
+
+        // 10文字の出張目的は2行に折り返し、どちらも出張目的欄に収まる
+        let item = Item {
+            purpose: Some("東京本社定例会議出席".to_string()),
+            name: "山田太郎".to_string(),
+            ..Default::default()
+        };
+        let (ops, report) = render(&item, &LayoutConfig::default());
+        let lines = cell_lines(&ops, PURPOSE_WRAP_X);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines.iter().map(|t| t.text.as_str()).collect::<String>(), "東京本社定例会議出席");
+        for line in &lines {
+            assert!(line.x_mm >= 41.0 && line.x_mm + estimate_text_width(&line.text, line.size) <= 66.0);
+        }
+        // 1行目は見出し（ベースライン34mm）の下、2行目はその下で1行の場合のベースラインより上
+        let first_top = lines[0].top_mm(A5_HEIGHT);
+        assert!(first_top - pt_to_mm(lines[0].size) > 34.0);
+        assert!(lines[1].top_mm(A5_HEIGHT) > first_top);
+        assert!(lines[1].top_mm(A5_HEIGHT) < 43.8);
+        assert_eq!(report.truncated_fields, 0);
+
+        // 25文字の出張目的は切り詰めずに出張目的欄に収まる
+        // （2行に収めるには最小サイズより小さい約5ptが必要なため、7ptの3行に折り返す）
+        let purpose = "北陸支店立ち上げ支援および新規顧客開拓訪問と打合せ";
+        assert_eq!(purpose.chars().count(), 25);
+        let item = Item {
+            purpose: Some(purpose.to_string()),
+            ..Default::default()
+        };
+        let (ops, report) = render(&item, &LayoutConfig::default());
+        let lines = cell_lines(&ops, PURPOSE_WRAP_X);
+        assert_eq!(lines.len(), 3);
+        assert!(lines.iter().all(|t| t.size == 7.0));
+        assert_eq!(lines.iter().map(|t| t.text.as_str()).collect::<String>(), purpose);
+        for line in &lines {
+            assert!(line.x_mm >= 41.0 && line.x_mm + estimate_text_width(&line.text, line.size) <= 66.0);
+        }
+        assert_eq!(report.truncated_fields, 0);
+
+        // 長い氏名は3行まで縮小して折り返す（1行目は2行の場合と同じ位置から下へ並べる）
+        let item = Item {
+            name: "寿限無寿限無五劫の擦り切れ海砂利水魚の水行末雲来末".to_string(),
+            ..Default::default()
+        };
+        let (ops, report) = render(&item, &LayoutConfig::default());
+        let lines = cell_lines(&ops, 99.0);
+        assert_eq!(lines.len(), 3);
+        assert!(lines.iter().all(|t| t.size == 7.0));
+        assert!((lines[0].top_mm(A5_HEIGHT) - first_top).abs() < 0.01);
+        assert!(lines.windows(2).all(|pair| pair[1].top_mm(A5_HEIGHT) > pair[0].top_mm(A5_HEIGHT)));
+        assert!((lines[2].top_mm(A5_HEIGHT) - 43.8).abs() < 0.01);
+        assert_eq!(report.truncated_fields, 0);
+    }
+
+    #[test]
+    fn test_name_shrinks_to_one_line() {
+        let fonts = FontSet::single(FontId("F1".to_string()));
+        let format = FormatOptions::default();
+        let item = Item {
+            name: "佐々木小次郎左衛門".to_string(),
+            ..Default::default()
+        };
+        let render = |layout: &LayoutConfig| {
+            let mut report = GenerationReport::default();
+            cell_lines(&PageRenderer::new(layout, &format, &fonts).page_operations(&item, &mut report), 99.0)
+        };
+
+        // 既定は最小サイズまで縮小して1行に収める
+        assert_eq!(render(&LayoutConfig::default()).len(), 1);
+
+        // 縮小しない設定の場合は折り返し
+        let layout = LayoutConfig {
+            name_min_font_size: None,
+            ..Default::default()
+        };
+        assert_eq!(render(&layout).len(), 2);
+
+        // 最小サイズまでに収まる場合は1行のまま縮小
+        let lines = render(&LayoutConfig::new().with_name_min_font_size(6.0));
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].text, item.name);
+        assert!(lines[0].size < 10.0 && lines[0].size >= 6.0);
+        assert!((lines[0].top_mm(A5_HEIGHT) - 43.8).abs() < 0.01);
+        let max_width = basic_info_cell_right(3) - 99.0 - 1.0;
+        assert!(estimate_text_width(&lines[0].text, lines[0].size) <= max_width + 0.01);
+
+        // 最小サイズでも収まらない場合は折り返す
+        assert_eq!(render(&LayoutConfig::new().with_name_min_font_size(9.0)).len(), 2);
+
+        // 短い氏名は10ptのまま
+        let short = Item {
+            name: "山田太郎".to_string(),
+            ..Default::default()
+        };
+        let mut report = GenerationReport::default();
+        let layout = LayoutConfig::new().with_name_min_font_size(6.0);
+        let ops = PageRenderer::new(&layout, &format, &fonts).page_operations(&short, &mut report);
+        assert_eq!(cell_lines(&ops, 99.0)[0].size, 10.0);
+    }
+
+    #[test]
+    fn test_basic_info_cell_overflow() {
+        let fonts = FontSet::single(FontId("F1".to_string()));
+        let format = FormatOptions::default();
+        let item = Item {
+            purpose: Some("あ".repeat(40)),
+            ..Default::default()
+        };
+
+        // 縮小しても収まらない場合は最小サイズで切り詰める
+        let mut report = GenerationReport::default();
+        let ops = PageRenderer::new(&LayoutConfig::default(), &format, &fonts).page_operations(&item, &mut report);
+        let lines = cell_lines(&ops, PURPOSE_WRAP_X);
+        assert_eq!(lines.len(), 3);
+        assert!(lines.iter().all(|t| t.size == MIN_FIT_FONT_SIZE));
+        assert!(lines[2].text.ends_with('…'));
+        assert_eq!(report.truncated_fields, 1);
+
+        // Truncate は縮小せずに切り詰める
+        let layout = LayoutConfig::new().with_cell_overflow(CellOverflow::Truncate);
+        let mut report = GenerationReport::default();
+        let ops = PageRenderer::new(&layout, &format, &fonts).page_operations(&item, &mut report);
+        let lines = cell_lines(&ops, PURPOSE_WRAP_X);
+        assert_eq!(lines.len(), 3);
+        assert!(lines.iter().all(|t| t.size == 7.0));
+        assert!(lines[2].text.ends_with('…'));
+        assert_eq!(report.truncated_fields, 1);
+
+        // 少しだけ収まらない場合は縮小して収める
+        let item = Item {
+            purpose: Some("あ".repeat(13)),
+            ..Default::default()
+        };
+        let mut report = GenerationReport::default();
+        let ops = PageRenderer::new(&LayoutConfig::default(), &format, &fonts).page_operations(&item, &mut report);
+        let lines = cell_lines(&ops, PURPOSE_WRAP_X);
+        assert_eq!(lines.iter().map(|t| t.text.as_str()).collect::<String>(), "あ".repeat(13));
+        assert_eq!(report.truncated_fields, 0);
+    }
+
+    /// 線の太さの設定（描画順）
+    fn outline_thicknesses(ops: &[Op]) -> Vec<f32> {
+        ops.iter()
+            .filter_map(|op| match op {
+                Op::SetOutlineThickness { pt } => Some(pt.0),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_default_line_styles_unchanged() {
+        let fonts = FontSet::single(FontId("F1".to_string()));
+        let layout = LayoutConfig::new().with_line_styles(LineStyles {
+            outer_frame: LineStyle::solid(0.5),
+            table_grid: LineStyle::solid(0.2),
+            title_underline: LineStyle::solid(0.3),
+            separator: LineStyle::dashed(0.1, 2, 2),
+        });
+        let ops = PageRenderer::new(&layout, &FormatOptions::default(), &fonts).page_operations(&example_item(), &mut GenerationReport::default());
+        testing::assert_ops_snapshot(EXAMPLE_ITEM_OPS, &ops);
+    }
+
+    #[test]
+    fn test_table_grid_line_style() {
+        let fonts = FontSet::single(FontId("F1".to_string()));
+        let format = FormatOptions::default();
+        let default_ops = PageRenderer::new(&LayoutConfig::default(), &format, &fonts).blank_page();
+        // 外枠・承認欄・基本情報・データ表・備考欄・タイトルの下線
+        assert_eq!(outline_thicknesses(&default_ops), vec![0.5, 0.2, 0.2, 0.2, 0.2, 0.3]);
+
+        let styles = LineStyles {
+            outer_frame: LineStyle::solid(1.0),
+            table_grid: LineStyle::solid(0.1),
+            ..Default::default()
+        };
+        let layout = LayoutConfig::new().with_line_styles(styles);
+        let ops = PageRenderer::new(&layout, &format, &fonts).blank_page();
+        assert_eq!(outline_thicknesses(&ops), vec![1.0, 0.1, 0.1, 0.1, 0.1, 0.3]);
+        // 破線が無い場合は破線の設定を出力しない
+        assert!(!ops.iter().any(|op| matches!(op, Op::SetLineDashPattern { .. })));
+
+        // 罫線を破線にした場合は外枠・タイトルの下線で破線を解除する
+        let styles = LineStyles {
+            table_grid: LineStyle::dashed(0.1, 1, 1),
+            ..Default::default()
+        };
+        let layout = LayoutConfig::new().with_line_styles(styles);
+        let ops = PageRenderer::new(&layout, &format, &fonts).blank_page();
+        let dashes: Vec<Option<i64>> = ops
+            .iter()
+            .filter_map(|op| match op {
+                Op::SetLineDashPattern { dash } => Some(dash.dash_1),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(dashes, vec![None, Some(1), Some(1), Some(1), Some(1), None]);
+    }
+
+    /// 塗りつぶしの矩形と直前に設定した塗りつぶしの色（描画順）
+    fn filled_rects(ops: &[Op]) -> Vec<(Polygon, Color)> {
+        let mut fill = None;
+        let mut rects = Vec::new();
+        for op in ops {
+            match op {
+                Op::SetFillColor { col } => fill = Some(col.clone()),
+                Op::DrawPolygon { polygon } if polygon.mode == PaintMode::Fill => {
+                    rects.push((polygon.clone(), fill.clone().expect("塗りつぶしの色が未設定")))
+                }
+                _ => {}
+            }
+        }
+        rects
+    }
+
+    #[test]
+    fn test_row_shading() {
+        let fonts = FontSet::single(FontId("F1".to_string()));
+        let format = FormatOptions::default();
+        let item = example_item();
+        let unshaded = PageRenderer::new(&LayoutConfig::default(), &format, &fonts).page_operations(&item, &mut GenerationReport::default());
+        assert!(filled_rects(&unshaded).is_empty());
+
+        let shading = Shading {
+            color_rgb: [0.85, 0.85, 0.85],
+            every_n_rows: 2,
+        };
+        let layout = LayoutConfig::new().with_row_shading(shading);
+        let ops = PageRenderer::new(&layout, &format, &fonts).page_operations(&item, &mut GenerationReport::default());
+
+        // 2・4・6行目（データ行の上端は49mm、行の高さ10mm）
+        let fills = filled_rects(&ops);
+        let tops: Vec<f32> = fills.iter().map(|(p, _)| A5_HEIGHT - pt_to_mm(p.rings[0].points[0].p.y.0)).collect();
+        assert_eq!(fills.len(), 3);
+        for (top, expected) in tops.iter().zip([59.0, 79.0, 99.0]) {
+            assert!((top - expected).abs() < 0.01);
+        }
+        for (_, color) in &fills {
+            assert_eq!(*color, Color::Rgb(Rgb { r: 0.85, g: 0.85, b: 0.85, icc_profile: None }));
+        }
+
+        // 罫線・文字より先に塗りつぶす
+        let last_fill = ops.iter().rposition(|op| matches!(op, Op::DrawPolygon { polygon } if polygon.mode == PaintMode::Fill));
+        let first_row_text = ops.iter().position(|op| matches!(op, Op::WriteText { items, .. } if items == &vec![TextItem::Text("東京".to_string())]));
+        let data_row_stroke = testing::rects_in(&ops)
+            .iter()
+            .position(|rect| (rect.top_mm(A5_HEIGHT) - 49.0).abs() < 0.01 && rect.height_mm > 5.0);
+        assert!(data_row_stroke.is_some());
+        assert!(last_fill < first_row_text);
+        let strokes_before = ops[..last_fill.unwrap()]
+            .iter()
+            .filter(|op| matches!(op, Op::DrawPolygon { polygon } if polygon.mode == PaintMode::Stroke))
+            .count();
+        // 外枠・承認欄・基本情報・データ表の見出しのみ
+        assert_eq!(strokes_before, 1 + 6 + 5 + layout.columns.len());
+    }
+
+    #[test]
+    fn test_row_shading_stays_inside_frame() {
+        let mut columns = default_columns();
+        columns[2].width_mm = 60.0;
+        let layout = LayoutConfig::new()
+            .with_columns(columns)
+            .with_row_shading(Shading {
+                every_n_rows: 3,
+                ..Default::default()
+            });
+        let fonts = FontSet::single(FontId("F1".to_string()));
+        let ops = PageRenderer::new(&layout, &FormatOptions::default(), &fonts).blank_page();
+
+        let fills = filled_rects(&ops);
+        assert_eq!(fills.len(), 2);
+        for (polygon, _) in &fills {
+            let right = polygon.rings[0].points.iter().map(|p| pt_to_mm(p.p.x.0)).fold(f32::MIN, f32::max);
+            assert!(right <= A5_WIDTH - Margins::DEFAULT.right + 0.01);
+        }
+    }
+
+    /// テキストを描画するWriteTextの位置
+    fn write_text_index(ops: &[Op], text: &str) -> usize {
+        ops.iter()
+            .position(|op| matches!(op, Op::WriteText { items, .. } if items == &vec![TextItem::Text(text.to_string())]))
+            .unwrap_or_else(|| panic!("テキストがありません: {}", text))
+    }
+
+    #[test]
+    fn test_negative_amount_color() {
+        let colors = ColorScheme {
+            negative_amount: RgbColor::RED,
+            ..Default::default()
+        };
+        let layout = LayoutConfig::new().with_colors(colors);
+        let fonts = FontSet::single(FontId("F1".to_string()));
+        let format = FormatOptions::default();
+        let mut item = example_item();
+        item.ryohi[1].price = Some(-1200);
+        item.ryohi[1].vol = None;
+        let ops = PageRenderer::new(&layout, &format, &fonts).page_operations(&item, &mut GenerationReport::default());
+
+        // 負の旅費日当は直前に赤を設定し、描画後に文字色へ戻す
+        let red = pdf_color(RgbColor::RED);
+        let black = pdf_color(RgbColor::BLACK);
+        let index = write_text_index(&ops, "-1,200");
+        assert_eq!(ops[index - 1], Op::SetFillColor { col: red.clone() });
+        assert_eq!(ops[index + 1], Op::SetFillColor { col: black.clone() });
+
+        // 正の金額・金額以外の列は文字色のまま
+        let index = write_text_index(&ops, "15,000");
+        assert_eq!(ops[index - 1], Op::SetFillColor { col: black.clone() });
+        assert_eq!(ops[index + 1], Op::EndTextSection);
+        let index = write_text_index(&ops, "福岡");
+        assert_eq!(ops[index - 1], Op::SetFillColor { col: black });
+        let red_texts = ops.iter().filter(|op| **op == Op::SetFillColor { col: red.clone() }).count();
+        // 旅費日当・計の2か所
+        assert_eq!(red_texts, 2);
+    }
+
+    #[test]
+    fn test_color_scheme() {
+        let blue = RgbColor::new(0.0, 0.0, 0.4);
+        let colors = ColorScheme {
+            grid: blue,
+            title: RgbColor::RED,
+            ..Default::default()
+        };
+        let layout = LayoutConfig::new().with_colors(colors);
+        let fonts = FontSet::single(FontId("F1".to_string()));
+        let ops = PageRenderer::new(&layout, &FormatOptions::default(), &fonts).blank_page();
+
+        let outlines: Vec<&Op> = ops.iter().filter(|op| matches!(op, Op::SetOutlineColor { .. })).collect();
+        assert_eq!(outlines, vec![&Op::SetOutlineColor { col: pdf_color(blue) }]);
+        let title = write_text_index(&ops, TITLE);
+        assert!(ops[..title].contains(&Op::SetFillColor { col: pdf_color(RgbColor::RED) }));
+        assert_eq!(ops[title + 1], Op::SetFillColor { col: pdf_color(RgbColor::BLACK) });
+    }
+
+    #[test]
+    fn test_renderers_with_different_layouts() {
+        let fonts = FontSet::single(FontId("F1".to_string()));
+        let format = FormatOptions::default();
+        let default_layout = LayoutConfig::default();
+        let wide_layout = LayoutConfig::new().with_approval_labels(vec![
+            "社長".to_string(),
+            "部長".to_string(),
+            "会計".to_string(),
+            "所属".to_string(),
+        ]);
+        let item = example_item();
+
+        let (default_pages, wide_pages) = std::thread::scope(|s| {
+            let default_pages = s.spawn(|| PageRenderer::new(&default_layout, &format, &fonts).blank_page());
+            let wide_pages = s.spawn(|| PageRenderer::new(&wide_layout, &format, &fonts).item_pages(&item, &mut GenerationReport::default()));
+            (default_pages.join().unwrap(), wide_pages.join().unwrap())
+        });
+        assert!(testing::find_text(&default_pages, "部長").is_none());
+        assert!(testing::find_text(&wide_pages[0], "部長").is_some());
+        assert!(testing::find_text(&wide_pages[0], "山田太郎").is_some());
+    }
+
+    #[test]
+    fn test_fit_font_size() {
+        assert_eq!(fit_font_size("12-34", 10.0, 26.75), 10.0);
+        let size = fit_font_size("品川ナンバー300あ12-34", 10.0, 26.75);
+        assert!(size < 10.0);
+        assert!(estimate_text_width("品川ナンバー300あ12-34", size) <= 26.75 + 0.01);
+        assert_eq!(fit_font_size(&"長".repeat(40), 10.0, 26.75), MIN_FIT_FONT_SIZE);
+    }
+
+    #[test]
+    fn test_estimate_text_width() {
+        let em = pt_to_mm(10.0);
+        assert!((estimate_text_width("12,420", 10.0) - em * 3.0).abs() < 0.001);
+        assert!((estimate_text_width("東京", 10.0) - em * 2.0).abs() < 0.001);
+        assert!((tracked_text_width("東京", 10.0, 2.0) - (em * 2.0 + 2.0)).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_format_date_mmdd() {
+        assert_eq!(format_date_mmdd("2024-01-15"), Some("01　 15".to_string()));
+        assert_eq!(format_date_mmdd("invalid"), Some("invalid".to_string()));
+    }
+
+    #[test]
+    fn test_expand_two_digit_year() {
+        // 2026年基準: 1946年〜2045年
+        assert_eq!(expand_two_digit_year(24, 2026), 2024);
+        assert_eq!(expand_two_digit_year(45, 2026), 2045);
+        assert_eq!(expand_two_digit_year(46, 2026), 1946);
+        assert_eq!(expand_two_digit_year(75, 2026), 1975);
+        assert_eq!(expand_two_digit_year(99, 2026), 1999);
+        assert_eq!(expand_two_digit_year(0, 2026), 2000);
+
+        // 2080年基準: 2000年〜2099年（固定の境界では1970年代になる年も20xx年）
+        assert_eq!(expand_two_digit_year(75, 2080), 2075);
+        assert_eq!(expand_two_digit_year(99, 2080), 2099);
+        assert_eq!(expand_two_digit_year(0, 2080), 2000);
+    }
+
+    #[test]
+    fn test_format_pay_day_full() {
+        assert_eq!(
+            format_pay_day_full("2024/01/25", 2026),
+            Some("清算日　2024年 01月 25日".to_string())
+        );
+        assert_eq!(
+            format_pay_day_full("2024-01-25", 2026),
+            Some("清算日　2024年 01月 25日".to_string())
+        );
+
+        // 2桁の年は基準の年の80年前〜19年後
+        assert_eq!(format_pay_day_full("24/1/5", 2026), Some("清算日　2024年 1月 5日".to_string()));
+        assert_eq!(format_pay_day_full("24/01/25", 2026), Some("清算日　2024年 01月 25日".to_string()));
+        assert_eq!(format_pay_day_full("99-12-31", 2026), Some("清算日　1999年 12月 31日".to_string()));
+
+        // 年月日の3つでない・空の部分がある
+        for invalid in ["2024/01", "2024/01/25/1", "", "2024/1/", "/1/5", "20240125"] {
+            assert_eq!(format_pay_day_full(invalid, 2026), None, "{}", invalid);
+        }
+    }
+}
@@ -0,0 +1,69 @@
+//! 承認欄の印影画像
+//!
+//! 物理的な押印の代わりに、承認欄（社長・会計・所属）の空欄にPNG画像の印影を描画する
+
+use std::path::Path;
+use std::sync::Arc;
+
+use printpdf::RawImage;
+
+use crate::error::PdfError;
+
+/// 印影画像（PNG）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SealImage {
+    /// PNGデータ
+    png: Arc<Vec<u8>>,
+}
+
+impl SealImage {
+    /// PNGデータから作成
+    ///
+    /// 画像の検証はPDF生成時に行う
+    pub fn from_png(png: impl Into<Vec<u8>>) -> Self {
+        Self {
+            png: Arc::new(png.into()),
+        }
+    }
+
+    /// PNGファイルから読み込み
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, PdfError> {
+        Ok(Self::from_png(std::fs::read(path)?))
+    }
+
+    /// PNGデータを取得
+    pub fn png(&self) -> &[u8] {
+        &self.png
+    }
+
+    /// PDFに埋め込む画像にデコード
+    pub(crate) fn decode(&self) -> Result<RawImage, PdfError> {
+        RawImage::decode_from_bytes(&self.png, &mut Vec::new())
+            .map_err(|e| PdfError::Validation(format!("印影画像を読み込めません: {}", e)))
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// 2×1ピクセルのPNG
+    pub(crate) const TEST_PNG: &[u8] = &[
+        0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x48, 0x44, 0x52, 0x00, 0x00,
+        0x00, 0x02, 0x00, 0x00, 0x00, 0x01, 0x08, 0x02, 0x00, 0x00, 0x00, 0x7b, 0x40, 0xe8, 0xdd, 0x00, 0x00, 0x00,
+        0x0d, 0x49, 0x44, 0x41, 0x54, 0x78, 0x9c, 0x63, 0xf8, 0xcf, 0xc0, 0x00, 0x44, 0x00, 0x08, 0xfe, 0x01, 0xff,
+        0xc6, 0x9e, 0x79, 0xf7, 0x00, 0x00, 0x00, 0x00, 0x49, 0x45, 0x4e, 0x44, 0xae, 0x42, 0x60, 0x82,
+    ];
+
+    #[test]
+    fn test_decode_png() {
+        let image = SealImage::from_png(TEST_PNG).decode().unwrap();
+        assert_eq!((image.width, image.height), (2, 1));
+    }
+
+    #[test]
+    fn test_decode_invalid_png() {
+        let result = SealImage::from_png(b"not a png".to_vec()).decode();
+        assert!(matches!(result, Err(PdfError::Validation(_))));
+    }
+}
@@ -0,0 +1,302 @@
+//! ページ操作のSVG変換
+//!
+//! printpdfのページ操作（`Op`）の矩形・線・テキストをSVGに変換する（WebのプレビューでPDFビューアを使わずに表示する用途）。
+//! 座標はmm単位（`viewBox` はページの大きさ）。フォントは埋め込まず `sans-serif` で表示するため、文字幅はPDFと一致しない。
+//! 印影画像・クリッピングは描画しない
+
+use std::fmt::Write;
+
+use printpdf::{Color, LinePoint, Op, PaintMode, TextItem};
+
+use crate::pdf::interpret::State;
+use crate::pdf::layout::pt_to_mm;
+
+/// 描画の色・線の状態
+#[derive(Debug, Clone)]
+struct Style {
+    /// 塗りつぶしの色（テキストにも使用）
+    fill: String,
+    /// 線の色
+    stroke: String,
+    /// 線の太さ (pt)
+    stroke_width: f32,
+    /// 破線のパターン (pt、空の場合は実線)
+    dash: Vec<i64>,
+}
+
+impl Default for Style {
+    fn default() -> Self {
+        Self {
+            fill: "#000000".to_string(),
+            stroke: "#000000".to_string(),
+            stroke_width: 1.0,
+            dash: Vec::new(),
+        }
+    }
+}
+
+/// ページ操作をSVGに変換
+///
+/// `width_mm` × `height_mm` はページの大きさ（A5横の場合は `A5_WIDTH` × `A5_HEIGHT`）
+pub fn ops_to_svg(ops: &[Op], width_mm: f32, height_mm: f32) -> String {
+    let mut svg = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}mm" height="{h}mm" viewBox="0 0 {w} {h}">"#,
+        w = width_mm,
+        h = height_mm
+    );
+    svg.push('\n');
+    svg.push_str(&format!(
+        r##"<rect width="{}" height="{}" fill="#ffffff"/>"##,
+        width_mm, height_mm
+    ));
+    svg.push('\n');
+
+    let mut state = State::default();
+    let mut styles = vec![Style::default()];
+    // 描画中のテキスト要素の行の開始位置（同じ行への続けての描画は同じ要素に連結）
+    let mut open_text: Option<(f32, f32)> = None;
+    for op in ops {
+        if let Op::WriteText { items, .. } = op {
+            let text: String = items
+                .iter()
+                .filter_map(|item| match item {
+                    TextItem::Text(text) => Some(text.as_str()),
+                    TextItem::Offset(_) => None,
+                })
+                .collect();
+            if open_text != Some(state.line) {
+                close_text(&mut svg, &mut open_text);
+                let style = styles.last().expect("描画状態がありません");
+                let (x, y) = state.transform(state.line);
+                let scale = state.scale();
+                let _ = write!(
+                    svg,
+                    r#"<text x="{:.2}" y="{:.2}" font-size="{:.2}" font-family="sans-serif" fill="{}""#,
+                    pt_to_mm(x),
+                    height_mm - pt_to_mm(y),
+                    pt_to_mm(state.font_size * scale),
+                    style.fill
+                );
+                if state.char_spacing != 0.0 {
+                    let _ = write!(svg, r#" letter-spacing="{:.2}""#, pt_to_mm(state.char_spacing * scale));
+                }
+                svg.push('>');
+                open_text = Some(state.line);
+            }
+            svg.push_str(&escape(&text));
+            continue;
+        }
+
+        // フォントの切り替えなどテキストの状態の変更では要素を閉じない
+        if matches!(
+            op,
+            Op::StartTextSection
+                | Op::EndTextSection
+                | Op::SaveGraphicsState
+                | Op::RestoreGraphicsState
+                | Op::SetTransformationMatrix { .. }
+                | Op::DrawPolygon { .. }
+                | Op::DrawLine { .. }
+        ) {
+            close_text(&mut svg, &mut open_text);
+        }
+        match op {
+            Op::SaveGraphicsState => {
+                let style = styles.last().expect("描画状態がありません").clone();
+                styles.push(style);
+            }
+            Op::RestoreGraphicsState if styles.len() > 1 => {
+                styles.pop();
+            }
+            Op::SetFillColor { col } => current(&mut styles).fill = svg_color(col),
+            Op::SetOutlineColor { col } => current(&mut styles).stroke = svg_color(col),
+            Op::SetOutlineThickness { pt } => current(&mut styles).stroke_width = pt.0,
+            Op::SetLineDashPattern { dash } => current(&mut styles).dash = dash.as_array(),
+            Op::DrawPolygon { polygon } => {
+                let (fill, stroke) = match polygon.mode {
+                    PaintMode::Clip => continue,
+                    PaintMode::Fill => (true, false),
+                    PaintMode::Stroke => (false, true),
+                    PaintMode::FillStroke => (true, true),
+                };
+                for ring in &polygon.rings {
+                    let d = path_data(&state, &ring.points, true, height_mm);
+                    push_path(&mut svg, &d, &state, styles.last().expect("描画状態がありません"), fill, stroke);
+                }
+            }
+            Op::DrawLine { line } => {
+                let d = path_data(&state, &line.points, line.is_closed, height_mm);
+                push_path(&mut svg, &d, &state, styles.last().expect("描画状態がありません"), false, true);
+            }
+            _ => {}
+        }
+        state.apply(op);
+    }
+    close_text(&mut svg, &mut open_text);
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// 現在の描画状態
+fn current(styles: &mut [Style]) -> &mut Style {
+    styles.last_mut().expect("描画状態がありません")
+}
+
+/// 描画中のテキスト要素を閉じる
+fn close_text(svg: &mut String, open_text: &mut Option<(f32, f32)>) {
+    if open_text.take().is_some() {
+        svg.push_str("</text>\n");
+    }
+}
+
+/// 頂点列をパスのデータに変換（ベジェ曲線の制御点は直線で結ぶ）
+fn path_data(state: &State, points: &[LinePoint], closed: bool, height_mm: f32) -> String {
+    let mut d = String::new();
+    for (i, point) in points.iter().enumerate() {
+        let (x, y) = state.transform((point.p.x.0, point.p.y.0));
+        let command = if i == 0 { 'M' } else { 'L' };
+        let _ = write!(d, "{}{:.2} {:.2} ", command, pt_to_mm(x), height_mm - pt_to_mm(y));
+    }
+    if closed {
+        d.push('Z');
+    }
+    d.trim_end().to_string()
+}
+
+/// パス要素を追加
+fn push_path(svg: &mut String, d: &str, state: &State, style: &Style, fill: bool, stroke: bool) {
+    if d.is_empty() {
+        return;
+    }
+    let _ = write!(svg, r#"<path d="{}" fill="{}""#, d, if fill { style.fill.as_str() } else { "none" });
+    if stroke {
+        let scale = state.scale();
+        let _ = write!(
+            svg,
+            r#" stroke="{}" stroke-width="{:.2}""#,
+            style.stroke,
+            pt_to_mm(style.stroke_width * scale)
+        );
+        if !style.dash.is_empty() {
+            let dash: Vec<String> = style
+                .dash
+                .iter()
+                .map(|&len| format!("{:.2}", pt_to_mm(len as f32 * scale)))
+                .collect();
+            let _ = write!(svg, r#" stroke-dasharray="{}""#, dash.join(" "));
+        }
+    }
+    svg.push_str("/>\n");
+}
+
+/// PDFの色をSVGの色（`#rrggbb`）に変換
+fn svg_color(color: &Color) -> String {
+    let (r, g, b) = match color {
+        Color::Rgb(rgb) => (rgb.r, rgb.g, rgb.b),
+        Color::Greyscale(grey) => (grey.percent, grey.percent, grey.percent),
+        Color::Cmyk(cmyk) => cmyk_to_rgb(cmyk.c, cmyk.m, cmyk.y, cmyk.k),
+        Color::SpotColor(spot) => cmyk_to_rgb(spot.c, spot.m, spot.y, spot.k),
+    };
+    let channel = |v: f32| (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+    format!("#{:02x}{:02x}{:02x}", channel(r), channel(g), channel(b))
+}
+
+/// CMYKをRGBに近似
+fn cmyk_to_rgb(c: f32, m: f32, y: f32, k: f32) -> (f32, f32, f32) {
+    ((1.0 - c) * (1.0 - k), (1.0 - m) * (1.0 - k), (1.0 - y) * (1.0 - k))
+}
+
+/// XMLの特殊文字をエスケープ
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use printpdf::{FontId, Mm, Point, Polygon, PolygonRing, Pt, Rgb, WindingOrder};
+
+    fn rect(x: f32, y: f32, width: f32, height: f32, mode: PaintMode) -> Op {
+        let point = |x: f32, y: f32| LinePoint {
+            p: Point::new(Mm(x), Mm(y)),
+            bezier: false,
+        };
+        Op::DrawPolygon {
+            polygon: Polygon {
+                rings: vec![PolygonRing {
+                    points: vec![point(x, y), point(x + width, y), point(x + width, y + height), point(x, y + height)],
+                }],
+                mode,
+                winding_order: WindingOrder::NonZero,
+            },
+        }
+    }
+
+    #[test]
+    fn test_rect_and_text() {
+        let font = FontId("F1".to_string());
+        let ops = vec![
+            Op::SetOutlineThickness { pt: Pt(1.0) },
+            Op::SetFillColor {
+                col: Color::Rgb(Rgb::new(1.0, 0.0, 0.0, None)),
+            },
+            rect(10.0, 20.0, 30.0, 5.0, PaintMode::Stroke),
+            rect(10.0, 20.0, 30.0, 5.0, PaintMode::Fill),
+            Op::StartTextSection,
+            Op::SetTextCursor { pos: Point::new(Mm(12.0), Mm(21.0)) },
+            Op::SetFontSize { size: Pt(10.0), font: font.clone() },
+            Op::WriteText {
+                items: vec![TextItem::Text("A&B".to_string())],
+                font: font.clone(),
+            },
+            Op::WriteText {
+                items: vec![TextItem::Text("<続き>".to_string())],
+                font,
+            },
+            Op::EndTextSection,
+        ];
+        let svg = ops_to_svg(&ops, 100.0, 50.0);
+        assert!(svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="100mm" height="50mm" viewBox="0 0 100 50">"#));
+        // Y座標は上端からの距離に変換
+        assert!(svg.contains(r##"<path d="M10.00 30.00 L40.00 30.00 L40.00 25.00 L10.00 25.00 Z" fill="none" stroke="#000000" stroke-width="0.35"/>"##));
+        assert!(svg.contains(r##"<path d="M10.00 30.00 L40.00 30.00 L40.00 25.00 L10.00 25.00 Z" fill="#ff0000"/>"##));
+        // 同じ行への続けての描画は1つのテキスト要素
+        assert!(svg.contains(r##"<text x="12.00" y="29.00" font-size="3.53" font-family="sans-serif" fill="#ff0000">A&amp;B&lt;続き&gt;</text>"##));
+        assert!(svg.trim_end().ends_with("</svg>"));
+    }
+
+    #[test]
+    fn test_transform_and_dash() {
+        let ops = vec![
+            Op::SaveGraphicsState,
+            Op::SetTransformationMatrix {
+                matrix: printpdf::CurTransMat::Raw([0.5, 0.0, 0.0, 0.5, 0.0, 0.0]),
+            },
+            Op::SetLineDashPattern {
+                dash: printpdf::LineDashPattern::from_array(&[2, 2], 0),
+            },
+            rect(0.0, 0.0, 20.0, 20.0, PaintMode::Stroke),
+            Op::RestoreGraphicsState,
+            rect(0.0, 0.0, 20.0, 20.0, PaintMode::Stroke),
+        ];
+        let svg = ops_to_svg(&ops, 100.0, 50.0);
+        let paths: Vec<&str> = svg.lines().filter(|line| line.starts_with("<path")).collect();
+        assert_eq!(paths.len(), 2);
+        assert!(paths[0].starts_with(r#"<path d="M0.00 50.00 L10.00 50.00 L10.00 40.00"#));
+        assert!(paths[0].contains("stroke-dasharray"));
+        // 描画状態の復元後は実線・等倍
+        assert!(paths[1].starts_with(r#"<path d="M0.00 50.00 L20.00 50.00 L20.00 30.00"#));
+        assert!(!paths[1].contains("stroke-dasharray"));
+    }
+}
@@ -0,0 +1,240 @@
+//! 消費税の内訳計算
+//!
+//! 旅費項目の税率（`Ryohi::tax_rate`）ごとに対象額と消費税額を集計する
+
+use std::collections::BTreeMap;
+
+use crate::models::{Item, Ryohi};
+use crate::pdf::text_utils::{line_total, Rounding};
+use crate::warning::Warning;
+
+/// 記載の税額と計算した税額の許容差（円）
+pub const TAX_TOLERANCE: i64 = 1;
+
+/// 消費税の端数処理の単位
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TaxRoundingUnit {
+    /// 旅費項目ごとに端数処理して合計
+    PerLine,
+    /// 税率ごとの合計額に対して1回だけ端数処理
+    #[default]
+    PerTotal,
+}
+
+/// 消費税の計算オプション
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TaxOptions {
+    /// 端数処理
+    pub rounding: Rounding,
+    /// 端数処理の単位
+    pub unit: TaxRoundingUnit,
+    /// 金額が税込か（falseの場合は税抜の金額に税率を掛ける）
+    pub inclusive: bool,
+}
+
+impl Default for TaxOptions {
+    fn default() -> Self {
+        Self {
+            rounding: Rounding::Floor,
+            unit: TaxRoundingUnit::PerTotal,
+            inclusive: false,
+        }
+    }
+}
+
+/// 税率ごとの内訳
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TaxLine {
+    /// 税率（%）
+    pub rate: u8,
+    /// 対象額（税抜）
+    pub base: i64,
+    /// 消費税額
+    pub tax: i64,
+}
+
+/// 消費税の内訳
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TaxBreakdown {
+    /// 税率ごとの内訳（税率の昇順）
+    pub lines: Vec<TaxLine>,
+    /// 消費税の合計
+    pub total_tax: i64,
+}
+
+impl TaxBreakdown {
+    /// 旅費項目から消費税の内訳を計算
+    ///
+    /// 税率の無い旅費項目は対象外。計（単価×数量）は `line_rounding` で端数処理した値を使用する
+    pub fn compute(ryohi: &[Ryohi], line_rounding: Rounding, options: &TaxOptions) -> Self {
+        // 税率ごとの (金額の合計, 項目ごとに端数処理した税額の合計)
+        let mut by_rate: BTreeMap<u8, (i64, i64)> = BTreeMap::new();
+        for r in ryohi {
+            let (Some(rate), Some(price)) = (r.tax_rate, r.price) else {
+                continue;
+            };
+            let amount = line_total(price, r.vol, line_rounding) as i64;
+            let entry = by_rate.entry(rate).or_default();
+            entry.0 += amount;
+            entry.1 += tax_of(amount, rate, options);
+        }
+
+        let lines: Vec<TaxLine> = by_rate
+            .into_iter()
+            .map(|(rate, (amount, line_tax))| {
+                let tax = match options.unit {
+                    TaxRoundingUnit::PerLine => line_tax,
+                    TaxRoundingUnit::PerTotal => tax_of(amount, rate, options),
+                };
+                let base = if options.inclusive { amount - tax } else { amount };
+                TaxLine { rate, base, tax }
+            })
+            .collect();
+        let total_tax = lines.iter().map(|line| line.tax).sum();
+        Self { lines, total_tax }
+    }
+
+    /// 内訳が無いか（税率の指定された旅費項目が無い）
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+}
+
+/// 金額に対する消費税額
+fn tax_of(amount: i64, rate: u8, options: &TaxOptions) -> i64 {
+    let rate = rate as f64;
+    let tax = if options.inclusive {
+        amount as f64 * rate / (100.0 + rate)
+    } else {
+        amount as f64 * rate / 100.0
+    };
+    options.rounding.apply(tax) as i64
+}
+
+/// 記載の税額（`Item::tax`）と計算した税額を照合
+///
+/// 税率の指定された旅費項目があり、差が許容差（±1円）を超える場合に警告を返す
+pub fn check_item_taxes(items: &[Item], line_rounding: Rounding, options: &TaxOptions) -> Vec<Warning> {
+    items
+        .iter()
+        .enumerate()
+        .filter_map(|(item_index, item)| {
+            let stated = item.tax?;
+            let breakdown = TaxBreakdown::compute(&item.ryohi, line_rounding, options);
+            if breakdown.is_empty() {
+                return None;
+            }
+            let stated = stated.round() as i64;
+            ((stated - breakdown.total_tax).abs() > TAX_TOLERANCE).then_some(Warning::TaxMismatch {
+                item_index,
+                stated,
+                computed: breakdown.total_tax,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ryohi(price: i32, rate: u8) -> Ryohi {
+        Ryohi {
+            price: Some(price),
+            tax_rate: Some(rate),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_mixed_rates_breakdown() {
+        let items = vec![ryohi(1000, 10), ryohi(2500, 8), ryohi(3000, 10), ryohi(500, 0)];
+        let breakdown = TaxBreakdown::compute(&items, Rounding::Round, &TaxOptions::default());
+        assert_eq!(
+            breakdown.lines,
+            vec![
+                TaxLine { rate: 0, base: 500, tax: 0 },
+                TaxLine { rate: 8, base: 2500, tax: 200 },
+                TaxLine { rate: 10, base: 4000, tax: 400 },
+            ]
+        );
+        assert_eq!(breakdown.total_tax, 600);
+    }
+
+    #[test]
+    fn test_per_line_and_per_total_rounding_differ() {
+        // 105円×10% = 10.5円 → 項目ごとの切り捨ては10円×2、合計に対しては21円
+        let items = vec![ryohi(105, 10), ryohi(105, 10), ryohi(1015, 8)];
+        let per_total = TaxBreakdown::compute(&items, Rounding::Round, &TaxOptions::default());
+        assert_eq!(per_total.lines[1], TaxLine { rate: 10, base: 210, tax: 21 });
+        assert_eq!(per_total.lines[0], TaxLine { rate: 8, base: 1015, tax: 81 });
+        assert_eq!(per_total.total_tax, 102);
+
+        let options = TaxOptions {
+            unit: TaxRoundingUnit::PerLine,
+            ..Default::default()
+        };
+        let per_line = TaxBreakdown::compute(&items, Rounding::Round, &options);
+        assert_eq!(per_line.lines[1], TaxLine { rate: 10, base: 210, tax: 20 });
+        assert_eq!(per_line.total_tax, 101);
+    }
+
+    #[test]
+    fn test_inclusive_amounts() {
+        let options = TaxOptions {
+            inclusive: true,
+            ..Default::default()
+        };
+        let breakdown = TaxBreakdown::compute(&[ryohi(1100, 10), ryohi(1080, 8)], Rounding::Round, &options);
+        assert_eq!(breakdown.lines[0], TaxLine { rate: 8, base: 1000, tax: 80 });
+        assert_eq!(breakdown.lines[1], TaxLine { rate: 10, base: 1000, tax: 100 });
+    }
+
+    #[test]
+    fn test_vol_and_missing_rate() {
+        let items = vec![
+            Ryohi {
+                price: Some(1000),
+                vol: Some(2.0),
+                tax_rate: Some(10),
+                ..Default::default()
+            },
+            Ryohi {
+                price: Some(5000),
+                ..Default::default()
+            },
+        ];
+        let breakdown = TaxBreakdown::compute(&items, Rounding::Round, &TaxOptions::default());
+        assert_eq!(breakdown.lines, vec![TaxLine { rate: 10, base: 2000, tax: 200 }]);
+        assert!(TaxBreakdown::compute(&items[1..], Rounding::Round, &TaxOptions::default()).is_empty());
+    }
+
+    #[test]
+    fn test_check_item_taxes() {
+        let item = |tax: Option<f64>| Item {
+            tax,
+            ryohi: vec![ryohi(1000, 10), ryohi(1000, 8)],
+            ..Default::default()
+        };
+        let options = TaxOptions::default();
+        // 計算値は180円（±1円は許容）
+        let items = vec![item(Some(181.0)), item(None), item(Some(200.0))];
+        let warnings = check_item_taxes(&items, Rounding::Round, &options);
+        assert_eq!(
+            warnings,
+            vec![Warning::TaxMismatch {
+                item_index: 2,
+                stated: 200,
+                computed: 180,
+            }]
+        );
+
+        // 税率の指定が無い場合は照合しない
+        let untaxed = Item {
+            tax: Some(999.0),
+            ryohi: vec![Ryohi::default()],
+            ..Default::default()
+        };
+        assert!(check_item_taxes(&[untaxed], Rounding::Round, &options).is_empty());
+    }
+}
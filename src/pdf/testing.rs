@@ -0,0 +1,763 @@
+//! ページ操作の検証用ヘルパー
+//!
+//! printpdfのページ操作（`Op`）の列を順に解釈し、描画されたテキスト・矩形の位置を取得する。
+//! テキストカーソル・フォントサイズ・変換行列（`SaveGraphicsState` / `RestoreGraphicsState` を含む）を追跡するため、
+//! 余白や面付けの変換後の位置で検証できる。
+//!
+//! 座標はPDFの座標系（ページ左下が原点）のmm単位。
+//! レイアウト定数（ページ上端からの距離）と比較する場合は `PlacedText::top_mm` を使用する。
+//!
+//! 生成したPDFのバイト列からは `extract_text` でテキストを抽出でき、
+//! フォントの埋め込み・エンコードを経ても文字が保たれていることを検証できる。
+//!
+//! 日本語フォントの無い環境では `MockFont` の空のフォントで生成を検証できる
+//! （テキストの抽出まで検証する場合は日本語の文字に空のグリフを割り当てた `MockFont::japanese`）。
+//!
+//! テスト・サンプルで使用するアイテムは `example_items` / `example_item` で作成し、
+//! レイアウトの回帰は `assert_ops_snapshot` で登録済みのページ操作と比較する。
+//!
+//! 本モジュールはテスト時、または `test-util` feature有効時のみ利用できる。
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use lopdf::content::Content;
+use lopdf::Object;
+use printpdf::{Op, TextItem};
+use regex::Regex;
+
+use crate::models::{Item, Ryohi};
+use crate::pdf::fonts::FontCache;
+use crate::pdf::interpret::State;
+use crate::pdf::layout::pt_to_mm;
+
+/// 描画されたテキスト
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlacedText {
+    /// テキスト
+    pub text: String,
+    /// 描画開始位置のX座標 (mm)
+    pub x_mm: f32,
+    /// ベースラインのY座標 (mm、ページ下端から)
+    pub y_mm: f32,
+    /// フォントサイズ (pt)
+    pub size: f32,
+}
+
+impl PlacedText {
+    /// ページ上端からのベースラインの距離 (mm)
+    pub fn top_mm(&self, page_height: f32) -> f32 {
+        page_height - self.y_mm
+    }
+}
+
+/// 描画された矩形
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlacedRect {
+    /// 左下のX座標 (mm)
+    pub x_mm: f32,
+    /// 左下のY座標 (mm、ページ下端から)
+    pub y_mm: f32,
+    /// 幅 (mm)
+    pub width_mm: f32,
+    /// 高さ (mm)
+    pub height_mm: f32,
+}
+
+impl PlacedRect {
+    /// ページ上端から矩形の上辺までの距離 (mm)
+    pub fn top_mm(&self, page_height: f32) -> f32 {
+        page_height - self.y_mm - self.height_mm
+    }
+}
+
+/// 描画されたテキストを描画順に取得
+pub fn texts_in(ops: &[Op]) -> Vec<PlacedText> {
+    let mut state = State::default();
+    let mut texts = Vec::new();
+    for op in ops {
+        match op {
+            Op::WriteText { items, .. } => {
+                let text: String = items
+                    .iter()
+                    .filter_map(|item| match item {
+                        TextItem::Text(text) => Some(text.as_str()),
+                        TextItem::Offset(_) => None,
+                    })
+                    .collect();
+                let (x, y) = state.transform(state.line);
+                texts.push(PlacedText {
+                    text,
+                    x_mm: pt_to_mm(x),
+                    y_mm: pt_to_mm(y),
+                    size: state.font_size,
+                });
+            }
+            _ => state.apply(op),
+        }
+    }
+    texts
+}
+
+/// 描画された矩形（4頂点の軸に平行な多角形）を描画順に取得
+pub fn rects_in(ops: &[Op]) -> Vec<PlacedRect> {
+    let mut state = State::default();
+    let mut rects = Vec::new();
+    for op in ops {
+        match op {
+            Op::DrawPolygon { polygon } => {
+                for ring in &polygon.rings {
+                    let points: Vec<(f32, f32)> = ring
+                        .points
+                        .iter()
+                        .map(|point| state.transform((point.p.x.0, point.p.y.0)))
+                        .collect();
+                    if let Some(rect) = axis_aligned_rect(&points) {
+                        rects.push(rect);
+                    }
+                }
+            }
+            _ => state.apply(op),
+        }
+    }
+    rects
+}
+
+/// 指定した文字列を含む最初のテキストを取得
+pub fn find_text(ops: &[Op], needle: &str) -> Option<PlacedText> {
+    texts_in(ops).into_iter().find(|text| text.text.contains(needle))
+}
+
+/// PDFのバイト列から全ページのテキストを抽出
+///
+/// ページ順に、テキストセクションごとに改行で区切って連結する。
+/// 埋め込みフォントは `ToUnicode` で復号するため、日本語のテキストも抽出できる
+/// （printpdfが出力するCMapはlopdfの `extract_text` では解析できないため独自に解析する）。
+/// `ToUnicode` の無いフォント（標準14フォント）は1バイト1文字として扱う。
+///
+/// # Panics
+/// PDFとして読み込めない場合
+pub fn extract_text(bytes: &[u8]) -> String {
+    let doc = lopdf::Document::load_mem(bytes).expect("PDFを読み込めません");
+    let mut text = String::new();
+    for page_id in doc.get_pages().into_values() {
+        let cmaps: HashMap<Vec<u8>, HashMap<u16, String>> = doc
+            .get_page_fonts(page_id)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|(name, font)| {
+                let stream = font.get_deref(b"ToUnicode", &doc).and_then(Object::as_stream).ok()?;
+                let data = stream.decompressed_content().unwrap_or_else(|_| stream.content.clone());
+                Some((name, parse_to_unicode(&data)))
+            })
+            .collect();
+
+        let content = doc.get_page_content(page_id).expect("ページの内容を読み込めません");
+        let content = Content::decode(&content).expect("ページの内容を解析できません");
+        let mut cmap = None;
+        for operation in &content.operations {
+            match operation.operator.as_str() {
+                "Tf" => {
+                    cmap = operation
+                        .operands
+                        .first()
+                        .and_then(|name| name.as_name().ok())
+                        .and_then(|name| cmaps.get(name));
+                }
+                "Tj" | "TJ" => decode_text(&mut text, cmap, &operation.operands),
+                "ET" if !text.ends_with('\n') => text.push('\n'),
+                _ => {}
+            }
+        }
+    }
+    text
+}
+
+/// テキスト描画演算子の文字列を復号して追加
+fn decode_text(text: &mut String, cmap: Option<&HashMap<u16, String>>, operands: &[Object]) {
+    for operand in operands {
+        match operand {
+            Object::String(bytes, _) => match cmap {
+                Some(cmap) => {
+                    for code in bytes.chunks(2) {
+                        let code = match code {
+                            [high, low] => u16::from_be_bytes([*high, *low]),
+                            [single] => *single as u16,
+                            _ => continue,
+                        };
+                        if let Some(s) = cmap.get(&code) {
+                            text.push_str(s);
+                        }
+                    }
+                }
+                None => text.extend(bytes.iter().map(|&b| b as char)),
+            },
+            Object::Array(items) => decode_text(text, cmap, items),
+            _ => {}
+        }
+    }
+}
+
+/// `ToUnicode` CMapの `bfchar` / `bfrange` を解析（2バイトコード → 文字列）
+fn parse_to_unicode(data: &[u8]) -> HashMap<u16, String> {
+    let source = String::from_utf8_lossy(data);
+    let token = Regex::new(r"<([0-9A-Fa-f]*)>|\[|\]|(begin|end)bf(char|range)").unwrap();
+
+    let mut map = HashMap::new();
+    let mut section = None;
+    let mut pending: Vec<Vec<u8>> = Vec::new();
+    let mut array: Option<Vec<Vec<u8>>> = None;
+    for caps in token.captures_iter(&source) {
+        let matched = &caps[0];
+        if let Some(kind) = caps.get(3) {
+            section = (&caps[2] == "begin").then(|| kind.as_str().to_string());
+            pending.clear();
+            continue;
+        }
+        match (matched, section.as_deref()) {
+            ("[", Some("range")) => array = Some(Vec::new()),
+            ("]", Some("range")) => {
+                if let (Some(dsts), [lo, _]) = (array.take(), pending.as_slice()) {
+                    let lo = code_of(lo);
+                    for (i, dst) in dsts.iter().enumerate() {
+                        map.insert(lo.wrapping_add(i as u16), utf16_of(dst));
+                    }
+                }
+                pending.clear();
+            }
+            (_, Some(kind)) if caps.get(1).is_some() => {
+                let bytes = hex_bytes(&caps[1]);
+                if let Some(ref mut dsts) = array {
+                    dsts.push(bytes);
+                    continue;
+                }
+                pending.push(bytes);
+                match (kind, pending.as_slice()) {
+                    ("char", [src, dst]) => {
+                        map.insert(code_of(src), utf16_of(dst));
+                        pending.clear();
+                    }
+                    ("range", [lo, hi, dst]) => {
+                        let (lo, hi) = (code_of(lo), code_of(hi));
+                        let units = utf16_units(dst);
+                        for (i, code) in (lo..=hi).enumerate() {
+                            let mut units = units.clone();
+                            if let Some(last) = units.last_mut() {
+                                *last = last.wrapping_add(i as u16);
+                            }
+                            map.insert(code, String::from_utf16_lossy(&units));
+                        }
+                        pending.clear();
+                    }
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+    map
+}
+
+fn hex_bytes(hex: &str) -> Vec<u8> {
+    hex.as_bytes()
+        .chunks(2)
+        .filter_map(|pair| u8::from_str_radix(std::str::from_utf8(pair).ok()?, 16).ok())
+        .collect()
+}
+
+fn code_of(bytes: &[u8]) -> u16 {
+    bytes.iter().fold(0u16, |code, &b| (code << 8) | b as u16)
+}
+
+fn utf16_units(bytes: &[u8]) -> Vec<u16> {
+    bytes.chunks(2).map(code_of).collect()
+}
+
+fn utf16_of(bytes: &[u8]) -> String {
+    String::from_utf16_lossy(&utf16_units(bytes))
+}
+
+/// 4頂点が軸に平行な矩形を構成する場合に矩形を取得
+fn axis_aligned_rect(points: &[(f32, f32)]) -> Option<PlacedRect> {
+    const EPSILON: f32 = 0.01;
+    if points.len() != 4 {
+        return None;
+    }
+    let axis_aligned = (0..4).all(|i| {
+        let (x1, y1) = points[i];
+        let (x2, y2) = points[(i + 1) % 4];
+        (x1 - x2).abs() < EPSILON || (y1 - y2).abs() < EPSILON
+    });
+    if !axis_aligned {
+        return None;
+    }
+    let min_x = points.iter().map(|p| p.0).fold(f32::INFINITY, f32::min);
+    let max_x = points.iter().map(|p| p.0).fold(f32::NEG_INFINITY, f32::max);
+    let min_y = points.iter().map(|p| p.1).fold(f32::INFINITY, f32::min);
+    let max_y = points.iter().map(|p| p.1).fold(f32::NEG_INFINITY, f32::max);
+    Some(PlacedRect {
+        x_mm: pt_to_mm(min_x),
+        y_mm: pt_to_mm(min_y),
+        width_mm: pt_to_mm(max_x - min_x),
+        height_mm: pt_to_mm(max_y - min_y),
+    })
+}
+
+/// 登録済みのページ操作を更新する環境変数
+pub const UPDATE_SNAPSHOTS_VAR: &str = "UPDATE_SNAPSHOTS";
+
+/// サンプルのアイテム（examples/generate_test.rs、レイアウトの回帰テスト等で共通）
+///
+/// 3件目は明細が1ページに収まらず継続ページになる
+pub fn example_items() -> Vec<Item> {
+    let long_ryohi = (1..=16)
+        .map(|day| Ryohi {
+            date: Some(format!("2024-02-{:02}", day)),
+            dest: Some("北九州".to_string()),
+            detail: vec!["高速代".to_string()],
+            kukan: Some("福岡　北九州".to_string()),
+            price: Some(1200),
+            vol: Some(1.0),
+            ..Default::default()
+        })
+        .collect();
+
+    vec![
+        Item {
+            car: "12-34".to_string(),
+            name: "山田太郎".to_string(),
+            purpose: Some("客先訪問".to_string()),
+            start_date: Some("2024-01-15".to_string()),
+            end_date: Some("2024-01-16".to_string()),
+            price: 25000,
+            tax: Some(2500.0),
+            description: None,
+            ryohi: vec![
+                Ryohi {
+                    date: Some("2024-01-15".to_string()),
+                    dest: Some("東京".to_string()),
+                    detail: vec!["交通費".to_string(), "高速代".to_string()],
+                    kukan: Some("福岡　東京".to_string()),
+                    price: Some(15000),
+                    vol: Some(1.0),
+                    ..Default::default()
+                },
+                Ryohi {
+                    date: Some("2024-01-16".to_string()),
+                    dest: Some("福岡".to_string()),
+                    detail: vec!["交通費".to_string()],
+                    kukan: Some("東京　福岡".to_string()),
+                    price: Some(10000),
+                    vol: Some(1.0),
+                    ..Default::default()
+                },
+            ],
+            office: Some("営業部".to_string()),
+            pay_day: Some("2024/01/25".to_string()),
+            ..Default::default()
+        },
+        Item {
+            car: "56-78".to_string(),
+            name: "鈴木花子".to_string(),
+            purpose: Some("研修参加".to_string()),
+            start_date: Some("2024-01-20".to_string()),
+            end_date: Some("2024-01-20".to_string()),
+            price: 8000,
+            tax: Some(800.0),
+            description: None,
+            ryohi: vec![Ryohi {
+                date: Some("2024-01-20".to_string()),
+                dest: Some("大阪".to_string()),
+                detail: vec!["交通費".to_string(), "宿泊費".to_string()],
+                kukan: Some("福岡　大阪".to_string()),
+                price: Some(8000),
+                vol: Some(1.0),
+                ..Default::default()
+            }],
+            office: Some("開発部".to_string()),
+            pay_day: Some("2024/01/31".to_string()),
+            ..Default::default()
+        },
+        Item {
+            car: "90-12".to_string(),
+            name: "佐藤一郎".to_string(),
+            purpose: Some("定期点検".to_string()),
+            price: 19200,
+            ryohi: long_ryohi,
+            office: Some("整備部".to_string()),
+            pay_day: Some("2024/02/29".to_string()),
+            ..Default::default()
+        },
+    ]
+}
+
+/// サンプルの1件目のアイテム
+pub fn example_item() -> Item {
+    example_items().swap_remove(0)
+}
+
+/// ページ操作を1行1操作のJSONに変換
+pub fn ops_to_json_lines(ops: &[Op]) -> String {
+    let lines: Vec<String> = ops
+        .iter()
+        .map(|op| serde_json::to_string(op).expect("ページ操作をJSONに変換できません"))
+        .collect();
+    format!("[\n{}\n]\n", lines.join(",\n"))
+}
+
+/// ページ操作を登録済みのJSON（`ops_to_json_lines` の形式）と比較
+///
+/// 環境変数 `UPDATE_SNAPSHOTS` が設定されている場合は比較せずに登録済みの内容を更新する
+///
+/// # Panics
+/// 登録済みの内容と異なる場合、または登録済みのファイルが無い場合
+pub fn assert_ops_snapshot(path: impl AsRef<Path>, ops: &[Op]) {
+    let path = path.as_ref();
+    let actual = ops_to_json_lines(ops);
+    if std::env::var_os(UPDATE_SNAPSHOTS_VAR).is_some() {
+        std::fs::write(path, &actual).unwrap_or_else(|e| panic!("{:?} に書き込めません: {}", path, e));
+        return;
+    }
+
+    let expected =
+        std::fs::read_to_string(path).unwrap_or_else(|e| panic!("登録済みのページ操作がありません: {:?}: {}", path, e));
+    if actual != expected {
+        // 差分が大きくなるため最初に異なる行のみ表示する
+        let line = actual
+            .lines()
+            .zip(expected.lines())
+            .position(|(a, e)| a != e)
+            .unwrap_or_else(|| actual.lines().count().min(expected.lines().count()));
+        panic!(
+            "ページ操作が {:?} の{}行目から異なります（意図した変更の場合は {}=1 で更新）\n実際: {}\n登録: {}",
+            path,
+            line + 1,
+            UPDATE_SNAPSHOTS_VAR,
+            actual.lines().nth(line).unwrap_or("（終端）"),
+            expected.lines().nth(line).unwrap_or("（終端）")
+        );
+    }
+}
+
+/// 空のフォントで生成するためのフォント（日本語フォントの無い環境用）
+///
+/// 一時ディレクトリにダミーのフォントファイルを作成し、ファイルの内容に関わらず空のフォントを返すキャッシュと組み合わせる。
+/// フォントファイルはdrop時に削除する
+#[derive(Debug)]
+pub struct MockFont {
+    /// ダミーのフォントファイルのパス
+    path: PathBuf,
+    /// 空のフォントを返すキャッシュ
+    cache: Arc<FontCache>,
+}
+
+impl MockFont {
+    /// ダミーのフォントファイルを作成（`name` はテストごとに異なる名前にする）
+    ///
+    /// # Panics
+    /// フォントファイルを作成できない場合
+    pub fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("print_pdf_mock_font_{}_{}.ttf", name, std::process::id()));
+        std::fs::write(&path, b"mock font").expect("ダミーのフォントファイルを作成できません");
+        Self {
+            path,
+            cache: Arc::new(FontCache::with_parser(|_| Some(printpdf::ParsedFont::default()))),
+        }
+    }
+
+    /// ダミーのフォントファイルのパス（`PdfConfig::with_font_path` 等に指定する）
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 空のフォントを返すキャッシュ（`with_font_cache` に指定する）
+    pub fn cache(&self) -> Arc<FontCache> {
+        self.cache.clone()
+    }
+
+    /// プロセス全体で共有するキャッシュ（`FontCache::global`）を空のフォントを返すキャッシュにする
+    ///
+    /// キャッシュを指定できない呼び出し（Pythonバインディング等）の検証用。
+    /// 共有のキャッシュを使用する前に呼び出す必要があり、使用済みの場合はfalseを返す
+    pub fn install_global(&self) -> bool {
+        FontCache::set_global(self.cache.clone())
+    }
+}
+
+impl MockFont {
+    /// 日本語の文字に空のグリフを割り当てたフォント（日本語フォントの無い環境でテキスト抽出を検証する用途）
+    ///
+    /// ASCII・全角記号・かな・漢字・全角英数字の各文字に、輪郭の無いグリフを割り当てた最小限のTrueTypeフォントを作成する。
+    /// 描画結果は空白だが、埋め込み・`ToUnicode` による復号は実際のフォントと同じように行われる
+    ///
+    /// # Panics
+    /// フォントファイルを作成できない場合
+    pub fn japanese(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("print_pdf_mock_font_{}_{}.ttf", name, std::process::id()));
+        std::fs::write(&path, minimal_truetype(JAPANESE_RANGES)).expect("ダミーのフォントファイルを作成できません");
+        Self {
+            path,
+            cache: Arc::new(FontCache::new()),
+        }
+    }
+}
+
+impl Drop for MockFont {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// `MockFont::japanese` がグリフを割り当てる文字の範囲
+const JAPANESE_RANGES: &[(char, char)] = &[
+    (' ', '~'),
+    ('\u{3000}', '\u{30FF}'),
+    ('\u{4E00}', '\u{9FFF}'),
+    ('\u{FF01}', '\u{FF9F}'),
+];
+
+/// 指定した範囲の文字に輪郭の無いグリフを割り当てたTrueTypeフォントのバイト列
+///
+/// グリフIDは範囲の順に1から割り当てる（0は `.notdef`）。ASCII・半角カナは半角、それ以外は全角の送り幅とする
+fn minimal_truetype(ranges: &[(char, char)]) -> Vec<u8> {
+    const UNITS_PER_EM: u16 = 1000;
+    let chars: Vec<char> = ranges.iter().flat_map(|&(start, end)| start..=end).collect();
+    let num_glyphs = u16::try_from(chars.len() + 1).expect("グリフ数が多すぎます");
+    let be16 = |out: &mut Vec<u8>, v: u16| out.extend_from_slice(&v.to_be_bytes());
+    let be32 = |out: &mut Vec<u8>, v: u32| out.extend_from_slice(&v.to_be_bytes());
+
+    let mut head = Vec::new();
+    for v in [0x0001_0000, 0x0001_0000, 0, 0x5F0F_3CF5] {
+        be32(&mut head, v);
+    }
+    be16(&mut head, 0x000B);
+    be16(&mut head, UNITS_PER_EM);
+    head.extend_from_slice(&[0; 16]);
+    for v in [0, 0xFF88, UNITS_PER_EM, 880, 0, 8, 2, 0, 0] {
+        be16(&mut head, v);
+    }
+
+    let mut hhea = Vec::new();
+    be32(&mut hhea, 0x0001_0000);
+    for v in [880, 0xFF88, 0, UNITS_PER_EM, 0, 0, UNITS_PER_EM, 1, 0, 0, 0, 0, 0, 0, 0, num_glyphs] {
+        be16(&mut hhea, v);
+    }
+
+    let mut maxp = Vec::new();
+    be32(&mut maxp, 0x0001_0000);
+    be16(&mut maxp, num_glyphs);
+    for v in [0, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 0] {
+        be16(&mut maxp, v);
+    }
+
+    let mut hmtx = Vec::new();
+    be16(&mut hmtx, UNITS_PER_EM);
+    be16(&mut hmtx, 0);
+    for &c in &chars {
+        let half = c.is_ascii() || ('\u{FF61}'..='\u{FF9F}').contains(&c);
+        be16(&mut hmtx, if half { UNITS_PER_EM / 2 } else { UNITS_PER_EM });
+        be16(&mut hmtx, 0);
+    }
+
+    // 全てのグリフが空（オフセットが全て0）
+    let loca = vec![0; (usize::from(num_glyphs) + 1) * 2];
+    let glyf = Vec::new();
+
+    // cmap（Windows Unicode BMP、format 4）
+    let mut segments = Vec::new();
+    let mut glyph_id = 1u16;
+    for &(start, end) in ranges {
+        let (start, end) = (start as u32 as u16, end as u32 as u16);
+        segments.push((start, end, glyph_id.wrapping_sub(start)));
+        glyph_id += end - start + 1;
+    }
+    segments.push((0xFFFF, 0xFFFF, 1));
+    let seg_count = segments.len() as u16;
+    let search_range = 2 * (1u16 << (15 - seg_count.leading_zeros()));
+    let mut subtable = Vec::new();
+    for v in [4, 16 + seg_count * 8, 0, seg_count * 2, search_range, search_range.trailing_zeros() as u16 - 1, seg_count * 2 - search_range] {
+        be16(&mut subtable, v);
+    }
+    segments.iter().for_each(|s| be16(&mut subtable, s.1));
+    be16(&mut subtable, 0);
+    segments.iter().for_each(|s| be16(&mut subtable, s.0));
+    segments.iter().for_each(|s| be16(&mut subtable, s.2));
+    segments.iter().for_each(|_| be16(&mut subtable, 0));
+    let mut cmap = Vec::new();
+    for v in [0, 1, 3, 1] {
+        be16(&mut cmap, v);
+    }
+    be32(&mut cmap, 12);
+    cmap.extend_from_slice(&subtable);
+
+    let mut post = Vec::new();
+    be32(&mut post, 0x0003_0000);
+    be32(&mut post, 0);
+    be16(&mut post, 0xFF9C);
+    be16(&mut post, 50);
+    post.extend_from_slice(&[0; 20]);
+
+    // テーブルはタグ順
+    let tables: [(&[u8; 4], Vec<u8>); 8] = [
+        (b"cmap", cmap),
+        (b"glyf", glyf),
+        (b"head", head),
+        (b"hhea", hhea),
+        (b"hmtx", hmtx),
+        (b"loca", loca),
+        (b"maxp", maxp),
+        (b"post", post),
+    ];
+    let num_tables = tables.len() as u16;
+    let search_range = 16 * (1u16 << (15 - num_tables.leading_zeros()));
+    let mut font = Vec::new();
+    be32(&mut font, 0x0001_0000);
+    for v in [num_tables, search_range, search_range.trailing_zeros() as u16 - 4, num_tables * 16 - search_range] {
+        be16(&mut font, v);
+    }
+    let mut offset = 12 + tables.len() * 16;
+    let mut data = Vec::new();
+    for (tag, table) in &tables {
+        let checksum = table
+            .chunks(4)
+            .map(|chunk| {
+                let mut word = [0; 4];
+                word[..chunk.len()].copy_from_slice(chunk);
+                u32::from_be_bytes(word)
+            })
+            .fold(0u32, u32::wrapping_add);
+        font.extend_from_slice(*tag);
+        be32(&mut font, checksum);
+        be32(&mut font, offset as u32);
+        be32(&mut font, table.len() as u32);
+        data.extend_from_slice(table);
+        data.resize(data.len().next_multiple_of(4), 0);
+        offset = 12 + tables.len() * 16 + data.len();
+    }
+    font.extend_from_slice(&data);
+    font
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pdf::layout::mm_to_pt;
+    use printpdf::{BuiltinFont, CurTransMat, FontId, Mm, PdfDocument, PdfPage, PdfSaveOptions, Point, Pt};
+
+    fn write(text: &str) -> Op {
+        Op::WriteText {
+            items: vec![TextItem::Text(text.to_string())],
+            font: FontId("test".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_texts_track_cursor_and_size() {
+        let ops = vec![
+            Op::StartTextSection,
+            Op::SetTextCursor { pos: Point::new(Mm(10.0), Mm(20.0)) },
+            Op::SetFontSize { size: Pt(9.0), font: FontId("test".to_string()) },
+            Op::SetLineHeight { lh: Pt(mm_to_pt(5.0)) },
+            write("一行目"),
+            Op::AddLineBreak,
+            write("二行目"),
+            Op::EndTextSection,
+            Op::StartTextSection,
+            Op::SetTextCursor { pos: Point::new(Mm(30.0), Mm(40.0)) },
+            write("別"),
+            Op::EndTextSection,
+        ];
+        let texts = texts_in(&ops);
+        assert_eq!(texts.len(), 3);
+        assert!((texts[0].x_mm - 10.0).abs() < 0.01);
+        assert!((texts[0].y_mm - 20.0).abs() < 0.01);
+        assert_eq!(texts[0].size, 9.0);
+        assert!((texts[1].y_mm - 15.0).abs() < 0.01);
+        // テキストセクションの開始でカーソルは原点に戻る
+        assert!((texts[2].x_mm - 30.0).abs() < 0.01);
+        assert!((texts[2].top_mm(148.0) - 108.0).abs() < 0.01);
+
+        assert_eq!(find_text(&ops, "二行").map(|t| t.text), Some("二行目".to_string()));
+        assert!(find_text(&ops, "三行目").is_none());
+    }
+
+    #[test]
+    fn test_extract_text_from_saved_document() {
+        let page = |text: &str| {
+            PdfPage::new(
+                Mm(210.0),
+                Mm(148.0),
+                vec![
+                    Op::StartTextSection,
+                    Op::SetTextCursor { pos: Point::new(Mm(10.0), Mm(20.0)) },
+                    Op::SetFontSizeBuiltinFont { size: Pt(12.0), font: BuiltinFont::Helvetica },
+                    Op::WriteTextBuiltinFont {
+                        items: vec![TextItem::Text(text.to_string())],
+                        font: BuiltinFont::Helvetica,
+                    },
+                    Op::EndTextSection,
+                ],
+            )
+        };
+        let mut doc = PdfDocument::new("test");
+        doc.with_pages(vec![page("First page"), page("Second page")]);
+        let bytes = doc.save(&PdfSaveOptions::default(), &mut Vec::new());
+
+        let text = extract_text(&bytes);
+        let first = text.find("First page").unwrap();
+        let second = text.find("Second page").unwrap();
+        assert!(first < second);
+    }
+
+    #[test]
+    fn test_parse_to_unicode() {
+        let cmap = b"begincmap\n%!PS-Adobe-3.0 Resource-CMap\n1 begincodespacerange\n<0000> <FFFF>\nendcodespacerange\n\
+2 beginbfchar\n<0001> <5C71>\n<0002> <7530>\nendbfchar\n\
+2 beginbfrange\n<0010> <0012> <0030>\n<0020> <0021> [<592A> <90CE>]\nendbfrange\nendcmap";
+        let map = parse_to_unicode(cmap);
+        assert_eq!(map[&1], "山");
+        assert_eq!(map[&2], "田");
+        assert_eq!(map[&0x11], "1");
+        assert_eq!(map[&0x12], "2");
+        assert_eq!(map[&0x20], "太");
+        assert_eq!(map[&0x21], "郎");
+        assert_eq!(map.len(), 7);
+    }
+
+    #[test]
+    fn test_transformation_matrix_applies_until_restore() {
+        let translate = mm_to_pt(5.0);
+        let ops = vec![
+            Op::SaveGraphicsState,
+            Op::SetTransformationMatrix {
+                matrix: CurTransMat::Raw([1.0, 0.0, 0.0, 1.0, translate, translate]),
+            },
+            Op::StartTextSection,
+            Op::SetTextCursor { pos: Point::new(Mm(10.0), Mm(20.0)) },
+            write("移動"),
+            Op::EndTextSection,
+            Op::RestoreGraphicsState,
+            Op::StartTextSection,
+            Op::SetTextCursor { pos: Point::new(Mm(10.0), Mm(20.0)) },
+            write("元"),
+            Op::EndTextSection,
+        ];
+        let texts = texts_in(&ops);
+        assert!((texts[0].x_mm - 15.0).abs() < 0.01);
+        assert!((texts[0].y_mm - 25.0).abs() < 0.01);
+        assert!((texts[1].x_mm - 10.0).abs() < 0.01);
+        assert!((texts[1].y_mm - 20.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_japanese_mock_font_maps_chars() {
+        let font = MockFont::japanese("testing_japanese");
+        let data = std::fs::read(font.path()).unwrap();
+        let parsed = printpdf::ParsedFont::from_bytes(&data, 0, &mut Vec::new()).unwrap();
+        for c in ['A', '　', 'あ', 'ア', '山', '！', 'ｱ'] {
+            assert!(parsed.lookup_glyph_index(c as u32).is_some_and(|gid| gid > 0), "{}", c);
+        }
+        assert_eq!(parsed.lookup_glyph_index('é' as u32).unwrap_or(0), 0);
+    }
+}
@@ -5,9 +5,334 @@
 //! - wrap_kukan: 区間テキストの折り返し
 //! - align_rows: 行数の調整
 //! - prepare_ryohi_for_print: 旅費データの印刷用準備
+//! - normalize: 入力テキストの正規化
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
 
 use regex::Regex;
-use crate::models::{format_price, Ryohi};
+use unicode_normalization::UnicodeNormalization;
+use crate::error::PdfError;
+use crate::warning::Warning;
+use crate::models::{format_price_styled, Item, NumberFormat, Ryohi};
+use crate::pdf::tax::TaxOptions;
+
+/// 金額計算時の端数処理
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Rounding {
+    /// 切り捨て
+    Floor,
+    /// 四捨五入
+    #[default]
+    Round,
+    /// 切り上げ
+    Ceil,
+}
+
+impl Rounding {
+    /// 端数処理を適用
+    pub fn apply(self, value: f64) -> f64 {
+        match self {
+            Rounding::Floor => value.floor(),
+            Rounding::Round => value.round(),
+            Rounding::Ceil => value.ceil(),
+        }
+    }
+}
+
+/// 数量の小数桁表示
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecimalsMode {
+    /// 小数部が0の場合は整数表示、それ以外は必要な桁数で表示
+    Auto,
+    /// 固定桁数で表示
+    Fixed(u8),
+}
+
+/// 数量の表示形式
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VolFormat {
+    /// 小数桁表示
+    pub decimals: DecimalsMode,
+    /// 単位（例: "泊", "回"）
+    pub unit_suffix: Option<String>,
+}
+
+impl Default for VolFormat {
+    fn default() -> Self {
+        Self {
+            decimals: DecimalsMode::Fixed(1),
+            unit_suffix: None,
+        }
+    }
+}
+
+impl VolFormat {
+    /// 数量をフォーマット
+    pub fn format(&self, vol: f64) -> String {
+        let mut s = match self.decimals {
+            DecimalsMode::Auto => {
+                if vol.fract() == 0.0 {
+                    format!("{:.0}", vol)
+                } else {
+                    // 浮動小数点の誤差を丸めてから末尾の0を除去
+                    let s = format!("{:.6}", vol);
+                    s.trim_end_matches('0').trim_end_matches('.').to_string()
+                }
+            }
+            DecimalsMode::Fixed(decimals) => format!("{:.*}", decimals as usize, vol),
+        };
+        if let Some(ref suffix) = self.unit_suffix {
+            s.push_str(suffix);
+        }
+        s
+    }
+
+    /// 数量配列をフォーマット（`vol_ar` 用）
+    pub fn format_all(&self, vols: &[f64]) -> Vec<String> {
+        vols.iter().map(|&v| self.format(v)).collect()
+    }
+}
+
+/// 摘要の折り返し方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WrapMode {
+    /// 摘要を「、」で連結し、収まらない項目は次の行へ送る（長すぎる項目は `DetailOverflow` に従う）
+    #[default]
+    JoinSegments,
+    /// 「、」で連結した文字列を文字数で機械的に折り返す（切り詰めなし）
+    HardChar,
+}
+
+/// 1行の文字数を超える摘要の項目の扱い（`WrapMode::JoinSegments`）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DetailOverflow {
+    /// 1行の幅ごとに続きの行へ折り返す
+    #[default]
+    Wrap,
+    /// 1行の幅で切り詰める（残りは印刷せず、`Warning::DetailTruncated` で通知する）
+    Truncate,
+}
+
+/// 行数の上限を超えた行の扱い
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RowOverflow {
+    /// 上限を超えた行を印刷しない
+    #[default]
+    Clip,
+    /// 上限を超えた行を最終行に連結する（列幅を超えて印刷される場合がある）
+    Merge,
+}
+
+/// 旅費項目1件あたりの行数の上限
+///
+/// 摘要と区間の行数を個別に制限し、1件の旅費項目が縦に伸びすぎないようにする
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RowLimits {
+    /// 摘要の最大行数（Noneの場合は制限しない）
+    pub max_detail_rows: Option<usize>,
+    /// 区間の最大行数（Noneの場合は制限しない）
+    pub max_kukan_rows: Option<usize>,
+    /// 上限を超えた行の扱い
+    pub overflow: RowOverflow,
+}
+
+/// 区間を折り返す前に適用する文字列の置換
+#[derive(Debug, Clone)]
+pub enum KukanReplacement {
+    /// 文字列をそのまま置換
+    Literal {
+        /// 置換前の文字列
+        from: String,
+        /// 置換後の文字列
+        to: String,
+    },
+    /// 正規表現に一致する部分を置換（置換後の文字列では `$1` 等でグループを参照できる）
+    Regex {
+        /// 置換する部分の正規表現
+        pattern: Regex,
+        /// 置換後の文字列
+        to: String,
+    },
+}
+
+impl KukanReplacement {
+    /// 文字列の置換を作成
+    pub fn literal(from: impl Into<String>, to: impl Into<String>) -> Self {
+        Self::Literal {
+            from: from.into(),
+            to: to.into(),
+        }
+    }
+
+    /// 正規表現の置換を作成（正規表現が不正な場合は `PdfError::Config`）
+    pub fn regex(pattern: &str, to: impl Into<String>) -> Result<Self, PdfError> {
+        let pattern = Regex::new(pattern)
+            .map_err(|e| PdfError::Config(format!("区間の置換の正規表現が不正です: {}: {}", pattern, e)))?;
+        Ok(Self::Regex { pattern, to: to.into() })
+    }
+
+    /// 既定の置換（`_九州外空車適用` の前の区切り、`適用*` と `追加` の間の半角スペース）
+    pub fn defaults() -> Vec<Self> {
+        vec![
+            Self::literal("_九州外空車適用", "　九州外空車適用"),
+            Self::literal("適用*   追加", "適用*　追加"),
+        ]
+    }
+
+    /// 文字列に置換を適用
+    fn apply(&self, text: &str) -> String {
+        match self {
+            Self::Literal { from, to } => text.replace(from.as_str(), to),
+            Self::Regex { pattern, to } => pattern.replace_all(text, to.as_str()).into_owned(),
+        }
+    }
+}
+
+/// 印刷用データの整形オプション
+#[derive(Debug, Clone)]
+pub struct FormatOptions {
+    /// 計（単価×数量）の端数処理
+    pub rounding: Rounding,
+    /// 数量の表示形式
+    pub vol_format: VolFormat,
+    /// 金額の表示形式
+    pub number_format: NumberFormat,
+    /// 摘要の折り返し方式
+    pub wrap_mode: WrapMode,
+    /// 1行の文字数を超える摘要の項目の扱い
+    pub detail_overflow: DetailOverflow,
+    /// 消費税の計算オプション
+    pub tax: TaxOptions,
+    /// 旅費項目1件あたりの摘要・区間の行数の上限
+    pub row_limits: RowLimits,
+    /// 区間を折り返す前に順に適用する置換（既定は `KukanReplacement::defaults`）
+    pub kukan_replacements: Vec<KukanReplacement>,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            rounding: Rounding::default(),
+            vol_format: VolFormat::default(),
+            number_format: NumberFormat::default(),
+            wrap_mode: WrapMode::default(),
+            detail_overflow: DetailOverflow::default(),
+            tax: TaxOptions::default(),
+            row_limits: RowLimits::default(),
+            kukan_replacements: KukanReplacement::defaults(),
+        }
+    }
+}
+
+/// 入力テキストの正規化オプション
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NormalizeOptions {
+    /// 全角数字を半角に変換するか（falseの場合は全角数字を保持）
+    pub halfwidth_digits: bool,
+}
+
+impl Default for NormalizeOptions {
+    fn default() -> Self {
+        Self {
+            halfwidth_digits: true,
+        }
+    }
+}
+
+/// 幅ゼロ文字かどうか
+fn is_zero_width(c: char) -> bool {
+    matches!(c, '\u{200B}'..='\u{200F}' | '\u{2060}' | '\u{FEFF}')
+}
+
+/// 全角数字かどうか
+fn is_fullwidth_digit(c: char) -> bool {
+    ('０'..='９').contains(&c)
+}
+
+/// 入力テキストを正規化
+///
+/// - NFKC正規化（半角カタカナ→全角、全角英数字→半角）
+/// - 改行以外の制御文字・幅ゼロ文字を除去
+/// - `halfwidth_digits` がfalseの場合は全角数字をそのまま残す
+pub fn normalize(text: &str, options: &NormalizeOptions) -> String {
+    let cleaned = text
+        .chars()
+        .filter(|&c| c == '\n' || !(c.is_control() || is_zero_width(c)));
+
+    if options.halfwidth_digits {
+        return cleaned.nfkc().collect();
+    }
+
+    // 全角数字を区切りとして、それ以外の部分のみNFKC正規化する
+    let mut result = String::new();
+    let mut segment = String::new();
+    for c in cleaned {
+        if is_fullwidth_digit(c) {
+            result.extend(segment.nfkc());
+            segment.clear();
+            result.push(c);
+        } else {
+            segment.push(c);
+        }
+    }
+    result.extend(segment.nfkc());
+    result
+}
+
+/// アイテムの全文字列フィールドを正規化したコピーを作成
+pub fn normalize_item(item: &Item, options: &NormalizeOptions) -> Item {
+    let mut item = item.clone();
+    item.for_each_text_mut(|_, value| *value = normalize(value, options));
+    item
+}
+
+/// アイテムの全文字列フィールドをNFC（合成済みの文字）に統一したコピーを作成
+///
+/// 分解された濁点・半濁点（NFD）はフォントによって結合されずに描画されるため、描画前に合成する。
+/// NFKCと異なり全角英数字・半角カタカナは変換しない
+pub fn nfc_item(item: &Item) -> Item {
+    let mut item = item.clone();
+    item.for_each_text_mut(|_, value| {
+        if !unicode_normalization::is_nfc(value) {
+            *value = value.nfc().collect();
+        }
+    });
+    item
+}
+
+/// 文字列をNFCに統一（合成済みの場合はそのまま）
+pub(crate) fn nfc_text(text: &str) -> Cow<'_, str> {
+    if unicode_normalization::is_nfc(text) {
+        Cow::Borrowed(text)
+    } else {
+        Cow::Owned(text.nfc().collect())
+    }
+}
+
+/// アイテムの全文字列フィールドがNFCか確認
+pub(crate) fn is_nfc_item(item: &Item) -> bool {
+    let mut nfc = true;
+    item.for_each_text(|_, value| nfc &= unicode_normalization::is_nfc(value));
+    nfc
+}
+
+/// 計（単価×数量）を計算
+///
+/// 数量が未設定・負数・非数の場合は1として扱う
+pub fn line_total(price: i32, vol: Option<f64>, rounding: Rounding) -> i32 {
+    let vol = match vol {
+        Some(v) if v.is_finite() && v >= 0.0 => v,
+        Some(v) => {
+            tracing::warn!("不正な数量のため1として計算: {}", v);
+            1.0
+        }
+        None => 1.0,
+    };
+
+    let total = rounding.apply(price as f64 * vol);
+    total.clamp(i32::MIN as f64, i32::MAX as f64) as i32
+}
 
 /// テキスト折り返し結果
 #[derive(Debug, Clone, Default)]
@@ -16,6 +341,8 @@ pub struct TextWrapResult {
     pub lines: Vec<String>,
     /// 行数
     pub row_count: usize,
+    /// 切り詰められた項目数
+    pub truncated: usize,
 }
 
 impl TextWrapResult {
@@ -24,6 +351,7 @@ impl TextWrapResult {
         Self {
             lines: Vec::new(),
             row_count: 0,
+            truncated: 0,
         }
     }
 
@@ -32,32 +360,51 @@ impl TextWrapResult {
         Self {
             lines: vec![line],
             row_count: 1,
+            truncated: 0,
         }
     }
 }
 
 /// 摘要テキストを指定文字数で折り返し
 ///
+/// 摘要文字列内の改行（`\n`, `\r\n`）は強制改行として扱い、改行後の部分は新しい行から配置する
+///
 /// # Arguments
 /// * `details` - 摘要文字列のスライス
-/// * `max_len` - 1行あたりの最大文字数
+/// * `max_len` - 1行あたりの最大文字数（`WrapMode::JoinSegments` で1項目が超える場合は全角換算の幅で分割、半角は0.5文字）
+/// * `mode` - 折り返し方式
+/// * `overflow` - 1行の文字数を超える項目の扱い（`WrapMode::JoinSegments` のみ）
 ///
 /// # Returns
 /// 折り返し結果
-pub fn wrap_detail(details: &[String], max_len: usize) -> TextWrapResult {
+pub fn wrap_detail(details: &[String], max_len: usize, mode: WrapMode, overflow: DetailOverflow) -> TextWrapResult {
     if details.is_empty() {
         return TextWrapResult::empty();
     }
+    if mode == WrapMode::HardChar {
+        return wrap_detail_hard(details, max_len);
+    }
 
     let mut result: Vec<String> = Vec::new();
     let mut current_line = String::new();
+    let mut truncated = 0;
+
+    let segments = details.iter().flat_map(|detail| {
+        detail
+            .lines()
+            .enumerate()
+            .map(|(i, segment)| (i > 0, segment))
+    });
+
+    for (forced_break, detail) in segments {
+        // 改行後の部分は新しい行から配置
+        if forced_break && !current_line.is_empty() {
+            result.push(std::mem::take(&mut current_line));
+        }
 
-    for detail in details {
         // 区切り文字を考慮した新しい行の長さ
         let separator = if current_line.is_empty() { "" } else { "、" };
-        let new_line_length = current_line.chars().count()
-            + separator.chars().count()
-            + detail.chars().count();
+        let new_line_length = current_line.chars().count() + separator.chars().count() + detail.chars().count();
 
         if new_line_length <= max_len {
             // 全体が収まる場合
@@ -70,11 +417,24 @@ pub fn wrap_detail(details: &[String], max_len: usize) -> TextWrapResult {
             }
 
             // 新しい詳細項目を次の行に配置
-            current_line = if detail.chars().count() > max_len {
-                // 詳細項目自体が最大長を超える場合は切り詰め
-                detail.chars().take(max_len).collect()
+            if detail.chars().count() <= max_len {
+                current_line = detail.to_string();
+                continue;
+            }
+
+            // 詳細項目自体が最大長を超える場合は表示幅（半角は全角の半分）で分割
+            let mut chunks = split_by_width(detail, max_len * 2);
+            current_line = if chunks.len() <= 1 {
+                detail.to_string()
+            } else if overflow == DetailOverflow::Truncate {
+                // 最大幅を超える場合は切り詰め
+                truncated += 1;
+                chunks.swap_remove(0)
             } else {
-                detail.clone()
+                // 最大幅を超える場合は最大幅ごとに折り返し、残りを現在の行とする
+                let last = chunks.pop().unwrap_or_default();
+                result.extend(chunks);
+                last
             };
         }
     }
@@ -94,90 +454,183 @@ pub fn wrap_detail(details: &[String], max_len: usize) -> TextWrapResult {
     TextWrapResult {
         lines: filtered_result,
         row_count,
+        truncated,
+    }
+}
+
+/// 文字の表示幅（全角を2、半角英数字・記号・半角カナを1とする）
+fn char_width(c: char) -> usize {
+    if c.is_ascii() || ('\u{FF61}'..='\u{FF9F}').contains(&c) {
+        1
+    } else {
+        2
+    }
+}
+
+/// 文字列の表示幅（全角を2とする）
+fn display_width(text: &str) -> usize {
+    text.chars().map(char_width).sum()
+}
+
+/// 表示幅が `max_width` 以内になるよう先頭から分割（1文字で超える場合もその文字は1行とする）
+fn split_by_width(text: &str, max_width: usize) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+    let mut width = 0;
+    for c in text.chars() {
+        let w = char_width(c);
+        if width + w > max_width && !current.is_empty() {
+            chunks.push(std::mem::take(&mut current));
+            width = 0;
+        }
+        current.push(c);
+        width += w;
+    }
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
+/// 1行に収まらず切り詰める摘要の警告（`DetailOverflow::Truncate` の場合に生成前に確認）
+///
+/// `max_len` は全角の文字数（半角は0.5文字として数える）
+pub(crate) fn truncated_detail_warnings(items: &[Item], max_len: usize) -> Vec<Warning> {
+    let mut warnings = Vec::new();
+    for (item_index, item) in items.iter().enumerate() {
+        for (ryohi_index, ryohi) in item.ryohi.iter().enumerate() {
+            for (detail_index, detail) in ryohi.detail.iter().enumerate() {
+                let too_wide = detail.lines().any(|line| display_width(line) > max_len * 2);
+                let length = detail.lines().map(|line| line.chars().count()).max().unwrap_or(0);
+                if too_wide {
+                    warnings.push(Warning::DetailTruncated {
+                        item_index,
+                        field: format!("ryohi[{}].detail[{}]", ryohi_index, detail_index),
+                        length,
+                        max_length: max_len,
+                    });
+                }
+            }
+        }
+    }
+    warnings
+}
+
+/// 摘要を「、」で連結し、`max_len` 文字ごとに折り返す（`WrapMode::HardChar`）
+fn wrap_detail_hard(details: &[String], max_len: usize) -> TextWrapResult {
+    let max_len = max_len.max(1);
+    let mut logical: Vec<String> = vec![String::new()];
+
+    for detail in details {
+        for (i, segment) in detail.lines().enumerate() {
+            let current = logical.last_mut().expect("logical is never empty");
+            if i > 0 && !current.is_empty() {
+                logical.push(segment.to_string());
+                continue;
+            }
+            if !current.is_empty() {
+                current.push('、');
+            }
+            current.push_str(segment);
+        }
+    }
+
+    let lines: Vec<String> = logical
+        .iter()
+        .flat_map(|line| {
+            let chars: Vec<char> = line.chars().collect();
+            chars
+                .chunks(max_len)
+                .map(|chunk| chunk.iter().collect::<String>())
+                .collect::<Vec<_>>()
+        })
+        .filter(|line| !line.trim().is_empty())
+        .collect();
+
+    let row_count = lines.len();
+    TextWrapResult {
+        lines,
+        row_count,
+        truncated: 0,
     }
 }
 
 /// 区間テキストを指定文字数で折り返し
 ///
+/// 区切り文字（全角・半角スペース、｜）で分割した区間を、1行が `max_len` 文字以内になるよう
+/// 全角スペース1つで連結する。単独で `max_len` を超える区間は `exceed*` の1行とする
+///
 /// # Arguments
 /// * `kukan` - 区間文字列
 /// * `max_len` - 1行あたりの最大文字数
+/// * `replacements` - 分割前に順に適用する置換（通常は `FormatOptions::kukan_replacements`）
 ///
 /// # Returns
 /// 折り返し結果
-pub fn wrap_kukan(kukan: &str, max_len: usize) -> TextWrapResult {
+pub fn wrap_kukan(kukan: &str, max_len: usize, replacements: &[KukanReplacement]) -> TextWrapResult {
     if kukan.is_empty() {
         return TextWrapResult::single(String::new());
     }
 
     // 特殊な文字列を置換
-    let mut kukan = kukan.to_string();
-    kukan = kukan.replace("_九州外空車適用", "　九州外空車適用");
-    kukan = kukan.replace("適用*   追加", "適用*　追加");
-    // 半角スペースを全角スペースに変換
-    kukan = kukan.replace(' ', "　");
+    let kukan = replacements
+        .iter()
+        .fold(kukan.to_string(), |kukan, replacement| replacement.apply(&kukan));
 
-    // 区切り文字で分割 (全角スペース、｜、半角スペース+|、など)
-    let re = Regex::new(r"[　｜]| \||\|").unwrap();
-    let parts: Vec<&str> = re.split(&kukan).collect();
+    // 区切り文字で分割（全角・半角スペース、｜、|）し、連続した区切り文字による空の区間は除く
+    let re = Regex::new(r"[　 ｜|]").unwrap();
 
     let mut result: Vec<String> = Vec::new();
-    let mut current_line = String::new();
-    let mut current_count: usize = 0;
+    // 現在行の区間と、全角スペース1つで連結した場合の文字数
+    let mut line: Vec<&str> = Vec::new();
+    let mut line_len: usize = 0;
+    let mut truncated = 0;
 
-    for part in parts {
+    for part in re.split(&kukan).filter(|part| !part.is_empty()) {
         let part_len = part.chars().count();
+        let joined_len = if line.is_empty() { part_len } else { line_len + 1 + part_len };
+
+        if joined_len <= max_len {
+            // 現在行に追加できる場合
+            line.push(part);
+            line_len = joined_len;
+            continue;
+        }
 
-        if current_count != 0 && current_count + part_len == max_len {
-            // ちょうど最大長になる場合
-            result.push(format!("{}{}", current_line, part));
-            current_line = String::new();
-            current_count = 0;
-        } else if part_len == max_len && current_line.is_empty() {
-            // 単体で最大長の場合
-            result.push(part.to_string());
-            current_count = 0;
-        } else if part_len > max_len {
-            // 最大長を超える場合
+        // 現在行を確定して次の行へ
+        if !line.is_empty() {
+            result.push(line.join("　"));
+            line.clear();
+        }
+        if part_len > max_len {
+            // 単独で最大長を超える場合
             result.push("exceed*".to_string());
-            current_count = 0;
-        } else if current_count + part_len + 1 > max_len {
-            // 現在行に追加すると最大長を超える場合
-            if !current_line.is_empty() {
-                result.push(current_line);
-            }
-            current_line = format!("{}　", part);
-            current_count = part_len + 1;
+            truncated += 1;
+            line_len = 0;
         } else {
-            // 現在行に追加できる場合
-            current_count += part_len + 1;
-            current_line.push_str(part);
-            current_line.push('　');
+            line.push(part);
+            line_len = part_len;
         }
     }
-
-    // 最後の行を処理
-    if current_count != 0 {
-        result.push(current_line);
+    if !line.is_empty() {
+        result.push(line.join("　"));
+    }
+    if result.is_empty() {
+        // 区切り文字のみの場合
+        return TextWrapResult::single(String::new());
     }
-
-    // 前後の全角スペースを削除
-    let result: Vec<String> = result
-        .into_iter()
-        .map(|line| {
-            let line = line.replace(' ', "　");
-            let line = line.trim_start_matches('　').to_string();
-            line.trim_end_matches('　').to_string()
-        })
-        .collect();
 
     let row_count = result.len();
     TextWrapResult {
         lines: result,
         row_count,
+        truncated,
     }
 }
 
+/// 行揃え結果 (日付配列, 行先配列, 金額配列, 数量配列, 計配列)
+pub type AlignedRows = (Vec<String>, Vec<String>, Vec<String>, Vec<String>, Vec<String>);
+
 /// 他のデータ項目を最大行数に合わせて配列を調整
 ///
 /// # Arguments
@@ -186,20 +639,23 @@ pub fn wrap_kukan(kukan: &str, max_len: usize) -> TextWrapResult {
 /// * `price` - 金額
 /// * `vol` - 数量
 /// * `max_rows` - 最大行数
+/// * `format` - 整形オプション（計の端数処理・数量の表示形式）
 ///
 /// # Returns
-/// (日付配列, 行先配列, 金額配列, 数量配列)
+/// (日付配列, 行先配列, 金額配列, 数量配列, 計配列)
 pub fn align_rows(
     date: Option<&str>,
     dest: Option<&str>,
     price: Option<i32>,
     vol: Option<f64>,
     max_rows: usize,
-) -> (Vec<String>, Vec<String>, Vec<String>, Vec<String>) {
+    format: &FormatOptions,
+) -> AlignedRows {
     let mut date_arr = vec![String::new(); max_rows];
     let mut dest_arr = vec![String::new(); max_rows];
     let mut price_arr = vec![String::new(); max_rows];
     let mut vol_arr = vec![String::new(); max_rows];
+    let mut total_arr = vec![String::new(); max_rows];
 
     // 最初の行に実際の値を設定
     if let Some(date_str) = date {
@@ -221,14 +677,40 @@ pub fn align_rows(
     }
 
     if let Some(price_val) = price {
-        price_arr[0] = format_price(price_val);
+        price_arr[0] = format_price_styled(price_val, &format.number_format);
+        total_arr[0] = format_price_styled(line_total(price_val, vol, format.rounding), &format.number_format);
     }
 
     if let Some(vol_val) = vol {
-        vol_arr[0] = format!("{:.1}", vol_val);
+        vol_arr[0] = format.vol_format.format(vol_val);
+    }
+
+    (date_arr, dest_arr, price_arr, vol_arr, total_arr)
+}
+
+/// 折り返し結果を行数の上限に収める
+///
+/// `Merge` の場合は上限を超えた行を `separator` で最終行に連結する。
+/// 行を削除・連結した場合は切り詰め項目として数える
+fn limit_rows(mut result: TextWrapResult, max_rows: Option<usize>, overflow: RowOverflow, separator: &str) -> TextWrapResult {
+    let Some(max_rows) = max_rows.map(|n| n.max(1)) else {
+        return result;
+    };
+    if result.lines.len() <= max_rows {
+        return result;
     }
 
-    (date_arr, dest_arr, price_arr, vol_arr)
+    let rest = result.lines.split_off(max_rows);
+    if overflow == RowOverflow::Merge {
+        let last = result.lines.last_mut().expect("max_rows is at least 1");
+        for line in rest {
+            last.push_str(separator);
+            last.push_str(&line);
+        }
+    }
+    result.row_count = result.lines.len();
+    result.truncated += 1;
+    result
 }
 
 /// 配列を最大行数まで拡張
@@ -267,8 +749,14 @@ pub struct RyohiPrintData {
     pub price_lines: Vec<String>,
     /// 数量行
     pub vol_lines: Vec<String>,
+    /// 計行（単価×数量）
+    pub total_lines: Vec<String>,
+    /// 独自データの行（キーごと）
+    pub custom_lines: BTreeMap<String, Vec<String>>,
     /// 最大行数
     pub max_rows: usize,
+    /// 切り詰められた項目数（摘要・区間）
+    pub truncated_fields: usize,
 }
 
 impl RyohiPrintData {
@@ -280,6 +768,7 @@ impl RyohiPrintData {
             && row >= self.kukan_lines.len()
             && row >= self.price_lines.len()
             && row >= self.vol_lines.len()
+            && row >= self.total_lines.len()
         {
             return false;
         }
@@ -303,6 +792,12 @@ impl RyohiPrintData {
         if row < self.vol_lines.len() && !self.vol_lines[row].trim().is_empty() {
             return true;
         }
+        if row < self.total_lines.len() && !self.total_lines[row].trim().is_empty() {
+            return true;
+        }
+        if self.custom_lines.values().any(|lines| lines.get(row).is_some_and(|line| !line.trim().is_empty())) {
+            return true;
+        }
 
         false
     }
@@ -331,6 +826,18 @@ impl RyohiPrintData {
     pub fn get_vol(&self, row: usize) -> &str {
         self.vol_lines.get(row).map(|s| s.as_str()).unwrap_or("")
     }
+
+    pub fn get_total(&self, row: usize) -> &str {
+        self.total_lines.get(row).map(|s| s.as_str()).unwrap_or("")
+    }
+
+    pub fn get_custom(&self, key: &str, row: usize) -> &str {
+        self.custom_lines
+            .get(key)
+            .and_then(|lines| lines.get(row))
+            .map(|s| s.as_str())
+            .unwrap_or("")
+    }
 }
 
 /// 旅費データを印刷用に準備
@@ -339,40 +846,59 @@ impl RyohiPrintData {
 /// * `ryohi` - 旅費データ
 /// * `max_detail_len` - 摘要の最大文字数
 /// * `max_kukan_len` - 区間の最大文字数
+/// * `format` - 整形オプション
 ///
 /// # Returns
 /// 印刷用に整形されたデータ
-pub fn prepare_ryohi_for_print(ryohi: &Ryohi, max_detail_len: usize, max_kukan_len: usize) -> RyohiPrintData {
+pub fn prepare_ryohi_for_print(
+    ryohi: &Ryohi,
+    max_detail_len: usize,
+    max_kukan_len: usize,
+    format: &FormatOptions,
+) -> RyohiPrintData {
     // 摘要を折り返し
     let detail_result = if !ryohi.detail.is_empty() {
-        wrap_detail(&ryohi.detail, max_detail_len)
+        wrap_detail(&ryohi.detail, max_detail_len, format.wrap_mode, format.detail_overflow)
     } else {
         TextWrapResult::single(String::new())
     };
 
     // 区間を折り返し
     let kukan_result = if let Some(ref kukan) = ryohi.kukan {
-        wrap_kukan(kukan, max_kukan_len)
+        wrap_kukan(kukan, max_kukan_len, &format.kukan_replacements)
     } else {
         TextWrapResult::single(String::new())
     };
 
+    // 摘要・区間の行数をそれぞれの上限に収める
+    let limits = &format.row_limits;
+    let detail_result = limit_rows(detail_result, limits.max_detail_rows, limits.overflow, "、");
+    let kukan_result = limit_rows(kukan_result, limits.max_kukan_rows, limits.overflow, "　");
+
     // 最大行数を決定
     let max_rows = detail_result.row_count.max(kukan_result.row_count).max(1);
 
     // 他のデータを最大行数に合わせる
-    let (date_lines, dest_lines, price_lines, vol_lines) = align_rows(
+    let (date_lines, dest_lines, price_lines, vol_lines, total_lines) = align_rows(
         ryohi.date.as_deref(),
         ryohi.dest.as_deref(),
         ryohi.price,
         ryohi.vol,
         max_rows,
+        format,
     );
 
     // すべての配列を最大行数に拡張
     let detail_lines = extend_to_max_rows(&detail_result.lines, max_rows);
     let kukan_lines = extend_to_max_rows(&kukan_result.lines, max_rows);
 
+    // 独自データは1行目に印刷
+    let custom_lines = ryohi
+        .custom
+        .iter()
+        .map(|(key, value)| (key.clone(), extend_to_max_rows(std::slice::from_ref(value), max_rows)))
+        .collect();
+
     RyohiPrintData {
         date_lines,
         dest_lines,
@@ -380,17 +906,78 @@ pub fn prepare_ryohi_for_print(ryohi: &Ryohi, max_detail_len: usize, max_kukan_l
         kukan_lines,
         price_lines,
         vol_lines,
+        total_lines,
+        custom_lines,
         max_rows,
+        truncated_fields: detail_result.truncated + kukan_result.truncated,
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::format_price;
+
+    #[test]
+    fn test_nfc_item() {
+        let item = Item {
+            name: "ハ\u{309A}ン".to_string(),
+            ryohi: vec![Ryohi {
+                detail: vec!["か\u{3099}そりん".to_string()],
+                print_kukan: Some(vec!["ｶﾞｿﾘﾝ１".to_string()]),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let item = nfc_item(&item);
+        assert_eq!(item.name, "パン");
+        assert_eq!(item.ryohi[0].detail, vec!["がそりん"]);
+        // 互換文字は変換しない
+        assert_eq!(item.ryohi[0].print_kukan, Some(vec!["ｶﾞｿﾘﾝ１".to_string()]));
+    }
+
+    #[test]
+    fn test_normalize_halfwidth_katakana() {
+        let options = NormalizeOptions::default();
+        assert_eq!(normalize("ｶﾞｿﾘﾝ代", &options), "ガソリン代");
+    }
+
+    #[test]
+    fn test_normalize_fullwidth_digits() {
+        assert_eq!(normalize("０１２", &NormalizeOptions::default()), "012");
+
+        let options = NormalizeOptions {
+            halfwidth_digits: false,
+        };
+        assert_eq!(normalize("ｶﾞ０１２Ａ", &options), "ガ０１２A");
+    }
+
+    #[test]
+    fn test_normalize_removes_zero_width_and_control() {
+        let options = NormalizeOptions::default();
+        assert_eq!(normalize("東京\u{200B}駅\t\r\n大阪", &options), "東京駅\n大阪");
+    }
+
+    #[test]
+    fn test_normalize_item_does_not_mutate_original() {
+        let item = Item {
+            name: "ﾔﾏﾀﾞ".to_string(),
+            ryohi: vec![Ryohi {
+                detail: vec!["高速代\u{200B}".to_string()],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let normalized = normalize_item(&item, &NormalizeOptions::default());
+        assert_eq!(normalized.name, "ヤマダ");
+        assert_eq!(normalized.ryohi[0].detail[0], "高速代");
+        assert_eq!(item.name, "ﾔﾏﾀﾞ");
+    }
 
     #[test]
     fn test_wrap_detail_empty() {
-        let result = wrap_detail(&[], 10);
+        let result = wrap_detail(&[], 10, WrapMode::JoinSegments, DetailOverflow::Wrap);
         assert_eq!(result.row_count, 0);
         assert!(result.lines.is_empty());
     }
@@ -398,7 +985,7 @@ mod tests {
     #[test]
     fn test_wrap_detail_single() {
         let details = vec!["テスト".to_string()];
-        let result = wrap_detail(&details, 10);
+        let result = wrap_detail(&details, 10, WrapMode::JoinSegments, DetailOverflow::Wrap);
         assert_eq!(result.row_count, 1);
         assert_eq!(result.lines[0], "テスト");
     }
@@ -406,11 +993,95 @@ mod tests {
     #[test]
     fn test_wrap_detail_multiple_fit_in_one_line() {
         let details = vec!["A".to_string(), "B".to_string(), "C".to_string()];
-        let result = wrap_detail(&details, 10);
+        let result = wrap_detail(&details, 10, WrapMode::JoinSegments, DetailOverflow::Wrap);
         assert_eq!(result.row_count, 1);
         assert_eq!(result.lines[0], "A、B、C");
     }
 
+    #[test]
+    fn test_wrap_detail_explicit_line_breaks() {
+        let details = vec!["高速代\nETC割引適用".to_string(), "駐車場".to_string()];
+        let result = wrap_detail(&details, 10, WrapMode::JoinSegments, DetailOverflow::Wrap);
+        assert_eq!(result.row_count, 3);
+        assert_eq!(result.lines, vec!["高速代", "ETC割引適用", "駐車場"]);
+
+        let details = vec!["A".to_string(), "B\r\nC".to_string()];
+        let result = wrap_detail(&details, 10, WrapMode::JoinSegments, DetailOverflow::Wrap);
+        assert_eq!(result.lines, vec!["A、B", "C"]);
+    }
+
+    #[test]
+    fn test_wrap_detail_line_break_regardless_of_max_len() {
+        let details = vec!["一行目\n二行目".to_string()];
+        for mode in [WrapMode::JoinSegments, WrapMode::HardChar] {
+            for max_len in [1, 3, 10, 100] {
+                let result = wrap_detail(&details, max_len, mode, DetailOverflow::Wrap);
+                assert!(result.row_count >= 2, "mode={:?}, max_len={}", mode, max_len);
+                assert!(result.lines.iter().all(|line| !line.contains('\n')));
+            }
+        }
+    }
+
+    #[test]
+    fn test_prepare_ryohi_row_limits_clip() {
+        let ryohi = Ryohi {
+            detail: vec!["一\n二\n三\n四".to_string()],
+            kukan: Some("東京".to_string()),
+            price: Some(1000),
+            ..Default::default()
+        };
+        let unlimited = prepare_ryohi_for_print(&ryohi, 10, 10, &FormatOptions::default());
+        assert_eq!(unlimited.max_rows, 4);
+
+        let format = FormatOptions {
+            row_limits: RowLimits {
+                max_detail_rows: Some(2),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let data = prepare_ryohi_for_print(&ryohi, 10, 10, &format);
+        assert_eq!(data.max_rows, 2);
+        assert_eq!(data.detail_lines, vec!["一", "二"]);
+        assert_eq!(data.truncated_fields, 1);
+        assert_eq!(data.price_lines.len(), 2);
+    }
+
+    #[test]
+    fn test_prepare_ryohi_row_limits_merge() {
+        let ryohi = Ryohi {
+            detail: vec!["一\n二\n三".to_string()],
+            kukan: Some("福岡　北九州　下関　広島　岡山".to_string()),
+            ..Default::default()
+        };
+        let format = FormatOptions {
+            row_limits: RowLimits {
+                max_detail_rows: Some(1),
+                max_kukan_rows: Some(1),
+                overflow: RowOverflow::Merge,
+            },
+            ..Default::default()
+        };
+        let data = prepare_ryohi_for_print(&ryohi, 10, 6, &format);
+        assert_eq!(data.max_rows, 1);
+        assert_eq!(data.detail_lines, vec!["一、二、三"]);
+        assert!(data.kukan_lines[0].starts_with("福岡"));
+        assert!(data.kukan_lines[0].ends_with("岡山"));
+        assert_eq!(data.truncated_fields, 2);
+    }
+
+    #[test]
+    fn test_prepare_ryohi_explicit_line_breaks() {
+        let ryohi = Ryohi {
+            detail: vec!["高速代\nETC割引適用".to_string(), "駐車場".to_string()],
+            price: Some(1000),
+            ..Default::default()
+        };
+        let data = prepare_ryohi_for_print(&ryohi, 10, 10, &FormatOptions::default());
+        assert_eq!(data.max_rows, 3);
+        assert_eq!(data.price_lines.len(), 3);
+    }
+
     #[test]
     fn test_wrap_detail_multiple_lines() {
         let details = vec![
@@ -418,32 +1089,230 @@ mod tests {
             "かきくけこ".to_string(),
             "さしすせそ".to_string(),
         ];
-        let result = wrap_detail(&details, 10);
+        let result = wrap_detail(&details, 10, WrapMode::JoinSegments, DetailOverflow::Wrap);
         // 「あいうえお、かきくけこ」は12文字なので収まらない
         assert!(result.row_count >= 2);
     }
 
+    #[test]
+    fn test_wrap_detail_truncated_count() {
+        let details = vec!["あいうえおかきくけこさしす".to_string(), "短い".to_string()];
+        let result = wrap_detail(&details, 10, WrapMode::JoinSegments, DetailOverflow::Truncate);
+        assert_eq!(result.truncated, 1);
+        assert_eq!(result.lines[0], "あいうえおかきくけこ");
+    }
+
+    #[test]
+    fn test_wrap_detail_long_entry_wraps_remainder() {
+        let detail = "レンタカー代（保険・免責補償含む）ガソリン満タン返し・乗り捨て料金込み";
+        assert_eq!(detail.chars().count(), 35);
+        let result = wrap_detail(&[detail.to_string()], 10, WrapMode::JoinSegments, DetailOverflow::Wrap);
+        assert_eq!(result.row_count, 4);
+        assert_eq!(result.truncated, 0);
+        assert!(result.lines.iter().all(|line| line.chars().count() <= 10));
+        assert_eq!(result.lines.concat(), detail);
+
+        // 残りの行には次の項目を連結できる
+        let details = vec!["あいうえおかきくけこさし".to_string(), "短い".to_string()];
+        let result = wrap_detail(&details, 10, WrapMode::JoinSegments, DetailOverflow::Wrap);
+        assert_eq!(result.lines, vec!["あいうえおかきくけこ", "さし、短い"]);
+
+        // 折り返した行数が印刷用データの行数になる
+        let ryohi = Ryohi {
+            detail: vec![detail.to_string()],
+            price: Some(1000),
+            ..Default::default()
+        };
+        let data = prepare_ryohi_for_print(&ryohi, 10, 22, &FormatOptions::default());
+        assert_eq!(data.max_rows, 4);
+        assert_eq!(data.truncated_fields, 0);
+        let format = FormatOptions {
+            detail_overflow: DetailOverflow::Truncate,
+            ..Default::default()
+        };
+        let data = prepare_ryohi_for_print(&ryohi, 10, 22, &format);
+        assert_eq!(data.max_rows, 1);
+        assert_eq!(data.truncated_fields, 1);
+    }
+
+    #[test]
+    fn test_wrap_detail_long_entry_by_display_width() {
+        // 半角は全角の半分の幅として折り返す
+        let detail = "ETC0123456789ABCDEFGHIJ高速代";
+        let result = wrap_detail(&[detail.to_string()], 10, WrapMode::JoinSegments, DetailOverflow::Wrap);
+        assert_eq!(result.lines, vec!["ETC0123456789ABCDEFG", "HIJ高速代"]);
+
+        let result = wrap_detail(&[detail.to_string()], 10, WrapMode::JoinSegments, DetailOverflow::Truncate);
+        assert_eq!(result.lines, vec!["ETC0123456789ABCDEFG"]);
+        assert_eq!(result.truncated, 1);
+
+        // 半角20文字は1行に収まる
+        let details = vec!["ABCDEFGHIJKLMNOPQRST".to_string()];
+        let result = wrap_detail(&details, 10, WrapMode::JoinSegments, DetailOverflow::Truncate);
+        assert_eq!(result.lines, details);
+        assert_eq!(result.truncated, 0);
+        let items = vec![Item {
+            ryohi: vec![Ryohi {
+                detail: details,
+                ..Default::default()
+            }],
+            ..Default::default()
+        }];
+        assert!(truncated_detail_warnings(&items, 10).is_empty());
+    }
+
+    #[test]
+    fn test_truncated_detail_warnings() {
+        let ryohi = Ryohi {
+            detail: vec!["短い".to_string(), "一行目\nあいうえおかきくけこさ".to_string()],
+            ..Default::default()
+        };
+        let items = vec![
+            Item::default(),
+            Item {
+                ryohi: vec![Ryohi::default(), ryohi],
+                ..Default::default()
+            },
+        ];
+        assert_eq!(
+            truncated_detail_warnings(&items, 10),
+            vec![Warning::DetailTruncated {
+                item_index: 1,
+                field: "ryohi[1].detail[1]".to_string(),
+                length: 11,
+                max_length: 10,
+            }]
+        );
+        assert_eq!(
+            truncated_detail_warnings(&items, 10)[0].to_string(),
+            "摘要を切り詰めました: item[1].ryohi[1].detail[1] (11文字, 1行10文字)"
+        );
+        assert!(truncated_detail_warnings(&items, 11).is_empty());
+    }
+
+    #[test]
+    fn test_wrap_detail_hard_char_splits_long_detail() {
+        let details = vec!["あいうえおかきくけこさしすせそたちつてとなにぬねの".to_string()];
+        let result = wrap_detail(&details, 10, WrapMode::HardChar, DetailOverflow::Wrap);
+        assert_eq!(result.truncated, 0);
+        assert_eq!(
+            result.lines,
+            vec!["あいうえおかきくけこ", "さしすせそたちつてと", "なにぬねの"]
+        );
+    }
+
+    #[test]
+    fn test_wrap_detail_hard_char_joins_and_breaks() {
+        let details = vec!["高速代".to_string(), "駐車場\n宿泊".to_string()];
+        let result = wrap_detail(&details, 5, WrapMode::HardChar, DetailOverflow::Wrap);
+        assert_eq!(result.lines, vec!["高速代、駐", "車場", "宿泊"]);
+        assert_eq!(result.row_count, 3);
+    }
+
+    #[test]
+    fn test_prepare_ryohi_hard_char_mode() {
+        let ryohi = Ryohi {
+            detail: vec!["あいうえおかきくけこさしすせそ".to_string()],
+            ..Default::default()
+        };
+        let format = FormatOptions {
+            wrap_mode: WrapMode::HardChar,
+            ..Default::default()
+        };
+        let data = prepare_ryohi_for_print(&ryohi, 10, 10, &format);
+        assert_eq!(data.max_rows, 2);
+    }
+
+    #[test]
+    fn test_wrap_kukan_exceed_count() {
+        let result = wrap_kukan("あいうえおかきくけこさしすせそたちつてとなにぬ　東京", 22, &KukanReplacement::defaults());
+        assert_eq!(result.truncated, 1);
+        assert_eq!(result.lines[0], "exceed*");
+    }
+
     #[test]
     fn test_wrap_kukan_empty() {
-        let result = wrap_kukan("", 22);
+        let result = wrap_kukan("", 22, &KukanReplacement::defaults());
         assert_eq!(result.row_count, 1);
         assert_eq!(result.lines[0], "");
     }
 
     #[test]
     fn test_wrap_kukan_simple() {
-        let result = wrap_kukan("東京　大阪", 22);
+        let result = wrap_kukan("東京　大阪", 22, &KukanReplacement::defaults());
         assert!(result.row_count >= 1);
     }
 
+    #[test]
+    fn test_wrap_kukan_lines() {
+        let lines = |kukan: &str, max_len: usize| wrap_kukan(kukan, max_len, &KukanReplacement::defaults()).lines;
+
+        // 区切り文字（半角スペース・｜・連続）は全角スペース1つで連結
+        assert_eq!(lines("東京駅　大阪駅", 22), vec!["東京駅　大阪駅"]);
+        assert_eq!(lines("東京駅 大阪駅｜名古屋|京都　　神戸 ", 22), vec!["東京駅　大阪駅　名古屋　京都　神戸"]);
+        assert_eq!(lines("　　", 22), vec![""]);
+
+        // ちょうど最大長の行・最大長を超える場合は次の行（行末に区切り文字を残さない）
+        assert_eq!(lines("東京駅　大阪駅", 7), vec!["東京駅　大阪駅"]);
+        assert_eq!(lines("東京駅　大阪駅", 6), vec!["東京駅", "大阪駅"]);
+        assert_eq!(lines("東京駅　大阪駅　名古屋駅　京都", 8), vec!["東京駅　大阪駅", "名古屋駅　京都"]);
+        assert_eq!(lines("新大阪駅　東京", 4), vec!["新大阪駅", "東京"]);
+
+        // 単独で最大長を超える区間
+        let result = wrap_kukan("東京　あいうえおか　大阪", 4, &KukanReplacement::defaults());
+        assert_eq!(result.lines, vec!["東京", "exceed*", "大阪"]);
+        assert_eq!(result.truncated, 1);
+        assert_eq!(result.row_count, 3);
+
+        // 特殊な文字列の置換
+        assert_eq!(lines("福岡　熊本_九州外空車適用", 22), vec!["福岡　熊本　九州外空車適用"]);
+        assert_eq!(lines("福岡　熊本_九州外空車適用", 8), vec!["福岡　熊本", "九州外空車適用"]);
+        assert_eq!(lines("九州外空車適用*   追加", 22), vec!["九州外空車適用*　追加"]);
+    }
+
+    #[test]
+    fn test_wrap_kukan_replacements() {
+        let lines = |kukan: &str, replacements: &[KukanReplacement]| wrap_kukan(kukan, 22, replacements).lines;
+
+        // 既定の置換は FormatOptions の既定値に含まれる
+        let defaults = FormatOptions::default().kukan_replacements;
+        assert_eq!(lines("熊本_九州外空車適用", &defaults), vec!["熊本　九州外空車適用"]);
+
+        // 既定の置換を無効にする
+        assert_eq!(lines("熊本_九州外空車適用", &[]), vec!["熊本_九州外空車適用"]);
+
+        // 利用者の置換（文字列・正規表現）を既定の置換に追加
+        let mut replacements = defaults.clone();
+        replacements.push(KukanReplacement::literal("→", "　"));
+        replacements.push(KukanReplacement::regex(r"\s*\((往復|片道)\)", "　$1").unwrap());
+        assert_eq!(
+            lines("福岡→熊本_九州外空車適用 (往復)", &replacements),
+            vec!["福岡　熊本　九州外空車適用　往復"]
+        );
+        assert!(matches!(KukanReplacement::regex("(", ""), Err(PdfError::Config(_))));
+
+        // 整形オプションの置換で折り返す
+        let ryohi = Ryohi {
+            kukan: Some("福岡→熊本".to_string()),
+            ..Default::default()
+        };
+        let format = FormatOptions {
+            kukan_replacements: vec![KukanReplacement::literal("→", "　")],
+            ..Default::default()
+        };
+        let data = prepare_ryohi_for_print(&ryohi, 10, 22, &format);
+        assert_eq!(data.get_kukan(0), "福岡　熊本");
+    }
+
     #[test]
     fn test_align_rows() {
-        let (date, dest, price, vol) = align_rows(
+        let (date, dest, price, vol, total) = align_rows(
             Some("2024-01-15"),
             Some("東京"),
             Some(1000),
             Some(1.5),
             3,
+            &FormatOptions::default(),
         );
 
         assert_eq!(date.len(), 3);
@@ -453,6 +1322,84 @@ mod tests {
         assert_eq!(dest[0], "東京");
         assert_eq!(price[0], "1,000");
         assert_eq!(vol[0], "1.5");
+        assert_eq!(total[0], "1,500");
+        assert_eq!(total[1], "");
+    }
+
+    #[test]
+    fn test_vol_format_auto() {
+        let format = VolFormat {
+            decimals: DecimalsMode::Auto,
+            unit_suffix: None,
+        };
+        assert_eq!(format.format(1.0), "1");
+        assert_eq!(format.format(1.5), "1.5");
+        assert_eq!(format.format(0.25), "0.25");
+    }
+
+    #[test]
+    fn test_vol_format_fixed() {
+        let format = VolFormat {
+            decimals: DecimalsMode::Fixed(2),
+            unit_suffix: None,
+        };
+        assert_eq!(format.format(1.5), "1.50");
+        // デフォルトは従来通り小数1桁
+        assert_eq!(VolFormat::default().format(2.0), "2.0");
+    }
+
+    #[test]
+    fn test_vol_format_unit_suffix() {
+        let format = VolFormat {
+            decimals: DecimalsMode::Auto,
+            unit_suffix: Some("泊".to_string()),
+        };
+        assert_eq!(format.format(2.0), "2泊");
+        assert_eq!(format.format_all(&[1.0, 1.5]), vec!["1泊", "1.5泊"]);
+    }
+
+    #[test]
+    fn test_align_rows_vol_format() {
+        let format = FormatOptions {
+            vol_format: VolFormat {
+                decimals: DecimalsMode::Auto,
+                unit_suffix: Some("回".to_string()),
+            },
+            ..Default::default()
+        };
+        let (_, _, _, vol, _) = align_rows(None, None, None, Some(3.0), 1, &format);
+        assert_eq!(vol[0], "3回");
+    }
+
+    #[test]
+    fn test_align_rows_number_format() {
+        let format = FormatOptions {
+            number_format: NumberFormat {
+                thousands_separator: None,
+            },
+            ..Default::default()
+        };
+        let (_, _, price, _, total) = align_rows(None, None, Some(8280), Some(1.5), 1, &format);
+        assert_eq!(price[0], "8280");
+        assert_eq!(total[0], "12420");
+
+        let (_, _, price, vol, _) = align_rows(None, None, Some(8280), Some(1.5), 1, &FormatOptions::default());
+        assert_eq!(price[0], "8,280");
+        assert_eq!(vol[0], "1.5");
+    }
+
+    #[test]
+    fn test_line_total() {
+        assert_eq!(format_price(line_total(8280, Some(1.5), Rounding::Round)), "12,420");
+        assert_eq!(line_total(1001, Some(0.5), Rounding::Round), 501);
+        assert_eq!(line_total(1001, Some(0.5), Rounding::Floor), 500);
+        assert_eq!(line_total(1001, Some(0.5), Rounding::Ceil), 501);
+        // 数量なしは1として扱う
+        assert_eq!(line_total(1000, None, Rounding::Round), 1000);
+        // 負の数量は1として扱う
+        assert_eq!(line_total(1000, Some(-2.0), Rounding::Round), 1000);
+        // 負の単価（返金）はそのまま計算
+        assert_eq!(line_total(-1000, Some(2.0), Rounding::Round), -2000);
     }
 
     #[test]
@@ -467,14 +1414,29 @@ mod tests {
             ..Default::default()
         };
 
-        let print_data = prepare_ryohi_for_print(&ryohi, 10, 22);
+        let print_data = prepare_ryohi_for_print(&ryohi, 10, 22, &FormatOptions::default());
 
         assert!(print_data.max_rows >= 1);
+        assert_eq!(print_data.get_total(0), "10,000");
         assert_eq!(print_data.get_date(0), "01/15");
         assert_eq!(print_data.get_dest(0), "東京");
         assert!(!print_data.get_detail(0).is_empty() || !print_data.get_kukan(0).is_empty());
     }
 
+    #[test]
+    fn test_prepare_ryohi_custom_columns() {
+        let ryohi = Ryohi {
+            custom: [("receipt".to_string(), "✓".to_string())].into_iter().collect(),
+            ..Default::default()
+        };
+        let data = prepare_ryohi_for_print(&ryohi, 10, 22, &FormatOptions::default());
+        assert_eq!(data.get_custom("receipt", 0), "✓");
+        assert_eq!(data.get_custom("receipt", 1), "");
+        assert_eq!(data.get_custom("parking", 0), "");
+        // 独自データのみの旅費項目も印刷対象
+        assert!(data.has_content_in_row(0));
+    }
+
     #[test]
     fn test_ryohi_print_data_has_content() {
         let data = RyohiPrintData {
@@ -484,7 +1446,9 @@ mod tests {
             kukan_lines: vec!["大阪　東京".to_string(), "".to_string()],
             price_lines: vec!["10,000".to_string(), "".to_string()],
             vol_lines: vec!["1.0".to_string(), "".to_string()],
+            total_lines: vec!["10,000".to_string(), "".to_string()],
             max_rows: 2,
+            ..Default::default()
         };
 
         assert!(data.has_content_in_row(0));
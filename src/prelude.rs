@@ -0,0 +1,16 @@
+//! よく使う型のまとめてインポート
+//!
+//! ```rust,ignore
+//! use print_pdf_service::prelude::*;
+//! use tower::ServiceExt;
+//!
+//! let result = PdfService::with_config(PdfConfig::from_env())
+//!     .oneshot(PdfRequest::new(items))
+//!     .await?;
+//! ```
+
+pub use crate::config::PdfConfig;
+pub use crate::error::PdfError;
+pub use crate::models::{Item, Ryohi};
+pub use crate::service::{PdfRequest, PdfResult, PdfService};
+pub use crate::warning::Warning;
@@ -0,0 +1,267 @@
+//! 印刷バックエンドの選択
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::error::PdfError;
+use crate::print::capabilities::PrinterCapabilities;
+use crate::print::options::PrintOptions;
+use crate::print::sumatra::SumatraPrinter;
+use crate::traits::Printer;
+
+/// 印刷バックエンド
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Backend {
+    /// SumatraPDFが見つかればSumatraPDF、見つからなければwinspool
+    #[default]
+    Auto,
+    /// SumatraPDF
+    Sumatra,
+    /// winspool API（`native-print` feature有効時のWindowsのみ、PDFの直接印刷を許可していないプリンターはSumatraPDFで印刷）
+    Winspool,
+    /// IPPによるネットワークプリンターへの直接送信（`ipp` feature有効時のみ）
+    Ipp,
+}
+
+impl Backend {
+    /// 名前からバックエンドを取得（auto / sumatra / winspool / ipp、大文字小文字は区別しない）
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "auto" => Some(Backend::Auto),
+            "sumatra" => Some(Backend::Sumatra),
+            "winspool" => Some(Backend::Winspool),
+            "ipp" => Some(Backend::Ipp),
+            _ => None,
+        }
+    }
+}
+
+/// 印刷バックエンドを作成
+///
+/// winspoolでPDFを直接印刷するプリンターは指定しない（`create_printer_with_passthrough` を参照）
+///
+/// # Arguments
+/// * `backend` - 使用するバックエンド
+/// * `sumatra_path` - SumatraPDFのパス（Noneの場合は検索する）
+/// * `ipp_printers` - IPPの論理プリンター名とURIの対応
+pub fn create_printer(
+    backend: Backend,
+    sumatra_path: Option<&Path>,
+    ipp_printers: &BTreeMap<String, String>,
+) -> Result<Box<dyn Printer>, PdfError> {
+    create_printer_with_passthrough(backend, sumatra_path, ipp_printers, &[])
+}
+
+/// winspoolでPDFを直接印刷するプリンターを指定して印刷バックエンドを作成
+///
+/// winspoolはPDFをRAWジョブとして送信するため、`passthrough_printers` に指定したプリンターのみで使用する。
+/// それ以外のプリンターはSumatraPDFが見つかればSumatraPDFで印刷し、見つからなければ
+/// `PdfError::PassthroughUnsupported` を返す
+///
+/// # Arguments
+/// * `backend` - 使用するバックエンド
+/// * `sumatra_path` - SumatraPDFのパス（Noneの場合は検索する）
+/// * `ipp_printers` - IPPの論理プリンター名とURIの対応
+/// * `passthrough_printers` - PDFの直接印刷に対応したプリンター名（winspoolで印刷する）
+pub fn create_printer_with_passthrough(
+    backend: Backend,
+    sumatra_path: Option<&Path>,
+    ipp_printers: &BTreeMap<String, String>,
+    passthrough_printers: &[String],
+) -> Result<Box<dyn Printer>, PdfError> {
+    match backend {
+        Backend::Sumatra => Ok(Box::new(sumatra_printer(sumatra_path)?)),
+        Backend::Winspool => {
+            let winspool = winspool_printer(passthrough_printers)?;
+            match sumatra_printer(sumatra_path) {
+                Ok(sumatra) => Ok(Box::new(PassthroughRouter {
+                    passthrough_printers: passthrough_printers.to_vec(),
+                    winspool,
+                    sumatra: Box::new(sumatra),
+                })),
+                Err(_) => Ok(winspool),
+            }
+        }
+        Backend::Ipp => ipp_printer(ipp_printers),
+        Backend::Auto => match sumatra_printer(sumatra_path) {
+            Ok(printer) => Ok(Box::new(printer)),
+            Err(e) => winspool_printer(passthrough_printers).map_err(|_| e),
+        },
+    }
+}
+
+/// PDFの直接印刷を許可したプリンターはwinspool、それ以外はSumatraPDFで印刷するプリンター
+///
+/// 印刷先はリクエストのプリンター（未指定の場合はシステムのデフォルトプリンター）で決め、
+/// フォールバックプリンターも同じバックエンドで印刷する
+struct PassthroughRouter {
+    passthrough_printers: Vec<String>,
+    winspool: Box<dyn Printer>,
+    sumatra: Box<dyn Printer>,
+}
+
+impl PassthroughRouter {
+    /// 印刷に使用するプリンター
+    fn route(&self, printer_name: Option<&str>) -> &dyn Printer {
+        let name = match printer_name {
+            Some(name) => Some(name.to_string()),
+            None => self.winspool.default_printer().ok().flatten(),
+        };
+        match name {
+            Some(name) if self.passthrough_printers.contains(&name) => self.winspool.as_ref(),
+            _ => self.sumatra.as_ref(),
+        }
+    }
+}
+
+impl Printer for PassthroughRouter {
+    fn print(&self, pdf_path: &Path, printer_name: Option<&str>) -> Result<(), PdfError> {
+        self.route(printer_name).print(pdf_path, printer_name)
+    }
+
+    fn list_printers(&self) -> Result<Vec<String>, PdfError> {
+        self.winspool.list_printers()
+    }
+
+    fn default_printer(&self) -> Result<Option<String>, PdfError> {
+        self.winspool.default_printer()
+    }
+
+    fn preview(&self, pdf_path: &Path) -> Result<(), PdfError> {
+        self.winspool.preview(pdf_path)
+    }
+
+    fn capabilities(&self, printer_name: Option<&str>) -> Result<Option<PrinterCapabilities>, PdfError> {
+        self.route(printer_name).capabilities(printer_name)
+    }
+
+    fn print_with_options(
+        &self,
+        pdf_path: &Path,
+        printer_name: Option<&str>,
+        options: &PrintOptions,
+    ) -> Result<Option<String>, PdfError> {
+        self.route(printer_name).print_with_options(pdf_path, printer_name, options)
+    }
+}
+
+/// SumatraPDFのプリンターを作成（パス未設定の場合は検索）
+fn sumatra_printer(sumatra_path: Option<&Path>) -> Result<SumatraPrinter, PdfError> {
+    let mut printer = SumatraPrinter::new();
+    match sumatra_path {
+        Some(path) => printer = printer.with_path(path),
+        None => {
+            printer.find_sumatra()?;
+        }
+    }
+    Ok(printer)
+}
+
+#[cfg(all(windows, feature = "native-print"))]
+fn winspool_printer(passthrough_printers: &[String]) -> Result<Box<dyn Printer>, PdfError> {
+    Ok(Box::new(
+        crate::print::winspool::WinspoolPrinter::new().with_pdf_passthrough_printers(passthrough_printers.to_vec()),
+    ))
+}
+
+#[cfg(not(all(windows, feature = "native-print")))]
+fn winspool_printer(_passthrough_printers: &[String]) -> Result<Box<dyn Printer>, PdfError> {
+    Err(PdfError::Print(
+        "winspool印刷にはWindows環境とnative-print featureが必要です".to_string(),
+    ))
+}
+
+#[cfg(feature = "ipp")]
+fn ipp_printer(printers: &BTreeMap<String, String>) -> Result<Box<dyn Printer>, PdfError> {
+    if printers.is_empty() {
+        return Err(PdfError::Print("IPPプリンターが登録されていません".to_string()));
+    }
+    Ok(Box::new(crate::print::ipp::IppPrinter::from_printers(printers.clone())))
+}
+
+#[cfg(not(feature = "ipp"))]
+fn ipp_printer(_printers: &BTreeMap<String, String>) -> Result<Box<dyn Printer>, PdfError> {
+    Err(PdfError::Print("IPP印刷にはipp featureが必要です".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backend_from_name() {
+        assert_eq!(Backend::from_name("auto"), Some(Backend::Auto));
+        assert_eq!(Backend::from_name(" Sumatra "), Some(Backend::Sumatra));
+        assert_eq!(Backend::from_name("WINSPOOL"), Some(Backend::Winspool));
+        assert_eq!(Backend::from_name("ipp"), Some(Backend::Ipp));
+        assert_eq!(Backend::from_name("cups"), None);
+    }
+
+    #[test]
+    fn test_create_sumatra_printer_with_path() {
+        let exe = std::env::current_exe().unwrap();
+        assert!(create_printer(Backend::Sumatra, Some(&exe), &BTreeMap::new()).is_ok());
+    }
+
+    #[cfg(not(all(windows, feature = "native-print")))]
+    #[test]
+    fn test_winspool_requires_feature() {
+        let result = create_printer(Backend::Winspool, None, &BTreeMap::new());
+        assert!(matches!(result, Err(PdfError::Print(_))));
+    }
+
+    /// 印刷結果を確認するためにMockPrinterを共有するアダプター
+    struct Shared(std::sync::Arc<crate::print::MockPrinter>);
+
+    impl Printer for Shared {
+        fn print(&self, pdf_path: &Path, printer_name: Option<&str>) -> Result<(), PdfError> {
+            self.0.print(pdf_path, printer_name)
+        }
+
+        fn list_printers(&self) -> Result<Vec<String>, PdfError> {
+            self.0.list_printers()
+        }
+
+        fn default_printer(&self) -> Result<Option<String>, PdfError> {
+            self.0.default_printer()
+        }
+    }
+
+    #[test]
+    fn test_passthrough_router() {
+        let winspool = std::sync::Arc::new(crate::print::MockPrinter::new().with_default_printer("Label"));
+        let sumatra = std::sync::Arc::new(crate::print::MockPrinter::new());
+        let printer = PassthroughRouter {
+            passthrough_printers: vec!["Label".to_string()],
+            winspool: Box::new(Shared(winspool.clone())),
+            sumatra: Box::new(Shared(sumatra.clone())),
+        };
+
+        // 許可したプリンター（デフォルトプリンターを含む）のみwinspoolで印刷
+        printer.print(Path::new("a.pdf"), Some("Label")).unwrap();
+        printer.print(Path::new("b.pdf"), None).unwrap();
+        let accepted = printer
+            .print_with_options(Path::new("c.pdf"), Some("Office"), &PrintOptions::default())
+            .unwrap();
+        assert_eq!(accepted.as_deref(), Some("Office"));
+        assert_eq!(winspool.printed(), vec![Some("Label".to_string()), None]);
+        assert_eq!(sumatra.printed(), vec![Some("Office".to_string())]);
+    }
+
+    #[test]
+    fn test_passthrough_unsupported_code() {
+        let error = PdfError::PassthroughUnsupported("Office".to_string());
+        assert_eq!(error.code(), 3);
+        assert!(error.to_string().contains("Office"));
+    }
+
+    #[cfg(feature = "ipp")]
+    #[test]
+    fn test_ipp_requires_printers() {
+        assert!(create_printer(Backend::Ipp, None, &BTreeMap::new()).is_err());
+
+        let printers = BTreeMap::from([("倉庫".to_string(), "ipp://printer/ipp/print".to_string())]);
+        let printer = create_printer(Backend::Ipp, None, &printers).unwrap();
+        assert_eq!(printer.list_printers().unwrap(), vec!["倉庫".to_string()]);
+    }
+}
@@ -0,0 +1,195 @@
+//! プリンターの対応する印刷設定
+//!
+//! 印刷前に両面印刷・用紙・部数の指定（`PrintOptions`）がプリンターで使用できるか確認する
+
+use serde_json::Value;
+
+use crate::error::PdfError;
+use crate::print::options::PrintOptions;
+
+/// Win32_Printer.Capabilities の部数指定
+const CAPABILITY_COPIES: i64 = 3;
+
+/// Win32_Printer.Capabilities の両面印刷
+const CAPABILITY_DUPLEX: i64 = 5;
+
+/// プリンターの対応する印刷設定（不明な項目は確認しない）
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PrinterCapabilities {
+    /// 両面印刷に対応しているか（Noneは不明）
+    pub duplex: Option<bool>,
+    /// 使用できる用紙の名前（空の場合は不明）
+    pub papers: Vec<String>,
+    /// 複数部数の指定に対応しているか（Noneは不明）
+    pub copies: Option<bool>,
+}
+
+impl PrinterCapabilities {
+    /// 新しい対応状況を作成（全て不明）
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 両面印刷の対応を設定
+    pub fn with_duplex(mut self, duplex: bool) -> Self {
+        self.duplex = Some(duplex);
+        self
+    }
+
+    /// 使用できる用紙を設定
+    pub fn with_papers(mut self, papers: Vec<String>) -> Self {
+        self.papers = papers;
+        self
+    }
+
+    /// 複数部数の指定の対応を設定
+    pub fn with_copies(mut self, copies: bool) -> Self {
+        self.copies = Some(copies);
+        self
+    }
+
+    /// 印刷オプションのうち対応していない設定の説明
+    pub fn unsupported(&self, options: &PrintOptions) -> Vec<String> {
+        let mut issues = Vec::new();
+        if let Some(duplex) = options.duplex {
+            if duplex.is_two_sided() && self.duplex == Some(false) {
+                issues.push(format!("両面印刷（{}）に対応していません", duplex.label()));
+            }
+        }
+        if let Some(ref paper) = options.paper {
+            if !self.papers.is_empty() && !self.papers.iter().any(|name| paper_matches(name, paper)) {
+                issues.push(format!("用紙 {} に対応していません（対応: {}）", paper, self.papers.join(", ")));
+            }
+        }
+        match options.copies {
+            Some(0) => issues.push("部数は1以上を指定してください".to_string()),
+            Some(copies) if copies > 1 && self.copies == Some(false) => {
+                issues.push(format!("部数の指定（{}部）に対応していません", copies));
+            }
+            _ => {}
+        }
+        issues
+    }
+
+    /// 印刷オプションがプリンターで使用できるか確認
+    ///
+    /// 対応していない設定がある場合は全ての内容をまとめた `PdfError::Print` を返す
+    pub fn check(&self, printer_name: Option<&str>, options: &PrintOptions) -> Result<(), PdfError> {
+        let issues = self.unsupported(options);
+        if issues.is_empty() {
+            return Ok(());
+        }
+        Err(PdfError::Print(format!(
+            "{}は印刷設定に対応していません: {}",
+            printer_name.unwrap_or("デフォルトプリンター"),
+            issues.join(" / ")
+        )))
+    }
+
+    /// PowerShellで取得したプリンターの情報（JSON）から対応状況を解析
+    ///
+    /// - `Properties`: `Get-PrinterProperty` の名前と値（`Config:DuplexUnit` で両面印刷ユニットの有無を判定）
+    /// - `DuplexingMode`: `Get-PrintConfiguration` の両面印刷の設定（両面の場合は対応とみなす）
+    /// - `PaperNames`・`Capabilities`: `Win32_Printer` の用紙の名前と機能
+    pub fn from_windows_json(json: &str) -> Result<Self, PdfError> {
+        let value: Value = serde_json::from_str(json)
+            .map_err(|e| PdfError::Print(format!("プリンターの情報を解析できません: {}", e)))?;
+
+        let properties = as_array(&value["Properties"]);
+        let duplex_unit = properties
+            .iter()
+            .find(|property| property["Name"].as_str() == Some("Config:DuplexUnit"))
+            .and_then(|property| property["Value"].as_str())
+            .map(|unit| unit.eq_ignore_ascii_case("Installed"));
+        let capabilities: Vec<i64> = as_array(&value["Capabilities"]).iter().filter_map(Value::as_i64).collect();
+        let two_sided = value["DuplexingMode"].as_str().is_some_and(|mode| mode.starts_with("TwoSided"));
+
+        let duplex = match duplex_unit {
+            Some(installed) => Some(installed || two_sided),
+            None if two_sided => Some(true),
+            None if !capabilities.is_empty() => Some(capabilities.contains(&CAPABILITY_DUPLEX)),
+            None => None,
+        };
+        let papers = as_array(&value["PaperNames"])
+            .iter()
+            .filter_map(Value::as_str)
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+            .collect();
+        let copies = (!capabilities.is_empty()).then(|| capabilities.contains(&CAPABILITY_COPIES));
+
+        Ok(Self { duplex, papers, copies })
+    }
+}
+
+/// 用紙の名前が一致するか（大文字小文字を区別せず、`A5 148 x 210 mm` のような寸法付きの名前を含む）
+fn paper_matches(name: &str, paper: &str) -> bool {
+    let name = name.trim().to_ascii_lowercase();
+    let paper = paper.trim().to_ascii_lowercase();
+    name == paper || name.strip_prefix(&paper).is_some_and(|rest| rest.starts_with([' ', '(']))
+}
+
+/// JSONの配列（ConvertTo-Jsonで1要素の配列が値になる場合・値が無い場合を含む）
+fn as_array(value: &Value) -> Vec<Value> {
+    match value {
+        Value::Array(values) => values.clone(),
+        Value::Null => Vec::new(),
+        value => vec![value.clone()],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::print::options::Duplex;
+
+    #[test]
+    fn test_from_windows_json() {
+        let json = r#"{"DuplexingMode":"OneSided","Properties":[{"Name":"Config:DuplexUnit","Value":"NotInstalled"},{"Name":"Config:Tray","Value":"3"}],"PaperNames":["A4","A5","Letter"],"Capabilities":[2,3,4]}"#;
+        let capabilities = PrinterCapabilities::from_windows_json(json).unwrap();
+        assert_eq!(
+            capabilities,
+            PrinterCapabilities::new()
+                .with_duplex(false)
+                .with_papers(vec!["A4".to_string(), "A5".to_string(), "Letter".to_string()])
+                .with_copies(true)
+        );
+
+        // プロパティが無い場合はWin32_Printerの機能、1要素の配列は値になる
+        let json = r#"{"DuplexingMode":null,"Properties":null,"PaperNames":"A4","Capabilities":[2,5]}"#;
+        let capabilities = PrinterCapabilities::from_windows_json(json).unwrap();
+        assert_eq!(capabilities.duplex, Some(true));
+        assert_eq!(capabilities.papers, vec!["A4".to_string()]);
+        assert_eq!(capabilities.copies, Some(false));
+
+        // 情報が無い場合は不明
+        let capabilities = PrinterCapabilities::from_windows_json(r#"{"DuplexingMode":"TwoSidedLongEdge"}"#).unwrap();
+        assert_eq!(capabilities, PrinterCapabilities::new().with_duplex(true));
+        assert!(matches!(PrinterCapabilities::from_windows_json("not json"), Err(PdfError::Print(_))));
+    }
+
+    #[test]
+    fn test_check_reports_all_unsupported_settings() {
+        let capabilities = PrinterCapabilities::new()
+            .with_duplex(false)
+            .with_papers(vec!["A4 (210 x 297 mm)".to_string(), "B5".to_string()])
+            .with_copies(false);
+        let options = PrintOptions::new().with_duplex(Duplex::LongEdge).with_paper("A5").with_copies(2);
+
+        let err = capabilities.check(Some("Office"), &options).unwrap_err();
+        let message = err.to_string();
+        assert!(matches!(err, PdfError::Print(_)));
+        assert!(message.contains("Office"));
+        assert!(message.contains("両面印刷（長辺とじ）"));
+        assert!(message.contains("用紙 A5"));
+        assert!(message.contains("2部"));
+
+        // 対応している設定・片面・1部は問題なし
+        let options = PrintOptions::new().with_duplex(Duplex::Simplex).with_paper("a4").with_copies(1);
+        assert!(capabilities.check(Some("Office"), &options).is_ok());
+        // 不明な項目は確認しない
+        let options = PrintOptions::new().with_duplex(Duplex::ShortEdge).with_paper("A3").with_copies(5);
+        assert!(PrinterCapabilities::new().check(None, &options).is_ok());
+        assert_eq!(PrinterCapabilities::new().unsupported(&PrintOptions::new().with_copies(0)).len(), 1);
+    }
+}
@@ -0,0 +1,461 @@
+//! IPP連携モジュール（`ipp` feature）
+//!
+//! ネットワークプリンターにIPPのPrint-JobでPDFを直接送信する。
+//! HTTP（`http://` / `ipp://`）のみ対応し、TLS（`https://` / `ipps://`）は対象外。
+
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::error::PdfError;
+use crate::traits::Printer;
+
+/// IPPのデフォルトポート
+const IPP_DEFAULT_PORT: u16 = 631;
+
+/// 送受信のタイムアウト
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// IPPのオペレーションID: Print-Job
+const OPERATION_PRINT_JOB: u16 = 0x0002;
+
+/// 属性グループ・値のタグ
+const TAG_OPERATION_ATTRIBUTES: u8 = 0x01;
+const TAG_END_OF_ATTRIBUTES: u8 = 0x03;
+const TAG_NAME_WITHOUT_LANGUAGE: u8 = 0x42;
+const TAG_URI: u8 = 0x45;
+const TAG_CHARSET: u8 = 0x47;
+const TAG_NATURAL_LANGUAGE: u8 = 0x48;
+const TAG_MIME_MEDIA_TYPE: u8 = 0x49;
+
+/// IPP プリンター
+pub struct IppPrinter {
+    /// プリンター名を指定しない場合の送信先URI
+    uri: Option<String>,
+    /// 論理プリンター名とIPPのURIの対応
+    printers: BTreeMap<String, String>,
+    /// 送受信のタイムアウト
+    timeout: Duration,
+}
+
+impl IppPrinter {
+    /// 送信先のURIを指定してIppPrinterを作成（例: `http://printer:631/ipp/print`）
+    pub fn new(uri: impl Into<String>) -> Self {
+        Self {
+            uri: Some(uri.into()),
+            printers: BTreeMap::new(),
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// 論理プリンター名とURIの対応からIppPrinterを作成
+    ///
+    /// 1台のみ登録されている場合は、プリンター名を指定しない印刷の送信先にする
+    pub fn from_printers(printers: BTreeMap<String, String>) -> Self {
+        let uri = match printers.len() {
+            1 => printers.values().next().cloned(),
+            _ => None,
+        };
+        Self {
+            uri,
+            printers,
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// 論理プリンター名とURIの対応を追加
+    pub fn with_printer(mut self, name: impl Into<String>, uri: impl Into<String>) -> Self {
+        self.printers.insert(name.into(), uri.into());
+        self
+    }
+
+    /// 送受信のタイムアウトを設定
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// プリンター名から送信先のURIを取得
+    ///
+    /// 登録されていない名前がURIの形式の場合はそのまま使用する
+    fn resolve_uri<'a>(&'a self, printer_name: Option<&'a str>) -> Result<&'a str, PdfError> {
+        match printer_name {
+            Some(name) => match self.printers.get(name) {
+                Some(uri) => Ok(uri),
+                None if name.contains("://") => Ok(name),
+                None => Err(PdfError::Print(format!("IPPプリンターが登録されていません: {}", name))),
+            },
+            None => self
+                .uri
+                .as_deref()
+                .ok_or_else(|| PdfError::Print("IPPプリンター名を指定してください".to_string())),
+        }
+    }
+
+    /// Print-Jobを送信してIPPのステータスコードを取得
+    fn send_print_job(&self, uri: &str, job_name: &str, document: &[u8]) -> Result<u16, PdfError> {
+        let target = IppUri::parse(uri)?;
+        let body = encode_print_job(uri, job_name, document);
+
+        let mut stream = TcpStream::connect((target.host.as_str(), target.port))
+            .map_err(|e| PdfError::Print(format!("IPPプリンターに接続できません: {}: {}", uri, e)))?;
+        // 送受信の失敗（タイムアウト・切断）は印刷エラーとする
+        let io_error = |action: &'static str| {
+            move |e: std::io::Error| PdfError::Print(format!("IPPプリンターとの{}に失敗しました: {}: {}", action, uri, e))
+        };
+        stream.set_read_timeout(Some(self.timeout)).map_err(io_error("接続設定"))?;
+        stream.set_write_timeout(Some(self.timeout)).map_err(io_error("接続設定"))?;
+
+        let header = format!(
+            "POST {} HTTP/1.1\r\nHost: {}:{}\r\nContent-Type: application/ipp\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            target.path,
+            target.host,
+            target.port,
+            body.len()
+        );
+        stream.write_all(header.as_bytes()).map_err(io_error("送信"))?;
+        stream.write_all(&body).map_err(io_error("送信"))?;
+        stream.flush().map_err(io_error("送信"))?;
+
+        let mut response = Vec::new();
+        stream.read_to_end(&mut response).map_err(io_error("受信"))?;
+        let ipp = http_body(&response)?;
+        parse_status(&ipp)
+    }
+}
+
+impl Printer for IppPrinter {
+    fn print(&self, pdf_path: &Path, printer_name: Option<&str>) -> Result<(), PdfError> {
+        let uri = self.resolve_uri(printer_name)?;
+        let document = std::fs::read(pdf_path)
+            .map_err(|e| PdfError::Print(format!("PDFファイル読み込みエラー: {}", e)))?;
+        let job_name = pdf_path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| "print-pdf-service".to_string());
+
+        tracing::info!("IPPで印刷中: {:?}, URI: {}", pdf_path, uri);
+        let status = self.send_print_job(uri, &job_name, &document)?;
+        if status >= 0x0100 {
+            return Err(PdfError::Print(format!(
+                "IPPエラー: status=0x{:04x} ({})",
+                status,
+                status_name(status)
+            )));
+        }
+
+        tracing::info!("印刷ジョブを送信しました: status=0x{:04x}", status);
+        Ok(())
+    }
+
+    fn list_printers(&self) -> Result<Vec<String>, PdfError> {
+        Ok(self.printers.keys().cloned().collect())
+    }
+
+    fn default_printer(&self) -> Result<Option<String>, PdfError> {
+        let Some(ref uri) = self.uri else {
+            return Ok(None);
+        };
+        Ok(self
+            .printers
+            .iter()
+            .find(|(_, printer_uri)| *printer_uri == uri)
+            .map(|(name, _)| name.clone()))
+    }
+}
+
+/// 接続先（`http://` / `ipp://` のURI）
+#[derive(Debug, PartialEq, Eq)]
+struct IppUri {
+    host: String,
+    port: u16,
+    path: String,
+}
+
+impl IppUri {
+    fn parse(uri: &str) -> Result<Self, PdfError> {
+        let invalid = || PdfError::Print(format!("IPPのURIが不正です: {}", uri));
+
+        let (rest, default_port) = if let Some(rest) = uri.strip_prefix("ipp://") {
+            (rest, IPP_DEFAULT_PORT)
+        } else if let Some(rest) = uri.strip_prefix("http://") {
+            (rest, 80)
+        } else {
+            return Err(invalid());
+        };
+
+        let (authority, path) = match rest.find('/') {
+            Some(index) => (&rest[..index], &rest[index..]),
+            None => (rest, "/"),
+        };
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) => (host, port.parse().map_err(|_| invalid())?),
+            None => (authority, default_port),
+        };
+        if host.is_empty() {
+            return Err(invalid());
+        }
+
+        Ok(Self {
+            host: host.to_string(),
+            port,
+            path: path.to_string(),
+        })
+    }
+}
+
+/// Print-Jobのリクエストを作成
+fn encode_print_job(uri: &str, job_name: &str, document: &[u8]) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(document.len() + 256);
+    buf.extend_from_slice(&[0x01, 0x01]); // IPP/1.1
+    buf.extend_from_slice(&OPERATION_PRINT_JOB.to_be_bytes());
+    buf.extend_from_slice(&1u32.to_be_bytes()); // request-id
+
+    buf.push(TAG_OPERATION_ATTRIBUTES);
+    push_attribute(&mut buf, TAG_CHARSET, "attributes-charset", "utf-8");
+    push_attribute(&mut buf, TAG_NATURAL_LANGUAGE, "attributes-natural-language", "ja");
+    push_attribute(&mut buf, TAG_URI, "printer-uri", uri);
+    push_attribute(&mut buf, TAG_NAME_WITHOUT_LANGUAGE, "requesting-user-name", "print-pdf-service");
+    push_attribute(&mut buf, TAG_NAME_WITHOUT_LANGUAGE, "job-name", job_name);
+    push_attribute(&mut buf, TAG_MIME_MEDIA_TYPE, "document-format", "application/pdf");
+    buf.push(TAG_END_OF_ATTRIBUTES);
+
+    buf.extend_from_slice(document);
+    buf
+}
+
+/// 属性を追加（値タグ・名前・値）
+fn push_attribute(buf: &mut Vec<u8>, tag: u8, name: &str, value: &str) {
+    buf.push(tag);
+    buf.extend_from_slice(&(name.len() as u16).to_be_bytes());
+    buf.extend_from_slice(name.as_bytes());
+    buf.extend_from_slice(&(value.len() as u16).to_be_bytes());
+    buf.extend_from_slice(value.as_bytes());
+}
+
+/// HTTPレスポンスからIPPのレスポンス本文を取得
+fn http_body(response: &[u8]) -> Result<Vec<u8>, PdfError> {
+    let split = response
+        .windows(4)
+        .position(|w| w == b"\r\n\r\n")
+        .ok_or_else(|| PdfError::Print("IPPプリンターの応答が不正です".to_string()))?;
+    let header = String::from_utf8_lossy(&response[..split]);
+    let body = &response[split + 4..];
+
+    let status_line = header.lines().next().unwrap_or_default();
+    let http_status = status_line.split_whitespace().nth(1).unwrap_or_default();
+    if http_status != "200" {
+        return Err(PdfError::Print(format!("IPPプリンターのHTTPエラー: {}", status_line)));
+    }
+
+    let chunked = header
+        .lines()
+        .any(|line| line.to_ascii_lowercase().starts_with("transfer-encoding:") && line.to_ascii_lowercase().contains("chunked"));
+    if chunked {
+        Ok(dechunk(body))
+    } else {
+        Ok(body.to_vec())
+    }
+}
+
+/// chunked転送の本文を結合
+fn dechunk(mut body: &[u8]) -> Vec<u8> {
+    let mut out = Vec::new();
+    while let Some(line_end) = body.windows(2).position(|w| w == b"\r\n") {
+        let size_text = String::from_utf8_lossy(&body[..line_end]);
+        let size = usize::from_str_radix(size_text.split(';').next().unwrap_or_default().trim(), 16).unwrap_or(0);
+        body = &body[line_end + 2..];
+        if size == 0 || body.len() < size {
+            break;
+        }
+        out.extend_from_slice(&body[..size]);
+        body = body.get(size + 2..).unwrap_or_default();
+    }
+    out
+}
+
+/// IPPのレスポンスからステータスコードを取得
+fn parse_status(ipp: &[u8]) -> Result<u16, PdfError> {
+    if ipp.len() < 8 {
+        return Err(PdfError::Print("IPPプリンターの応答が不正です".to_string()));
+    }
+    Ok(u16::from_be_bytes([ipp[2], ipp[3]]))
+}
+
+/// 主なIPPステータスコードの名前
+fn status_name(status: u16) -> &'static str {
+    match status {
+        0x0400 => "client-error-bad-request",
+        0x0401 => "client-error-forbidden",
+        0x0402 => "client-error-not-authenticated",
+        0x0403 => "client-error-not-authorized",
+        0x0404 => "client-error-not-possible",
+        0x0406 => "client-error-not-found",
+        0x040A => "client-error-document-format-not-supported",
+        0x0500 => "server-error-internal-error",
+        0x0501 => "server-error-operation-not-supported",
+        0x0502 => "server-error-service-unavailable",
+        0x0506 => "server-error-not-accepting-jobs",
+        0x0507 => "server-error-busy",
+        _ => "unknown",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+    use std::thread::JoinHandle;
+
+    /// 1件のリクエストを受け付けて指定のステータスを返すモックIPPサーバー
+    fn mock_ipp_server(status: u16) -> (String, JoinHandle<Vec<u8>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let uri = format!("http://{}/ipp/print", listener.local_addr().unwrap());
+        let handle = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 4096];
+            loop {
+                let n = stream.read(&mut buf).unwrap();
+                request.extend_from_slice(&buf[..n]);
+                if let Some(split) = request.windows(4).position(|w| w == b"\r\n\r\n") {
+                    let header = String::from_utf8_lossy(&request[..split]).to_lowercase();
+                    let length: usize = header
+                        .lines()
+                        .find_map(|line| line.strip_prefix("content-length:"))
+                        .map(|v| v.trim().parse().unwrap())
+                        .unwrap();
+                    if request.len() >= split + 4 + length {
+                        break;
+                    }
+                }
+                if n == 0 {
+                    break;
+                }
+            }
+
+            let mut ipp = vec![0x01, 0x01];
+            ipp.extend_from_slice(&status.to_be_bytes());
+            ipp.extend_from_slice(&1u32.to_be_bytes());
+            ipp.push(TAG_END_OF_ATTRIBUTES);
+            let header = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/ipp\r\nContent-Length: {}\r\n\r\n",
+                ipp.len()
+            );
+            stream.write_all(header.as_bytes()).unwrap();
+            stream.write_all(&ipp).unwrap();
+            request
+        });
+        (uri, handle)
+    }
+
+    fn write_test_pdf(name: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(name);
+        std::fs::write(&path, b"%PDF-1.4\n%%EOF\n").unwrap();
+        path
+    }
+
+    #[test]
+    fn test_parse_uri() {
+        assert_eq!(
+            IppUri::parse("ipp://printer/ipp/print").unwrap(),
+            IppUri {
+                host: "printer".to_string(),
+                port: 631,
+                path: "/ipp/print".to_string(),
+            }
+        );
+        assert_eq!(IppUri::parse("http://10.0.0.5:8631").unwrap().path, "/");
+        assert!(IppUri::parse("ipps://printer/ipp/print").is_err());
+    }
+
+    #[test]
+    fn test_encode_print_job() {
+        let body = encode_print_job("ipp://printer/ipp/print", "job.pdf", b"%PDF");
+        assert_eq!(&body[..4], &[0x01, 0x01, 0x00, 0x02]);
+        assert!(body.windows(15).any(|w| w == b"application/pdf"));
+        assert!(body.ends_with(b"\x03%PDF"));
+    }
+
+    #[test]
+    fn test_print_job_to_mock_server() {
+        let (uri, server) = mock_ipp_server(0x0000);
+        let pdf_path = write_test_pdf("print_pdf_service_ipp_ok.pdf");
+
+        let printer = IppPrinter::from_printers(BTreeMap::from([("倉庫".to_string(), uri)]));
+        let result = printer.print(&pdf_path, Some("倉庫"));
+        let request = server.join().unwrap();
+        std::fs::remove_file(&pdf_path).unwrap();
+
+        assert!(result.is_ok(), "{:?}", result);
+        assert!(request.starts_with(b"POST /ipp/print HTTP/1.1"));
+        assert!(request.windows(15).any(|w| w == b"application/pdf"));
+        assert!(request.ends_with(b"%PDF-1.4\n%%EOF\n"));
+        assert_eq!(printer.default_printer().unwrap(), Some("倉庫".to_string()));
+    }
+
+    #[test]
+    fn test_print_job_error_status() {
+        let (uri, server) = mock_ipp_server(0x040A);
+        let pdf_path = write_test_pdf("print_pdf_service_ipp_error.pdf");
+
+        let result = IppPrinter::new(uri).print(&pdf_path, None);
+        server.join().unwrap();
+        std::fs::remove_file(&pdf_path).unwrap();
+
+        match result {
+            Err(PdfError::Print(message)) => {
+                assert!(message.contains("0x040a"), "{}", message);
+                assert!(message.contains("document-format-not-supported"), "{}", message);
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_print_job_timeout() {
+        // リクエストを受け付けて応答しないサーバー
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let uri = format!("http://{}/ipp/print", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            std::thread::sleep(Duration::from_millis(500));
+            drop(stream);
+        });
+        let pdf_path = write_test_pdf("print_pdf_service_ipp_timeout.pdf");
+
+        let result = IppPrinter::new(uri).with_timeout(Duration::from_millis(100)).print(&pdf_path, None);
+        server.join().unwrap();
+        std::fs::remove_file(&pdf_path).unwrap();
+
+        match result {
+            Err(PdfError::Print(message)) => assert!(message.contains("受信に失敗"), "{}", message),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_print_job_connection_closed() {
+        // 接続直後に切断するサーバー
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let uri = format!("http://{}/ipp/print", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || drop(listener.accept().unwrap()));
+        let pdf_path = write_test_pdf("print_pdf_service_ipp_closed.pdf");
+
+        let result = IppPrinter::new(uri).print(&pdf_path, None);
+        server.join().unwrap();
+        std::fs::remove_file(&pdf_path).unwrap();
+
+        assert!(matches!(result, Err(PdfError::Print(_))), "{:?}", result);
+    }
+
+    #[test]
+    fn test_unknown_printer_name() {
+        let printer = IppPrinter::from_printers(BTreeMap::new());
+        assert!(matches!(printer.resolve_uri(Some("不明")), Err(PdfError::Print(_))));
+        assert!(matches!(printer.resolve_uri(None), Err(PdfError::Print(_))));
+        assert_eq!(printer.resolve_uri(Some("ipp://p/ipp")).unwrap(), "ipp://p/ipp");
+    }
+}
@@ -0,0 +1,248 @@
+//! 印刷を実行しないテスト用プリンター
+//!
+//! 実際のプリンターやSumatraPDFを使用せずに印刷処理を検証するため、
+//! 印刷・表示の要求をメモリ上に記録する。
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::error::PdfError;
+use crate::print::capabilities::PrinterCapabilities;
+use crate::print::options::PrintOptions;
+use crate::traits::{print_with_fallback, Printer};
+
+/// 記録された印刷要求
+#[derive(Debug, Clone)]
+pub struct PrintJob {
+    /// 印刷したPDFファイルのパス
+    pub pdf_path: PathBuf,
+    /// プリンター名（Noneはデフォルトプリンター）
+    pub printer_name: Option<String>,
+    /// 印刷オプション（`print` で直接印刷した場合はデフォルト）
+    pub options: PrintOptions,
+    /// 印刷を受け付けたか（オフラインのプリンターはfalse）
+    pub accepted: bool,
+}
+
+/// 印刷要求を記録するテスト用プリンター
+#[derive(Debug, Default)]
+pub struct MockPrinter {
+    /// プリンター一覧（Noneの場合は一覧の取得に失敗する）
+    printers: Option<Vec<String>>,
+    /// デフォルトプリンター
+    default_printer: Option<String>,
+    /// 印刷に失敗するプリンター
+    offline: Vec<String>,
+    /// プリンターごとの対応する印刷設定（未設定のプリンターは不明）
+    capabilities: HashMap<String, PrinterCapabilities>,
+    /// 印刷要求の記録
+    jobs: Mutex<Vec<PrintJob>>,
+    /// 表示要求の記録
+    previews: Mutex<Vec<PathBuf>>,
+}
+
+impl MockPrinter {
+    /// 新しいテスト用プリンターを作成
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// プリンター一覧を設定
+    pub fn with_printers(mut self, printers: Vec<String>) -> Self {
+        self.printers = Some(printers);
+        self
+    }
+
+    /// デフォルトプリンターを設定
+    pub fn with_default_printer(mut self, name: impl Into<String>) -> Self {
+        self.default_printer = Some(name.into());
+        self
+    }
+
+    /// 印刷に失敗するプリンターを追加
+    pub fn with_offline(mut self, name: impl Into<String>) -> Self {
+        self.offline.push(name.into());
+        self
+    }
+
+    /// プリンターの対応する印刷設定を設定
+    pub fn with_capabilities(mut self, name: impl Into<String>, capabilities: PrinterCapabilities) -> Self {
+        self.capabilities.insert(name.into(), capabilities);
+        self
+    }
+
+    /// 記録された印刷要求を取得（失敗した試行を含む）
+    pub fn jobs(&self) -> Vec<PrintJob> {
+        self.jobs.lock().unwrap().clone()
+    }
+
+    /// 受け付けた印刷要求のプリンター名を取得
+    pub fn printed(&self) -> Vec<Option<String>> {
+        self.jobs
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|job| job.accepted)
+            .map(|job| job.printer_name.clone())
+            .collect()
+    }
+
+    /// 記録された表示要求を取得
+    pub fn previews(&self) -> Vec<PathBuf> {
+        self.previews.lock().unwrap().clone()
+    }
+
+    /// 印刷要求を記録
+    fn record(&self, pdf_path: &Path, printer_name: Option<&str>, options: &PrintOptions) -> Result<(), PdfError> {
+        let offline = printer_name.filter(|name| self.offline.iter().any(|p| p == name));
+        self.jobs.lock().unwrap().push(PrintJob {
+            pdf_path: pdf_path.to_path_buf(),
+            printer_name: printer_name.map(|s| s.to_string()),
+            options: options.clone(),
+            accepted: offline.is_none(),
+        });
+        match offline {
+            Some(name) => Err(PdfError::Print(format!("オフライン: {}", name))),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Printer for MockPrinter {
+    fn print(&self, pdf_path: &Path, printer_name: Option<&str>) -> Result<(), PdfError> {
+        self.record(pdf_path, printer_name, &PrintOptions::default())
+    }
+
+    fn list_printers(&self) -> Result<Vec<String>, PdfError> {
+        self.printers
+            .clone()
+            .ok_or_else(|| PdfError::Print("プリンター一覧は取得できません".to_string()))
+    }
+
+    fn default_printer(&self) -> Result<Option<String>, PdfError> {
+        Ok(self.default_printer.clone())
+    }
+
+    fn preview(&self, pdf_path: &Path) -> Result<(), PdfError> {
+        self.previews.lock().unwrap().push(pdf_path.to_path_buf());
+        Ok(())
+    }
+
+    fn capabilities(&self, printer_name: Option<&str>) -> Result<Option<PrinterCapabilities>, PdfError> {
+        let name = printer_name.or(self.default_printer.as_deref());
+        Ok(name.and_then(|name| self.capabilities.get(name).cloned()))
+    }
+
+    fn print_with_options(
+        &self,
+        pdf_path: &Path,
+        printer_name: Option<&str>,
+        options: &PrintOptions,
+    ) -> Result<Option<String>, PdfError> {
+        print_with_fallback(&WithOptions { printer: self, options }, pdf_path, printer_name, options)
+    }
+}
+
+/// 印刷オプションを記録に含めるためのアダプター
+struct WithOptions<'a> {
+    printer: &'a MockPrinter,
+    options: &'a PrintOptions,
+}
+
+impl Printer for WithOptions<'_> {
+    fn print(&self, pdf_path: &Path, printer_name: Option<&str>) -> Result<(), PdfError> {
+        self.printer.record(pdf_path, printer_name, self.options)
+    }
+
+    fn list_printers(&self) -> Result<Vec<String>, PdfError> {
+        self.printer.list_printers()
+    }
+
+    fn default_printer(&self) -> Result<Option<String>, PdfError> {
+        self.printer.default_printer()
+    }
+
+    fn capabilities(&self, printer_name: Option<&str>) -> Result<Option<PrinterCapabilities>, PdfError> {
+        self.printer.capabilities(printer_name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_records_jobs_with_options() {
+        let printer = MockPrinter::new().with_offline("Main");
+        let options = PrintOptions::new().with_fallback_printers(vec!["Backup".to_string()]);
+        let accepted = printer
+            .print_with_options(Path::new("report.pdf"), Some("Main"), &options)
+            .unwrap();
+        assert_eq!(accepted, Some("Backup".to_string()));
+
+        let jobs = printer.jobs();
+        assert_eq!(jobs.len(), 2);
+        assert_eq!(jobs[0].printer_name.as_deref(), Some("Main"));
+        assert!(!jobs[0].accepted);
+        assert_eq!(jobs[1].pdf_path, PathBuf::from("report.pdf"));
+        assert_eq!(jobs[1].options.fallback_printers, vec!["Backup".to_string()]);
+        assert_eq!(printer.printed(), vec![Some("Backup".to_string())]);
+    }
+
+    #[test]
+    fn test_skips_printers_missing_from_list() {
+        let printer = MockPrinter::new().with_printers(vec!["Backup".to_string()]);
+        let options = PrintOptions::new().with_fallback_printers(vec!["Backup".to_string()]);
+        let accepted = printer
+            .print_with_options(Path::new("report.pdf"), Some("Removed"), &options)
+            .unwrap();
+        assert_eq!(accepted, Some("Backup".to_string()));
+        assert_eq!(printer.jobs().len(), 1);
+    }
+
+    #[test]
+    fn test_skips_printers_without_capabilities() {
+        use crate::print::options::Duplex;
+
+        let printer = MockPrinter::new()
+            .with_default_printer("Office")
+            .with_capabilities("Office", PrinterCapabilities::new().with_duplex(false))
+            .with_capabilities("Backup", PrinterCapabilities::new().with_duplex(true).with_papers(vec!["A4".to_string()]));
+        let options = PrintOptions::new()
+            .with_fallback_printers(vec!["Backup".to_string(), "Unknown".to_string()])
+            .with_duplex(Duplex::LongEdge);
+
+        // 対応状況の確認は既定では行わない
+        let accepted = printer.print_with_options(Path::new("report.pdf"), None, &options).unwrap();
+        assert_eq!(accepted, None);
+        let options = options.with_capability_check(true);
+
+        // 両面印刷に対応していないデフォルトプリンターは印刷せずにスキップ
+        let accepted = printer.print_with_options(Path::new("report.pdf"), None, &options).unwrap();
+        assert_eq!(accepted, Some("Backup".to_string()));
+        assert_eq!(printer.printed(), vec![None, Some("Backup".to_string())]);
+        assert!(printer.check_options(None, &options).is_err());
+
+        // 全ての候補が対応していない場合は内容を含むエラー（対応状況が不明なプリンターは確認しない）
+        let options = options.with_paper("A5").with_fallback_printers(vec!["Backup".to_string()]);
+        let err = printer.print_with_options(Path::new("report.pdf"), Some("Office"), &options).unwrap_err();
+        assert!(err.to_string().contains("両面印刷"));
+        assert!(err.to_string().contains("用紙 A5"));
+        assert_eq!(printer.jobs().len(), 2);
+        assert!(printer.check_options(Some("Unknown"), &options).is_ok());
+    }
+
+    #[test]
+    fn test_direct_print_and_preview() {
+        let printer = MockPrinter::new().with_default_printer("Office");
+        printer.print(Path::new("a.pdf"), None).unwrap();
+        assert!(printer.print(Path::new("a.pdf"), Some("Main")).is_ok());
+        printer.preview(Path::new("b.pdf")).unwrap();
+
+        assert_eq!(printer.printed(), vec![None, Some("Main".to_string())]);
+        assert_eq!(printer.previews(), vec![PathBuf::from("b.pdf")]);
+        assert_eq!(printer.default_printer().unwrap(), Some("Office".to_string()));
+        assert!(printer.list_printers().is_err());
+    }
+}
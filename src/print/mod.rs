@@ -1,7 +1,29 @@
 //! 印刷モジュール
 //!
-//! SumatraPDFを使用したPDF印刷機能
+//! SumatraPDF、winspool API（`native-print` feature）、またはIPP（`ipp` feature）を使用したPDF印刷機能
+//!
+//! テスト時または `test-util` feature有効時は、印刷要求を記録するだけの `MockPrinter` を利用できる
 
+pub mod backend;
+pub mod capabilities;
+#[cfg(feature = "ipp")]
+pub mod ipp;
+#[cfg(any(test, feature = "test-util"))]
+pub mod mock;
+pub mod options;
 pub mod sumatra;
+pub mod viewer;
+#[cfg(all(windows, feature = "native-print"))]
+pub mod winspool;
 
-pub use sumatra::SumatraPrinter;
+pub use backend::{create_printer, create_printer_with_passthrough, Backend};
+pub use capabilities::PrinterCapabilities;
+#[cfg(feature = "ipp")]
+pub use ipp::IppPrinter;
+#[cfg(any(test, feature = "test-util"))]
+pub use mock::{MockPrinter, PrintJob};
+pub use options::{Duplex, PrintOptions};
+pub use sumatra::{SumatraPrinter, SumatraVersion};
+pub use viewer::open_in_viewer;
+#[cfg(all(windows, feature = "native-print"))]
+pub use winspool::WinspoolPrinter;
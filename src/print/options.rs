@@ -0,0 +1,172 @@
+//! 印刷オプション
+
+/// 両面印刷
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Duplex {
+    /// 片面
+    Simplex,
+    /// 両面（長辺とじ）
+    LongEdge,
+    /// 両面（短辺とじ）
+    ShortEdge,
+}
+
+impl Duplex {
+    /// 両面印刷か
+    pub fn is_two_sided(self) -> bool {
+        self != Duplex::Simplex
+    }
+
+    /// 名前から取得（`simplex` / `long_edge` / `short_edge`、大文字小文字を区別しない）
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "simplex" => Some(Duplex::Simplex),
+            "long_edge" => Some(Duplex::LongEdge),
+            "short_edge" => Some(Duplex::ShortEdge),
+            _ => None,
+        }
+    }
+
+    /// 表示用の名前
+    pub fn label(self) -> &'static str {
+        match self {
+            Duplex::Simplex => "片面",
+            Duplex::LongEdge => "長辺とじ",
+            Duplex::ShortEdge => "短辺とじ",
+        }
+    }
+}
+
+/// 印刷オプション
+///
+/// 両面印刷・用紙・部数はSumatraPDFでは `-print-settings` に指定する。
+/// `with_capability_check` を有効にすると印刷前にプリンターの対応状況（`Printer::capabilities`）を確認し、
+/// 対応していないプリンターでは印刷しない
+#[derive(Debug, Clone, Default)]
+pub struct PrintOptions {
+    /// フォールバックプリンター（優先プリンターで印刷できない場合に順番に試行）
+    pub fallback_printers: Vec<String>,
+    /// 両面印刷（Noneはプリンターの設定）
+    pub duplex: Option<Duplex>,
+    /// 用紙の名前（例: "A5"、Noneはプリンターの設定）
+    pub paper: Option<String>,
+    /// 部数（Noneは1部）
+    pub copies: Option<u32>,
+    /// 印刷前にプリンターの対応状況を確認するか（既定は確認しない）
+    pub check_capabilities: bool,
+}
+
+impl PrintOptions {
+    /// 新しい印刷オプションを作成
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// フォールバックプリンターを設定
+    pub fn with_fallback_printers(mut self, printers: Vec<String>) -> Self {
+        self.fallback_printers = printers;
+        self
+    }
+
+    /// 両面印刷を設定
+    pub fn with_duplex(mut self, duplex: Duplex) -> Self {
+        self.duplex = Some(duplex);
+        self
+    }
+
+    /// 用紙を設定
+    pub fn with_paper(mut self, paper: impl Into<String>) -> Self {
+        self.paper = Some(paper.into());
+        self
+    }
+
+    /// 部数を設定（1以上）
+    pub fn with_copies(mut self, copies: u32) -> Self {
+        self.copies = Some(copies);
+        self
+    }
+
+    /// 印刷前にプリンターの対応状況を確認するか設定
+    ///
+    /// 確認にはプリンターごとに対応状況の取得（Windowsでは複数のPowerShellの実行）が必要なため既定では無効
+    pub fn with_capability_check(mut self, check: bool) -> Self {
+        self.check_capabilities = check;
+        self
+    }
+
+    /// 両面印刷・用紙・部数のいずれかを指定しているか
+    pub fn has_settings(&self) -> bool {
+        self.duplex.is_some() || self.paper.is_some() || self.copies.is_some()
+    }
+
+    /// 試行するプリンターの候補を順番に取得
+    ///
+    /// 先頭は優先プリンター（Noneはデフォルトプリンター）、以降はフォールバックプリンター
+    pub fn candidates(&self, printer_name: Option<&str>) -> Vec<Option<String>> {
+        let mut candidates = vec![printer_name.map(|s| s.to_string())];
+        for fallback in &self.fallback_printers {
+            let candidate = Some(fallback.clone());
+            if !candidates.contains(&candidate) {
+                candidates.push(candidate);
+            }
+        }
+        candidates
+    }
+}
+
+/// プリンター一覧に存在しない候補を除外（デフォルトプリンターは常に残す）
+pub(crate) fn filter_available(candidates: Vec<Option<String>>, available: &[String]) -> Vec<Option<String>> {
+    candidates
+        .into_iter()
+        .filter(|candidate| match candidate {
+            Some(name) => {
+                let found = available.iter().any(|p| p == name);
+                if !found {
+                    tracing::warn!("プリンターが見つからないためスキップ: {}", name);
+                }
+                found
+            }
+            None => true,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter_available() {
+        let candidates = vec![
+            Some("Offline".to_string()),
+            Some("Online".to_string()),
+            None,
+        ];
+        let available = vec!["Online".to_string()];
+        let filtered = filter_available(candidates, &available);
+        assert_eq!(filtered, vec![Some("Online".to_string()), None]);
+    }
+
+    #[test]
+    fn test_candidates_order() {
+        let options = PrintOptions::new()
+            .with_fallback_printers(vec!["Backup1".to_string(), "Backup2".to_string()]);
+        let candidates = options.candidates(Some("Main"));
+        assert_eq!(
+            candidates,
+            vec![
+                Some("Main".to_string()),
+                Some("Backup1".to_string()),
+                Some("Backup2".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_candidates_default_printer_and_duplicates() {
+        let options = PrintOptions::new()
+            .with_fallback_printers(vec!["Backup".to_string(), "Backup".to_string()]);
+        let candidates = options.candidates(None);
+        assert_eq!(candidates, vec![None, Some("Backup".to_string())]);
+    }
+}
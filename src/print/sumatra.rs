@@ -2,21 +2,94 @@
 //!
 //! SumatraPDFを使用してPDFを印刷
 
+use std::collections::HashMap;
+use std::ffi::OsString;
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::{Arc, Mutex, OnceLock};
 
 use crate::error::PdfError;
+use crate::fs::{Fs, RealFs};
+use crate::print::capabilities::PrinterCapabilities;
+use crate::print::options::{Duplex, PrintOptions};
+use crate::print::viewer::{detach, open_in_viewer};
+use crate::traits::{print_with_fallback, Printer};
+
+/// SumatraPDFのパスを指定する環境変数
+pub const SUMATRA_PATH_ENV: &str = "SUMATRA_PDF_PATH";
+
+/// 動作を確認しているSumatraPDFの最小バージョン（これより古い場合は警告）
+pub const MIN_SUPPORTED_VERSION: SumatraVersion = SumatraVersion::new(3, 0, 0);
+
+/// `-print-settings` に対応したSumatraPDFのバージョン（これより古い場合は引数から除く）
+pub const PRINT_SETTINGS_VERSION: SumatraVersion = SumatraVersion::new(2, 1, 0);
+
+/// バージョンリソース（VS_FIXEDFILEINFO）の署名
+const FIXED_FILE_INFO_SIGNATURE: [u8; 4] = 0xFEEF_04BDu32.to_le_bytes();
+
+/// SumatraPDFのバージョン
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SumatraVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl SumatraVersion {
+    /// バージョンを作成
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self { major, minor, patch }
+    }
+
+    /// テキスト中の最初の `数字.数字[.数字]` をバージョンとして解析
+    ///
+    /// `SumatraPDF 3.5.2`、`SumatraPDF-3.4.6-64.exe` などの形式に対応する
+    pub fn parse(text: &str) -> Option<Self> {
+        text.split(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .filter_map(|token| {
+                let mut parts = token.trim_matches('.').split('.').map(|part| part.parse::<u32>().ok());
+                let major = parts.next()??;
+                let minor = parts.next()??;
+                let patch = parts.next().unwrap_or(Some(0))?;
+                Some(Self::new(major, minor, patch))
+            })
+            .next()
+    }
+}
+
+impl std::fmt::Display for SumatraVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
 
 /// SumatraPDF プリンター
 pub struct SumatraPrinter {
     /// SumatraPDFの実行ファイルパス
     sumatra_path: Option<PathBuf>,
+    /// 追加のコマンドライン引数（ファイルパスの直前に付与）
+    extra_args: Vec<String>,
+    /// ファイル操作（実行ファイルの検索・PDFの絶対パス取得）
+    fs: Arc<dyn Fs>,
+    /// SumatraPDFのバージョン（検索時に取得、不明な場合はNone）
+    version: Option<SumatraVersion>,
 }
 
 impl SumatraPrinter {
     /// 新しいSumatraPrinterを作成
     pub fn new() -> Self {
-        Self { sumatra_path: None }
+        Self {
+            sumatra_path: None,
+            extra_args: Vec::new(),
+            fs: Arc::new(RealFs),
+            version: None,
+        }
+    }
+
+    /// ファイル操作を設定（テストではメモリ上のファイルシステムを使用）
+    pub fn with_fs(mut self, fs: Arc<dyn Fs>) -> Self {
+        self.fs = fs;
+        self
     }
 
     /// SumatraPDFのパスを手動で設定
@@ -25,12 +98,83 @@ impl SumatraPrinter {
         self
     }
 
+    /// 追加のコマンドライン引数を設定
+    ///
+    /// `-print-settings "fit"` など、APIで表現できないオプションを渡すための手段。
+    /// 引数は検証せずにPDFファイルパスの直前へそのまま付与するため、
+    /// 不正な引数を渡すと印刷コマンド自体が失敗したり、意図しない動作になる場合がある。
+    pub fn with_extra_args(mut self, args: Vec<String>) -> Self {
+        self.extra_args = args;
+        self
+    }
+
+    /// SumatraPDFのバージョンを手動で設定（`find_sumatra` は検出せずにこの値を使用する）
+    pub fn with_version(mut self, version: SumatraVersion) -> Self {
+        self.version = Some(version);
+        self
+    }
+
+    /// SumatraPDFのバージョンを取得（不明な場合はNone）
+    pub fn version(&self) -> Option<SumatraVersion> {
+        self.version
+    }
+
     /// SumatraPDFを検索
+    ///
+    /// 見つかった場合はバージョンも取得し、印刷時の引数の調整に使用する（`with_version` で設定済みの場合はそのまま）
     pub fn find_sumatra(&mut self) -> Result<PathBuf, PdfError> {
+        match self.discover() {
+            Some(path) => {
+                if self.version.is_none() {
+                    self.version = detect_version(self.fs.as_ref(), &path);
+                }
+                match self.version {
+                    Some(version) if version < MIN_SUPPORTED_VERSION => tracing::warn!(
+                        "動作未確認のSumatraPDFのバージョンです（{}以上を推奨）: {}",
+                        MIN_SUPPORTED_VERSION,
+                        version
+                    ),
+                    Some(version) => tracing::info!("SumatraPDFのバージョン: {}", version),
+                    None => tracing::warn!("SumatraPDFのバージョンを取得できません: {:?}", path),
+                }
+                self.sumatra_path = Some(path.clone());
+                Ok(path)
+            }
+            None => Err(PdfError::Print(
+                "SumatraPDF実行ファイルが見つかりません".to_string(),
+            )),
+        }
+    }
+
+    /// SumatraPDFが利用可能か確認
+    ///
+    /// 検索処理を実行するが、設定済みのパスは変更しない
+    pub fn is_available(&self) -> bool {
+        self.discover().is_some()
+    }
+
+    /// SumatraPDFの実行ファイルを検索（状態を変更しない）
+    ///
+    /// 設定済みのパス、環境変数 `SUMATRA_PDF_PATH`、既知のインストール先の順に検索する
+    fn discover(&self) -> Option<PathBuf> {
+        let env_path = std::env::var_os(SUMATRA_PATH_ENV).map(PathBuf::from);
+        self.discover_from(env_path)
+    }
+
+    /// 環境変数の値を指定してSumatraPDFの実行ファイルを検索
+    fn discover_from(&self, env_path: Option<PathBuf>) -> Option<PathBuf> {
         if let Some(ref path) = self.sumatra_path {
-            if path.exists() {
-                return Ok(path.clone());
+            if self.fs.exists(path) {
+                return Some(path.clone());
+            }
+        }
+
+        if let Some(path) = env_path {
+            if self.fs.exists(&path) {
+                tracing::info!("SumatraPDF found via {}: {:?}", SUMATRA_PATH_ENV, path);
+                return Some(path);
             }
+            tracing::warn!("{}のSumatraPDFが見つかりません: {:?}", SUMATRA_PATH_ENV, path);
         }
 
         // 複数の場所でSumatraPDFを探す
@@ -70,11 +214,10 @@ impl SumatraPrinter {
         for search_path in &all_search_paths {
             for candidate in &candidates {
                 let full_path = Path::new(search_path).join(candidate);
-                if full_path.exists() {
-                    if let Ok(abs_path) = std::fs::canonicalize(&full_path) {
+                if self.fs.exists(&full_path) {
+                    if let Ok(abs_path) = self.fs.canonicalize(&full_path) {
                         tracing::info!("SumatraPDF found: {:?}", abs_path);
-                        self.sumatra_path = Some(abs_path.clone());
-                        return Ok(abs_path);
+                        return Some(abs_path);
                     }
                 }
             }
@@ -86,18 +229,15 @@ impl SumatraPrinter {
                 let path_str = String::from_utf8_lossy(&output.stdout);
                 if let Some(first_line) = path_str.lines().next() {
                     let path = PathBuf::from(first_line.trim());
-                    if path.exists() {
+                    if self.fs.exists(&path) {
                         tracing::info!("SumatraPDF found in PATH: {:?}", path);
-                        self.sumatra_path = Some(path.clone());
-                        return Ok(path);
+                        return Some(path);
                     }
                 }
             }
         }
 
-        Err(PdfError::Print(
-            "SumatraPDF実行ファイルが見つかりません".to_string(),
-        ))
+        None
     }
 
     /// PDFを印刷
@@ -106,29 +246,29 @@ impl SumatraPrinter {
     /// * `pdf_path` - 印刷するPDFファイルのパス
     /// * `printer_name` - プリンター名（None の場合はデフォルトプリンター）
     pub fn print(&self, pdf_path: &Path, printer_name: Option<&str>) -> Result<(), PdfError> {
+        self.print_with_settings(pdf_path, printer_name, &PrintOptions::default())
+    }
+
+    /// 両面印刷・用紙・部数を `-print-settings` に指定してPDFを印刷
+    fn print_with_settings(&self, pdf_path: &Path, printer_name: Option<&str>, options: &PrintOptions) -> Result<(), PdfError> {
         let sumatra_path = self.sumatra_path.as_ref().ok_or_else(|| {
             PdfError::Print("SumatraPDFのパスが設定されていません".to_string())
         })?;
 
         // PDFファイルの絶対パスを取得
-        let abs_pdf_path = std::fs::canonicalize(pdf_path).map_err(|e| {
+        let abs_pdf_path = self.fs.canonicalize(pdf_path).map_err(|e| {
             PdfError::Print(format!("PDFファイルの絶対パス取得エラー: {}", e))
         })?;
 
-        // SumatraPDFコマンドを構築
-        let mut cmd = Command::new(sumatra_path);
-
         if let Some(printer) = printer_name {
-            // 特定のプリンターに印刷
-            cmd.arg("-print-to").arg(printer);
             tracing::info!("SumatraPDFで印刷中: {:?}, プリンター: {}", abs_pdf_path, printer);
         } else {
-            // デフォルトプリンターに印刷
-            cmd.arg("-print-to-default");
             tracing::info!("SumatraPDFで印刷中: {:?}, デフォルトプリンター", abs_pdf_path);
         }
 
-        cmd.arg(&abs_pdf_path);
+        // SumatraPDFコマンドを構築
+        let mut cmd = Command::new(sumatra_path);
+        cmd.args(self.build_args(&abs_pdf_path, printer_name, options)?);
 
         // コマンド実行
         let output = cmd.output().map_err(|e| {
@@ -147,6 +287,81 @@ impl SumatraPrinter {
         Ok(())
     }
 
+    /// 印刷コマンドの引数を構築
+    fn build_args(&self, pdf_path: &Path, printer_name: Option<&str>, options: &PrintOptions) -> Result<Vec<OsString>, PdfError> {
+        let mut args: Vec<OsString> = Vec::new();
+
+        if let Some(printer) = printer_name {
+            // 特定のプリンターに印刷
+            args.push("-print-to".into());
+            args.push(printer.into());
+        } else {
+            // デフォルトプリンターに印刷
+            args.push("-print-to-default".into());
+        }
+
+        args.extend(self.supported_extra_args(options)?.into_iter().map(OsString::from));
+        args.push(pdf_path.into());
+        Ok(args)
+    }
+
+    /// バージョンに対応した追加の引数
+    ///
+    /// 印刷オプションの両面印刷・用紙・部数は `-print-settings` に追加する（追加の引数の設定値と結合）。
+    /// `-print-settings` に対応していないバージョンでは、追加の引数の `-print-settings` は設定値とあわせて除き（印刷自体が失敗するため）、
+    /// 印刷オプションの設定がある場合は設定どおりに印刷できないため `PdfError::Print` を返す
+    fn supported_extra_args(&self, options: &PrintOptions) -> Result<Vec<String>, PdfError> {
+        let supports_settings = self.version.is_none_or(|version| version >= PRINT_SETTINGS_VERSION);
+        let mut settings = print_settings(options);
+        let mut args = Vec::new();
+        let mut iter = self.extra_args.iter();
+        while let Some(arg) = iter.next() {
+            if arg == "-print-settings" {
+                let value = iter.next();
+                if !supports_settings {
+                    tracing::warn!(
+                        "SumatraPDF {}は-print-settingsに対応していないため無視します: {:?}",
+                        self.version.map(|v| v.to_string()).unwrap_or_default(),
+                        value
+                    );
+                    continue;
+                }
+                let values: Vec<String> = value.cloned().into_iter().chain(settings.drain(..)).collect();
+                args.push(arg.clone());
+                args.push(values.join(","));
+                continue;
+            }
+            args.push(arg.clone());
+        }
+        if !settings.is_empty() {
+            if !supports_settings {
+                return Err(PdfError::Print(format!(
+                    "SumatraPDF {}は-print-settingsに対応していないため印刷設定を指定できません: {}",
+                    self.version.map(|v| v.to_string()).unwrap_or_default(),
+                    settings.join(",")
+                )));
+            }
+            args.push("-print-settings".to_string());
+            args.push(settings.join(","));
+        }
+        Ok(args)
+    }
+
+    /// オプションを指定してPDFを印刷
+    ///
+    /// 優先プリンターで印刷できない場合はフォールバックプリンターを順番に試行する（`Printer::print_with_options`）
+    ///
+    /// # Returns
+    /// 印刷を受け付けたプリンター名（Noneはデフォルトプリンター）
+    pub fn print_with_options(
+        &self,
+        pdf_path: &Path,
+        printer_name: Option<&str>,
+        options: &PrintOptions,
+    ) -> Result<Option<String>, PdfError> {
+        Printer::print_with_options(self, pdf_path, printer_name, options)
+    }
+
     /// 利用可能なプリンター一覧を取得
     pub fn list_printers() -> Result<Vec<String>, PdfError> {
         // PowerShellを使用してプリンター一覧を取得
@@ -172,6 +387,34 @@ impl SumatraPrinter {
         Ok(printers)
     }
 
+    /// プリンターの対応する印刷設定を取得
+    ///
+    /// `Get-PrinterProperty`・`Get-PrintConfiguration` と `Win32_Printer` の用紙・機能から判定する
+    pub fn get_capabilities(printer_name: &str) -> Result<PrinterCapabilities, PdfError> {
+        let name = printer_name.replace('\'', "''");
+        let script = format!(
+            "$name = '{name}'; \
+             $config = Get-PrintConfiguration -PrinterName $name; \
+             $properties = @(Get-PrinterProperty -PrinterName $name | ForEach-Object {{ [pscustomobject]@{{ Name = $_.PropertyName; Value = [string]$_.Value }} }}); \
+             $printer = Get-CimInstance -ClassName Win32_Printer | Where-Object {{ $_.Name -eq $name }} | Select-Object -First 1; \
+             [pscustomobject]@{{ DuplexingMode = [string]$config.DuplexingMode; Properties = $properties; PaperNames = @($printer.PrinterPaperNames); Capabilities = @($printer.Capabilities) }} | ConvertTo-Json -Depth 3 -Compress"
+        );
+        let output = Command::new("powershell")
+            .args(["-Command", &script])
+            .output()
+            .map_err(|e| PdfError::Print(format!("プリンターの印刷設定取得エラー: {}", e)))?;
+
+        if !output.status.success() {
+            return Err(PdfError::Print(format!(
+                "プリンターの印刷設定の取得に失敗しました: {}, {}",
+                printer_name,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+
+        PrinterCapabilities::from_windows_json(&String::from_utf8_lossy(&output.stdout))
+    }
+
     /// デフォルトプリンターを取得
     pub fn get_default_printer() -> Result<Option<String>, PdfError> {
         let output = Command::new("powershell")
@@ -197,6 +440,140 @@ impl SumatraPrinter {
     }
 }
 
+impl Printer for SumatraPrinter {
+    fn print(&self, pdf_path: &Path, printer_name: Option<&str>) -> Result<(), PdfError> {
+        SumatraPrinter::print(self, pdf_path, printer_name)
+    }
+
+    fn list_printers(&self) -> Result<Vec<String>, PdfError> {
+        SumatraPrinter::list_printers()
+    }
+
+    fn default_printer(&self) -> Result<Option<String>, PdfError> {
+        SumatraPrinter::get_default_printer()
+    }
+
+    fn capabilities(&self, printer_name: Option<&str>) -> Result<Option<PrinterCapabilities>, PdfError> {
+        let name = match printer_name {
+            Some(name) => name.to_string(),
+            None => match SumatraPrinter::get_default_printer()? {
+                Some(name) => name,
+                None => return Ok(None),
+            },
+        };
+        SumatraPrinter::get_capabilities(&name).map(Some)
+    }
+
+    /// 両面印刷・用紙・部数を `-print-settings` に指定して印刷
+    fn print_with_options(
+        &self,
+        pdf_path: &Path,
+        printer_name: Option<&str>,
+        options: &PrintOptions,
+    ) -> Result<Option<String>, PdfError> {
+        print_with_fallback(&WithSettings { printer: self, options }, pdf_path, printer_name, options)
+    }
+
+    /// SumatraPDFで表示（パスが未設定の場合は既定のビューアー）
+    fn preview(&self, pdf_path: &Path) -> Result<(), PdfError> {
+        let Some(ref sumatra_path) = self.sumatra_path else {
+            return open_in_viewer(pdf_path);
+        };
+
+        tracing::info!("SumatraPDFで表示中: {:?}", pdf_path);
+        let child = Command::new(sumatra_path)
+            .arg(pdf_path)
+            .spawn()
+            .map_err(|e| PdfError::Print(format!("SumatraPDF実行エラー: {}", e)))?;
+        detach(child);
+        Ok(())
+    }
+}
+
+/// 印刷オプションを `-print-settings` に指定するためのアダプター
+struct WithSettings<'a> {
+    printer: &'a SumatraPrinter,
+    options: &'a PrintOptions,
+}
+
+impl Printer for WithSettings<'_> {
+    fn print(&self, pdf_path: &Path, printer_name: Option<&str>) -> Result<(), PdfError> {
+        self.printer.print_with_settings(pdf_path, printer_name, self.options)
+    }
+
+    fn list_printers(&self) -> Result<Vec<String>, PdfError> {
+        self.printer.list_printers()
+    }
+
+    fn default_printer(&self) -> Result<Option<String>, PdfError> {
+        self.printer.default_printer()
+    }
+
+    fn capabilities(&self, printer_name: Option<&str>) -> Result<Option<PrinterCapabilities>, PdfError> {
+        self.printer.capabilities(printer_name)
+    }
+}
+
+/// 印刷オプションの `-print-settings` の設定値（例: `2x`、`duplexlong`、`paper=A5`）
+fn print_settings(options: &PrintOptions) -> Vec<String> {
+    let mut settings = Vec::new();
+    if let Some(copies) = options.copies.filter(|&copies| copies > 1) {
+        settings.push(format!("{}x", copies));
+    }
+    if let Some(duplex) = options.duplex {
+        settings.push(
+            match duplex {
+                Duplex::Simplex => "simplex",
+                Duplex::LongEdge => "duplexlong",
+                Duplex::ShortEdge => "duplexshort",
+            }
+            .to_string(),
+        );
+    }
+    if let Some(ref paper) = options.paper {
+        settings.push(format!("paper={}", paper));
+    }
+    settings
+}
+
+/// 実行ファイルのパス・ファイルサイズごとの検出したバージョン
+type VersionCache = Mutex<HashMap<(PathBuf, u64), Option<SumatraVersion>>>;
+
+/// SumatraPDFのバージョンを取得
+///
+/// ファイル名にバージョンが含まれる場合（`SumatraPDF-3.5.2-64.exe`）はそれを使用し、
+/// それ以外は実行ファイルのバージョンリソースから読み取る（SumatraPDFは起動しない）。
+/// 印刷のたびに実行ファイルを読み込まないよう、パスとファイルサイズごとに結果をキャッシュする
+fn detect_version(fs: &dyn Fs, path: &Path) -> Option<SumatraVersion> {
+    static CACHE: OnceLock<VersionCache> = OnceLock::new();
+
+    if let Some(version) = path.file_stem().and_then(|stem| SumatraVersion::parse(&stem.to_string_lossy())) {
+        return Some(version);
+    }
+    let key = (path.to_path_buf(), fs.metadata(path).ok()?.len);
+    let cache = CACHE.get_or_init(Default::default);
+    if let Some(&version) = cache.lock().unwrap().get(&key) {
+        return version;
+    }
+    let version = fs.read(path).ok().and_then(|data| version_from_resource(&data));
+    cache.lock().unwrap().insert(key, version);
+    version
+}
+
+/// 実行ファイルのバージョンリソース（VS_FIXEDFILEINFO のファイルバージョン）からバージョンを取得
+fn version_from_resource(data: &[u8]) -> Option<SumatraVersion> {
+    let start = data
+        .windows(FIXED_FILE_INFO_SIGNATURE.len())
+        .position(|window| window == FIXED_FILE_INFO_SIGNATURE)?;
+    let word = |offset: usize| -> Option<u32> {
+        let bytes = data.get(start + offset..start + offset + 4)?;
+        Some(u32::from_le_bytes(bytes.try_into().ok()?))
+    };
+    // 署名・構造体のバージョンに続く dwFileVersionMS（メジャー・マイナー）と dwFileVersionLS（パッチ・ビルド）
+    let (high, low) = (word(8)?, word(12)?);
+    Some(SumatraVersion::new(high >> 16, high & 0xFFFF, low >> 16))
+}
+
 impl Default for SumatraPrinter {
     fn default() -> Self {
         Self::new()
@@ -219,6 +596,169 @@ mod tests {
         assert!(printer.sumatra_path.is_some());
     }
 
+    #[test]
+    fn test_discover_with_mock_fs() {
+        let install = Path::new("C:\\Program Files\\SumatraPDF").join("SumatraPDF.exe");
+        let fs = Arc::new(crate::fs::MockFs::new().with_file(&install, "exe"));
+        let mut printer = SumatraPrinter::new().with_fs(fs);
+
+        let found = printer.find_sumatra().unwrap();
+        assert!(found.ends_with(&install));
+        assert!(SumatraPrinter::new().with_fs(Arc::new(crate::fs::MockFs::new())).discover().is_none());
+    }
+
+    #[test]
+    fn test_discover_env_path() {
+        let fs = crate::fs::MockFs::new()
+            .with_file("/opt/sumatra/SumatraPDF.exe", "exe")
+            .with_file("/configured.exe", "exe");
+        let fs = Arc::new(fs);
+
+        // 環境変数のパスを使用
+        let printer = SumatraPrinter::new().with_fs(fs.clone());
+        assert_eq!(
+            printer.discover_from(Some(PathBuf::from("/opt/sumatra/SumatraPDF.exe"))),
+            Some(PathBuf::from("/opt/sumatra/SumatraPDF.exe"))
+        );
+
+        // 設定済みのパスが優先
+        let printer = SumatraPrinter::new().with_fs(fs.clone()).with_path("/configured.exe");
+        assert_eq!(
+            printer.discover_from(Some(PathBuf::from("/opt/sumatra/SumatraPDF.exe"))),
+            Some(PathBuf::from("/configured.exe"))
+        );
+
+        // 存在しないパスは無視して検索を続ける
+        let printer = SumatraPrinter::new().with_fs(fs);
+        assert_eq!(printer.discover_from(Some(PathBuf::from("/missing.exe"))), None);
+    }
+
+    #[test]
+    fn test_is_available_does_not_mutate() {
+        let exe = std::env::current_exe().unwrap();
+        let printer = SumatraPrinter::new().with_path(&exe);
+        assert!(printer.is_available());
+        assert_eq!(printer.sumatra_path, Some(exe));
+
+        let printer = SumatraPrinter::new();
+        let _ = printer.is_available();
+        assert!(printer.sumatra_path.is_none());
+    }
+
+    #[test]
+    fn test_build_args_with_extra_args() {
+        let printer = SumatraPrinter::new().with_extra_args(vec![
+            "-print-settings".to_string(),
+            "fit".to_string(),
+        ]);
+        let args = printer.build_args(Path::new("test.pdf"), Some("Main"), &PrintOptions::default()).unwrap();
+        assert_eq!(args, ["-print-to", "Main", "-print-settings", "fit", "test.pdf"]);
+
+        let args = SumatraPrinter::new().build_args(Path::new("test.pdf"), None, &PrintOptions::default()).unwrap();
+        assert_eq!(args, ["-print-to-default", "test.pdf"]);
+    }
+
+    #[test]
+    fn test_build_args_with_print_settings() {
+        let options = PrintOptions::new().with_copies(2).with_duplex(Duplex::LongEdge).with_paper("A5");
+        let args = SumatraPrinter::new().build_args(Path::new("test.pdf"), None, &options).unwrap();
+        assert_eq!(args, ["-print-to-default", "-print-settings", "2x,duplexlong,paper=A5", "test.pdf"]);
+
+        // 追加の引数の設定値と結合
+        let printer = SumatraPrinter::new().with_extra_args(vec!["-print-settings".to_string(), "fit".to_string()]);
+        let args = printer
+            .build_args(Path::new("test.pdf"), None, &PrintOptions::new().with_copies(1).with_duplex(Duplex::Simplex))
+            .unwrap();
+        assert_eq!(args, ["-print-to-default", "-print-settings", "fit,simplex", "test.pdf"]);
+
+        // -print-settingsに対応していないバージョンでは設定どおりに印刷できないためエラー
+        let printer = SumatraPrinter::new().with_version(SumatraVersion::new(2, 0, 1));
+        let err = printer.build_args(Path::new("test.pdf"), None, &options).unwrap_err();
+        assert!(matches!(err, PdfError::Print(ref message) if message.contains("2x,duplexlong,paper=A5")));
+        // 1部のみの指定は設定不要
+        let args = printer.build_args(Path::new("test.pdf"), None, &PrintOptions::new().with_copies(1)).unwrap();
+        assert_eq!(args, ["-print-to-default", "test.pdf"]);
+    }
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(SumatraVersion::parse("SumatraPDF 3.5.2"), Some(SumatraVersion::new(3, 5, 2)));
+        assert_eq!(SumatraVersion::parse("SumatraPDF-3.4.6-64"), Some(SumatraVersion::new(3, 4, 6)));
+        assert_eq!(SumatraVersion::parse("v2.1"), Some(SumatraVersion::new(2, 1, 0)));
+        assert_eq!(SumatraVersion::parse("SumatraPDF"), None);
+        assert_eq!(SumatraVersion::parse("SumatraPDF-64"), None);
+        assert!(SumatraVersion::new(3, 4, 6) < SumatraVersion::new(3, 5, 0));
+        assert_eq!(SumatraVersion::new(3, 5, 2).to_string(), "3.5.2");
+    }
+
+    #[test]
+    fn test_find_sumatra_detects_version() {
+        let install = Path::new("C:\\Program Files\\SumatraPDF").join("SumatraPDF-3.4.6-64.exe");
+        let fs = Arc::new(crate::fs::MockFs::new().with_file(&install, "exe"));
+        let mut printer = SumatraPrinter::new().with_fs(fs);
+        printer.find_sumatra().unwrap();
+        assert_eq!(printer.version(), Some(SumatraVersion::new(3, 4, 6)));
+    }
+
+    #[test]
+    fn test_detect_version_from_resource() {
+        let mut exe = b"MZ\0\0 resource".to_vec();
+        exe.extend_from_slice(&FIXED_FILE_INFO_SIGNATURE);
+        for word in [0x0001_0000u32, (3 << 16) | 5, 2 << 16] {
+            exe.extend_from_slice(&word.to_le_bytes());
+        }
+        let install = Path::new("C:\\SumatraPDF").join("SumatraPDF.exe");
+        let fs = Arc::new(crate::fs::MockFs::new().with_file(&install, exe));
+        let mut printer = SumatraPrinter::new().with_fs(fs.clone()).with_path(&install);
+        printer.find_sumatra().unwrap();
+        assert_eq!(printer.version(), Some(SumatraVersion::new(3, 5, 2)));
+
+        // バージョンリソースが無い場合は不明
+        assert_eq!(version_from_resource(b"MZ no resource"), None);
+        let other = Path::new("C:\\Other").join("SumatraPDF.exe");
+        fs.write(&other, b"MZ").unwrap();
+        assert_eq!(detect_version(fs.as_ref(), &other), None);
+
+        // 手動で設定したバージョンは検出結果で置き換えない
+        let mut printer = SumatraPrinter::new()
+            .with_fs(fs)
+            .with_path(&other)
+            .with_version(SumatraVersion::new(3, 4, 6));
+        printer.find_sumatra().unwrap();
+        assert_eq!(printer.version(), Some(SumatraVersion::new(3, 4, 6)));
+    }
+
+    #[test]
+    fn test_build_args_for_old_version() {
+        let extra_args = vec![
+            "-print-settings".to_string(),
+            "fit".to_string(),
+            "-silent".to_string(),
+        ];
+        // -print-settingsに対応していないバージョンでは設定値とあわせて除く
+        let printer = SumatraPrinter::new()
+            .with_extra_args(extra_args.clone())
+            .with_version(SumatraVersion::new(2, 0, 1));
+        let args = printer.build_args(Path::new("test.pdf"), None, &PrintOptions::default()).unwrap();
+        assert_eq!(args, ["-print-to-default", "-silent", "test.pdf"]);
+
+        // 対応しているバージョン・不明な場合はそのまま
+        let printer = SumatraPrinter::new()
+            .with_extra_args(extra_args.clone())
+            .with_version(SumatraVersion::new(3, 5, 2));
+        assert_eq!(printer.build_args(Path::new("test.pdf"), None, &PrintOptions::default()).unwrap().len(), 5);
+        let printer = SumatraPrinter::new().with_extra_args(extra_args);
+        assert_eq!(printer.build_args(Path::new("test.pdf"), None, &PrintOptions::default()).unwrap().len(), 5);
+    }
+
+    #[test]
+    fn test_print_with_options_without_path() {
+        let printer = SumatraPrinter::new();
+        let options = PrintOptions::new();
+        let result = printer.print_with_options(Path::new("test.pdf"), Some("Main"), &options);
+        assert!(matches!(result, Err(PdfError::Print(_))));
+    }
+
     #[test]
     #[ignore] // 実際のプリンターが必要
     fn test_list_printers() {
@@ -0,0 +1,56 @@
+//! 既定のビューアーでのPDF表示
+
+use std::ffi::OsString;
+use std::path::Path;
+use std::process::{Child, Command};
+
+use crate::error::PdfError;
+
+/// PDFを既定のビューアーで開く
+///
+/// ビューアーの終了は待たない（Windows: `cmd /c start`、macOS: `open`、その他: `xdg-open`）
+pub fn open_in_viewer(pdf_path: &Path) -> Result<(), PdfError> {
+    let (program, args) = viewer_command(pdf_path);
+    tracing::info!("ビューアーで表示中: {:?}", pdf_path);
+    let child = Command::new(&program)
+        .args(&args)
+        .spawn()
+        .map_err(|e| PdfError::Print(format!("ビューアー起動エラー: {}: {}", program, e)))?;
+    detach(child);
+    Ok(())
+}
+
+/// 起動したプロセスの終了をバックグラウンドで待機（ゾンビプロセスを残さない）
+pub(crate) fn detach(mut child: Child) {
+    std::thread::spawn(move || {
+        let _ = child.wait();
+    });
+}
+
+/// プラットフォームごとのビューアー起動コマンド
+fn viewer_command(pdf_path: &Path) -> (String, Vec<OsString>) {
+    if cfg!(windows) {
+        // startは最初の引用符付き引数をウィンドウタイトルとして扱うため空のタイトルを渡す
+        (
+            "cmd".to_string(),
+            vec!["/c".into(), "start".into(), "".into(), pdf_path.into()],
+        )
+    } else if cfg!(target_os = "macos") {
+        ("open".to_string(), vec![pdf_path.into()])
+    } else {
+        ("xdg-open".to_string(), vec![pdf_path.into()])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_viewer_command_passes_path() {
+        let path = Path::new("report.pdf");
+        let (program, args) = viewer_command(path);
+        assert!(!program.is_empty());
+        assert_eq!(args.last(), Some(&OsString::from("report.pdf")));
+    }
+}
@@ -0,0 +1,268 @@
+//! winspool API連携モジュール（`native-print` feature）
+//!
+//! SumatraPDFを使用せず、WindowsのスプーラーにPDFをRAWジョブとして送信する。
+//!
+//! # 制限事項
+//! PDFを変換せずにそのまま送信するため、PDFの直接印刷（PDFダイレクトプリント）に
+//! 対応したプリンターでのみ正しく印刷される。非対応のプリンターではPDFの内容が
+//! 文字として印字されたり、ジョブが破棄されたりする。
+//! PDFのラスタライズや外部プログラムによる印刷はこのバックエンドの対象外とし、
+//! 必要な場合はSumatraPDFバックエンドを使用する。
+//!
+//! WindowsにはプリンターがPDFを直接受け付けるかを確認するAPIが無く、ドライバー名からも判断できない
+//! （「Microsoft Print to PDF」「Adobe PDF」等はPostScript・XPSのドライバー）。
+//! そのため `WinspoolPrinter::with_pdf_passthrough_printers` で許可したプリンターにのみ送信し、
+//! それ以外のプリンターには送信せずに `PdfError::PassthroughUnsupported` を返す
+//! （`Backend::Winspool` では許可していないプリンターをSumatraPDFで印刷する）。
+
+use std::ffi::c_void;
+use std::path::{Path, PathBuf};
+
+use windows::core::{PCWSTR, PWSTR};
+use windows::Win32::Foundation::HANDLE;
+use windows::Win32::Graphics::Printing::{
+    ClosePrinter, EndDocPrinter, EndPagePrinter, EnumPrintersW, GetDefaultPrinterW, OpenPrinterW,
+    StartDocPrinterW, StartPagePrinter, WritePrinter, DOC_INFO_1W, PRINTER_ENUM_CONNECTIONS,
+    PRINTER_ENUM_LOCAL, PRINTER_INFO_4W,
+};
+
+use crate::error::PdfError;
+use crate::traits::Printer;
+
+/// 印刷ジョブのデフォルトのドキュメント名
+const DEFAULT_DOCUMENT_NAME: &str = "出張旅費精算書";
+
+/// winspool API プリンター
+pub struct WinspoolPrinter {
+    /// スプーラーに表示するドキュメント名
+    document_name: String,
+    /// 出力先ファイル（Noneの場合はプリンターのポートに出力）
+    output_file: Option<PathBuf>,
+    /// PDFの直接印刷を許可したプリンター名
+    passthrough_printers: Vec<String>,
+}
+
+impl WinspoolPrinter {
+    /// 新しいWinspoolPrinterを作成
+    pub fn new() -> Self {
+        Self {
+            document_name: DEFAULT_DOCUMENT_NAME.to_string(),
+            output_file: None,
+            passthrough_printers: Vec::new(),
+        }
+    }
+
+    /// スプーラーに表示するドキュメント名を設定
+    pub fn with_document_name(mut self, name: impl Into<String>) -> Self {
+        self.document_name = name.into();
+        self
+    }
+
+    /// プリンターのポートの代わりにファイルへ出力（動作確認用）
+    pub fn with_output_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.output_file = Some(path.into());
+        self
+    }
+
+    /// PDFの直接印刷に対応したプリンター名を設定（このプリンターにのみ送信する）
+    pub fn with_pdf_passthrough_printers(mut self, printers: Vec<String>) -> Self {
+        self.passthrough_printers = printers;
+        self
+    }
+
+    /// 開いたプリンターにRAWジョブとしてデータを送信
+    ///
+    /// # Safety
+    /// `handle` は `OpenPrinterW` で開いた有効なハンドルであること
+    unsafe fn write_raw_job(&self, handle: HANDLE, data: &[u8]) -> Result<(), PdfError> {
+        let mut document_name = to_wide(&self.document_name);
+        let mut datatype = to_wide("RAW");
+        let mut output_file = self
+            .output_file
+            .as_ref()
+            .map(|path| to_wide(&path.to_string_lossy()));
+        let doc_info = DOC_INFO_1W {
+            pDocName: PWSTR(document_name.as_mut_ptr()),
+            pOutputFile: output_file
+                .as_mut()
+                .map_or(PWSTR::null(), |path| PWSTR(path.as_mut_ptr())),
+            pDatatype: PWSTR(datatype.as_mut_ptr()),
+        };
+
+        if StartDocPrinterW(handle, 1, &doc_info) == 0 {
+            return Err(PdfError::Print(format!(
+                "印刷ジョブの開始に失敗しました: {}",
+                windows::core::Error::from_win32()
+            )));
+        }
+
+        let mut written = 0u32;
+        let result = if !StartPagePrinter(handle).as_bool() {
+            Err(PdfError::Print(format!(
+                "印刷ページの開始に失敗しました: {}",
+                windows::core::Error::from_win32()
+            )))
+        } else {
+            let ok = WritePrinter(handle, data.as_ptr() as *const c_void, data.len() as u32, &mut written);
+            let _ = EndPagePrinter(handle);
+            if !ok.as_bool() || written as usize != data.len() {
+                Err(PdfError::Print(format!(
+                    "印刷データの送信に失敗しました: {}/{}バイト",
+                    written,
+                    data.len()
+                )))
+            } else {
+                Ok(())
+            }
+        };
+        let _ = EndDocPrinter(handle);
+        result
+    }
+}
+
+impl Default for WinspoolPrinter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Printer for WinspoolPrinter {
+    fn print(&self, pdf_path: &Path, printer_name: Option<&str>) -> Result<(), PdfError> {
+        let data = std::fs::read(pdf_path)
+            .map_err(|e| PdfError::Print(format!("PDFファイル読み込みエラー: {}", e)))?;
+
+        let printer_name = match printer_name {
+            Some(name) => name.to_string(),
+            None => self
+                .default_printer()?
+                .ok_or_else(|| PdfError::Print("デフォルトプリンターが設定されていません".to_string()))?,
+        };
+        if !self.passthrough_printers.contains(&printer_name) {
+            return Err(PdfError::PassthroughUnsupported(printer_name));
+        }
+        tracing::info!("winspoolで印刷中: {:?}, プリンター: {}", pdf_path, printer_name);
+
+        let wide_name = to_wide(&printer_name);
+        let mut handle = HANDLE::default();
+        unsafe { OpenPrinterW(PCWSTR(wide_name.as_ptr()), &mut handle, None) }
+            .map_err(|e| PdfError::Print(format!("プリンターを開けません: {}: {}", printer_name, e)))?;
+
+        let result = unsafe { self.write_raw_job(handle, &data) };
+        unsafe {
+            let _ = ClosePrinter(handle);
+        }
+
+        if result.is_ok() {
+            tracing::info!("印刷ジョブを送信しました");
+        }
+        result
+    }
+
+    fn list_printers(&self) -> Result<Vec<String>, PdfError> {
+        let flags = PRINTER_ENUM_LOCAL | PRINTER_ENUM_CONNECTIONS;
+        let mut needed = 0u32;
+        let mut returned = 0u32;
+
+        // 必要なバッファサイズを取得（バッファ不足のエラーになる）
+        unsafe {
+            let _ = EnumPrintersW(flags, PCWSTR::null(), 4, None, &mut needed, &mut returned);
+        }
+        if needed == 0 {
+            return Ok(Vec::new());
+        }
+
+        // PRINTER_INFO_4Wの配置に合わせてu64単位で確保
+        let mut buffer = vec![0u64; (needed as usize).div_ceil(8)];
+        let printers = unsafe {
+            let bytes = std::slice::from_raw_parts_mut(buffer.as_mut_ptr() as *mut u8, buffer.len() * 8);
+            EnumPrintersW(flags, PCWSTR::null(), 4, Some(bytes), &mut needed, &mut returned)
+                .map_err(|e| PdfError::Print(format!("プリンター一覧取得エラー: {}", e)))?;
+
+            let infos = std::slice::from_raw_parts(buffer.as_ptr() as *const PRINTER_INFO_4W, returned as usize);
+            infos
+                .iter()
+                .filter(|info| !info.pPrinterName.is_null())
+                .filter_map(|info| info.pPrinterName.to_string().ok())
+                .filter(|name| !name.is_empty())
+                .collect()
+        };
+
+        Ok(printers)
+    }
+
+    fn default_printer(&self) -> Result<Option<String>, PdfError> {
+        let mut len = 0u32;
+        unsafe {
+            let _ = GetDefaultPrinterW(PWSTR::null(), &mut len);
+        }
+        if len == 0 {
+            return Ok(None);
+        }
+
+        let mut buffer = vec![0u16; len as usize];
+        let ok = unsafe { GetDefaultPrinterW(PWSTR(buffer.as_mut_ptr()), &mut len) };
+        if !ok.as_bool() {
+            return Ok(None);
+        }
+
+        let end = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+        let name = String::from_utf16_lossy(&buffer[..end]);
+        if name.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(name))
+        }
+    }
+}
+
+/// NUL終端のUTF-16文字列に変換
+fn to_wide(s: &str) -> Vec<u16> {
+    s.encode_utf16().chain(std::iter::once(0)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_list_printers() {
+        let printers = WinspoolPrinter::new().list_printers().unwrap();
+        println!("Printers: {:?}", printers);
+    }
+
+    #[test]
+    fn test_default_printer() {
+        let default = WinspoolPrinter::new().default_printer().unwrap();
+        if let Some(name) = default {
+            assert!(WinspoolPrinter::new().list_printers().unwrap().contains(&name));
+        }
+    }
+
+    #[test]
+    fn test_print_to_microsoft_print_to_pdf() {
+        const DEVICE: &str = "Microsoft Print to PDF";
+        let printer = WinspoolPrinter::new();
+        if !printer.list_printers().unwrap().iter().any(|name| name == DEVICE) {
+            println!("{}が無いためスキップ", DEVICE);
+            return;
+        }
+
+        let dir = std::env::temp_dir();
+        let pdf_path = dir.join("print_pdf_service_winspool_input.pdf");
+        let output = dir.join("print_pdf_service_winspool_output.pdf");
+        std::fs::write(&pdf_path, b"%PDF-1.4\n%%EOF\n").unwrap();
+
+        // 許可していないプリンターには送信しない
+        let result = WinspoolPrinter::new().print(&pdf_path, Some(DEVICE));
+        assert!(matches!(result, Err(PdfError::PassthroughUnsupported(_))), "{:?}", result);
+
+        // スプーラーへの送信のみ確認（このデバイスはPDFを解釈しないため出力の内容は確認しない）
+        let result = printer
+            .with_pdf_passthrough_printers(vec![DEVICE.to_string()])
+            .with_output_file(&output)
+            .print(&pdf_path, Some(DEVICE));
+        let _ = std::fs::remove_file(&pdf_path);
+        let _ = std::fs::remove_file(&output);
+        assert!(result.is_ok(), "{:?}", result);
+    }
+}
@@ -0,0 +1,81 @@
+//! Pythonバインディング（`python` feature）
+//!
+//! 分析用のノートブック等から、辞書のリストでPDFを生成する。
+//! 辞書は `Item` のJSONと同じキー（camelCase）で、JSONを経由して変換する。
+//! 処理は `blocking::PdfClient` で行い、設定は環境変数（`PdfConfig::from_env`）から読み込む。
+//! 呼び出し元が同じプロセスのため、出力先には出力ディレクトリの外の絶対パスも指定できる。
+//!
+//! ビルドは maturin（`pyproject.toml`）:
+//! ```bash
+//! maturin develop --release
+//! ```
+
+use pyo3::create_exception;
+use pyo3::exceptions::PyException;
+use pyo3::prelude::*;
+use pyo3::types::PyList;
+
+use crate::blocking::PdfClient;
+use crate::config::PdfConfig;
+use crate::error::PdfError;
+use crate::models::Item;
+use crate::service::PdfRequest;
+
+create_exception!(
+    print_pdf_service,
+    PdfGenerationError,
+    PyException,
+    "PDFの生成・印刷のエラー（code: PdfError::code のエラーコード、message: エラーメッセージ）"
+);
+
+/// アイテムの辞書のリストからPDFを生成（`print` がTrueの場合は印刷）
+///
+/// 結果は `PdfResult` のJSONと同じキーの辞書。生成中はGILを解放する
+#[pyfunction]
+#[pyo3(signature = (items, output_path, *, print = false, printer = None))]
+fn generate(
+    py: Python<'_>,
+    items: &Bound<'_, PyList>,
+    output_path: &str,
+    print: bool,
+    printer: Option<String>,
+) -> PyResult<PyObject> {
+    let json = py.import("json")?;
+    let items_json: String = json.call_method1("dumps", (items,))?.extract()?;
+    let items: Vec<Item> = serde_json::from_str(&items_json)
+        .map_err(|e| generation_error(py, &PdfError::Validation(format!("アイテムを変換できません: {}", e))))?;
+
+    let mut req = PdfRequest::new(items).with_output_path(output_path).with_print(print);
+    if let Some(name) = printer {
+        req = req.with_printer_name(name);
+    }
+    let config = PdfConfig::from_env().with_allow_absolute_output(true);
+    let result = py
+        .allow_threads(|| PdfClient::new(config).call(req))
+        .map_err(|e| generation_error(py, &e))?;
+
+    let result_json = serde_json::to_string(&result).map_err(|e| {
+        generation_error(py, &PdfError::Generation(format!("結果をJSONに変換できません: {}", e)))
+    })?;
+    Ok(json.call_method1("loads", (result_json,))?.unbind())
+}
+
+/// エラーコードとメッセージを属性に設定した `PdfGenerationError`
+fn generation_error(py: Python<'_>, error: &PdfError) -> PyErr {
+    let message = error.to_string();
+    let err = PdfGenerationError::new_err(message.clone());
+    let value = err.value(py);
+    // 例外のインスタンスへの属性の追加は失敗しない
+    let _ = value.setattr("code", error.code());
+    let _ = value.setattr("message", message);
+    err
+}
+
+/// Pythonモジュール `print_pdf_service`
+#[pymodule]
+#[pyo3(name = "print_pdf_service")]
+pub fn python_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(generate, m)?)?;
+    m.add("PdfGenerationError", m.py().get_type::<PdfGenerationError>())?;
+    Ok(())
+}
@@ -3,32 +3,74 @@
 //! tower::Serviceを実装したPDF生成サービス
 
 use std::future::Future;
-use std::path::PathBuf;
+use std::io::Write;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 use std::pin::Pin;
+use std::sync::Arc;
 use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
+use base64::Engine;
+use serde::Serialize;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
 use tower::Service;
 use tracing::info;
 
-use crate::config::PdfConfig;
-use crate::error::PdfError;
-use crate::models::Item;
-use crate::pdf::generator::ReportLabStylePdfClient;
+use crate::audit::{next_request_id, AuditEntry, AuditItem};
+use crate::config::{format_issues, PdfConfig, PrintFailurePolicy, RequestLimits};
+use crate::error::{ArtifactLocation, PdfError};
+use crate::fs::{unique_suffix, Fs, RealFs};
+use crate::models::{DedupePolicy, Item, PrintRequest};
+use crate::pdf::fonts::FontCache;
+use crate::pdf::generator::{GenerationReport, GenerationStats, PendingDocument, ReportLabStylePdfClient};
+use crate::pdf::seal::SealImage;
+use crate::pdf::text_utils::{normalize_item, NormalizeOptions};
+use crate::print::backend::{create_printer_with_passthrough, Backend};
+use crate::print::options::PrintOptions;
 use crate::print::sumatra::SumatraPrinter;
-use crate::traits::PdfGenerator;
+use crate::print::viewer::open_in_viewer;
+use crate::traits::{PdfGenerator, Printer};
+use crate::warning::Warning;
 
 /// PDF生成リクエスト
 #[derive(Debug, Clone)]
 pub struct PdfRequest {
     /// 精算書項目リスト
     pub items: Vec<Item>,
-    /// 出力パス
+    /// 出力パス（相対パスは設定の出力ディレクトリを基準に解決）
     pub output_path: PathBuf,
     /// 印刷フラグ
     pub print: bool,
+    /// 印刷せずにビューアーで表示するか（印刷フラグとは同時に指定できない）
+    pub preview: bool,
     /// プリンター名
     pub printer_name: Option<String>,
+    /// 印刷オプション（Noneの場合は設定のデフォルト印刷オプション）
+    pub print_options: Option<PrintOptions>,
+    /// 生成前に入力テキストを正規化するか
+    pub normalize_text: bool,
+    /// 入力テキストの正規化オプション
+    pub normalize_options: NormalizeOptions,
+    /// 重複した旅費項目の扱い
+    pub dedupe: DedupePolicy,
+    /// 車両番号を検証・整形する場合の最大文字数（Noneの場合は検証しない）
+    pub car_max_len: Option<usize>,
+    /// 空白の精算書の部数（0の場合はアイテムから生成、1以上の場合はアイテムを指定すると `PdfError::Validation`）
+    pub blank_copies: usize,
+    /// 承認欄（社長・会計・所属）の列ごとの印影画像（Noneの列は空欄）
+    pub approvals: Vec<Option<SealImage>>,
+    /// 一時ディレクトリの一意なパスに出力するか（出力パスは使用しない）
+    pub temp_output: bool,
+    /// 全アイテムをまとめたPDFに加えて、アイテムごとのPDFも出力するか
+    pub item_files: bool,
+    /// 監査記録のリクエストID（Noneの場合は受付時に採番）
+    pub request_id: Option<String>,
+    /// リクエストの大きさの上限（設定の上限より小さい項目のみ適用）
+    pub limits: Option<RequestLimits>,
 }
 
 impl PdfRequest {
@@ -38,7 +80,27 @@ impl PdfRequest {
             items,
             output_path: PathBuf::from("travel_expense.pdf"),
             print: false,
+            preview: false,
             printer_name: None,
+            print_options: None,
+            normalize_text: false,
+            normalize_options: NormalizeOptions::default(),
+            dedupe: DedupePolicy::Off,
+            car_max_len: None,
+            blank_copies: 0,
+            approvals: Vec::new(),
+            temp_output: false,
+            item_files: false,
+            request_id: None,
+            limits: None,
+        }
+    }
+
+    /// 空白の精算書（枠・表・見出しのみ）を指定部数生成するリクエストを作成
+    pub fn blank_forms(copies: usize) -> Self {
+        Self {
+            blank_copies: copies,
+            ..Self::new(Vec::new())
         }
     }
 
@@ -54,15 +116,171 @@ impl PdfRequest {
         self
     }
 
+    /// 印刷せずにビューアーで表示するかを設定
+    pub fn with_preview(mut self, preview: bool) -> Self {
+        self.preview = preview;
+        self
+    }
+
     /// プリンター名を設定
     pub fn with_printer_name(mut self, name: impl Into<String>) -> Self {
         self.printer_name = Some(name.into());
         self
     }
+
+    /// 印刷オプションを設定
+    pub fn with_print_options(mut self, options: PrintOptions) -> Self {
+        self.print_options = Some(options);
+        self
+    }
+
+    /// フォールバックプリンターを設定
+    pub fn with_fallback_printers(mut self, printers: Vec<String>) -> Self {
+        self.print_options.get_or_insert_with(PrintOptions::default).fallback_printers = printers;
+        self
+    }
+
+    /// 承認欄の印影画像を設定（承認欄の列順）
+    pub fn with_approvals(mut self, approvals: Vec<Option<SealImage>>) -> Self {
+        self.approvals = approvals;
+        self
+    }
+
+    /// 一時ディレクトリの一意なパスに出力
+    ///
+    /// 印刷後に破棄する用途を想定。生成したファイルは `PdfResult::cleanup` で削除する
+    pub fn with_temp_output(mut self) -> Self {
+        self.temp_output = true;
+        self
+    }
+
+    /// 全アイテムをまとめたPDFに加えて、アイテムごとのPDFも出力するかを設定
+    ///
+    /// アイテムごとのPDFは出力パスと同じディレクトリに `<ファイル名>_<連番3桁>.pdf` で出力し、
+    /// パスは `PdfResult::item_paths` で返す。印刷するのはまとめたPDFのみ
+    pub fn with_item_files(mut self, item_files: bool) -> Self {
+        self.item_files = item_files;
+        self
+    }
+
+    /// リクエストの大きさの上限を設定
+    ///
+    /// 設定（`PdfConfig::limits`）より小さい上限のみ適用し、設定の上限を緩めることはできない
+    pub fn with_limits(mut self, limits: RequestLimits) -> Self {
+        self.limits = Some(limits);
+        self
+    }
+
+    /// 監査記録のリクエストIDを設定（呼び出し元のシステムのIDと対応付ける場合）
+    pub fn with_request_id(mut self, id: impl Into<String>) -> Self {
+        self.request_id = Some(id.into());
+        self
+    }
+
+    /// 生成前に入力テキストを正規化するかを設定
+    ///
+    /// 正規化は作業用のコピーに対して行い、リクエストの内容は変更しない
+    pub fn with_normalize_text(mut self, normalize: bool) -> Self {
+        self.normalize_text = normalize;
+        self
+    }
+
+    /// 入力テキストの正規化オプションを設定
+    pub fn with_normalize_options(mut self, options: NormalizeOptions) -> Self {
+        self.normalize_options = options;
+        self
+    }
+
+    /// 重複した旅費項目の扱いを設定
+    pub fn with_dedupe(mut self, policy: DedupePolicy) -> Self {
+        self.dedupe = policy;
+        self
+    }
+
+    /// 車両番号の検証・整形を有効化
+    ///
+    /// `format_car` で整形できない値や最大文字数を超える値は警告として記録する
+    pub fn with_car_validation(mut self, max_len: usize) -> Self {
+        self.car_max_len = Some(max_len);
+        self
+    }
+
+    /// 生成に使用するアイテムを取得
+    ///
+    /// 正規化・重複除去はコピーに対して行い、リクエストの内容は変更しない
+    ///
+    /// # Returns
+    /// (生成に使用するアイテム, 警告)
+    fn working_items(&self) -> (Vec<Item>, Vec<Warning>) {
+        let mut warnings = Vec::new();
+        let items = self
+            .items
+            .iter()
+            .enumerate()
+            .map(|(item_index, item)| {
+                let mut item = if self.normalize_text {
+                    normalize_item(item, &self.normalize_options)
+                } else {
+                    item.clone()
+                };
+
+                if let Some(max_len) = self.car_max_len {
+                    if let Some(e) = item.normalize_car(max_len) {
+                        warnings.push(Warning::InvalidField {
+                            item_index,
+                            field: "car".to_string(),
+                            message: e.to_string(),
+                        });
+                    }
+                }
+
+                let removed = self.dedupe == DedupePolicy::Exact;
+                for duplicate in item.dedupe_ryohi(self.dedupe) {
+                    warnings.push(Warning::DuplicateRyohi {
+                        item_index,
+                        ryohi_index: duplicate.index,
+                        duplicate_of: duplicate.duplicate_of,
+                        date: duplicate.date,
+                        price: duplicate.price,
+                        removed,
+                    });
+                }
+                item
+            })
+            .collect();
+        (items, warnings)
+    }
+}
+
+impl From<PrintRequest> for PdfRequest {
+    /// 共通の項目（精算書項目・印刷フラグ・プリンター名・出力パス）を引き継ぐ
+    ///
+    /// 出力パスが指定されていない場合はデフォルトの出力パスを使用する
+    fn from(request: PrintRequest) -> Self {
+        let mut pdf_request = PdfRequest::new(request.items).with_print(request.print);
+        pdf_request.printer_name = request.printer_name;
+        if let Some(path) = request.output_path {
+            pdf_request.output_path = path;
+        }
+        pdf_request
+    }
+}
+
+impl From<PdfRequest> for PrintRequest {
+    /// 共通の項目（精算書項目・印刷フラグ・プリンター名・出力パス）のみを引き継ぐ
+    fn from(request: PdfRequest) -> Self {
+        Self {
+            items: request.items,
+            print: request.print,
+            printer_name: request.printer_name,
+            output_path: Some(request.output_path),
+        }
+    }
 }
 
 /// PDF生成結果
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct PdfResult {
     /// 生成されたPDFファイルのパス
     pub pdf_path: PathBuf,
@@ -70,50 +288,874 @@ pub struct PdfResult {
     pub file_size: u64,
     /// 印刷が実行されたか
     pub printed: bool,
+    /// ビューアーで表示されたか
+    pub previewed: bool,
+    /// 印刷を受け付けたプリンター名（Noneは未印刷、またはデフォルトプリンターの名前を取得できない場合）
+    pub printer_name: Option<String>,
+    /// 行数上限を超えて印刷されなかった行数（0ならデータ欠落なし）
+    pub dropped_rows: usize,
+    /// 切り詰められた項目数（0ならデータ欠落なし）
+    pub truncated_fields: usize,
+    /// 警告（フォントに存在しない文字の置換など）
+    pub warnings: Vec<Warning>,
+    /// ファイルが使用中のため書き込みを再試行した回数
+    pub write_retries: usize,
+    /// 総ページ数とアイテムごとのページ範囲・ページごとの外部ID
+    pub stats: GenerationStats,
+    /// 一時ファイルに出力したか（`cleanup` で削除する）
+    pub temporary: bool,
+    /// アイテムごとのPDFのパス（アイテム順、要求しなかった場合は空）
+    pub item_paths: Vec<PathBuf>,
+    /// 出力したPDFのSHA-256（16進数）
+    pub checksum: Option<String>,
 }
 
 impl PdfResult {
     /// 新しいPDF生成結果を作成
     pub fn new(pdf_path: PathBuf, printed: bool) -> std::io::Result<Self> {
-        let metadata = std::fs::metadata(&pdf_path)?;
+        Self::from_fs(&RealFs, pdf_path, printed)
+    }
+
+    /// ファイル操作を指定してPDF生成結果を作成
+    pub fn from_fs(fs: &dyn Fs, pdf_path: PathBuf, printed: bool) -> std::io::Result<Self> {
+        let metadata = fs.metadata(&pdf_path)?;
         Ok(Self {
             pdf_path,
-            file_size: metadata.len(),
+            file_size: metadata.len,
             printed,
+            previewed: false,
+            printer_name: None,
+            dropped_rows: 0,
+            truncated_fields: 0,
+            warnings: Vec::new(),
+            write_retries: 0,
+            stats: GenerationStats::default(),
+            temporary: false,
+            item_paths: Vec::new(),
+            checksum: None,
         })
     }
+
+    /// 生成レポートの内容を設定
+    pub fn with_report(mut self, report: &GenerationReport) -> Self {
+        self.dropped_rows = report.dropped_rows;
+        self.truncated_fields = report.truncated_fields;
+        self.warnings = report.warnings.clone();
+        self.write_retries = report.write_retries;
+        self.checksum = report.checksum.clone();
+        self
+    }
+
+    /// ページの統計を設定
+    pub fn with_stats(mut self, stats: &GenerationStats) -> Self {
+        self.stats = stats.clone();
+        self
+    }
+
+    /// 一時ファイルに出力した場合はファイルを削除（それ以外は何もしない）
+    ///
+    /// 削除済みの場合も成功とする
+    pub fn cleanup(&self) -> std::io::Result<()> {
+        self.cleanup_with_fs(&RealFs)
+    }
+
+    /// ファイル操作を指定して一時ファイル（アイテムごとのPDFを含む）を削除
+    pub fn cleanup_with_fs(&self, fs: &dyn Fs) -> std::io::Result<()> {
+        if !self.temporary {
+            return Ok(());
+        }
+        for path in std::iter::once(&self.pdf_path).chain(&self.item_paths) {
+            match fs.remove_file(path) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 一時ディレクトリの一意な出力パスを作成（プロセスID・時刻・連番）
+fn temp_output_path() -> PathBuf {
+    std::env::temp_dir().join(format!("print-pdf-{}.pdf", unique_suffix()))
+}
+
+/// ウォームアップ結果
+#[derive(Debug, Clone)]
+pub struct WarmUpReport {
+    /// 使用するフォントのパス
+    pub font_path: PathBuf,
+    /// フォントの読み込み・パースにかかった時間
+    pub font_parse_time: Duration,
+    /// テスト生成したPDFのサイズ（バイト）
+    pub test_pdf_size: u64,
+    /// SumatraPDFのパス（印刷が設定されていない場合はNone）
+    pub sumatra_path: Option<PathBuf>,
+    /// デフォルトプリンター
+    pub default_printer: Option<String>,
 }
 
 /// tower::Serviceを実装したPDF生成サービス
-#[derive(Debug, Clone, Default)]
+///
+/// 設定・フォントのキャッシュ等の読み取り専用の資源は全てのクローンで共有する
+/// （接続ごとにクローンしてもフォントの読み込みは1回のみ）。リクエストごとの状態は `call` の中に閉じる。
+/// `poll_ready` は常に準備完了を返し、`call` の返すFutureはサービスを借用しないため、
+/// `&mut PdfService` のまま `ServiceExt::ready_oneshot` 等で使用できる
+#[derive(Clone)]
 pub struct PdfService {
+    /// 全てのクローンで共有する資源
+    inner: Arc<Inner>,
+}
+
+/// サービスの全てのクローンで共有する読み取り専用の資源
+///
+/// ビルダーで変更する場合は `Arc::make_mut` で複製する（クローン前の構築時のみ）。
+/// リクエストの処理（検証・生成・印刷・結果の作成）は非同期のサービスと `blocking::PdfClient` で共通
+#[derive(Clone)]
+struct Inner {
     /// 設定
     config: PdfConfig,
+    /// フォントのキャッシュ
+    fonts: Arc<FontCache>,
+    /// 1件のリクエストのPDF生成の制限時間
+    timeout: Option<Duration>,
+    /// アイテムを分割して生成する場合の1回の生成処理のアイテム数（Noneは分割しない）
+    chunk_size: Option<usize>,
+    /// 生成を中断するためのトークン（リクエストごとに子トークンを使用）
+    cancel: CancellationToken,
+    /// 使用するプリンター（Noneの場合は設定の印刷バックエンドから作成）
+    printer: Option<Arc<dyn Printer>>,
+    /// ファイル操作
+    fs: Arc<dyn Fs>,
+}
+
+/// 受付時に確定したリクエストの内容
+struct Job {
+    /// 出力パス（絶対パスに解決済み）
+    output_path: PathBuf,
+    /// 一時ディレクトリに出力するか
+    temp_output: bool,
+    /// アイテムごとのPDFも出力するか
+    item_files: bool,
+    /// 生成に使用するアイテム（正規化・重複除去済み）
+    items: Vec<Item>,
+    /// 入力データ検証時の警告
+    request_warnings: Vec<Warning>,
+    /// 空白の精算書の部数
+    blank_copies: usize,
+    /// 承認欄の印影画像
+    approvals: Vec<Option<SealImage>>,
+    /// 印刷するか
+    print: bool,
+    /// ビューアーで表示するか
+    preview: bool,
+    /// 印刷先のプリンター名（Noneの場合はシステムのデフォルト）
+    printer_name: Option<String>,
+    /// 印刷オプション
+    print_options: PrintOptions,
+}
+
+/// 生成したPDF
+struct Generated {
+    /// 出力したパス
+    pdf_path: PathBuf,
+    /// 出力したアイテムごとのPDFのパス
+    item_paths: Vec<PathBuf>,
+    /// 生成レポート
+    report: GenerationReport,
+    /// 出力したページの統計
+    stats: GenerationStats,
+}
+
+impl Inner {
+    /// 設定とフォントのキャッシュを使用するクライアントを作成
+    fn client(&self) -> ReportLabStylePdfClient {
+        ReportLabStylePdfClient::from_config(&self.config).with_font_cache(self.fonts.clone())
+    }
+
+    /// 印刷時にプリンターを作成するための設定を取得
+    fn printer_factory(&self) -> PrinterFactory {
+        PrinterFactory {
+            printer: self.printer.clone(),
+            backend: self.config.printer_backend,
+            sumatra_path: self.config.sumatra_path.clone(),
+            ipp_printers: self.config.ipp_printers.clone(),
+            pdf_passthrough_printers: self.config.pdf_passthrough_printers.clone(),
+        }
+    }
+
+    /// 使用するプリンター名を決定（リクエスト > 設定のデフォルトプリンター > システムのデフォルト）
+    fn effective_printer_name(&self, printer_name: Option<&str>) -> Option<String> {
+        printer_name
+            .map(|s| s.to_string())
+            .or_else(|| self.config.default_printer.clone())
+    }
+
+    /// リクエストの印刷先と印刷オプションを決定（未指定の項目は設定のデフォルトを使用）
+    fn print_target(&self, req: &PdfRequest) -> (Option<String>, PrintOptions) {
+        let options = req
+            .print_options
+            .clone()
+            .unwrap_or_else(|| self.config.default_print_options.clone());
+        (self.effective_printer_name(req.printer_name.as_deref()), options)
+    }
+
+    /// リクエストを検証して処理内容を確定
+    fn job(&self, req: PdfRequest) -> Result<Job, PdfError> {
+        if req.print && req.preview {
+            return Err(PdfError::Config("印刷とプレビューは同時に指定できません".to_string()));
+        }
+        if req.blank_copies > 0 && !req.items.is_empty() {
+            return Err(PdfError::Validation(
+                "空白の精算書の部数とアイテムは同時に指定できません".to_string(),
+            ));
+        }
+        // 正規化等でアイテムを複製する前に大きさを検証
+        let limits = match req.limits {
+            Some(ref limits) => self.config.limits.tightened(limits),
+            None => self.config.limits,
+        };
+        limits.check(&req.items)?;
+        let output_path = if req.temp_output {
+            temp_output_path()
+        } else {
+            self.config.resolve_output_path(&req.output_path)?
+        };
+        let (items, request_warnings) = req.working_items();
+        let (printer_name, print_options) = self.print_target(&req);
+        Ok(Job {
+            output_path,
+            temp_output: req.temp_output,
+            item_files: req.item_files,
+            items,
+            request_warnings,
+            blank_copies: req.blank_copies,
+            approvals: req.approvals,
+            print: req.print,
+            preview: req.preview,
+            printer_name,
+            print_options,
+        })
+    }
+
+    /// リクエストのPDFを生成するクライアントを作成（出力先のディレクトリも作成）
+    fn job_client(&self, job: &Job, cancel: CancellationToken) -> Result<ReportLabStylePdfClient, PdfError> {
+        if let Some(parent) = job.output_path.parent() {
+            self.fs.create_dir_all(parent)?;
+        }
+        Ok(self
+            .client()
+            .with_output_path(&job.output_path)
+            .with_fs(self.fs.clone())
+            .with_approval_seals(job.approvals.clone())
+            .with_cancellation_token(cancel))
+    }
+
+    /// アイテムを分割して生成する場合の1回の生成処理のアイテム数（分割しない場合はNone）
+    fn chunk_size_for(&self, job: &Job) -> Option<usize> {
+        self.chunk_size
+            .filter(|&size| job.blank_copies == 0 && job.items.len() > size)
+    }
+
+    /// PDFを生成（アイテム数が多い場合は `PdfService::with_chunked_generation` に従って分割）
+    async fn generate_job_async(self: Arc<Self>, job: Arc<Job>, cancel: CancellationToken) -> Result<Generated, PdfError> {
+        match self.chunk_size_for(&job) {
+            Some(chunk_size) => {
+                let item_files = job.item_files;
+                generate_in_chunks(
+                    move || {
+                        let mut client = self.job_client(&job, cancel)?;
+                        let pending = client.begin_chunked(&job.items)?;
+                        Ok((client, pending))
+                    },
+                    item_files,
+                    chunk_size,
+                )
+                .await
+            }
+            None => tokio::task::spawn_blocking(move || self.generate_job(&job, cancel))
+                .await
+                .map_err(|e| PdfError::Generation(format!("タスク実行エラー: {}", e)))?,
+        }
+    }
+
+    /// PDFを生成
+    fn generate_job(&self, job: &Job, cancel: CancellationToken) -> Result<Generated, PdfError> {
+        let mut client = self.job_client(job, cancel)?;
+        let (pdf_path, item_paths) = if job.blank_copies > 0 {
+            (client.generate_blank_forms(job.blank_copies)?, Vec::new())
+        } else if job.item_files {
+            client.generate_with_item_files(&job.items)?
+        } else {
+            (client.generate(&job.items)?, Vec::new())
+        };
+        Ok(Generated {
+            pdf_path,
+            item_paths,
+            report: client.last_report().clone(),
+            stats: client.last_stats().clone(),
+        })
+    }
+
+    /// 生成したPDFを印刷
+    ///
+    /// # Returns
+    /// 印刷を受け付けたプリンター名（システムのデフォルトプリンターで印刷した場合はその名前、取得できない場合はNone）
+    fn print_job(&self, job: &Job, generated: &Generated) -> Result<Option<String>, PdfError> {
+        self.printer_factory()
+            .create()
+            .and_then(|printer| {
+                let accepted =
+                    printer.print_with_options(&generated.pdf_path, job.printer_name.as_deref(), &job.print_options)?;
+                Ok(accepted.or_else(|| match printer.default_printer() {
+                    Ok(name) => name,
+                    Err(e) => {
+                        tracing::warn!("デフォルトプリンターの名前を取得できません: {}", e);
+                        None
+                    }
+                }))
+            })
+            .map_err(|e| self.handle_print_failure(job, generated, e))
+    }
+
+    /// 生成したPDFをビューアーで表示（バックエンドを作成できない場合は既定のビューアー）
+    fn preview_job(&self, generated: &Generated) -> Result<(), PdfError> {
+        match self.printer_factory().create() {
+            Ok(printer) => printer.preview(&generated.pdf_path),
+            Err(_) => open_in_viewer(&generated.pdf_path),
+        }
+    }
+
+    /// 印刷に失敗したPDFを `PdfConfig::on_print_failure` に従って処理し、最終的な場所を含むエラーを返す
+    ///
+    /// 一時ファイルは設定に関わらず削除する。削除・移動できない場合は出力先に残したものとして返す
+    fn handle_print_failure(&self, job: &Job, generated: &Generated, e: PdfError) -> PdfError {
+        let policy = if job.temp_output {
+            &PrintFailurePolicy::Delete
+        } else {
+            &self.config.on_print_failure
+        };
+        let kept = ArtifactLocation::Kept(generated.pdf_path.clone());
+        let artifact = match policy {
+            PrintFailurePolicy::Keep => kept,
+            PrintFailurePolicy::Delete => {
+                for path in &generated.item_paths {
+                    let _ = self.fs.remove_file(path);
+                }
+                match self.fs.remove_file(&generated.pdf_path) {
+                    Ok(()) => ArtifactLocation::Deleted,
+                    Err(err) => {
+                        tracing::warn!("印刷に失敗したPDFを削除できません: {:?}: {}", generated.pdf_path, err);
+                        kept
+                    }
+                }
+            }
+            PrintFailurePolicy::MoveToFailedDir(dir) => match self.move_failed(dir, generated) {
+                Ok(path) => ArtifactLocation::Moved(path),
+                Err(err) => {
+                    tracing::warn!("印刷に失敗したPDFを移動できません: {:?}: {}", generated.pdf_path, err);
+                    kept
+                }
+            },
+        };
+        let message = match e {
+            PdfError::Print(message) => message,
+            e => e.to_string(),
+        };
+        PdfError::PrintFailed { message, artifact }
+    }
+
+    /// 印刷に失敗したPDF（アイテムごとのPDFを含む）を指定したディレクトリに移動
+    ///
+    /// 以前に失敗したPDFを上書きしないよう、同じ名前のファイルがある場合は連番を付ける
+    ///
+    /// # Returns
+    /// 移動後のPDFのパス
+    fn move_failed(&self, dir: &Path, generated: &Generated) -> Result<PathBuf, PdfError> {
+        // 設定で指定した移動先のため、絶対パスは出力ディレクトリの外でもそのまま使用する
+        let dir = if dir.is_absolute() {
+            dir.to_path_buf()
+        } else {
+            self.config.resolve_output_path(dir)?
+        };
+        self.fs.create_dir_all(&dir)?;
+        let target = |path: &Path| unused_path(self.fs.as_ref(), &dir.join(path.file_name().unwrap_or_default()));
+        for path in &generated.item_paths {
+            if let Err(err) = move_file(self.fs.as_ref(), path, &target(path)) {
+                tracing::warn!("印刷に失敗したアイテムのPDFを移動できません: {:?}: {}", path, err);
+            }
+        }
+        let moved = target(&generated.pdf_path);
+        move_file(self.fs.as_ref(), &generated.pdf_path, &moved)?;
+        Ok(moved)
+    }
+
+    /// 生成結果を作成
+    fn finish_job(&self, job: &Job, generated: &Generated, accepted: Option<Option<String>>) -> Result<PdfResult, PdfError> {
+        let mut result = PdfResult::from_fs(self.fs.as_ref(), generated.pdf_path.clone(), accepted.is_some())?
+            .with_report(&generated.report)
+            .with_stats(&generated.stats);
+        result.previewed = job.preview;
+        result.printer_name = accepted.flatten();
+        result.temporary = job.temp_output;
+        result.item_paths = generated.item_paths.clone();
+        // 入力データ検証時の警告を先頭に追加
+        result.warnings.splice(0..0, job.request_warnings.iter().cloned());
+
+        info!(
+            "PDF生成完了: path={:?}, size={}bytes, printed={}, printer={:?}",
+            result.pdf_path, result.file_size, result.printed, result.printer_name
+        );
+        Ok(result)
+    }
+
+    /// 監査記録を作成（記録しない場合はNone）
+    fn audit_entry(&self, req: &PdfRequest) -> Option<AuditEntry> {
+        if !self.config.audit_sink.enabled() {
+            return None;
+        }
+        let items = req
+            .items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| AuditItem::from_item(index, item, self.config.audit_redaction))
+            .collect();
+        let request_id = req.request_id.clone().unwrap_or_else(next_request_id);
+        let mut entry = AuditEntry::now(request_id, items);
+        entry.blank_copies = req.blank_copies;
+        entry.print_requested = req.print;
+        let (printer_name, print_options) = self.print_target(req);
+        entry.printer_name = printer_name;
+        // 印刷する部数（印刷しなかった場合は記録時に0にする）
+        entry.copies = print_options.copies.unwrap_or(1).max(1) as usize;
+        Some(entry)
+    }
+
+    /// 処理の結果を監査記録に設定して記録
+    ///
+    /// 監査記録の失敗でリクエストは失敗させない。チェックサムは生成時に書き込んだ内容から計算したもの
+    fn audit(&self, entry: Option<AuditEntry>, output_path: Option<PathBuf>, outcome: &Result<PdfResult, PdfError>) {
+        let Some(mut entry) = entry else {
+            return;
+        };
+        entry.output_path = output_path;
+        let entry = match outcome {
+            Ok(result) => {
+                entry.checksum = result.checksum.clone();
+                if result.printed {
+                    entry.printer_name = result.printer_name.clone();
+                } else {
+                    entry.copies = 0;
+                }
+                entry
+            }
+            Err(e) => {
+                entry.copies = 0;
+                entry.with_error(e)
+            }
+        };
+        self.config.audit_sink.record(entry);
+    }
+
+    /// 呼び出し元のスレッドでリクエストを処理（生成の制限時間は適用しない）
+    fn run(&self, req: PdfRequest) -> Result<PdfResult, PdfError> {
+        info!("PDF生成リクエスト受信: items={}", req.items.len());
+        let audit = self.audit_entry(&req);
+        let job = self.job(req);
+        let output_path = job.as_ref().ok().map(|job| job.output_path.clone());
+        let outcome = job.and_then(|job| self.run_job(&job));
+        self.audit(audit, output_path, &outcome);
+        outcome
+    }
+
+    /// 確定したリクエストを呼び出し元のスレッドで処理
+    fn run_job(&self, job: &Job) -> Result<PdfResult, PdfError> {
+        let generated = self.generate_job(job, self.cancel.child_token())?;
+        let accepted = if job.print {
+            Some(self.print_job(job, &generated)?)
+        } else {
+            None
+        };
+        if job.preview {
+            self.preview_job(&generated)?;
+        }
+        self.finish_job(job, &generated, accepted)
+    }
+}
+
+impl Default for PdfService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for PdfService {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PdfService")
+            .field("config", &self.inner.config)
+            .field("fonts", &self.inner.fonts)
+            .field("timeout", &self.inner.timeout)
+            .field("chunk_size", &self.inner.chunk_size)
+            .field("cancel", &self.inner.cancel)
+            .field("printer", &self.inner.printer.as_ref().map(|_| "dyn Printer"))
+            .finish_non_exhaustive()
+    }
+}
+
+/// アイテムを分割し、複数回の `spawn_blocking` でPDFを生成
+///
+/// フォントの読み込み（`begin`）・`chunk_size` 件ごとのページ操作の作成・書き込みを別々のタスクで実行し、
+/// 大量のアイテムでもブロッキング用のスレッドを長時間占有しない。キャンセル後の範囲は処理しない
+async fn generate_in_chunks<F>(begin: F, item_files: bool, chunk_size: usize) -> Result<Generated, PdfError>
+where
+    F: FnOnce() -> Result<(ReportLabStylePdfClient, PendingDocument), PdfError> + Send + 'static,
+{
+    let join_error = |e: tokio::task::JoinError| PdfError::Generation(format!("タスク実行エラー: {}", e));
+    let (mut client, mut pending) = tokio::task::spawn_blocking(begin).await.map_err(join_error)??;
+
+    let count = pending.item_count();
+    for start in (0..count).step_by(chunk_size.max(1)) {
+        if client.is_cancelled() {
+            break;
+        }
+        let range = start..count.min(start + chunk_size.max(1));
+        (client, pending) = tokio::task::spawn_blocking(move || {
+            client.render_chunk(&mut pending, range);
+            (client, pending)
+        })
+        .await
+        .map_err(join_error)?;
+    }
+
+    tokio::task::spawn_blocking(move || {
+        let (pdf_path, item_paths) = client.write_pending(pending, item_files)?;
+        Ok(Generated {
+            pdf_path,
+            item_paths,
+            report: client.last_report().clone(),
+            stats: client.last_stats().clone(),
+        })
+    })
+    .await
+    .map_err(join_error)?
+}
+
+/// ファイルを移動（名前の変更ができない場合は複製して元のファイルを削除）
+fn move_file(fs: &dyn Fs, from: &Path, to: &Path) -> std::io::Result<()> {
+    if fs.rename(from, to).is_ok() {
+        return Ok(());
+    }
+    fs.write(to, &fs.read(from)?)?;
+    fs.remove_file(from)
+}
+
+/// 存在しないファイルのパス（存在する場合は `job-1.pdf`、`job-2.pdf` のように連番を付ける）
+fn unused_path(fs: &dyn Fs, path: &Path) -> PathBuf {
+    if !fs.exists(path) {
+        return path.to_path_buf();
+    }
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let extension = path.extension().map(|ext| format!(".{}", ext.to_string_lossy())).unwrap_or_default();
+    (1..)
+        .map(|n| path.with_file_name(format!("{}-{}{}", stem, n, extension)))
+        .find(|candidate| !fs.exists(candidate))
+        .expect("連番のファイル名は有限個しか存在しない")
+}
+
+/// 印刷時にプリンターを作成するための設定
+#[derive(Clone)]
+struct PrinterFactory {
+    printer: Option<Arc<dyn Printer>>,
+    backend: Backend,
+    sumatra_path: Option<PathBuf>,
+    ipp_printers: BTreeMap<String, String>,
+    pdf_passthrough_printers: Vec<String>,
+}
+
+impl PrinterFactory {
+    /// プリンターを作成（設定済みのプリンターがあればそれを使用）
+    fn create(&self) -> Result<Arc<dyn Printer>, PdfError> {
+        match self.printer {
+            Some(ref printer) => Ok(printer.clone()),
+            None => create_printer_with_passthrough(
+                self.backend,
+                self.sumatra_path.as_deref(),
+                &self.ipp_printers,
+                &self.pdf_passthrough_printers,
+            )
+            .map(Arc::from),
+        }
+    }
 }
 
 impl PdfService {
     /// 新しいPDF生成サービスを作成
     pub fn new() -> Self {
-        Self {
-            config: PdfConfig::new(),
-        }
+        Self::with_config(PdfConfig::new())
     }
 
     /// 設定を指定してサービスを作成
     pub fn with_config(config: PdfConfig) -> Self {
-        Self { config }
+        Self {
+            inner: Arc::new(Inner {
+                config,
+                fonts: FontCache::global(),
+                timeout: None,
+                chunk_size: None,
+                cancel: CancellationToken::new(),
+                printer: None,
+                fs: Arc::new(RealFs),
+            }),
+        }
+    }
+
+    /// 構築時に共有する資源を変更
+    fn inner_mut(&mut self) -> &mut Inner {
+        Arc::make_mut(&mut self.inner)
+    }
+
+    /// ファイル操作を設定（テストではメモリ上のファイルシステムを使用）
+    pub fn with_fs(mut self, fs: Arc<dyn Fs>) -> Self {
+        self.inner_mut().fs = fs;
+        self
+    }
+
+    /// 使用するプリンターを設定（設定の印刷バックエンドより優先）
+    pub fn with_printer(mut self, printer: Arc<dyn Printer>) -> Self {
+        self.inner_mut().printer = Some(printer);
+        self
+    }
+
+    /// フォントのキャッシュを設定（既定はプロセス全体で共有するキャッシュ）
+    pub fn with_font_cache(mut self, cache: Arc<FontCache>) -> Self {
+        self.inner_mut().fonts = cache;
+        self
+    }
+
+    /// フォントのキャッシュを取得（全てのクローンで共通）
+    pub fn font_cache(&self) -> &Arc<FontCache> {
+        &self.inner.fonts
+    }
+
+    /// 呼び出し元のスレッドでリクエストを処理（`blocking::PdfClient` で使用）
+    pub(crate) fn call_blocking(&self, req: PdfRequest) -> Result<PdfResult, PdfError> {
+        self.inner.run(req)
+    }
+
+    /// PDF生成の制限時間を設定
+    ///
+    /// 制限時間を超えた場合は生成を中断し、`PdfError::Generation` を返す
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.inner_mut().timeout = Some(timeout);
+        self
+    }
+
+    /// 大量のアイテムを分割して生成する（`chunk_size` はブロッキング用のスレッドで1回に処理するアイテム数）
+    ///
+    /// アイテム数が `chunk_size` を超えるリクエストは、フォントの読み込み・`chunk_size` 件ごとのページ作成・
+    /// 書き込みを別々の `spawn_blocking` で実行し、他のタスクがブロッキング用のスレッドを使用できるようにする。
+    /// `chunk_size` 以下のリクエストと空白の精算書は従来どおり1回で生成する。
+    /// 出力するPDFは分割しない場合と同じ（`blocking::PdfClient` では分割しない）
+    pub fn with_chunked_generation(mut self, chunk_size: usize) -> Self {
+        self.inner_mut().chunk_size = Some(chunk_size.max(1));
+        self
+    }
+
+    /// 生成を中断するためのトークンを設定
+    ///
+    /// キャンセルすると処理中・以降のリクエストは `PdfError::Generation("キャンセルされました")` を返す。
+    /// 生成は同期処理のため、キャンセルはアイテムの処理の合間に確認する。
+    pub fn with_cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.inner_mut().cancel = token;
+        self
+    }
+
+    /// 設定を検証してサービスを作成
+    ///
+    /// 設定に問題がある場合は全ての問題点をまとめた `PdfError::Config` を返す
+    pub fn try_with_config(config: PdfConfig) -> Result<Self, PdfError> {
+        config
+            .validate()
+            .map_err(|issues| PdfError::Config(format_issues(&issues)))?;
+        Ok(Self::with_config(config))
+    }
+
+    /// フォントの読み込みと印刷環境の確認を事前に行う
+    ///
+    /// Kubernetesのreadinessプローブ等で、リクエスト受付前に生成可能かを確認する用途を想定。
+    /// フォントはキャッシュされるため、2回目以降の呼び出しは軽量に実行できる。
+    /// SumatraPDFのパスが設定されている場合のみ印刷環境を確認する（印刷は行わない）。
+    pub fn warm_up(&mut self) -> Result<WarmUpReport, PdfError> {
+        let mut client = self.inner.client();
+
+        // フォントを検索・パースしてキャッシュに載せる
+        let started = Instant::now();
+        client.font_loader_mut().load_font()?;
+        let font_parse_time = started.elapsed();
+        let font_path = client
+            .font_loader_mut()
+            .font_path()
+            .cloned()
+            .ok_or_else(|| PdfError::FontLoad("フォントが設定されていません".to_string()))?;
+
+        // 1件のPDFをメモリ上に生成して全体の処理を確認
+        let test_pdf_size = client.generate_to_vec(&[Item::default()])?.len() as u64;
+
+        // 印刷環境を確認
+        let config = &self.inner.config;
+        let (sumatra_path, default_printer) = if config.sumatra_path.is_some() {
+            let mut printer = SumatraPrinter::new();
+            if let Some(ref path) = config.sumatra_path {
+                printer = printer.with_path(path);
+            }
+            let sumatra_path = printer.find_sumatra()?;
+            (Some(sumatra_path), SumatraPrinter::get_default_printer()?)
+        } else {
+            (None, None)
+        };
+
+        let report = WarmUpReport {
+            font_path,
+            font_parse_time,
+            test_pdf_size,
+            sumatra_path,
+            default_printer,
+        };
+        info!("ウォームアップ完了: {:?}", report);
+        Ok(report)
+    }
+
+    /// PDFを生成して非同期の書き込み先に出力
+    ///
+    /// 生成はブロッキングスレッドで行い、チャネル経由でチャンクを書き込み先に転送する。
+    /// 中間のVecや一時ファイルを作らずにHTTPレスポンス等へストリーミングできる。
+    ///
+    /// # Returns
+    /// 書き込んだバイト数
+    pub async fn generate_to_async_writer<W>(&self, items: Vec<Item>, w: &mut W) -> Result<u64, PdfError>
+    where
+        W: AsyncWrite + Unpin + Send,
+    {
+        let inner = self.inner.clone();
+        let (tx, mut rx) = mpsc::channel::<Vec<u8>>(16);
+
+        let handle = tokio::task::spawn_blocking(move || {
+            let mut client = inner.client();
+            let mut writer = std::io::BufWriter::with_capacity(64 * 1024, ChannelWriter { tx });
+            let written = client.generate_to_writer(&items, &mut writer)?;
+            writer.flush().map_err(|e| {
+                PdfError::FileIO(std::io::Error::new(e.kind(), format!("PDF書き込みエラー: {}", e)))
+            })?;
+            Ok::<u64, PdfError>(written)
+        });
+
+        let mut write_error = None;
+        while let Some(chunk) = rx.recv().await {
+            if let Err(e) = w.write_all(&chunk).await {
+                write_error = Some(e);
+                break;
+            }
+        }
+        // 受信側を閉じて生成側の送信を失敗させる
+        drop(rx);
+
+        let result = handle
+            .await
+            .map_err(|e| PdfError::Generation(format!("タスク実行エラー: {}", e)))?;
+
+        if let Some(e) = write_error {
+            return Err(PdfError::FileIO(std::io::Error::new(
+                e.kind(),
+                format!("PDF書き込みエラー: {}", e),
+            )));
+        }
+        let written = result?;
+
+        w.flush().await.map_err(|e| {
+            PdfError::FileIO(std::io::Error::new(e.kind(), format!("PDF書き込みエラー: {}", e)))
+        })?;
+
+        Ok(written)
+    }
+
+    /// PDFを生成してメモリ上のバイト列として取得
+    pub async fn generate_to_vec(&self, items: Vec<Item>) -> Result<Vec<u8>, PdfError> {
+        let mut bytes = Vec::new();
+        self.generate_to_async_writer(items, &mut bytes).await?;
+        Ok(bytes)
+    }
+
+    /// PDFを生成してBase64文字列として取得
+    ///
+    /// JSON APIでPDFをインラインで返す場合に使用する（ファイルを再取得する往復が不要）
+    pub async fn generate_base64(&self, items: Vec<Item>) -> Result<String, PdfError> {
+        Ok(encode_base64(&self.generate_to_vec(items).await?))
+    }
+
+    /// PDFを生成して `data:application/pdf;base64,` 形式のデータURIとして取得
+    pub async fn generate_data_uri(&self, items: Vec<Item>) -> Result<String, PdfError> {
+        Ok(to_data_uri(&self.generate_to_vec(items).await?))
+    }
+}
+
+/// PDFのデータURIの接頭辞
+pub const PDF_DATA_URI_PREFIX: &str = "data:application/pdf;base64,";
+
+/// バイト列をBase64（標準、パディングあり）にエンコード
+pub fn encode_base64(bytes: &[u8]) -> String {
+    base64::engine::general_purpose::STANDARD.encode(bytes)
+}
+
+/// PDFのバイト列をデータURIに変換
+pub fn to_data_uri(bytes: &[u8]) -> String {
+    format!("{}{}", PDF_DATA_URI_PREFIX, encode_base64(bytes))
+}
+
+/// 書き込まれたバイト列をチャネルへ送るライター（ブロッキングスレッド用）
+struct ChannelWriter {
+    tx: mpsc::Sender<Vec<u8>>,
+}
+
+impl Write for ChannelWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.tx.blocking_send(buf.to_vec()).map_err(|_| {
+            std::io::Error::new(std::io::ErrorKind::BrokenPipe, "書き込み先が閉じられました")
+        })?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
     }
 }
 
 #[async_trait]
 impl PdfGenerator for PdfService {
     async fn generate(&mut self, items: Vec<Item>) -> Result<PathBuf, PdfError> {
-        let output_path = self.config.output_path.clone();
+        let inner = self.inner.clone();
+
+        if let Some(chunk_size) = inner.chunk_size.filter(|&size| items.len() > size) {
+            let generated = generate_in_chunks(
+                move || {
+                    let mut client = inner
+                        .client()
+                        .with_output_path(&inner.config.output_path)
+                        .with_fs(inner.fs.clone());
+                    let pending = client.begin_chunked(&items)?;
+                    Ok((client, pending))
+                },
+                false,
+                chunk_size,
+            )
+            .await?;
+            return Ok(generated.pdf_path);
+        }
 
         // PDF生成は同期処理なのでtokio::task::spawn_blockingを使用
         let result = tokio::task::spawn_blocking(move || {
-            let mut client = ReportLabStylePdfClient::new()
-                .with_output_path(&output_path);
+            let mut client = inner
+                .client()
+                .with_output_path(&inner.config.output_path)
+                .with_fs(inner.fs.clone());
             client.generate(&items)
         })
         .await
@@ -129,19 +1171,14 @@ impl PdfGenerator for PdfService {
     ) -> Result<PathBuf, PdfError> {
         let pdf_path = self.generate(items).await?;
 
-        let printer_name = printer.map(|s| s.to_string());
-        let sumatra_path = self.config.sumatra_path.clone();
+        let printer_name = self.inner.effective_printer_name(printer);
+        let factory = self.inner.printer_factory();
         let pdf_path_clone = pdf_path.clone();
 
         // 印刷も同期処理
         tokio::task::spawn_blocking(move || {
-            let mut sumatra_printer = SumatraPrinter::new();
-            if let Some(ref path) = sumatra_path {
-                sumatra_printer = sumatra_printer.with_path(path);
-            } else {
-                sumatra_printer.find_sumatra()?;
-            }
-            sumatra_printer.print(&pdf_path_clone, printer_name.as_deref())
+            let printer = factory.create()?;
+            printer.print(&pdf_path_clone, printer_name.as_deref())
         })
         .await
         .map_err(|e| PdfError::Print(format!("タスク実行エラー: {}", e)))??;
@@ -161,53 +1198,67 @@ impl Service<PdfRequest> for PdfService {
 
     fn call(&mut self, req: PdfRequest) -> Self::Future {
         info!("PDF生成リクエスト受信: items={}", req.items.len());
-
-        let output_path = req.output_path.clone();
-        let items = req.items.clone();
-        let print = req.print;
-        let printer_name = req.printer_name.clone();
-        let sumatra_path = self.config.sumatra_path.clone();
+        let audit = self.inner.audit_entry(&req);
+        // 生成・結果・印刷で同じパスを使うため、受付時に絶対パスに解決する
+        let job = self.inner.job(req);
+        let inner = self.inner.clone();
+        let timeout = self.inner.timeout;
+        let cancel = self.inner.cancel.child_token();
 
         Box::pin(async move {
-            // PDF生成
-            let pdf_path = tokio::task::spawn_blocking(move || {
-                let mut client = ReportLabStylePdfClient::new()
-                    .with_output_path(&output_path);
-                client.generate(&items)
-            })
-            .await
-            .map_err(|e| PdfError::Generation(format!("タスク実行エラー: {}", e)))??;
-
-            // 印刷が必要な場合
-            let printed = if print {
-                let pdf_path_clone = pdf_path.clone();
-                let printer_name_clone = printer_name.clone();
-
-                tokio::task::spawn_blocking(move || {
-                    let mut printer = SumatraPrinter::new();
-                    if let Some(ref path) = sumatra_path {
-                        printer = printer.with_path(path);
-                    } else {
-                        printer.find_sumatra()?;
-                    }
-                    printer.print(&pdf_path_clone, printer_name_clone.as_deref())
-                })
-                .await
-                .map_err(|e| PdfError::Print(format!("タスク実行エラー: {}", e)))??;
+            let output_path = job.as_ref().ok().map(|job| job.output_path.clone());
+            let outcome = async {
+                let job = Arc::new(job?);
 
-                true
-            } else {
-                false
-            };
+                // PDF生成
+                let generation = inner.clone().generate_job_async(job.clone(), cancel.clone());
+                let generated = match timeout {
+                    Some(limit) => match tokio::time::timeout(limit, generation).await {
+                        Ok(joined) => joined,
+                        Err(_) => {
+                            // 実行中の生成処理も次のアイテムの処理前に中断させる
+                            cancel.cancel();
+                            return Err(PdfError::Generation(format!(
+                                "タイムアウトのためキャンセルされました: {:?}",
+                                limit
+                            )));
+                        }
+                    },
+                    None => generation.await,
+                };
+                let generated = Arc::new(generated?);
 
-            let result = PdfResult::new(pdf_path, printed)?;
+                // 印刷が必要な場合（印刷を受け付けたプリンター名を記録）
+                let accepted = if job.print {
+                    let accepted = tokio::task::spawn_blocking({
+                        let inner = inner.clone();
+                        let job = job.clone();
+                        let generated = generated.clone();
+                        move || inner.print_job(&job, &generated)
+                    })
+                    .await
+                    .map_err(|e| inner.handle_print_failure(&job, &generated, PdfError::Print(format!("タスク実行エラー: {}", e))))??;
+                    Some(accepted)
+                } else {
+                    None
+                };
 
-            info!(
-                "PDF生成完了: path={:?}, size={}bytes, printed={}",
-                result.pdf_path, result.file_size, result.printed
-            );
+                // プレビューが必要な場合
+                if job.preview {
+                    tokio::task::spawn_blocking({
+                        let inner = inner.clone();
+                        let generated = generated.clone();
+                        move || inner.preview_job(&generated)
+                    })
+                    .await
+                    .map_err(|e| PdfError::Print(format!("タスク実行エラー: {}", e)))??;
+                }
 
-            Ok(result)
+                inner.finish_job(&job, &generated, accepted)
+            }
+            .await;
+            inner.audit(audit, output_path, &outcome);
+            outcome
         })
     }
 }
@@ -215,6 +1266,12 @@ impl Service<PdfRequest> for PdfService {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::io::ErrorKind;
+    use crate::fs::MockFs;
+    use crate::pdf::testing::MockFont;
+    use crate::print::MockPrinter;
+    use crate::models::{Ryohi, DEFAULT_MAX_CAR_LENGTH};
+    use crate::pdf::layout::LayoutConfig;
 
     #[test]
     fn test_pdf_request_builder() {
@@ -229,10 +1286,710 @@ mod tests {
         assert_eq!(req.printer_name, Some("MyPrinter".to_string()));
     }
 
+    #[test]
+    fn test_pdf_request_from_print_request() {
+        let item = Item {
+            car: "12-34".to_string(),
+            ..Default::default()
+        };
+        let print_request = PrintRequest {
+            items: vec![item],
+            print: true,
+            printer_name: Some("MyPrinter".to_string()),
+            output_path: None,
+        };
+
+        let req = PdfRequest::from(print_request);
+        assert_eq!(req.items.len(), 1);
+        assert!(req.print);
+        assert_eq!(req.printer_name, Some("MyPrinter".to_string()));
+        assert_eq!(req.output_path, PdfRequest::new(Vec::new()).output_path);
+
+        let back = PrintRequest::from(req.with_output_path("/tmp/job.pdf"));
+        assert_eq!(back.output_path, Some(PathBuf::from("/tmp/job.pdf")));
+        assert_eq!(PdfRequest::from(back.clone()).output_path, PathBuf::from("/tmp/job.pdf"));
+        assert_eq!(back.items[0].car, "12-34");
+        assert!(back.print);
+        assert_eq!(back.printer_name, Some("MyPrinter".to_string()));
+    }
+
+    #[test]
+    fn test_cancelled_service_call() {
+        let token = CancellationToken::new();
+        token.cancel();
+        let fs = Arc::new(MockFs::new());
+        let mut service = PdfService::new().with_cancellation_token(token).with_fs(fs.clone());
+        let request = PdfRequest::new(vec![Item::default()]).with_output_path("cancelled.pdf");
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let result = runtime.block_on(service.call(request));
+        assert!(matches!(result, Err(PdfError::Generation(ref message)) if message == "キャンセルされました"));
+        assert!(fs.paths().is_empty());
+    }
+
+    #[test]
+    fn test_empty_items_rejected() {
+        let fs = Arc::new(MockFs::new());
+        let mut service = PdfService::new().with_fs(fs.clone());
+        let request = PdfRequest::new(Vec::new()).with_output_path("empty.pdf").with_print(true);
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let result = runtime.block_on(service.call(request));
+        assert!(matches!(result, Err(PdfError::Validation(_))));
+        assert!(fs.paths().is_empty());
+    }
+
+    #[test]
+    fn test_request_limits() {
+        let fs = Arc::new(MockFs::new());
+        let config = PdfConfig::new().with_limits(RequestLimits::DEFAULT.with_max_items(2));
+        let service = PdfService::with_config(config).with_fs(fs.clone());
+        let items = vec![Item::default(); 3];
+
+        // 設定の上限を超える（リクエストで緩めることはできない）
+        let result = service.call_blocking(
+            PdfRequest::new(items.clone()).with_limits(RequestLimits::DEFAULT.with_max_items(10)),
+        );
+        assert!(matches!(result, Err(PdfError::Validation(ref message)) if message.contains("max_items=2")));
+        // リクエストでさらに制限する
+        let result = service.call_blocking(
+            PdfRequest::new(items[..2].to_vec()).with_limits(RequestLimits::DEFAULT.with_max_items(1)),
+        );
+        assert!(matches!(result, Err(PdfError::Validation(ref message)) if message.contains("max_items=1")));
+        assert!(fs.paths().is_empty());
+    }
+
+    #[test]
+    fn test_encode_base64_and_data_uri() {
+        assert_eq!(encode_base64(b"%PDF-1.7"), "JVBERi0xLjc=");
+        assert_eq!(to_data_uri(b"%PDF-1.7"), "data:application/pdf;base64,JVBERi0xLjc=");
+        assert_eq!(encode_base64(&[]), "");
+    }
+
+    #[test]
+    fn test_generate_base64_propagates_errors() {
+        let service = PdfService::new();
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let result = runtime.block_on(service.generate_base64(Vec::new()));
+        assert!(matches!(result, Err(PdfError::Validation(_))));
+        let result = runtime.block_on(service.generate_data_uri(Vec::new()));
+        assert!(matches!(result, Err(PdfError::Validation(_))));
+    }
+
+    #[test]
+    fn test_pdf_request_approvals() {
+        let seal = SealImage::from_png(vec![0x89, 0x50, 0x4e, 0x47]);
+        let req = PdfRequest::new(Vec::new()).with_approvals(vec![None, Some(seal.clone())]);
+        assert_eq!(req.approvals, vec![None, Some(seal)]);
+        assert!(PdfRequest::new(Vec::new()).approvals.is_empty());
+    }
+
+    #[test]
+    fn test_blank_forms_request() {
+        let request = PdfRequest::blank_forms(5).with_output_path("blank.pdf");
+        assert_eq!(request.blank_copies, 5);
+        assert!(request.items.is_empty());
+        assert_eq!(PdfRequest::new(Vec::new()).blank_copies, 0);
+    }
+
+    #[test]
+    fn test_blank_forms_with_items_rejected() {
+        let fs = Arc::new(MockFs::new());
+        let mut service = PdfService::new().with_fs(fs.clone());
+        let mut request = PdfRequest::blank_forms(2).with_output_path("blank.pdf");
+        request.items = vec![Item::default()];
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let result = runtime.block_on(service.call(request));
+        assert!(matches!(result, Err(PdfError::Validation(_))));
+        assert!(fs.paths().is_empty());
+    }
+
+    #[test]
+    fn test_output_path_traversal_rejected() {
+        let fs = Arc::new(MockFs::new());
+        let mut service =
+            PdfService::with_config(PdfConfig::new().with_output_path("/srv/output")).with_fs(fs.clone());
+        let request = PdfRequest::new(vec![Item::default()]).with_output_path("../escape.pdf");
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let result = runtime.block_on(service.call(request));
+        assert!(matches!(result, Err(PdfError::Config(_))));
+        assert!(fs.paths().is_empty());
+    }
+
+    #[test]
+    fn test_preview_with_print_rejected() {
+        let fs = Arc::new(MockFs::new());
+        let mut service = PdfService::new().with_fs(fs.clone());
+        let request = PdfRequest::new(vec![Item::default()])
+            .with_output_path("preview.pdf")
+            .with_print(true)
+            .with_preview(true);
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let result = runtime.block_on(service.call(request));
+        assert!(matches!(result, Err(PdfError::Config(_))));
+        assert!(fs.paths().is_empty());
+    }
+
+    #[test]
+    fn test_pdf_request_fallback_printers() {
+        let req = PdfRequest::new(vec![Item::default()])
+            .with_fallback_printers(vec!["Backup".to_string()]);
+        assert_eq!(req.print_options.unwrap().fallback_printers, vec!["Backup".to_string()]);
+    }
+
+    /// サービスの印刷先の決定とプリンターの作成を経由して印刷
+    fn print_via_service(service: &PdfService, req: &PdfRequest) -> Option<String> {
+        let (printer_name, options) = service.inner.print_target(req);
+        let printer = service.inner.printer_factory().create().unwrap();
+        printer
+            .print_with_options(std::path::Path::new("job.pdf"), printer_name.as_deref(), &options)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_print_target_precedence() {
+        let printer = Arc::new(MockPrinter::new());
+        let config = PdfConfig::new().with_default_printer("Label");
+        let service = PdfService::with_config(config).with_printer(printer.clone());
+
+        // リクエストのプリンター名が最優先
+        let req = PdfRequest::new(Vec::new()).with_printer_name("Office");
+        assert_eq!(print_via_service(&service, &req), Some("Office".to_string()));
+
+        // 未指定の場合は設定のデフォルトプリンター
+        let req = PdfRequest::new(Vec::new());
+        assert_eq!(print_via_service(&service, &req), Some("Label".to_string()));
+
+        // どちらも未指定の場合はシステムのデフォルトプリンター
+        let service = PdfService::new().with_printer(printer.clone());
+        assert_eq!(print_via_service(&service, &req), None);
+
+        assert_eq!(
+            printer.printed(),
+            vec![Some("Office".to_string()), Some("Label".to_string()), None]
+        );
+    }
+
+    #[test]
+    fn test_print_target_default_print_options() {
+        let printer = Arc::new(MockPrinter::new().with_offline("Label"));
+        let config = PdfConfig::new()
+            .with_default_printer("Label")
+            .with_default_print_options(PrintOptions::new().with_fallback_printers(vec!["Backup".to_string()]));
+        let service = PdfService::with_config(config).with_printer(printer.clone());
+
+        // 設定のフォールバックプリンターを使用
+        let req = PdfRequest::new(Vec::new());
+        assert_eq!(print_via_service(&service, &req), Some("Backup".to_string()));
+
+        // リクエストの印刷オプションが優先
+        let req = PdfRequest::new(Vec::new()).with_fallback_printers(vec!["Spare".to_string()]);
+        assert_eq!(print_via_service(&service, &req), Some("Spare".to_string()));
+    }
+
+    #[test]
+    fn test_pdf_request_working_items_normalized() {
+        let item = Item {
+            name: "ﾔﾏﾀﾞ".to_string(),
+            ..Default::default()
+        };
+        let req = PdfRequest::new(vec![item.clone()]);
+        assert_eq!(req.working_items().0[0].name, "ﾔﾏﾀﾞ");
+
+        let req = req.with_normalize_text(true);
+        assert_eq!(req.working_items().0[0].name, "ヤマダ");
+        assert_eq!(req.items[0].name, "ﾔﾏﾀﾞ");
+    }
+
+    #[test]
+    fn test_pdf_request_working_items_car_validation() {
+        let items = vec![
+            Item {
+                car: "1234".to_string(),
+                ..Default::default()
+            },
+            Item {
+                car: "品川300あ12-34".to_string(),
+                ..Default::default()
+            },
+        ];
+        let req = PdfRequest::new(items).with_car_validation(DEFAULT_MAX_CAR_LENGTH);
+        let (items, warnings) = req.working_items();
+        assert_eq!(items[0].car, "12-34");
+        assert_eq!(warnings.len(), 1);
+        assert!(matches!(&warnings[0], Warning::InvalidField { item_index: 1, field, .. } if field == "car"));
+    }
+
+    #[test]
+    fn test_pdf_request_working_items_dedupe() {
+        let toll = Ryohi {
+            date: Some("2025-01-15".to_string()),
+            kukan: Some("東京～大阪".to_string()),
+            price: Some(1500),
+            ..Default::default()
+        };
+        let item = Item {
+            ryohi: vec![toll.clone(), toll],
+            ..Default::default()
+        };
+        let req = PdfRequest::new(vec![Item::default(), item]).with_dedupe(DedupePolicy::Exact);
+
+        let (items, warnings) = req.working_items();
+        assert_eq!(items[1].ryohi.len(), 1);
+        assert_eq!(req.items[1].ryohi.len(), 2);
+        assert_eq!(
+            warnings,
+            vec![Warning::DuplicateRyohi {
+                item_index: 1,
+                ryohi_index: 1,
+                duplicate_of: 0,
+                date: Some("2025-01-15".to_string()),
+                price: Some(1500),
+                removed: true,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_try_with_config() {
+        let config = PdfConfig::new().with_output_path(std::env::temp_dir());
+        assert!(PdfService::try_with_config(config).is_ok());
+
+        let config = PdfConfig::new()
+            .with_output_path(std::env::temp_dir())
+            .with_sumatra_path(std::env::temp_dir().join("print_pdf_missing_sumatra.exe"));
+        let result = PdfService::try_with_config(config.clone());
+        assert!(matches!(result, Err(PdfError::Config(_))));
+
+        // 従来のコンストラクタは検証しない
+        let _ = PdfService::with_config(config);
+
+        // 不明な環境変数は警告のみで起動できる
+        let config = PdfConfig::from_vars([("PDF_OUTPUTPATH".to_string(), "/srv/output".to_string())])
+            .with_output_path(std::env::temp_dir());
+        assert!(PdfService::try_with_config(config).is_ok());
+    }
+
+    #[test]
+    fn test_warm_up() {
+        let mut service = PdfService::with_config(PdfConfig::new());
+        let result = service.warm_up();
+        if cfg!(windows) {
+            // 日本語フォントが存在する環境ではPDF生成まで確認できる
+            let report = result.unwrap();
+            assert!(report.font_path.exists());
+            assert!(report.test_pdf_size > 0);
+            assert!(report.sumatra_path.is_none());
+
+            // 2回目はキャッシュを使用する
+            let second = service.warm_up().unwrap();
+            assert_eq!(second.font_path, report.font_path);
+        } else {
+            // フォントが無い環境ではパニックせずにエラーを返す
+            assert!(matches!(result, Err(PdfError::FontLoad(_))));
+        }
+    }
+
+    #[test]
+    fn test_pdf_result_with_report() {
+        let fs = MockFs::new().with_file("output/report.pdf", "%PDF");
+        let report = GenerationReport {
+            dropped_rows: 2,
+            truncated_fields: 1,
+            continuation_pages: 0,
+            embedded_fonts: 1,
+            font_data_bytes: 0,
+            write_retries: 1,
+            checksum: Some("abc".to_string()),
+            warnings: vec![Warning::UnsupportedGlyph {
+                item_index: 0,
+                field: "name".to_string(),
+                chars: vec!['😀'],
+            }],
+        };
+        let result = PdfResult::from_fs(&fs, PathBuf::from("output/report.pdf"), false)
+            .unwrap()
+            .with_report(&report);
+
+        assert_eq!(result.file_size, 4);
+        assert_eq!(result.dropped_rows, 2);
+        assert_eq!(result.truncated_fields, 1);
+        assert_eq!(result.warnings.len(), 1);
+        assert_eq!(result.write_retries, 1);
+        assert_eq!(result.checksum, Some("abc".to_string()));
+
+        let stats = GenerationStats {
+            total_pages: 3,
+            pages_per_item: vec![0..2, 2..3],
+            external_ids: BTreeMap::from([(2, vec!["V-002".to_string()])]),
+        };
+        let json = serde_json::to_value(result.with_stats(&stats)).unwrap();
+        assert_eq!(json["stats"]["totalPages"], 3);
+        assert_eq!(json["stats"]["pagesPerItem"][1]["start"], 2);
+        assert_eq!(json["stats"]["externalIds"]["2"][0], "V-002");
+    }
+
+    #[test]
+    fn test_channel_writer() {
+        let (tx, mut rx) = mpsc::channel(4);
+        let mut writer = ChannelWriter { tx };
+        writer.write_all(b"%PDF").unwrap();
+        assert_eq!(rx.try_recv().unwrap(), b"%PDF".to_vec());
+
+        // 受信側が閉じられたら BrokenPipe
+        drop(rx);
+        let err = writer.write(b"x").unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::BrokenPipe);
+    }
+
     #[test]
     fn test_pdf_service_new() {
         let service = PdfService::new();
         // デフォルトの出力パスは "./output"
-        assert!(service.config.output_path.to_string_lossy().contains("output"));
+        assert!(service.inner.config.output_path.to_string_lossy().contains("output"));
+    }
+
+    /// 空のフォントで生成するサービス（フォントはdrop時に削除する）
+    fn mock_font_service(name: &str, fs: Arc<MockFs>) -> (PdfService, MockFont) {
+        let font = MockFont::new(&format!("service_{}", name));
+        let config = PdfConfig::new().with_font_path(font.path()).with_output_path("/srv/output");
+        let service = PdfService::with_config(config).with_fs(fs).with_font_cache(font.cache());
+        (service, font)
+    }
+
+    #[test]
+    fn test_cloned_services_share_font_cache() {
+        let fs = Arc::new(MockFs::new());
+        let (service, _font) = mock_font_service("shared", fs.clone());
+        let cache = service.font_cache().clone();
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let results = runtime.block_on(async {
+            let handles: Vec<_> = (0..8)
+                .map(|i| {
+                    let mut service = service.clone();
+                    let request = PdfRequest::new(vec![Item::default()]).with_output_path(format!("job{}.pdf", i));
+                    tokio::spawn(async move { service.call(request).await })
+                })
+                .collect();
+            let mut results = Vec::new();
+            for handle in handles {
+                results.push(handle.await.unwrap());
+            }
+            results
+        });
+
+        // 8件とも別のファイルに出力し、フォントの読み込みは1回のみ
+        let paths: Vec<PathBuf> = results.into_iter().map(|r| r.unwrap().pdf_path).collect();
+        for (i, path) in paths.iter().enumerate() {
+            assert_eq!(*path, PathBuf::from(format!("/srv/output/job{}.pdf", i)));
+        }
+        assert_eq!(fs.paths().len(), 8);
+        assert_eq!(cache.load_count(), 1);
+        assert!(Arc::ptr_eq(service.clone().font_cache(), &cache));
+    }
+
+    #[test]
+    fn test_temp_output_cleanup() {
+        let fs = Arc::new(MockFs::new());
+        let (service, _font) = mock_font_service("temp", fs.clone());
+        let printer = Arc::new(MockPrinter::new().with_offline("Offline"));
+        let mut service = service.with_printer(printer);
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let mut call = |request: PdfRequest| runtime.block_on(service.call(request));
+        let request = PdfRequest::new(vec![Item::default()]).with_temp_output();
+        let first = call(request.clone()).unwrap();
+        let second = call(request.with_print(true)).unwrap();
+        let kept = call(PdfRequest::new(vec![Item::default()]).with_output_path("kept.pdf")).unwrap();
+        // 印刷に失敗した場合は一時ファイルを残さない
+        let failed = call(PdfRequest::new(vec![Item::default()]).with_temp_output().with_print(true).with_printer_name("Offline"));
+        assert!(matches!(
+            failed,
+            Err(PdfError::PrintFailed {
+                artifact: ArtifactLocation::Deleted,
+                ..
+            })
+        ));
+
+        // 一時ディレクトリの別々のパスに出力
+        assert!(first.temporary && second.temporary && !kept.temporary);
+        assert_ne!(first.pdf_path, second.pdf_path);
+        assert!(first.pdf_path.starts_with(std::env::temp_dir()));
+        assert!(second.printed);
+        assert_eq!(fs.paths().len(), 3);
+
+        first.cleanup_with_fs(fs.as_ref()).unwrap();
+        second.cleanup_with_fs(fs.as_ref()).unwrap();
+        // 削除済み・一時ファイル以外は何もしない
+        first.cleanup_with_fs(fs.as_ref()).unwrap();
+        kept.cleanup_with_fs(fs.as_ref()).unwrap();
+        assert_eq!(fs.paths(), vec![PathBuf::from("/srv/output/kept.pdf")]);
+    }
+
+    #[test]
+    fn test_generation_stats_in_result() {
+        let fs = Arc::new(MockFs::new());
+        let font = MockFont::new("service_stats");
+        let config = PdfConfig::new()
+            .with_font_path(font.path())
+            .with_output_path("/srv/output")
+            .with_layout(LayoutConfig::default().with_continuation_pages(true));
+        let mut service = PdfService::with_config(config).with_fs(fs.clone()).with_font_cache(font.cache());
+
+        let ryohi = Ryohi {
+            date: Some("2024-01-15".to_string()),
+            price: Some(1000),
+            ..Default::default()
+        };
+        let item = |rows: usize, id: &str| Item {
+            ryohi: vec![ryohi.clone(); rows],
+            external_id: Some(id.to_string()),
+            ..Default::default()
+        };
+        // 2件目は継続ページを含む2ページ
+        let request = PdfRequest::new(vec![item(1, "A-1"), item(20, "A-2"), item(1, "A-3")]).with_output_path("stats.pdf");
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let result = runtime.block_on(service.call(request)).unwrap();
+
+        assert_eq!(result.stats.total_pages, 4);
+        assert_eq!(result.stats.pages_per_item, vec![0..1, 1..3, 3..4]);
+        assert_eq!(result.stats.external_ids[&2], vec!["A-2".to_string()]);
+        assert_eq!(fs.paths(), vec![PathBuf::from("/srv/output/stats.pdf")]);
+    }
+
+    #[test]
+    fn test_default_printer_name_recorded() {
+        let fs = Arc::new(MockFs::new());
+        let (service, _font) = mock_font_service("default_printer", fs.clone());
+        let printer = Arc::new(MockPrinter::new().with_default_printer("Office"));
+        let mut service = service.with_printer(printer.clone());
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let result = runtime.block_on(service.call(PdfRequest::new(vec![Item::default()]).with_print(true))).unwrap();
+
+        // プリンター名を指定せずにシステムのデフォルトで印刷した場合もその名前を記録
+        assert!(result.printed);
+        assert_eq!(result.printer_name, Some("Office".to_string()));
+        assert_eq!(printer.printed().len(), 1);
+    }
+
+    #[test]
+    fn test_preview_opens_generated_pdf() {
+        let fs = Arc::new(MockFs::new());
+        let (service, _font) = mock_font_service("preview", fs.clone());
+        let printer = Arc::new(MockPrinter::new());
+        let mut service = service.with_printer(printer.clone());
+
+        let request = PdfRequest::new(vec![Item::default()])
+            .with_output_path("preview.pdf")
+            .with_preview(true);
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let result = runtime.block_on(service.call(request)).unwrap();
+
+        // 生成したPDFをビューアーで表示し、印刷はしない
+        assert!(result.previewed);
+        assert!(!result.printed);
+        assert_eq!(printer.previews(), vec![result.pdf_path.clone()]);
+        assert!(printer.printed().is_empty());
+    }
+
+    #[test]
+    fn test_chunked_generation_matches_single_shot() {
+        let fs = Arc::new(MockFs::new());
+        let (service, font) = mock_font_service("chunked", fs.clone());
+        let config = service
+            .inner
+            .config
+            .clone()
+            .with_fixed_date(Some(printpdf::DateTime::from_unix_timestamp(1_711_929_600).unwrap()));
+        let single = PdfService::with_config(config)
+            .with_fs(fs.clone())
+            .with_font_cache(font.cache());
+        let chunked = single.clone().with_chunked_generation(2);
+        let items: Vec<Item> = (0..5)
+            .map(|i| Item {
+                name: format!("社員{}", i),
+                ..Default::default()
+            })
+            .collect();
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let (expected, actual, small, cancelled) = runtime.block_on(async {
+            let request = |path: &str| PdfRequest::new(items.clone()).with_output_path(path).with_item_files(true);
+            let expected = single.clone().call(request("single.pdf")).await;
+            let actual = chunked.clone().call(request("chunked.pdf")).await;
+            // アイテム数が分割単位以下の場合は1回で生成
+            let small = chunked.clone().call(PdfRequest::new(items[..2].to_vec())).await;
+            let token = CancellationToken::new();
+            token.cancel();
+            let cancelled = chunked
+                .clone()
+                .with_cancellation_token(token)
+                .call(request("cancelled.pdf"))
+                .await;
+            (expected, actual, small, cancelled)
+        });
+
+        let (expected, actual) = (expected.unwrap(), actual.unwrap());
+        assert_eq!(actual.stats, expected.stats);
+        assert_eq!(actual.item_paths.len(), 5);
+        assert_eq!(fs.file(&actual.pdf_path), fs.file(&expected.pdf_path));
+        for (chunked, single) in actual.item_paths.iter().zip(&expected.item_paths) {
+            assert_eq!(fs.file(chunked), fs.file(single));
+        }
+        assert_eq!(small.unwrap().stats.total_pages, 2);
+        assert!(matches!(cancelled, Err(PdfError::Generation(_))));
+        assert!(fs.file(Path::new("/srv/output/cancelled.pdf")).is_none());
+    }
+
+    #[test]
+    fn test_print_failure_policy() {
+        let failing = |name: &str, fs: Arc<MockFs>, policy: PrintFailurePolicy| {
+            let (service, font) = mock_font_service(name, fs);
+            let config = PdfConfig {
+                on_print_failure: policy,
+                ..service.inner.config.clone()
+            };
+            let service = PdfService::with_config(config)
+                .with_fs(service.inner.fs.clone())
+                .with_font_cache(font.cache())
+                .with_printer(Arc::new(MockPrinter::new().with_offline("Offline")));
+            let request = PdfRequest::new(vec![Item::default(), Item::default()])
+                .with_output_path("job.pdf")
+                .with_item_files(true)
+                .with_print(true)
+                .with_printer_name("Offline");
+            let result = service.call_blocking(request);
+            match result {
+                Err(PdfError::PrintFailed { message, artifact }) => {
+                    assert!(message.contains("オフライン"));
+                    artifact
+                }
+                other => panic!("印刷エラーではありません: {:?}", other.map(|r| r.pdf_path)),
+            }
+        };
+
+        // 出力先に残す（既定）
+        let fs = Arc::new(MockFs::new());
+        let artifact = failing("keep", fs.clone(), PrintFailurePolicy::Keep);
+        assert_eq!(artifact, ArtifactLocation::Kept(PathBuf::from("/srv/output/job.pdf")));
+        assert_eq!(fs.paths().len(), 3);
+
+        // 削除（アイテムごとのPDFを含む）
+        let fs = Arc::new(MockFs::new());
+        let artifact = failing("delete", fs.clone(), PrintFailurePolicy::Delete);
+        assert_eq!(artifact, ArtifactLocation::Deleted);
+        assert_eq!(artifact.path(), None);
+        assert!(fs.paths().is_empty());
+
+        // ディレクトリを作成して移動（相対パスは出力ディレクトリが基準）
+        let fs = Arc::new(MockFs::new());
+        let artifact = failing("move", fs.clone(), PrintFailurePolicy::MoveToFailedDir(PathBuf::from("failed")));
+        assert_eq!(artifact, ArtifactLocation::Moved(PathBuf::from("/srv/output/failed/job.pdf")));
+        assert!(!fs.metadata(Path::new("/srv/output/failed")).unwrap().is_file);
+        assert_eq!(
+            fs.paths(),
+            ["job.pdf", "job_001.pdf", "job_002.pdf"]
+                .iter()
+                .map(|name| PathBuf::from("/srv/output/failed").join(name))
+                .collect::<Vec<_>>()
+        );
+        // 同じ名前で再度失敗した場合は以前のPDFを上書きしない
+        let artifact = failing("move_again", fs.clone(), PrintFailurePolicy::MoveToFailedDir(PathBuf::from("failed")));
+        assert_eq!(artifact, ArtifactLocation::Moved(PathBuf::from("/srv/output/failed/job-1.pdf")));
+        assert_eq!(
+            fs.paths(),
+            ["job-1.pdf", "job.pdf", "job_001-1.pdf", "job_001.pdf", "job_002-1.pdf", "job_002.pdf"]
+                .iter()
+                .map(|name| PathBuf::from("/srv/output/failed").join(name))
+                .collect::<Vec<_>>()
+        );
+
+        // 移動できない場合は残した場所を返す
+        let fs = Arc::new(MockFs::new());
+        fs.fail_next("/srv/failed/job.pdf", vec![ErrorKind::PermissionDenied, ErrorKind::PermissionDenied]);
+        let artifact = failing("move_denied", fs.clone(), PrintFailurePolicy::MoveToFailedDir(PathBuf::from("/srv/failed")));
+        assert_eq!(artifact, ArtifactLocation::Kept(PathBuf::from("/srv/output/job.pdf")));
+        assert!(fs.file("/srv/output/job.pdf").is_some());
+        let message = PdfError::PrintFailed {
+            message: "オフライン".to_string(),
+            artifact,
+        }
+        .to_string();
+        assert!(message.contains("/srv/output/job.pdf"));
+    }
+
+    #[test]
+    fn test_item_files_with_combined() {
+        let fs = Arc::new(MockFs::new());
+        let (service, _font) = mock_font_service("item_files", fs.clone());
+        let printer = Arc::new(MockPrinter::new());
+        let mut service = service.with_printer(printer.clone());
+        let items: Vec<Item> = ["V-1", "V-2", "V-3"]
+            .iter()
+            .map(|id| Item {
+                external_id: Some(id.to_string()),
+                ..Default::default()
+            })
+            .collect();
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let mut call = |request: PdfRequest| runtime.block_on(service.call(request));
+        let result = call(
+            PdfRequest::new(items.clone())
+                .with_output_path("batch.pdf")
+                .with_item_files(true)
+                .with_print(true),
+        )
+        .unwrap();
+        let temp = call(PdfRequest::new(items).with_temp_output().with_item_files(true)).unwrap();
+
+        // まとめたPDFとアイテムごとのPDFを出力し、印刷はまとめたPDFのみ
+        assert_eq!(result.pdf_path, PathBuf::from("/srv/output/batch.pdf"));
+        assert_eq!(
+            result.item_paths,
+            (1..=3).map(|n| PathBuf::from(format!("/srv/output/batch_{:03}.pdf", n))).collect::<Vec<_>>()
+        );
+        assert_eq!(printer.printed().len(), 1);
+        assert_eq!(result.stats.total_pages, 3);
+        let page_count = |path: &PathBuf| lopdf::Document::load_mem(&fs.file(path).unwrap()).unwrap().get_pages().len();
+        assert_eq!(page_count(&result.pdf_path), 3);
+        for (index, path) in result.item_paths.iter().enumerate() {
+            assert_eq!(page_count(path), 1);
+            let doc = lopdf::Document::load_mem(&fs.file(path).unwrap()).unwrap();
+            let info = doc.trailer.get(b"Info").and_then(|info| doc.get_dictionary(info.as_reference()?)).unwrap();
+            let keywords = String::from_utf8_lossy(info.get(b"Keywords").unwrap().as_str().unwrap()).to_string();
+            assert!(keywords.contains(&format!("V-{}", index + 1)));
+        }
+
+        // 一時ファイルはアイテムごとのPDFも削除
+        assert_eq!(temp.item_paths.len(), 3);
+        assert!(temp.item_paths.iter().all(|path| path.starts_with(std::env::temp_dir())));
+        assert_eq!(fs.paths().len(), 8);
+        temp.cleanup_with_fs(fs.as_ref()).unwrap();
+        assert_eq!(fs.paths().len(), 4);
+    }
+
+    #[test]
+    fn test_service_by_mut_reference() {
+        let fs = Arc::new(MockFs::new());
+        let mut service = PdfService::new().with_fs(fs.clone());
+
+        // `ServiceExt::ready_oneshot` と同様に `&mut PdfService` を準備してから呼び出す
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let result = runtime.block_on(async {
+            let mut by_ref = &mut service;
+            std::future::poll_fn(|cx| Service::<PdfRequest>::poll_ready(&mut by_ref, cx)).await.unwrap();
+            let future = by_ref.call(PdfRequest::new(Vec::new()).with_output_path("empty.pdf"));
+            // Futureはサービスを借用しないため、完了前にサービスを再利用できる
+            let _ = service.clone();
+            future.await
+        });
+        assert!(matches!(result, Err(PdfError::Validation(_))));
+        assert!(fs.paths().is_empty());
     }
 }
@@ -1,12 +1,15 @@
-//! PDF生成トレイト
+//! PDF生成・印刷トレイト
 //!
 //! rust-scraperパターンを踏襲
 
 use async_trait::async_trait;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use crate::error::PdfError;
 use crate::models::Item;
+use crate::print::capabilities::PrinterCapabilities;
+use crate::print::options::{filter_available, PrintOptions};
+use crate::print::viewer::open_in_viewer;
 
 /// PDF生成トレイト
 #[async_trait]
@@ -34,3 +37,161 @@ pub trait PdfGenerator: Send + Sync {
         printer: Option<&str>,
     ) -> Result<PathBuf, PdfError>;
 }
+
+/// 印刷バックエンドトレイト
+///
+/// 印刷は同期処理のため、非同期処理からは `spawn_blocking` などで呼び出す
+pub trait Printer: Send + Sync {
+    /// PDFを印刷
+    ///
+    /// # Arguments
+    /// * `pdf_path` - 印刷するPDFファイルのパス
+    /// * `printer_name` - プリンター名（Noneの場合はデフォルトプリンター）
+    fn print(&self, pdf_path: &Path, printer_name: Option<&str>) -> Result<(), PdfError>;
+
+    /// 利用可能なプリンター一覧を取得
+    fn list_printers(&self) -> Result<Vec<String>, PdfError>;
+
+    /// デフォルトプリンターを取得
+    fn default_printer(&self) -> Result<Option<String>, PdfError>;
+
+    /// 印刷せずにPDFをビューアーで表示（ビューアーの終了は待たない）
+    ///
+    /// デフォルトでは既定のビューアーで開く
+    fn preview(&self, pdf_path: &Path) -> Result<(), PdfError> {
+        open_in_viewer(pdf_path)
+    }
+
+    /// プリンターの対応する印刷設定を取得（Noneはデフォルトプリンター）
+    ///
+    /// デフォルトでは取得できないものとしてNoneを返す（印刷前の確認を行わない）
+    fn capabilities(&self, printer_name: Option<&str>) -> Result<Option<PrinterCapabilities>, PdfError> {
+        let _ = printer_name;
+        Ok(None)
+    }
+
+    /// 両面印刷・用紙・部数の指定がプリンターで使用できるか印刷前に確認
+    ///
+    /// 対応していない設定がある場合は内容を含む `PdfError::Print` を返す。
+    /// 対応状況が取得できないプリンターでは確認しない
+    fn check_options(&self, printer_name: Option<&str>, options: &PrintOptions) -> Result<(), PdfError> {
+        match self.capabilities(printer_name)? {
+            Some(capabilities) => capabilities.check(printer_name, options),
+            None => Ok(()),
+        }
+    }
+
+    /// オプションを指定してPDFを印刷
+    ///
+    /// 優先プリンターで印刷できない場合はフォールバックプリンターを順番に試行する。
+    /// プリンター一覧が取得できる場合は、一覧に存在しないプリンターをスキップする。
+    /// 対応状況の確認（`PrintOptions::with_capability_check`）が有効で両面印刷・用紙・部数を指定した場合は、
+    /// 対応していないプリンターをスキップする（`check_options`）。
+    ///
+    /// # Returns
+    /// 印刷を受け付けたプリンター名（Noneはデフォルトプリンター）
+    fn print_with_options(
+        &self,
+        pdf_path: &Path,
+        printer_name: Option<&str>,
+        options: &PrintOptions,
+    ) -> Result<Option<String>, PdfError> {
+        print_with_fallback(self, pdf_path, printer_name, options)
+    }
+}
+
+/// 優先プリンター・フォールバックプリンターの順に印刷を試行（`Printer::print_with_options` の既定の処理）
+pub(crate) fn print_with_fallback<P: Printer + ?Sized>(
+    printer: &P,
+    pdf_path: &Path,
+    printer_name: Option<&str>,
+    options: &PrintOptions,
+) -> Result<Option<String>, PdfError> {
+    let mut candidates = options.candidates(printer_name);
+
+    // オンラインのプリンターのみに絞り込む（一覧取得に失敗した場合は全て試行）
+    if candidates.len() > 1 {
+        if let Ok(available) = printer.list_printers() {
+            candidates = filter_available(candidates, &available);
+        }
+    }
+
+    let mut errors = Vec::new();
+    for candidate in candidates {
+        // 対応状況が取得できない場合は確認せずに印刷する
+        if options.check_capabilities && options.has_settings() {
+            if let Ok(Some(capabilities)) = printer.capabilities(candidate.as_deref()) {
+                if let Err(e) = capabilities.check(candidate.as_deref(), options) {
+                    tracing::warn!("印刷設定に対応していないためスキップ: {}", e);
+                    errors.push(e.to_string());
+                    continue;
+                }
+            }
+        }
+        match printer.print(pdf_path, candidate.as_deref()) {
+            Ok(()) => {
+                tracing::info!(
+                    "印刷ジョブ受付: {}",
+                    candidate.as_deref().unwrap_or("デフォルトプリンター")
+                );
+                return Ok(candidate);
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "印刷失敗: {}, {}",
+                    candidate.as_deref().unwrap_or("デフォルトプリンター"),
+                    e
+                );
+                errors.push(format!(
+                    "{}: {}",
+                    candidate.as_deref().unwrap_or("デフォルトプリンター"),
+                    e
+                ));
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        return Err(PdfError::Print(
+            "利用可能なプリンターがありません".to_string(),
+        ));
+    }
+
+    Err(PdfError::Print(format!(
+        "全てのプリンターで印刷に失敗しました: {}",
+        errors.join(" / ")
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pdf::generator::ReportLabStylePdfClient;
+    use crate::print::{MockPrinter, SumatraPrinter};
+    use crate::service::PdfService;
+
+    #[test]
+    fn test_pdf_generator_trait_object() {
+        // dyn PdfGeneratorとして扱えることを確認（コンパイルテスト）
+        let generators: Vec<Box<dyn PdfGenerator>> = vec![
+            Box::new(PdfService::new()),
+            Box::new(ReportLabStylePdfClient::new()),
+        ];
+        assert_eq!(generators.len(), 2);
+    }
+
+    #[test]
+    fn test_printer_preview_overridable() {
+        let recorder = MockPrinter::new();
+        let printer: &dyn Printer = &recorder;
+        printer.preview(Path::new("report.pdf")).unwrap();
+        assert_eq!(recorder.previews(), vec![PathBuf::from("report.pdf")]);
+    }
+
+    #[test]
+    fn test_printer_trait_object() {
+        // dyn Printerとして扱えることを確認（コンパイルテスト）
+        let printers: Vec<Box<dyn Printer>> = vec![Box::new(SumatraPrinter::new())];
+        assert_eq!(printers.len(), 1);
+    }
+}
@@ -0,0 +1,214 @@
+//! 警告型定義
+//!
+//! PDF生成は継続できるが、呼び出し元で確認すべき事象を表す
+
+use serde::Serialize;
+
+use crate::pdf::fonts::FontRole;
+
+/// PDF生成時の警告
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum Warning {
+    /// フォントに存在しない文字を代替文字に置換した
+    #[serde(rename_all = "camelCase")]
+    UnsupportedGlyph {
+        /// アイテムのインデックス
+        item_index: usize,
+        /// フィールド名（例: "ryohi[0].detail[1]"）
+        field: String,
+        /// 置換された文字
+        chars: Vec<char>,
+    },
+
+    /// 入力値の検証で問題を検出した（値はそのまま印刷する）
+    #[serde(rename_all = "camelCase")]
+    InvalidField {
+        /// アイテムのインデックス
+        item_index: usize,
+        /// フィールド名
+        field: String,
+        /// 内容
+        message: String,
+    },
+
+    /// 用途別のフォントを読み込めず本文フォントで代替した
+    #[serde(rename_all = "camelCase")]
+    FontFallback {
+        /// フォントの用途
+        role: FontRole,
+        /// 読み込めなかった理由
+        message: String,
+    },
+
+    /// フォールバックフォントを読み込めず本文フォントのみで描画した
+    #[serde(rename_all = "camelCase")]
+    FallbackFontUnavailable {
+        /// 読み込めなかった理由
+        message: String,
+    },
+
+    /// 重複した旅費項目を検出した
+    #[serde(rename_all = "camelCase")]
+    DuplicateRyohi {
+        /// アイテムのインデックス
+        item_index: usize,
+        /// 重複した旅費項目のインデックス（重複除去前）
+        ryohi_index: usize,
+        /// 重複元の旅費項目のインデックス
+        duplicate_of: usize,
+        /// 日付
+        date: Option<String>,
+        /// 金額
+        price: Option<i32>,
+        /// 削除されたか
+        removed: bool,
+    },
+
+    /// 直前の旅費項目と日付・行先が同じ（重複入力の可能性がある。印刷内容は変更しない）
+    #[serde(rename_all = "camelCase")]
+    PossibleDuplicateRyohi {
+        /// アイテムのインデックス
+        item_index: usize,
+        /// 旅費項目のインデックス（直前の項目と同じ日付・行先）
+        ryohi_index: usize,
+        /// 日付
+        date: String,
+        /// 行先
+        dest: String,
+    },
+
+    /// 1行の文字数を超える摘要を切り詰めた（`DetailOverflow::Truncate`）
+    #[serde(rename_all = "camelCase")]
+    DetailTruncated {
+        /// アイテムのインデックス
+        item_index: usize,
+        /// フィールド名（例: "ryohi[0].detail[1]"）
+        field: String,
+        /// 摘要の文字数（改行を含む場合は最も長い行）
+        length: usize,
+        /// 1行の最大文字数
+        max_length: usize,
+    },
+
+    /// 記載の税額と旅費項目から計算した税額が一致しない（±1円を超える差）
+    #[serde(rename_all = "camelCase")]
+    TaxMismatch {
+        /// アイテムのインデックス
+        item_index: usize,
+        /// 記載の税額（`Item::tax`）
+        stated: i64,
+        /// 計算した税額
+        computed: i64,
+    },
+}
+
+impl std::fmt::Display for Warning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Warning::UnsupportedGlyph {
+                item_index,
+                field,
+                chars,
+            } => {
+                let chars: String = chars.iter().collect();
+                write!(
+                    f,
+                    "フォントに存在しない文字: item[{}].{}: {}",
+                    item_index, field, chars
+                )
+            }
+            Warning::InvalidField {
+                item_index,
+                field,
+                message,
+            } => write!(f, "入力値の問題: item[{}].{}: {}", item_index, field, message),
+            Warning::FontFallback { role, message } => {
+                write!(f, "{}フォントを本文フォントで代替: {}", role, message)
+            }
+            Warning::FallbackFontUnavailable { message } => {
+                write!(f, "フォールバックフォントを読み込めません: {}", message)
+            }
+            Warning::DuplicateRyohi {
+                item_index,
+                ryohi_index,
+                duplicate_of,
+                date,
+                price,
+                removed,
+            } => {
+                write!(
+                    f,
+                    "重複した旅費項目{}: item[{}].ryohi[{}] (ryohi[{}]と重複, 日付={}, 金額={})",
+                    if *removed { "を削除" } else { "" },
+                    item_index,
+                    ryohi_index,
+                    duplicate_of,
+                    date.as_deref().unwrap_or("-"),
+                    price.map(|p| p.to_string()).unwrap_or_else(|| "-".to_string())
+                )
+            }
+            Warning::PossibleDuplicateRyohi {
+                item_index,
+                ryohi_index,
+                date,
+                dest,
+            } => write!(
+                f,
+                "重複の可能性がある旅費項目: item[{}].ryohi[{}] (ryohi[{}]と日付・行先が同じ, 日付={}, 行先={})",
+                item_index,
+                ryohi_index,
+                ryohi_index.saturating_sub(1),
+                date,
+                dest
+            ),
+            Warning::DetailTruncated {
+                item_index,
+                field,
+                length,
+                max_length,
+            } => write!(
+                f,
+                "摘要を切り詰めました: item[{}].{} ({}文字, 1行{}文字)",
+                item_index, field, length, max_length
+            ),
+            Warning::TaxMismatch {
+                item_index,
+                stated,
+                computed,
+            } => write!(
+                f,
+                "税額が一致しません: item[{}] (記載={}, 計算={})",
+                item_index, stated, computed
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_warning_display() {
+        let warning = Warning::UnsupportedGlyph {
+            item_index: 1,
+            field: "name".to_string(),
+            chars: vec!['😀'],
+        };
+        assert_eq!(warning.to_string(), "フォントに存在しない文字: item[1].name: 😀");
+    }
+
+    #[test]
+    fn test_warning_serialize() {
+        let warning = Warning::UnsupportedGlyph {
+            item_index: 0,
+            field: "ryohi[0].detail[0]".to_string(),
+            chars: vec!['𠮷'],
+        };
+        let json = serde_json::to_value(&warning).unwrap();
+        assert_eq!(json["type"], "unsupportedGlyph");
+        assert_eq!(json["itemIndex"], 0);
+        assert_eq!(json["field"], "ryohi[0].detail[0]");
+    }
+}
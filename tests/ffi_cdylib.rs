@@ -0,0 +1,45 @@
+//! FFIの共有ライブラリのビルド確認
+//!
+//! `ffi` featureでビルドした共有ライブラリ（.so / .dll / .dylib）が出力され、
+//! C#・Delphi等から呼び出す関数を含むことを確認する。
+//!
+//! 使用方法:
+//! ```bash
+//! cargo test --features ffi --test ffi_cdylib
+//! ```
+
+use std::path::PathBuf;
+use std::process::Command;
+
+/// 呼び出し元が読み込む関数
+const EXPORTED: [&str; 3] = ["ppdf_generate", "ppdf_free", "ppdf_last_error_message"];
+
+#[test]
+fn test_cdylib_is_built() {
+    let output = Command::new(env!("CARGO"))
+        .args(["build", "--offline", "--lib", "--features", "ffi", "--message-format=json"])
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    let library_name = format!("{}print_pdf_service{}", std::env::consts::DLL_PREFIX, std::env::consts::DLL_SUFFIX);
+    let library: PathBuf = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .filter(|message| message["reason"] == "compiler-artifact" && message["target"]["name"] == "print_pdf_service")
+        .flat_map(|message| message["filenames"].as_array().cloned().unwrap_or_default())
+        .filter_map(|filename| filename.as_str().map(PathBuf::from))
+        .find(|path| path.file_name().is_some_and(|name| name.to_string_lossy() == library_name))
+        .expect("共有ライブラリが出力されていません（Cargo.tomlの[lib] crate-typeにcdylibが必要）");
+
+    let data = std::fs::read(&library).unwrap();
+    for name in EXPORTED {
+        assert!(
+            data.windows(name.len()).any(|window| window == name.as_bytes()),
+            "{:?}に{}が含まれていません",
+            library,
+            name
+        );
+    }
+}
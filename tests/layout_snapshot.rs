@@ -0,0 +1,30 @@
+//! レイアウトの回帰テスト
+//!
+//! サンプルのアイテム（`pdf::testing::example_items`）から作成したページ操作を、ページごとに登録済みのJSONと比較する。
+//! PDFのバイト列はフォントや作成日時に依存するため、フォントを埋め込む前のページ操作を対象にする。
+//!
+//! レイアウトを意図して変更した場合は登録済みの内容を更新する:
+//! ```bash
+//! UPDATE_SNAPSHOTS=1 cargo test --features test-util --test layout_snapshot
+//! ```
+
+use print_pdf_service::pdf::testing::{assert_ops_snapshot, example_items};
+use print_pdf_service::pdf::{FontSet, LayoutConfig};
+use print_pdf_service::ReportLabStylePdfClient;
+use printpdf::FontId;
+
+/// 登録済みのページ操作のディレクトリ
+const SNAPSHOT_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures");
+
+#[test]
+fn test_page_operations_match_snapshot() {
+    let client = ReportLabStylePdfClient::new().with_layout(LayoutConfig::new().with_continuation_pages(true));
+    let fonts = FontSet::single(FontId("F1".to_string()));
+    let (pages, report) = client.render_page_operations_with_fonts(&fonts, &example_items());
+    assert_eq!(report.continuation_pages, 1);
+    assert_eq!(pages.len(), 4);
+
+    for (index, ops) in pages.iter().enumerate() {
+        assert_ops_snapshot(format!("{}/example_items_page{}.json", SNAPSHOT_DIR, index + 1), ops);
+    }
+}
@@ -0,0 +1,145 @@
+//! 公開APIの確認
+//!
+//! 意図して公開している項目を参照し、削除・移動・シグネチャの変更でビルドを失敗させる。
+//! 公開APIを変更した場合はここも更新してください。
+
+use std::any::TypeId;
+use std::path::{Path, PathBuf};
+
+use print_pdf_service::{audit, blocking, config, fs, pdf, print, service};
+use print_pdf_service::{
+    create_printer, create_printer_with_passthrough, format_car, generate_pdf, generate_sync, ArtifactLocation, AuditEntry, AuditItem, AuditOutcome, AuditSink, Backend,
+    ConfigIssue, DedupePolicy, Duplex, DuplicateRyohi, FormatOptions, Fs, GenerationReport, GenerationStats, Item,
+    JsonlFileSink, LayoutConfig, NameRedaction, NoopSink, NumberFormat, PageRenderer, PdfConfig, PdfError,
+    PdfGenerator, PdfRequest, PdfResult, PdfService, PrintFailurePolicy, PrintOptions, PrintRequest, Printer, PrinterCapabilities, RealFs, RequestLimits,
+    ReportLabStylePdfClient, Ryohi, SealImage, SumatraPrinter, ValidationError, WarmUpReport, Warning, WriteRetry,
+};
+
+fn type_id<T: ?Sized + 'static>() -> TypeId {
+    TypeId::of::<T>()
+}
+
+#[test]
+fn test_prelude_matches_root() {
+    use print_pdf_service::prelude;
+
+    assert_eq!(type_id::<prelude::PdfService>(), type_id::<PdfService>());
+    assert_eq!(type_id::<prelude::PdfRequest>(), type_id::<PdfRequest>());
+    assert_eq!(type_id::<prelude::PdfResult>(), type_id::<PdfResult>());
+    assert_eq!(type_id::<prelude::Item>(), type_id::<Item>());
+    assert_eq!(type_id::<prelude::Ryohi>(), type_id::<Ryohi>());
+    assert_eq!(type_id::<prelude::PdfConfig>(), type_id::<PdfConfig>());
+    assert_eq!(type_id::<prelude::PdfError>(), type_id::<PdfError>());
+    assert_eq!(type_id::<prelude::Warning>(), type_id::<Warning>());
+}
+
+#[test]
+fn test_root_reexports_match_modules() {
+    // ルートと各モジュールで同じ型
+    assert_eq!(type_id::<ReportLabStylePdfClient>(), type_id::<pdf::generator::ReportLabStylePdfClient>());
+    assert_eq!(type_id::<LayoutConfig>(), type_id::<pdf::layout::LayoutConfig>());
+    assert_eq!(type_id::<FormatOptions>(), type_id::<pdf::text_utils::FormatOptions>());
+    assert_eq!(type_id::<PageRenderer<'static>>(), type_id::<pdf::renderer::PageRenderer<'static>>());
+    assert_eq!(type_id::<SumatraPrinter>(), type_id::<print::sumatra::SumatraPrinter>());
+    assert_eq!(type_id::<PrintOptions>(), type_id::<print::options::PrintOptions>());
+    assert_eq!(type_id::<Backend>(), type_id::<print::backend::Backend>());
+    assert_eq!(type_id::<PdfService>(), type_id::<service::PdfService>());
+    assert_eq!(type_id::<AuditEntry>(), type_id::<audit::AuditEntry>());
+    assert_eq!(type_id::<PdfConfig>(), type_id::<config::PdfConfig>());
+    assert_eq!(type_id::<RealFs>(), type_id::<fs::RealFs>());
+}
+
+#[cfg(feature = "test-util")]
+#[test]
+fn test_test_util_reexports_match_modules() {
+    use print_pdf_service::{MockFs, MockPrinter};
+
+    assert_eq!(type_id::<MockFs>(), type_id::<fs::MockFs>());
+    assert_eq!(type_id::<MockPrinter>(), type_id::<print::MockPrinter>());
+}
+
+#[test]
+fn test_public_items() {
+    // 型・トレイト
+    let _ = [
+        type_id::<ArtifactLocation>(),
+        type_id::<ConfigIssue>(),
+        type_id::<PrintFailurePolicy>(),
+        type_id::<RequestLimits>(),
+        type_id::<DedupePolicy>(),
+        type_id::<Duplex>(),
+        type_id::<DuplicateRyohi>(),
+        type_id::<GenerationReport>(),
+        type_id::<GenerationStats>(),
+        type_id::<NumberFormat>(),
+        type_id::<PrintRequest>(),
+        type_id::<PrinterCapabilities>(),
+        type_id::<RealFs>(),
+        type_id::<SealImage>(),
+        type_id::<ValidationError>(),
+        type_id::<WarmUpReport>(),
+        type_id::<WriteRetry>(),
+        type_id::<AuditItem>(),
+        type_id::<AuditOutcome>(),
+        type_id::<JsonlFileSink>(),
+        type_id::<NameRedaction>(),
+        type_id::<NoopSink>(),
+        type_id::<blocking::PdfClient>(),
+        type_id::<dyn AuditSink>(),
+        type_id::<dyn Fs>(),
+        type_id::<dyn PdfGenerator>(),
+        type_id::<dyn Printer>(),
+        type_id::<pdf::FontSet>(),
+        type_id::<pdf::FontCache>(),
+        type_id::<pdf::FontLoader>(),
+        type_id::<pdf::ColumnSpec>(),
+        type_id::<pdf::ColumnSource>(),
+        type_id::<pdf::ColumnAlign>(),
+        type_id::<pdf::Margins>(),
+        type_id::<pdf::Imposition>(),
+        type_id::<pdf::CellOverflow>(),
+        type_id::<pdf::LineStyles>(),
+        type_id::<pdf::ColorScheme>(),
+        type_id::<pdf::Shading>(),
+        type_id::<pdf::TaxOptions>(),
+        type_id::<pdf::NormalizeOptions>(),
+        type_id::<pdf::RyohiPrintData>(),
+        type_id::<pdf::PagePostprocess>(),
+        type_id::<pdf::KukanReplacement>(),
+        type_id::<pdf::DetailOverflow>(),
+    ];
+
+    // 関数
+    let _: fn(&[Item], &Path, &PdfConfig) -> Result<PathBuf, PdfError> = generate_pdf;
+    let _: fn(&[Item], &str) -> Result<PathBuf, PdfError> = |items, path| generate_sync(items, path);
+    let _: fn(&str) -> Result<String, ValidationError> = format_car;
+    let _: fn(&Path, usize) -> PathBuf = pdf::item_output_path;
+    let _: fn(&[printpdf::Op], f32, f32) -> String = pdf::ops_to_svg;
+    let _: fn() -> Vec<pdf::ColumnSpec> = pdf::default_columns;
+    let _: fn(&str) -> std::collections::BTreeMap<usize, Vec<String>> = pdf::parse_external_id_keywords;
+    let _: fn(&[u8]) -> String = service::encode_base64;
+    let _: fn(&[u8]) -> String = service::to_data_uri;
+    let _: fn(&Path) -> Result<(), PdfError> = print::open_in_viewer;
+    let _: fn(&[ConfigIssue]) -> String = config::format_issues;
+    let _ = create_printer;
+    let _ = create_printer_with_passthrough;
+
+    // 定数
+    let _: (f32, f32) = (pdf::A5_WIDTH, pdf::A5_HEIGHT);
+    let _: usize = pdf::MAX_DATA_ROWS_PER_PAGE;
+    let _: [&str; 3] = [pdf::TITLE, pdf::SUBTOTAL_LABEL, pdf::CARRY_FORWARD_LABEL];
+    let _: (f32, f32) = (pdf::DATA_TABLE_TOP, pdf::DATA_TABLE_HEADER_HEIGHT);
+    let _: pdf::SummaryTable = pdf::SummaryTable::DEFAULT;
+}
+
+#[cfg(feature = "test-util")]
+#[test]
+fn test_test_util_items() {
+    use print_pdf_service::{MockPrinter, PrintJob};
+
+    assert_eq!(type_id::<MockPrinter>(), type_id::<print::MockPrinter>());
+    let _ = type_id::<PrintJob>();
+    let _ = type_id::<pdf::testing::MockFont>();
+    let _: fn(&[u8]) -> String = pdf::testing::extract_text;
+    let _: fn(&[printpdf::Op], &str) -> Option<pdf::testing::PlacedText> = pdf::testing::find_text;
+}
@@ -0,0 +1,46 @@
+"""Pythonバインディングのテスト（pytest形式）
+
+maturin develop でインストール後に pytest で実行するか、Rustのテストから実行する:
+    cargo test --features python --test python_bindings
+Rustのテストでは空のフォントで生成する。pytestで直接実行する場合は日本語フォント（PDF_FONT_PATH 等）が必要。
+"""
+
+import print_pdf_service
+from print_pdf_service import PdfGenerationError
+
+# PdfError::code のエラーコード
+VALIDATION = 6
+
+ITEM = {
+    "car": "12-34",
+    "name": "山田太郎",
+    "purpose": "客先訪問",
+    "startDate": "2024-01-15",
+    "endDate": "2024-01-15",
+    "price": 15000,
+    "ryohi": [
+        {"date": "2024-01-15", "dest": "東京", "detail": ["交通費"], "kukan": "福岡　東京", "price": 15000, "vol": 1.0},
+    ],
+}
+
+
+def test_generate_one_item(tmp_path):
+    output = tmp_path / "sample.pdf"
+    result = print_pdf_service.generate([ITEM], str(output))
+
+    assert result["pdfPath"] == str(output)
+    assert result["printed"] is False
+    assert result["fileSize"] == output.stat().st_size
+    assert output.read_bytes().startswith(b"%PDF")
+
+
+def test_invalid_items_raise_generation_error(tmp_path):
+    for items in ([], [{"name": 1}]):
+        try:
+            print_pdf_service.generate(items, str(tmp_path / "invalid.pdf"))
+        except PdfGenerationError as e:
+            assert e.code == VALIDATION
+            assert e.message == str(e)
+        else:
+            raise AssertionError(f"例外が発生しませんでした: {items}")
+    assert not (tmp_path / "invalid.pdf").exists()
@@ -0,0 +1,57 @@
+//! Pythonバインディングのテスト
+//!
+//! 組み込みのPythonインタープリターにモジュールを登録し、`tests/python` のpytest形式のテストを実行する。
+//! 日本語フォントの無い環境でも生成を検証できるよう、空のフォント（`pdf::testing::MockFont`）で生成する。
+//!
+//! 使用方法:
+//! ```bash
+//! cargo test --features python,test-util --test python_bindings
+//! ```
+
+use std::ffi::CString;
+
+use print_pdf_service::pdf::testing::MockFont;
+use print_pdf_service::python::python_module;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+/// pytest形式のテストのディレクトリ
+const PYTHON_TESTS_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/python");
+
+/// `test_*.py` の `test_*` 関数を実行（`tmp_path` 引数には一時ディレクトリを渡す）
+const RUNNER: &str = r#"
+import inspect, pathlib, sys, tempfile
+
+sys.stdout.reconfigure(encoding="utf-8")
+sys.path.insert(0, tests_dir)
+for module_path in sorted(pathlib.Path(tests_dir).glob("test_*.py")):
+    module = __import__(module_path.stem)
+    for name, func in sorted(vars(module).items()):
+        if not name.startswith("test_") or not callable(func):
+            continue
+        with tempfile.TemporaryDirectory() as tmp:
+            kwargs = {"tmp_path": pathlib.Path(tmp)} if "tmp_path" in inspect.signature(func).parameters else {}
+            func(**kwargs)
+        print(f"{module_path.stem}::{name} ok")
+"#;
+
+#[test]
+fn test_python_bindings() {
+    // モジュールは環境変数の設定と共有のフォントキャッシュで生成する
+    let font = MockFont::new("python_bindings");
+    assert!(font.install_global(), "フォントのキャッシュが既に使用されています");
+    std::env::set_var("PDF_FONT_PATH", font.path());
+
+    pyo3::append_to_inittab!(python_module);
+    pyo3::prepare_freethreaded_python();
+
+    Python::with_gil(|py| {
+        let globals = PyDict::new(py);
+        globals.set_item("tests_dir", PYTHON_TESTS_DIR).unwrap();
+        let runner = CString::new(RUNNER).unwrap();
+        if let Err(e) = py.run(&runner, Some(&globals), None) {
+            e.display(py);
+            panic!("Pythonのテストが失敗しました: {}", e);
+        }
+    });
+}
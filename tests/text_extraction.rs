@@ -0,0 +1,42 @@
+//! 生成したPDFからのテキスト抽出テスト
+//!
+//! フォントの埋め込み・エンコードを経ても日本語のテキストが保たれていることを確認する。
+//! 日本語フォントの無い環境でも実行できるよう、日本語の文字に空のグリフを割り当てたフォント
+//! （`pdf::testing::MockFont::japanese`）で生成する。
+//!
+//! 使用方法:
+//! ```bash
+//! cargo test --features test-util --test text_extraction
+//! ```
+
+use print_pdf_service::pdf::testing::{example_item, extract_text, MockFont};
+use print_pdf_service::pdf::{wrap_kukan, FontLoader, KukanReplacement, MAX_KUKAN_LENGTH};
+use print_pdf_service::{Item, ReportLabStylePdfClient};
+
+/// 折り返しが発生する区間
+const LONG_KUKAN: &str = "福岡　北九州　下関　広島　岡山　姫路　神戸　大阪　京都";
+
+/// テストデータを作成（サンプルの1件目の区間を折り返しの区間に置き換え）
+fn create_test_items() -> Vec<Item> {
+    let mut item = example_item();
+    item.ryohi[0].kukan = Some(LONG_KUKAN.to_string());
+    vec![item]
+}
+
+#[test]
+fn test_japanese_text_survives_embedding() {
+    let font = MockFont::japanese("text_extraction");
+    let mut client = ReportLabStylePdfClient::new()
+        .with_font_loader(FontLoader::new().with_font_path(font.path()))
+        .with_font_cache(font.cache());
+    let bytes = client.generate_to_vec(&create_test_items()).unwrap();
+
+    let text = extract_text(&bytes);
+    assert!(text.contains("山田太郎"), "{}", text);
+    assert!(text.contains("客先訪問"), "{}", text);
+    assert!(text.contains("25,000"), "{}", text);
+
+    let kukan = wrap_kukan(LONG_KUKAN, MAX_KUKAN_LENGTH, &KukanReplacement::defaults());
+    assert!(kukan.lines.len() >= 2);
+    assert!(text.contains(&kukan.lines[1]), "{}", text);
+}